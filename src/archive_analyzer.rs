@@ -0,0 +1,758 @@
+use crate::bash_analyzer::{
+    CommandAnalysis, ExtractedPath, Finding, PathContext, detect_code_from_stdin,
+    extract_paths_recursive,
+};
+use crate::facts::Fact;
+
+/// Archivers whose archives, sources and extraction directories are analysed.
+pub(crate) const ARCHIVE_COMMANDS: &[&str] = &[
+    "tar", "gtar", "bsdtar", "unzip", "zip", "7z", "7za", "7zr", "cpio", "ar",
+];
+
+/// Short tar options that take a value (attached, the next argument, or in
+/// old-style bundles the next unused argument).
+const TAR_SHORT_VALUE: &str = "bCfFgHIKLNTVX";
+
+/// Long tar options that take a value as the next argument when not
+/// written `--option=value`.
+const TAR_LONG_VALUE: &[&str] = &[
+    "file",
+    "directory",
+    "files-from",
+    "exclude-from",
+    "listed-incremental",
+    "blocking-factor",
+    "starting-file",
+    "tape-length",
+    "after-date",
+    "newer",
+    "newer-mtime",
+    "label",
+    "format",
+    "use-compress-program",
+    "info-script",
+    "new-volume-script",
+    "to-command",
+    "rsh-command",
+    "strip-components",
+    "exclude",
+    "transform",
+    "xform",
+    "owner",
+    "group",
+    "mode",
+    "mtime",
+    "checkpoint-action",
+    "suffix",
+    "index-file",
+    "volno-file",
+    "record-size",
+    "sparse-version",
+];
+
+/// tar options whose value is a command tar runs.
+const TAR_PROGRAM_OPTIONS: &[&str] = &[
+    "use-compress-program",
+    "info-script",
+    "new-volume-script",
+    "rsh-command",
+];
+
+/// What a tar invocation does to its archive.
+#[derive(Clone, Copy, PartialEq)]
+enum TarMode {
+    /// Write a new archive from files (`-c`)
+    Create,
+    /// Add files to an existing archive (`-r`, `-u`, `-A`, `--delete`)
+    Modify,
+    /// Write members to disk (`-x`)
+    Extract,
+    /// Only read the archive (`-t`, `-d`, or no mode)
+    Read,
+}
+
+/// Analyze an archiver invocation.
+pub(crate) fn extract_archive(
+    cmd: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    match cmd {
+        "tar" | "gtar" | "bsdtar" => extract_tar(cmd, sub_cmd, args, out, depth),
+        "unzip" => extract_unzip(sub_cmd, args, out),
+        "zip" => extract_zip(sub_cmd, args, out, depth),
+        "cpio" => extract_cpio(sub_cmd, args, out, depth),
+        "ar" => extract_ar(sub_cmd, args, out),
+        _ => extract_7z(cmd, sub_cmd, args, out),
+    }
+}
+
+fn push_path(out: &mut CommandAnalysis, raw: &str, label: &'static str, write: bool) {
+    // `-` is stdin/stdout
+    if raw.is_empty() || raw == "-" || raw.starts_with("/dev/") {
+        return;
+    }
+    out.paths.push(ExtractedPath {
+        raw: raw.to_string(),
+        context: PathContext::ArchivePath { label, write },
+    });
+}
+
+/// An `archive.extract` fact: which archive is unpacked where, and whether
+/// the archiver keeps absolute member names.
+fn extract_fact(
+    tool: &str,
+    sub_cmd: &str,
+    archive: Option<&str>,
+    dest: Option<&str>,
+    absolute_names: bool,
+) -> Fact {
+    Fact::new("archive.extract", sub_cmd)
+        .with("tool", tool)
+        .with("archive", archive.unwrap_or("-"))
+        .with("dest", dest.unwrap_or("."))
+        .with("absolute_names", absolute_names.to_string())
+}
+
+/// A command an archiver runs (`tar --to-command`, `zip -TT`): an
+/// `archive.exec` fact, and the command analysed like any other.
+fn push_program(
+    tool: &str,
+    option: &str,
+    command: &str,
+    sub_cmd: &str,
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    out.findings.push(Finding::Fact(
+        Fact::new("archive.exec", sub_cmd)
+            .with("tool", tool)
+            .with("option", option)
+            .with("command", command),
+    ));
+    extract_paths_recursive(command, out, depth + 1);
+}
+
+// ============================================================================
+// tar
+// ============================================================================
+
+/// A tar argument after option parsing: an option by its long name, or an
+/// operand. Kept in order because `-C` applies to the operands after it.
+enum TarArg {
+    Option(String, Option<String>),
+    Operand(String),
+}
+
+/// Long name of a short tar option.
+fn tar_long_name(tool: &str, short: char) -> String {
+    let name = match short {
+        'f' => "file",
+        'C' => "directory",
+        'T' => "files-from",
+        'X' => "exclude-from",
+        'g' => "listed-incremental",
+        // bsdtar's `-I` is `-T`
+        'I' if tool == "bsdtar" => "files-from",
+        'I' => "use-compress-program",
+        'F' => "info-script",
+        'P' => "absolute-names",
+        'c' => "create",
+        'x' => "extract",
+        't' => "list",
+        'r' => "append",
+        'u' => "update",
+        'A' => "catenate",
+        'd' => "diff",
+        _ => return short.to_string(),
+    };
+    name.to_string()
+}
+
+fn parse_tar_args(tool: &str, args: &[String]) -> Vec<TarArg> {
+    let mut parsed = Vec::new();
+    let mut i = 0;
+    // Old-style bundle: `tar xzf a.tar`, values taken from the next arguments
+    if let Some(first) = args.first()
+        && !first.starts_with('-')
+        && !first.is_empty()
+        && first.chars().all(|c| c.is_ascii_alphabetic())
+    {
+        i = 1;
+        for short in first.chars() {
+            let value = if TAR_SHORT_VALUE.contains(short) {
+                i += 1;
+                args.get(i - 1).cloned()
+            } else {
+                None
+            };
+            parsed.push(TarArg::Option(tar_long_name(tool, short), value));
+        }
+    }
+    while i < args.len() {
+        let arg = &args[i];
+        i += 1;
+        if arg == "--" {
+            parsed.extend(args[i..].iter().cloned().map(TarArg::Operand));
+            break;
+        }
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None if TAR_LONG_VALUE.contains(&long) => {
+                    i += 1;
+                    (long, args.get(i - 1).cloned())
+                }
+                None => (long, None),
+            };
+            let name = match name {
+                "get" => "extract",
+                "concatenate" => "catenate",
+                "compare" => "diff",
+                name => name,
+            };
+            parsed.push(TarArg::Option(name.to_string(), value));
+            continue;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            parsed.push(TarArg::Operand(arg.clone()));
+            continue;
+        }
+        // Short bundle: a value-taking letter takes the rest of the bundle,
+        // or the next argument
+        for (pos, short) in arg.char_indices().skip(1) {
+            if TAR_SHORT_VALUE.contains(short) {
+                let attached = &arg[pos + 1..];
+                let value = if attached.is_empty() {
+                    i += 1;
+                    args.get(i - 1).cloned()
+                } else {
+                    Some(attached.to_string())
+                };
+                parsed.push(TarArg::Option(tar_long_name(tool, short), value));
+                break;
+            }
+            parsed.push(TarArg::Option(tar_long_name(tool, short), None));
+        }
+    }
+    parsed
+}
+
+/// tar, GNU tar and bsdtar: the archive (`-f`), the extraction directory
+/// (`-C`), source files relative to `-C`, file lists, and the programs tar
+/// runs (`--to-command`, `-I`, `--checkpoint-action=exec=`).
+fn extract_tar(
+    tool: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let parsed = parse_tar_args(tool, args);
+    let mode = parsed
+        .iter()
+        .find_map(|arg| match arg {
+            TarArg::Option(name, _) => match name.as_str() {
+                "create" => Some(TarMode::Create),
+                "append" | "update" | "catenate" | "delete" => Some(TarMode::Modify),
+                "extract" => Some(TarMode::Extract),
+                "list" | "diff" => Some(TarMode::Read),
+                _ => None,
+            },
+            TarArg::Operand(_) => None,
+        })
+        .unwrap_or(TarMode::Read);
+
+    let mut archive = None;
+    let mut dest: Option<String> = None;
+    let mut absolute_names = false;
+    let mut strip_components = None;
+    for arg in &parsed {
+        let (name, value) = match arg {
+            TarArg::Operand(operand) => {
+                if matches!(mode, TarMode::Create | TarMode::Modify) {
+                    // Sources are read relative to the last `-C`
+                    let source = match &dest {
+                        Some(dir) if !operand.starts_with('/') && !operand.starts_with('~') => {
+                            format!("{}/{operand}", dir.trim_end_matches('/'))
+                        }
+                        _ => operand.clone(),
+                    };
+                    push_path(out, &source, "archive source", false);
+                }
+                continue;
+            }
+            TarArg::Option(name, value) => (name.as_str(), value.as_deref()),
+        };
+        match (name, value) {
+            ("file", Some(file)) => {
+                push_path(
+                    out,
+                    file,
+                    "archive",
+                    matches!(mode, TarMode::Create | TarMode::Modify),
+                );
+                archive = Some(file.to_string());
+            }
+            ("directory", Some(dir)) => {
+                if mode == TarMode::Extract {
+                    push_path(out, dir, "extraction directory", true);
+                }
+                dest = Some(dir.to_string());
+            }
+            ("files-from" | "exclude-from", Some(list)) => {
+                push_path(out, list, "archive file list", false)
+            }
+            ("listed-incremental", Some(snapshot)) => {
+                push_path(out, snapshot, "tar snapshot file", true)
+            }
+            ("absolute-names", _) => absolute_names = true,
+            ("strip-components", Some(n)) => strip_components = Some(n.to_string()),
+            ("to-command", Some(command)) => {
+                // Each member's contents are piped into the command
+                detect_code_from_stdin(command, sub_cmd, out);
+                push_program(tool, name, command, sub_cmd, out, depth);
+            }
+            ("checkpoint-action", Some(action)) => {
+                if let Some(command) = action.strip_prefix("exec=") {
+                    push_program(tool, name, command, sub_cmd, out, depth);
+                }
+            }
+            (name, Some(command)) if TAR_PROGRAM_OPTIONS.contains(&name) => {
+                push_program(tool, name, command, sub_cmd, out, depth);
+            }
+            _ => {}
+        }
+    }
+
+    if mode == TarMode::Extract {
+        let mut fact = extract_fact(
+            tool,
+            sub_cmd,
+            archive.as_deref(),
+            dest.as_deref(),
+            absolute_names,
+        );
+        if let Some(n) = strip_components {
+            fact = fact.with("strip_components", n);
+        }
+        out.findings.push(Finding::Fact(fact));
+    }
+}
+
+// ============================================================================
+// unzip and zip
+// ============================================================================
+
+/// unzip options that only list, test or print members instead of writing them.
+const UNZIP_LIST_FLAGS: &str = "lvtzZpc";
+
+/// `unzip [-opts] archive [members…] [-x excluded…] [-d dir]`
+fn extract_unzip(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let mut archive = None;
+    let mut dest = None;
+    let mut writes = true;
+    let mut in_exclusions = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        i += 1;
+        if let Some(short) = arg.strip_prefix('-')
+            && !short.is_empty()
+        {
+            in_exclusions = false;
+            for (pos, flag) in short.char_indices() {
+                match flag {
+                    'd' => {
+                        let attached = &short[pos + 1..];
+                        dest = if attached.is_empty() {
+                            i += 1;
+                            args.get(i - 1).cloned()
+                        } else {
+                            Some(attached.to_string())
+                        };
+                        break;
+                    }
+                    'P' => {
+                        // The password is the rest of the bundle or the next argument
+                        if short[pos + 1..].is_empty() {
+                            i += 1;
+                        }
+                        break;
+                    }
+                    'x' => in_exclusions = true,
+                    'Z' => {
+                        // zipinfo mode: everything after is zipinfo's
+                        writes = false;
+                        break;
+                    }
+                    flag if UNZIP_LIST_FLAGS.contains(flag) => writes = false,
+                    _ => {}
+                }
+            }
+            continue;
+        }
+        // The first operand is the archive; later ones are member names
+        if archive.is_none() && !in_exclusions {
+            archive = Some(arg.clone());
+        }
+    }
+    if let Some(archive) = &archive {
+        push_path(out, archive, "archive", false);
+    }
+    if writes {
+        if let Some(dir) = &dest {
+            push_path(out, dir, "extraction directory", true);
+        }
+        out.findings.push(Finding::Fact(extract_fact(
+            "unzip",
+            sub_cmd,
+            archive.as_deref(),
+            dest.as_deref(),
+            false,
+        )));
+    }
+}
+
+/// zip options that take the next argument as their value.
+const ZIP_VALUE_FLAGS: &[&str] = &[
+    "-b",
+    "--temp-path",
+    "-n",
+    "--suffixes",
+    "-t",
+    "--from-date",
+    "-tt",
+    "--before-date",
+    "-P",
+    "--password",
+    "-s",
+    "--split-size",
+    "-Z",
+    "--compression-method",
+    "-O",
+    "--output-file",
+    "-TT",
+    "--unzip-command",
+    "-sp",
+    "-la",
+    "--log-append",
+    "-lf",
+    "--logfile-path",
+    "-ll",
+    "-FS",
+];
+
+/// `zip [-opts] archive sources… [-x patterns…]`: the archive is written,
+/// sources are read (and deleted with `-m`), and `-TT` runs a command.
+fn extract_zip(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let mut archive = None;
+    let mut sources = Vec::new();
+    let mut moves = false;
+    let mut in_patterns = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        i += 1;
+        if arg.starts_with('-') && arg != "-" && arg != "-@" {
+            in_patterns = false;
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+                _ if ZIP_VALUE_FLAGS.contains(&arg) => {
+                    i += 1;
+                    (arg, args.get(i - 1).cloned())
+                }
+                _ => (arg, None),
+            };
+            match (flag, value) {
+                ("-m" | "--move", _) => moves = true,
+                ("-x" | "--exclude" | "-i" | "--include", _) => in_patterns = true,
+                ("-b" | "--temp-path", Some(dir)) => {
+                    push_path(out, &dir, "archive temp directory", true)
+                }
+                ("-O" | "--output-file", Some(file)) => push_path(out, &file, "archive", true),
+                ("-lf" | "--logfile-path", Some(file)) => {
+                    push_path(out, &file, "zip log file", true)
+                }
+                ("-TT" | "--unzip-command", Some(command)) => {
+                    push_program("zip", flag, &command, sub_cmd, out, depth)
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if in_patterns {
+            continue;
+        }
+        if archive.is_none() {
+            archive = Some(arg.to_string());
+        } else {
+            sources.push(arg.to_string());
+        }
+    }
+    if let Some(archive) = &archive {
+        push_path(out, archive, "archive", true);
+    }
+    for source in &sources {
+        push_path(out, source, "archive source", moves);
+    }
+}
+
+// ============================================================================
+// 7-Zip
+// ============================================================================
+
+/// `7z <command> [-switches…] archive [files…]`
+fn extract_7z(tool: &str, sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let Some(command) = args.first().map(|c| c.to_ascii_lowercase()) else {
+        return;
+    };
+    let mut archive = None;
+    let mut files = Vec::new();
+    let mut dest = None;
+    let mut absolute_names = false;
+    for arg in &args[1..] {
+        let Some(switch) = arg.strip_prefix('-') else {
+            if archive.is_none() {
+                archive = Some(arg.clone());
+            } else {
+                files.push(arg.clone());
+            }
+            continue;
+        };
+        // Switch values are attached: `-o/tmp/out`, `-w/tmp`, `-i@list.txt`
+        match switch.chars().next() {
+            Some('o') => dest = Some(switch[1..].to_string()),
+            Some('w') if switch.len() > 1 => {
+                push_path(out, &switch[1..], "archive temp directory", true)
+            }
+            Some('i' | 'x') => {
+                if let Some(list) = switch[1..]
+                    .trim_start_matches(['r', '-', '0', '!'])
+                    .strip_prefix('@')
+                {
+                    push_path(out, list, "archive file list", false);
+                }
+            }
+            _ if switch == "spf" || switch == "spf2" => absolute_names = true,
+            _ => {}
+        }
+    }
+
+    let writes_archive = matches!(command.as_str(), "a" | "u" | "d" | "rn");
+    if let Some(archive) = &archive {
+        push_path(out, archive, "archive", writes_archive);
+    }
+    match command.as_str() {
+        "a" | "u" => {
+            for file in files.iter().filter(|f| !f.starts_with('@')) {
+                push_path(out, file, "archive source", false);
+            }
+        }
+        "x" | "e" => {
+            if let Some(dir) = &dest {
+                push_path(out, dir, "extraction directory", true);
+            }
+            out.findings.push(Finding::Fact(extract_fact(
+                tool,
+                sub_cmd,
+                archive.as_deref(),
+                dest.as_deref(),
+                absolute_names,
+            )));
+        }
+        _ => {}
+    }
+}
+
+// ============================================================================
+// cpio
+// ============================================================================
+
+/// Short cpio options that take a value.
+const CPIO_SHORT_VALUE: &str = "CDEFHIMOR";
+
+/// Long cpio options that take a value as the next argument.
+const CPIO_LONG_VALUE: &[&str] = &[
+    "file",
+    "directory",
+    "pattern-file",
+    "format",
+    "io-size",
+    "message",
+    "owner",
+    "rsh-command",
+    "block-size",
+];
+
+/// `cpio -i` (extract from stdin or `-F`/`-I`), `cpio -o` (create on stdout or
+/// `-F`/`-O`) and `cpio -p dir` (copy into a directory). cpio keeps absolute
+/// member names unless `--no-absolute-filenames`.
+fn extract_cpio(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let mut mode = None;
+    let mut archive = None;
+    let mut dest = None;
+    let mut absolute_names = true;
+    let mut operands = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        i += 1;
+        let mut options = Vec::new();
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None if CPIO_LONG_VALUE.contains(&long) => {
+                    i += 1;
+                    (long, args.get(i - 1).cloned())
+                }
+                None => (long, None),
+            };
+            if name == "no-absolute-filenames" {
+                absolute_names = false;
+            }
+            let short = match name {
+                "extract" => 'i',
+                "create" => 'o',
+                "pass-through" => 'p',
+                "file" => 'F',
+                "directory" => 'D',
+                "pattern-file" => 'E',
+                "rsh-command" => '!',
+                _ => continue,
+            };
+            options.push((short, value));
+        } else if let Some(bundle) = arg.strip_prefix('-')
+            && !bundle.is_empty()
+        {
+            for (pos, short) in bundle.char_indices() {
+                if CPIO_SHORT_VALUE.contains(short) {
+                    let attached = &bundle[pos + 1..];
+                    let value = if attached.is_empty() {
+                        i += 1;
+                        args.get(i - 1).cloned()
+                    } else {
+                        Some(attached.to_string())
+                    };
+                    options.push((short, value));
+                    break;
+                }
+                options.push((short, None));
+            }
+        } else {
+            operands.push(arg.to_string());
+        }
+        for (short, value) in options {
+            match (short, value) {
+                ('i' | 'o' | 'p', _) => mode = Some(short),
+                ('F' | 'I' | 'O', Some(file)) => archive = Some((short, file)),
+                ('D', Some(dir)) => dest = Some(dir),
+                ('E', Some(list)) => push_path(out, &list, "archive file list", false),
+                ('!', Some(command)) => {
+                    push_program("cpio", "--rsh-command", &command, sub_cmd, out, depth)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    match mode {
+        Some('i') => {
+            if let Some((_, file)) = &archive {
+                push_path(out, file, "archive", false);
+            }
+            if let Some(dir) = &dest {
+                push_path(out, dir, "extraction directory", true);
+            }
+            out.findings.push(Finding::Fact(extract_fact(
+                "cpio",
+                sub_cmd,
+                archive.as_ref().map(|(_, file)| file.as_str()),
+                dest.as_deref(),
+                absolute_names,
+            )));
+        }
+        Some('o') => {
+            if let Some((_, file)) = &archive {
+                push_path(out, file, "archive", true);
+            }
+        }
+        Some('p') => {
+            // Pass-through copies the files named on stdin into the directory
+            if let Some(dir) = operands.first() {
+                push_path(out, dir, "extraction directory", true);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ============================================================================
+// ar
+// ============================================================================
+
+/// `ar [-]key[modifiers] [relpos] [count] archive [members…]`: `x` extracts
+/// members into the current directory (or `--output`), `r`/`q` add files.
+fn extract_ar(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let mut output = None;
+    let mut rest = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        i += 1;
+        if let Some(dir) = arg.strip_prefix("--output=") {
+            output = Some(dir.to_string());
+        } else if arg == "--output" {
+            output = args.get(i).cloned();
+            i += 1;
+        } else if arg.starts_with("--") {
+            // --plugin, --target take a value
+            if !arg.contains('=') && matches!(arg.as_str(), "--plugin" | "--target") {
+                i += 1;
+            }
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+    let Some(key) = rest.first().map(|k| k.trim_start_matches('-').to_string()) else {
+        return;
+    };
+    let Some(operation) = key.chars().find(|c| "dmpqrstx".contains(*c)) else {
+        return;
+    };
+    // Positioning modifiers take a member name, `N` a count
+    let skip = key
+        .chars()
+        .filter(|c| matches!(c, 'a' | 'b' | 'i' | 'N'))
+        .count()
+        .min(2);
+    let mut operands = rest.iter().skip(1 + skip);
+    let Some(archive) = operands.next() else {
+        return;
+    };
+    let writes_archive = matches!(operation, 'd' | 'm' | 'q' | 'r' | 's');
+    push_path(out, archive, "archive", writes_archive);
+    match operation {
+        'q' | 'r' => {
+            for member in operands {
+                push_path(out, member, "archive source", false);
+            }
+        }
+        'x' => {
+            if let Some(dir) = &output {
+                push_path(out, dir, "extraction directory", true);
+            }
+            // `P` keeps the full member paths instead of their file names
+            out.findings.push(Finding::Fact(extract_fact(
+                "ar",
+                sub_cmd,
+                Some(archive),
+                output.as_deref(),
+                key.contains('P'),
+            )));
+        }
+        _ => {}
+    }
+}
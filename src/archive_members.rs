@@ -0,0 +1,277 @@
+use flate2::read::{DeflateDecoder, GzDecoder};
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+
+use crate::internalonly::normalize_path;
+
+/// Members listed before giving up on an archive.
+const MAX_MEMBERS: usize = 10_000;
+
+/// Bytes of (decompressed) tar stream read before giving up.
+const MAX_TAR_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Longest symlink target read from a zip entry.
+const MAX_LINK_BYTES: u64 = 4096;
+
+/// An entry of an archive: its name and, for symbolic and hard links,
+/// what it points at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveMember {
+    pub name: String,
+    pub link: Option<Link>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Link {
+    /// Symlink target, relative to the member's directory
+    Symbolic(String),
+    /// Another member's name, relative to the archive root
+    Hard(String),
+}
+
+/// List the members of a local tar, gzip-compressed tar or zip archive.
+/// Returns None when the file can't be read or has another format. Very
+/// large archives are listed up to a limit.
+pub fn list_members(path: &Path) -> Option<Vec<ArchiveMember>> {
+    let mut file = File::open(path).ok()?;
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic).ok()?;
+    file.seek(SeekFrom::Start(0)).ok()?;
+    match magic {
+        [0x1f, 0x8b, ..] => read_tar(GzDecoder::new(BufReader::new(file)).take(MAX_TAR_BYTES)),
+        [b'P', b'K', 3, 4] | [b'P', b'K', 5, 6] => read_zip(&mut file).ok(),
+        _ => {
+            let mut header = [0u8; 512];
+            file.read_exact(&mut header).ok()?;
+            file.seek(SeekFrom::Start(0)).ok()?;
+            header[257..262]
+                .eq(b"ustar")
+                .then(|| read_tar(BufReader::new(file).take(MAX_TAR_BYTES)))
+                .flatten()
+        }
+    }
+}
+
+/// Where extracting `member` into `dest` writes: leading `/` is stripped
+/// unless the archiver keeps absolute names, and `strip` leading components
+/// are dropped (`tar --strip-components`). None when nothing is written.
+pub fn member_target(
+    dest: &Path,
+    name: &str,
+    absolute_names: bool,
+    strip: usize,
+) -> Option<PathBuf> {
+    let path = Path::new(name);
+    if path.is_absolute() && absolute_names {
+        return Some(normalize_path(path));
+    }
+    let relative: PathBuf = path
+        .components()
+        .filter(|c| {
+            !matches!(
+                c,
+                Component::RootDir | Component::Prefix(_) | Component::CurDir
+            )
+        })
+        .skip(strip)
+        .collect();
+    if relative.as_os_str().is_empty() {
+        return None;
+    }
+    Some(normalize_path(&dest.join(relative)))
+}
+
+// ============================================================================
+// tar
+// ============================================================================
+
+fn read_tar(mut reader: impl Read) -> Option<Vec<ArchiveMember>> {
+    let mut members = Vec::new();
+    let mut long_name = None;
+    let mut long_link = None;
+    let mut header = [0u8; 512];
+    while members.len() < MAX_MEMBERS {
+        if reader.read_exact(&mut header).is_err() || header.iter().all(|&b| b == 0) {
+            break;
+        }
+        let size = parse_octal(&header[124..136])?;
+        let padded = size.div_ceil(512) * 512;
+        let type_flag = header[156];
+        match type_flag {
+            // GNU long name/link and pax headers describe the next member
+            b'L' | b'K' | b'x' => {
+                let data = read_data(&mut reader, size, padded)?;
+                if type_flag == b'x' {
+                    for (key, value) in pax_records(&data) {
+                        match key.as_str() {
+                            "path" => long_name = Some(value),
+                            "linkpath" => long_link = Some(value),
+                            _ => {}
+                        }
+                    }
+                } else {
+                    let text = c_string(&data);
+                    if type_flag == b'L' {
+                        long_name = Some(text);
+                    } else {
+                        long_link = Some(text);
+                    }
+                }
+                continue;
+            }
+            _ => skip(&mut reader, padded).ok()?,
+        }
+        // pax global headers and volume labels aren't files
+        if matches!(type_flag, b'g' | b'V') {
+            continue;
+        }
+        let name = long_name.take().unwrap_or_else(|| {
+            let name = c_string(&header[..100]);
+            // POSIX ustar splits long names into prefix/name
+            let prefix = c_string(&header[345..500]);
+            if header[257..263] == *b"ustar\0" && !prefix.is_empty() {
+                format!("{prefix}/{name}")
+            } else {
+                name
+            }
+        });
+        let target = long_link
+            .take()
+            .unwrap_or_else(|| c_string(&header[157..257]));
+        let link = match type_flag {
+            b'1' => Some(Link::Hard(target)),
+            b'2' => Some(Link::Symbolic(target)),
+            _ => None,
+        };
+        members.push(ArchiveMember { name, link });
+    }
+    Some(members)
+}
+
+fn read_data(reader: &mut impl Read, size: u64, padded: u64) -> Option<Vec<u8>> {
+    if size > MAX_LINK_BYTES * 16 {
+        return None;
+    }
+    let mut data = vec![0u8; padded as usize];
+    reader.read_exact(&mut data).ok()?;
+    data.truncate(size as usize);
+    Some(data)
+}
+
+fn skip(reader: &mut impl Read, bytes: u64) -> io::Result<()> {
+    let copied = io::copy(&mut reader.take(bytes), &mut io::sink())?;
+    if copied < bytes {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
+}
+
+fn parse_octal(field: &[u8]) -> Option<u64> {
+    let text = c_string(field);
+    let digits = text.trim_matches(|c: char| c == ' ' || c == '\0');
+    if digits.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(digits, 8).ok()
+}
+
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// Records of a pax extended header: `<length> <key>=<value>\n`.
+fn pax_records(data: &[u8]) -> Vec<(String, String)> {
+    let text = String::from_utf8_lossy(data);
+    text.lines()
+        .filter_map(|line| {
+            let (_, record) = line.split_once(' ')?;
+            let (key, value) = record.split_once('=')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+// ============================================================================
+// zip
+// ============================================================================
+
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+
+/// Read the central directory at the end of a zip file. Symlink entries
+/// (Unix mode `120000`) store their target as the entry's data.
+fn read_zip(file: &mut File) -> io::Result<Vec<ArchiveMember>> {
+    let len = file.seek(SeekFrom::End(0))?;
+    // The end record is 22 bytes plus a comment of up to 64 KiB
+    let tail_len = len.min(22 + 65_535);
+    file.seek(SeekFrom::Start(len - tail_len))?;
+    let mut tail = vec![0u8; tail_len as usize];
+    file.read_exact(&mut tail)?;
+    let eocd = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&i| u32_at(&tail, i) == EOCD_SIGNATURE)
+        .ok_or(io::ErrorKind::InvalidData)?;
+    let entries = u16_at(&tail, eocd + 10) as usize;
+    let directory_size = u32_at(&tail, eocd + 12) as usize;
+    let directory_offset = u32_at(&tail, eocd + 16) as u64;
+
+    file.seek(SeekFrom::Start(directory_offset))?;
+    let mut directory = vec![0u8; directory_size.min(len as usize)];
+    file.read_exact(&mut directory)?;
+
+    let mut members = Vec::new();
+    let mut pos = 0;
+    for _ in 0..entries.min(MAX_MEMBERS) {
+        if pos + 46 > directory.len() || u32_at(&directory, pos) != CENTRAL_SIGNATURE {
+            break;
+        }
+        let method = u16_at(&directory, pos + 10);
+        let compressed_size = u32_at(&directory, pos + 20) as u64;
+        let name_len = u16_at(&directory, pos + 28) as usize;
+        let extra_len = u16_at(&directory, pos + 30) as usize;
+        let comment_len = u16_at(&directory, pos + 32) as usize;
+        let mode = u32_at(&directory, pos + 38) >> 16;
+        let local_offset = u32_at(&directory, pos + 42) as u64;
+        let name_end = (pos + 46 + name_len).min(directory.len());
+        let name = String::from_utf8_lossy(&directory[pos + 46..name_end]).into_owned();
+        let link = if mode & 0o170_000 == 0o120_000 {
+            read_zip_link(file, local_offset, method, compressed_size)
+                .ok()
+                .map(Link::Symbolic)
+        } else {
+            None
+        };
+        members.push(ArchiveMember { name, link });
+        pos += 46 + name_len + extra_len + comment_len;
+    }
+    Ok(members)
+}
+
+/// Read a symlink entry's target from its local header and data.
+fn read_zip_link(file: &mut File, offset: u64, method: u16, size: u64) -> io::Result<String> {
+    let mut header = [0u8; 30];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut header)?;
+    let skip = u16_at(&header, 26) as i64 + u16_at(&header, 28) as i64;
+    file.seek(SeekFrom::Current(skip))?;
+    let data = file.take(size.min(MAX_LINK_BYTES));
+    let mut target = String::new();
+    match method {
+        0 => data.take(MAX_LINK_BYTES).read_to_string(&mut target)?,
+        8 => DeflateDecoder::new(data)
+            .take(MAX_LINK_BYTES)
+            .read_to_string(&mut target)?,
+        _ => return Err(io::ErrorKind::Unsupported.into()),
+    };
+    Ok(target)
+}
+
+fn u16_at(bytes: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([bytes[pos], bytes[pos + 1]])
+}
+
+fn u32_at(bytes: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]])
+}
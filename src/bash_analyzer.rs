@@ -1,59 +1,366 @@
+use crate::archive_analyzer::{self, ARCHIVE_COMMANDS};
+use crate::container_analyzer::{self, CONTAINER_COMMANDS};
+use crate::data_flow::{self, FlowSource, FlowTracker};
+use crate::database_analyzer::{self, DATABASE_COMMANDS};
+use crate::definitions::{self, Function, Scope};
+use crate::facts::Fact;
+use crate::git_analyzer;
+use crate::infra_analyzer::{self, INFRA_COMMANDS};
 use crate::internalonly::{check_path_containment, resolve_target};
+use crate::interpreter_analyzer;
+use crate::listener_analyzer::{self, PACKAGE_RUNNERS, SERVER_COMMANDS};
+use crate::network_analyzer::{self, NETWORK_COMMANDS, PIP_COMMANDS};
+use crate::obfuscation;
+use crate::package_analyzer::{self, PACKAGE_COMMANDS};
+use crate::persistence_analyzer::{self, PERSISTENCE_COMMANDS};
+use crate::remote_analyzer::{self, REMOTE_COMMANDS};
+use crate::resource_analyzer::{self, RESOURCE_COMMANDS};
+use crate::script_analyzer::{self, AWK_COMMANDS};
 use crate::util::truncate;
 use regex::Regex;
 use std::path::Path;
 use std::sync::LazyLock;
 
 /// Maximum recursion depth for eval/bash -c parsing.
-const MAX_RECURSION: usize = 5;
+pub(crate) const MAX_RECURSION: usize = 5;
 
 /// Regex for shell output redirections: `>file`, `>>file`, `2>file`, `&>file`
-static REDIRECT_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?:\d*>{1,2}|&>)\s*(\S+)").unwrap()
-});
+pub(crate) static REDIRECT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:\d*>{1,2}|&>)\s*(\S+)").unwrap());
+
+/// A redirection to bash's network pseudo-devices: `>/dev/tcp/HOST/PORT`,
+/// `3<>/dev/udp/HOST/PORT`, `</dev/tcp/HOST/PORT`. Captures host and port.
+static DEV_SOCKET_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[<>]\s*/dev/(?:tcp|udp)/([^/\s]+)/([^/\s;&|)]+)").unwrap());
+
+/// A here-string, `<<< word`: a one-word heredoc. Captures the word.
+static HERE_STRING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<<<\s*((?:'[^']*'|"(?:[^"\\]|\\.)*"|[^\s;&|)'"])+)"#).unwrap());
+
+/// An assignment word whose value runs a command substitution: `x=$(…)`,
+/// `FOO="v$(…)" cmd`, ``x=`…` ``. Captures the substitution's opening.
+static ASSIGNED_SUBSTITUTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?:^|\s)[A-Za-z_][A-Za-z0-9_]*="?[^\s'"$`]*(\$\(|`)"#).unwrap());
+
+/// Matches of `re` that start outside quotes, so `awk '{print > "x"}'` has
+/// no redirection.
+pub(crate) fn unquoted_captures<'a>(re: &Regex, command: &'a str) -> Vec<regex::Captures<'a>> {
+    let unquoted = unquoted_offsets(command);
+    re.captures_iter(command)
+        .filter(|cap| cap.get(0).is_some_and(|m| unquoted[m.start()]))
+        .collect()
+}
+
+/// `command` with the matches of `re` that start outside quotes removed.
+pub(crate) fn strip_unquoted(re: &Regex, command: &str) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    for cap in unquoted_captures(re, command) {
+        let m = cap.get(0).unwrap();
+        out.push_str(&command[last..m.start()]);
+        last = m.end();
+    }
+    out.push_str(&command[last..]);
+    out
+}
+
+/// For each byte of `command`, whether it is outside quotes and not escaped.
+fn unquoted_offsets(command: &str) -> Vec<bool> {
+    let mut unquoted = vec![false; command.len() + 1];
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut escape_next = false;
+    for (pos, ch) in command.char_indices() {
+        if escape_next {
+            escape_next = false;
+            continue;
+        }
+        match ch {
+            '\\' if !in_single_quote => escape_next = true,
+            '\'' if !in_double_quote => in_single_quote = !in_single_quote,
+            '"' if !in_single_quote => in_double_quote = !in_double_quote,
+            _ => unquoted[pos] = !in_single_quote && !in_double_quote,
+        }
+    }
+    unquoted
+}
 
 /// File-manipulating commands whose non-flag arguments are paths.
 const FILE_COMMANDS: &[&str] = &[
-    "cat", "less", "more", "head", "tail", "cp", "mv", "rm", "touch", "mkdir",
-    "rmdir", "chmod", "chown", "ln", "stat", "file", "wc", "sort", "uniq",
-    "diff", "patch", "tee", "install", "rsync", "scp", "tar", "zip", "unzip",
-    "gzip", "gunzip", "bzip2", "xz",
+    "cat", "less", "more", "head", "tail", "cp", "mv", "rm", "touch", "mkdir", "rmdir", "chmod",
+    "chown", "ln", "stat", "file", "wc", "sort", "uniq", "diff", "patch", "tee", "install", "gzip",
+    "gunzip", "bzip2", "xz",
+];
+
+/// File commands that only read their operands.
+const READ_FILE_COMMANDS: &[&str] = &[
+    "cat", "less", "more", "head", "tail", "stat", "file", "wc", "sort", "uniq", "diff",
 ];
 
+/// File commands that read every operand but the last, which they write.
+const COPY_FILE_COMMANDS: &[&str] = &["cp", "install", "ln"];
+
 /// Search commands that take paths as non-flag arguments, but have some flags
 /// that consume an argument.
-const SEARCH_COMMANDS: &[&str] = &["rg", "grep", "find", "fd", "ag", "ack"];
+const SEARCH_COMMANDS: &[&str] = &["rg", "grep", "fd", "ag", "ack"];
 
 /// Flags for search commands that consume the next argument (so we skip it).
 const SEARCH_ARG_FLAGS: &[&str] = &[
-    "-e", "-f", "-g", "--glob", "-t", "--type", "-T", "--type-not",
-    "--iglob", "-m", "--max-count", "-A", "-B", "-C", "--context",
-    "--max-depth", "--maxdepth", "-d", "--depth", "--ignore-file",
-    "--color", "--colors", "-j", "--threads", "--path-separator",
-    "--sortr", "--sort", "-E", "--encoding", "--regex-size-limit",
-    "--dfa-size-limit", "-p", "--path", "--search-path", "--exec",
-    "--exec-batch", "-x",
+    "-e",
+    "-f",
+    "-g",
+    "--glob",
+    "-t",
+    "--type",
+    "-T",
+    "--type-not",
+    "--iglob",
+    "-m",
+    "--max-count",
+    "-A",
+    "-B",
+    "-C",
+    "--context",
+    "--max-depth",
+    "--maxdepth",
+    "-d",
+    "--depth",
+    "--ignore-file",
+    "--color",
+    "--colors",
+    "-j",
+    "--threads",
+    "--path-separator",
+    "--sortr",
+    "--sort",
+    "-E",
+    "--encoding",
+    "--regex-size-limit",
+    "--dfa-size-limit",
+    "-p",
+    "--path",
+    "--search-path",
+    "--exec",
+    "--exec-batch",
+    "-x",
+];
+
+/// `find` tests and actions that consume the next argument (so we skip it).
+const FIND_VALUE_FLAGS: &[&str] = &[
+    "-name",
+    "-iname",
+    "-path",
+    "-ipath",
+    "-wholename",
+    "-iwholename",
+    "-regex",
+    "-iregex",
+    "-regextype",
+    "-lname",
+    "-ilname",
+    "-type",
+    "-xtype",
+    "-user",
+    "-group",
+    "-uid",
+    "-gid",
+    "-perm",
+    "-size",
+    "-links",
+    "-inum",
+    "-atime",
+    "-ctime",
+    "-mtime",
+    "-amin",
+    "-cmin",
+    "-mmin",
+    "-used",
+    "-maxdepth",
+    "-mindepth",
+    "-fstype",
+    "-context",
+    "-printf",
 ];
 
-/// Execute-like commands where the first non-flag argument is a file to run.
+/// `find` tests whose value is a path that gets read (stat'd or opened).
+const FIND_PATH_FLAGS: &[&str] = &["-newer", "-anewer", "-cnewer", "-samefile", "-files0-from"];
+
+/// `find` actions that write their output to a file.
+const FIND_OUTPUT_FLAGS: &[&str] = &["-fprint", "-fprint0", "-fls"];
+
+/// `find` actions that run a command, terminated by `;` or `+`.
+const FIND_EXEC_FLAGS: &[&str] = &["-exec", "-execdir", "-ok", "-okdir"];
+
+/// Interpreters and `source`: the script operand is a file to run, found
+/// after the interpreter's own options.
 const EXEC_COMMANDS: &[&str] = &[
-    "python", "python3", "node", "ruby", "perl", "lua", "php",
-    "source", ".", "deno", "bun", "tsx", "ts-node",
+    "python", "python2", "python3", "node", "ruby", "perl", "lua", "php", "source", ".", "deno",
+    "bun", "tsx", "ts-node",
 ];
 
-/// Interpreters that support -c/-e flags for inline code execution.
-const INLINE_CODE_INTERPRETERS: &[&str] = &[
-    "python", "python3", "ruby", "perl", "lua", "php", "node",
+/// How a wrapper command (sudo, timeout, xargs, ...) takes its own arguments
+/// before handing off to the command it runs.
+struct WrapperSpec {
+    name: &'static str,
+    /// Flags that consume the following argument.
+    value_flags: &'static [&'static str],
+    /// Flags whose value (next argument or `--flag=value`) is a filesystem path.
+    path_flags: &'static [&'static str],
+    /// Positional arguments the wrapper takes before the wrapped command.
+    positional: usize,
+    /// Whether the first positional argument is a filesystem path (flock's lock file).
+    positional_is_path: bool,
+    /// Flags whose value is a complete shell command string (`su -c`, `flock -c`).
+    command_flags: &'static [&'static str],
+    /// The wrapped command is passed to a shell as one joined string (`watch`).
+    joins_args: bool,
+}
+
+impl WrapperSpec {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            value_flags: &[],
+            path_flags: &[],
+            positional: 0,
+            positional_is_path: false,
+            command_flags: &[],
+            joins_args: false,
+        }
+    }
+}
+
+/// Commands that run another command given as their trailing arguments.
+const WRAPPERS: &[WrapperSpec] = &[
+    WrapperSpec {
+        value_flags: &[
+            "-u",
+            "--user",
+            "-g",
+            "--group",
+            "-h",
+            "--host",
+            "-p",
+            "--prompt",
+            "-r",
+            "--role",
+            "-t",
+            "--type",
+            "-U",
+            "--other-user",
+            "-T",
+            "--command-timeout",
+        ],
+        path_flags: &["-C", "--close-from", "-D", "--chdir"],
+        ..WrapperSpec::new("sudo")
+    },
+    WrapperSpec {
+        value_flags: &["-u"],
+        path_flags: &["-C"],
+        ..WrapperSpec::new("doas")
+    },
+    WrapperSpec {
+        value_flags: &["-u", "--unset"],
+        path_flags: &["-C", "--chdir"],
+        command_flags: &["-S", "--split-string"],
+        ..WrapperSpec::new("env")
+    },
+    WrapperSpec {
+        value_flags: &["-s", "--signal", "-k", "--kill-after"],
+        positional: 1,
+        ..WrapperSpec::new("timeout")
+    },
+    WrapperSpec::new("nohup"),
+    WrapperSpec::new("setsid"),
+    WrapperSpec {
+        value_flags: &["-n", "--adjustment"],
+        ..WrapperSpec::new("nice")
+    },
+    WrapperSpec {
+        value_flags: &[
+            "-c",
+            "--class",
+            "-n",
+            "--classdata",
+            "-p",
+            "--pid",
+            "-P",
+            "--pgid",
+            "-u",
+            "--uid",
+        ],
+        ..WrapperSpec::new("ionice")
+    },
+    WrapperSpec {
+        value_flags: &["-i", "--input", "-o", "--output", "-e", "--error"],
+        ..WrapperSpec::new("stdbuf")
+    },
+    WrapperSpec {
+        value_flags: &["-f", "--format"],
+        path_flags: &["-o", "--output"],
+        ..WrapperSpec::new("time")
+    },
+    WrapperSpec::new("command"),
+    WrapperSpec::new("builtin"),
+    WrapperSpec {
+        value_flags: &["-a"],
+        ..WrapperSpec::new("exec")
+    },
+    WrapperSpec {
+        value_flags: &["-s", "--shell", "-g", "--group", "-G", "--supp-group"],
+        command_flags: &["-c", "--command"],
+        // `su [user]` without -c starts an interactive shell, never a command
+        positional: usize::MAX,
+        ..WrapperSpec::new("su")
+    },
+    WrapperSpec {
+        value_flags: &[
+            "-d",
+            "--delimiter",
+            "-E",
+            "--eof",
+            "-I",
+            "--replace",
+            "-L",
+            "--max-lines",
+            "-n",
+            "--max-args",
+            "-P",
+            "--max-procs",
+            "-s",
+            "--max-chars",
+            "--process-slot-var",
+        ],
+        path_flags: &["-a", "--arg-file"],
+        ..WrapperSpec::new("xargs")
+    },
+    WrapperSpec {
+        value_flags: &["-n", "--interval", "-d", "--differences", "-q", "--equexit"],
+        joins_args: true,
+        ..WrapperSpec::new("watch")
+    },
+    WrapperSpec {
+        value_flags: &["-w", "--wait", "--timeout", "-E", "--conflict-exit-code"],
+        command_flags: &["-c", "--command"],
+        positional: 1,
+        positional_is_path: true,
+        ..WrapperSpec::new("flock")
+    },
+];
+
+/// Shells that execute code read from stdin when given no script operand.
+const SHELLS: &[&str] = &[
+    "sh", "bash", "zsh", "dash", "ksh", "mksh", "ash", "fish", "csh", "tcsh",
+];
+
+/// Interpreters that execute code read from stdin when given no script operand or `-`.
+const STDIN_INTERPRETERS: &[&str] = &[
+    "python", "python2", "python3", "node", "ruby", "perl", "php", "lua", "Rscript",
 ];
 
 /// Download commands where specific flags point to output paths.
 const DOWNLOAD_COMMANDS: &[&str] = &["curl", "wget"];
 
-/// Regex to extract absolute paths and home paths from inline code strings.
-static PATH_IN_CODE_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"(?:/[a-zA-Z0-9_.@-][a-zA-Z0-9_.@/-]*|~/[a-zA-Z0-9_.@/-]+|\$HOME/[a-zA-Z0-9_.@/-]+)"#).unwrap()
-});
-
 // ============================================================================
 // Extracted path types
 // ============================================================================
@@ -76,10 +383,16 @@ pub enum PathContext {
     CdImplicitHome,
     /// cd - (unpredictable navigation)
     CdDash,
-    /// Argument to a file-manipulating command (cat, cp, mv, rm, etc.)
+    /// Argument a file command only reads (cat, head, the sources of cp, etc.)
     FileCommandArg,
+    /// Argument a file command creates or modifies (rm, mv, touch, the destination of cp, etc.)
+    FileCommandWrite,
     /// Path argument to a search command (rg, grep, find, fd, etc.)
     SearchCommandArg,
+    /// Search root of a `find` that uses `-delete`
+    FindDelete,
+    /// Output file of a `find` action (-fprint, -fprintf, -fls)
+    FindOutput,
     /// Script/file argument to an exec command (python, node, etc.)
     ExecTarget,
     /// Path found inside inline code (python -c, node -e, etc.)
@@ -94,25 +407,117 @@ pub enum PathContext {
     UploadData,
     /// File argument to sed
     SedFile,
-    /// Path argument to dd (if=, of=)
+    /// File edited in place by `sed -i`
+    SedInPlace,
+    /// Input path of dd (if=)
     DdPath,
+    /// Output path of dd (of=)
+    DdOutput,
+    /// Path argument to a wrapper command (flock lock file, time -o, xargs -a)
+    WrapperArg,
+    /// Repository, work tree or target path of a git command, with its label
+    /// (`git -C directory`, `git clone target`, ...) and whether git writes there
+    GitPath { label: &'static str, write: bool },
+    /// Host side of a container bind mount, `docker cp` or `docker build`,
+    /// with its label and whether the container or CLI writes there
+    ContainerHostPath { label: &'static str, write: bool },
+    /// Archive, extraction directory, source or file list of an archiver
+    /// (`tar -C`, `unzip -d`, `7z -o`), with its label and whether it is written
+    ArchivePath { label: &'static str, write: bool },
+    /// Database file, SQL script or output file of a database CLI (`sqlite3
+    /// app.db`, `psql -f`, `\o file`), with its label and whether it is written
+    DatabasePath { label: &'static str, write: bool },
+    /// File a sed, awk or perl script reads or writes (`w file`, `print >
+    /// "file"`, `getline < file`, awk input files), and whether it is written
+    ScriptPath { write: bool },
+    /// Path-valued interpreter option (`ruby -I`, `node --env-file`, `deno
+    /// --allow-write=`), with its label and whether the interpreter writes there
+    InterpreterPath { label: &'static str, write: bool },
+    /// Local side of an scp/rsync transfer with a remote host: a file sent
+    /// to it (upload) or a destination written from it (download)
+    RemoteTransfer { upload: bool },
+    /// Directory a file server (`python -m http.server`, `php -S`) serves
+    ServedDirectory,
     /// Path-like argument to an unrecognized command
     UnknownCommandArg,
 }
 
 impl PathContext {
+    /// Whether the command creates, modifies or deletes the path.
+    pub fn is_write(&self) -> bool {
+        match self {
+            PathContext::Redirection
+            | PathContext::FileCommandWrite
+            | PathContext::FindDelete
+            | PathContext::FindOutput
+            | PathContext::DownloadOutput
+            | PathContext::SedInPlace
+            | PathContext::DdOutput => true,
+            PathContext::GitPath { write, .. }
+            | PathContext::ContainerHostPath { write, .. }
+            | PathContext::InterpreterPath { write, .. }
+            | PathContext::ArchivePath { write, .. }
+            | PathContext::DatabasePath { write, .. }
+            | PathContext::ScriptPath { write, .. } => *write,
+            PathContext::RemoteTransfer { upload } => !upload,
+            _ => false,
+        }
+    }
+
     /// Label for use in containment error messages.
     pub fn label(&self) -> &str {
         match self {
             PathContext::Redirection => "redirection target",
             PathContext::DownloadOutput => "download output path",
             PathContext::UploadData => "upload/data file path",
+            PathContext::FindDelete => "find -delete target",
+            PathContext::FindOutput => "find output file",
+            PathContext::RemoteTransfer { upload: true } => "upload source",
+            PathContext::RemoteTransfer { upload: false } => "download destination",
+            PathContext::ServedDirectory => "served directory",
+            PathContext::ScriptPath { write: true } => "script output file",
+            PathContext::ScriptPath { write: false } => "script input file",
+            PathContext::GitPath { label, .. }
+            | PathContext::ContainerHostPath { label, .. }
+            | PathContext::InterpreterPath { label, .. }
+            | PathContext::ArchivePath { label, .. }
+            | PathContext::DatabasePath { label, .. } => label,
             // InlineCodeRef has custom message handling in check_extracted_path
             _ => "path",
         }
     }
 }
 
+/// Everything the analyzer learned about a bash command.
+#[derive(Debug, Default)]
+pub struct CommandAnalysis {
+    pub paths: Vec<ExtractedPath>,
+    pub findings: Vec<Finding>,
+}
+
+/// A risky construct recognised in a bash command that isn't tied to a single path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Finding {
+    /// A pipeline feeds the output of earlier stages to a shell or interpreter
+    /// reading code from stdin (`curl … | sh`).
+    PipeToInterpreter { interpreter: String, source: String },
+    /// The command uses techniques that hide what it runs (encoded payloads,
+    /// dynamic eval, variable indirection, ...).
+    Obfuscation { score: u32, techniques: Vec<String> },
+    /// Part of the command could not be analyzed, so its paths and findings
+    /// may be incomplete.
+    Unanalyzable { command: String, reason: String },
+    /// A structured observation for fact rules to match (`git.push.force`, ...).
+    Fact(Fact),
+    /// Data read from `source` by `reader` reaches a network sink, through
+    /// a pipeline, a file written earlier, or a variable.
+    DataFlow {
+        source: FlowSource,
+        reader: String,
+        sink: Fact,
+    },
+}
+
 // ============================================================================
 // Public API
 // ============================================================================
@@ -135,9 +540,31 @@ pub fn analyze(command: &str, project_root: &Path) -> Option<String> {
 /// Extract all filesystem paths referenced by a bash command.
 /// Returns structured results with context about how each path was referenced.
 pub fn extract_paths(command: &str) -> Vec<ExtractedPath> {
-    let mut paths = Vec::new();
-    extract_paths_recursive(command, &mut paths, 0);
-    paths
+    analyze_command(command).paths
+}
+
+/// Run the full analysis of a bash command: referenced paths plus risky
+/// constructs that aren't tied to a single path.
+pub fn analyze_command(command: &str) -> CommandAnalysis {
+    let mut out = CommandAnalysis::default();
+    extract_paths_recursive(command, &mut out, 0);
+    // Writes to shell startup files, autostart entries, cron and unit directories
+    let startup: Vec<Finding> = out
+        .paths
+        .iter()
+        .filter(|ep| ep.context.is_write())
+        .filter_map(|ep| persistence_analyzer::startup_file_fact(command, &ep.raw))
+        .map(Finding::Fact)
+        .collect();
+    out.findings.extend(startup);
+    let obfuscation = obfuscation::score(command);
+    if obfuscation.score > 0 {
+        out.findings.push(Finding::Obfuscation {
+            score: obfuscation.score,
+            techniques: obfuscation.techniques,
+        });
+    }
+    out
 }
 
 // ============================================================================
@@ -175,97 +602,666 @@ fn check_extracted_path(ep: &ExtractedPath, project_root: &Path) -> Option<Strin
 // Extraction engine (single parser — the only place command structure is parsed)
 // ============================================================================
 
-fn extract_paths_recursive(command: &str, paths: &mut Vec<ExtractedPath>, depth: usize) {
+pub(crate) fn extract_paths_recursive(command: &str, out: &mut CommandAnalysis, depth: usize) {
+    extract_in_scope(command, out, depth, &mut Scope::default());
+}
+
+/// Analyse `command` with the functions and aliases in `scope` defined.
+/// Definitions it makes are added to `scope` as they are reached.
+fn extract_in_scope(command: &str, out: &mut CommandAnalysis, depth: usize, scope: &mut Scope) {
     if depth > MAX_RECURSION {
+        out.findings.push(Finding::Unanalyzable {
+            command: command.to_string(),
+            reason: format!("nested more than {MAX_RECURSION} levels deep"),
+        });
         return;
     }
 
-    // Collect redirection targets
-    for cap in REDIRECT_RE.captures_iter(command) {
-        let target = &cap[1];
-        if !target.starts_with("/dev/") {
-            paths.push(ExtractedPath {
-                raw: target.to_string(),
-                context: PathContext::Redirection,
+    // Heredoc bodies are stdin, not command text
+    let (stripped, heredocs) = strip_heredocs(command);
+    let command = stripped.as_str();
+
+    // Function bodies are analysed where they are defined and called
+    let (without_functions, functions) = definitions::take_functions(command);
+    let command = without_functions.as_str();
+
+    // Endless loops span several sub-commands
+    resource_analyzer::scan_loops(command, out);
+
+    // Split on shell operators and extract paths from each sub-command
+    let segments = split_shell_segments(command);
+    let mut flows = FlowTracker::default();
+    // Where an earlier `cd` in the list moved to, which relative paths are under
+    let mut cwd: Option<String> = None;
+    // `NAME=value` words exported so far, which later commands inherit
+    let mut exported: Vec<String> = Vec::new();
+    for (idx, seg) in segments.iter().enumerate() {
+        // `a & b` starts `a` in the background, then runs `b`
+        for (job, (command, background)) in split_background(&seg.command).into_iter().enumerate() {
+            let (trimmed, bodies) = take_heredocs(command.trim(), &heredocs);
+            let trimmed = trimmed.as_str();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let first_word = trimmed.split_whitespace().next();
+            if let Some(function) = functions.iter().find(|f| first_word == Some(&f.marker)) {
+                define_function(function, out, depth, scope);
+                continue;
+            }
+            if let Some(aliases) = scope.define_aliases(trimmed) {
+                // `alias ll='cat ~/.ssh/id_rsa'` runs its value wherever `ll` is used
+                for (name, value) in aliases {
+                    extract_in_scope(&value, out, depth + 1, &mut scope.entering(&name));
+                }
+                continue;
+            }
+            let preceded_by = if job == 0 {
+                seg.preceded_by
+            } else {
+                Some(ShellOperator::Sequence)
+            };
+            let call = scope.expand(trimmed);
+            if preceded_by == Some(ShellOperator::Pipe) {
+                // A function or alias reads the pipe with the first command it runs
+                let first = call
+                    .as_ref()
+                    .and_then(|c| split_shell_segments(&c.command).into_iter().next());
+                let reader = first.as_ref().map_or(trimmed, |s| s.command.trim());
+                detect_code_from_stdin(reader, segments[idx - 1].command.trim(), out);
+                extract_piped_sql(
+                    reader,
+                    segments[idx - 1].command.trim(),
+                    &exported,
+                    out,
+                    depth,
+                );
+            }
+            let mut stage = CommandAnalysis::default();
+            if background {
+                stage
+                    .findings
+                    .push(Finding::Fact(persistence_analyzer::background_fact(
+                        trimmed, "&",
+                    )));
+            }
+            for cap in unquoted_captures(&REDIRECT_RE, trimmed) {
+                // `> "${HOME}/.bashrc"` writes the unquoted word
+                let target = shlex::split(&cap[1])
+                    .map_or_else(|| cap[1].to_string(), |words| words.concat());
+                // `>(cmd)` is a process substitution, not a file
+                if !target.is_empty() && !target.starts_with("/dev/") && !target.starts_with('(') {
+                    stage.paths.push(ExtractedPath {
+                        raw: target,
+                        context: PathContext::Redirection,
+                    });
+                }
+            }
+            for cap in unquoted_captures(&DEV_SOCKET_RE, trimmed) {
+                let fact = network_analyzer::connect_fact(
+                    trimmed,
+                    "bash",
+                    &cap[1],
+                    Some(&cap[2].to_string()),
+                );
+                stage.findings.push(Finding::Fact(fact));
+            }
+            match call {
+                Some(call) => {
+                    extract_in_scope(
+                        &call.command,
+                        &mut stage,
+                        depth + 1,
+                        &mut scope.entering(&call.name),
+                    );
+                }
+                None => extract_paths_from_sub_command(trimmed, &exported, &mut stage, depth),
+            }
+            for heredoc in bodies {
+                let operator = format!("<<{}", heredoc.delimiter);
+                extract_heredoc_code(
+                    trimmed,
+                    &exported,
+                    &operator,
+                    &heredoc.body,
+                    &mut stage,
+                    depth,
+                );
+            }
+            for cap in unquoted_captures(&HERE_STRING_RE, trimmed) {
+                if let Some(words) = shlex::split(&cap[1]) {
+                    extract_heredoc_code(
+                        trimmed,
+                        &exported,
+                        "<<<",
+                        &words.concat(),
+                        &mut stage,
+                        depth,
+                    );
+                }
+            }
+            if let Some(dir) = &cwd {
+                for ep in &mut stage.paths {
+                    if let Some(raw) = under_directory(dir, ep) {
+                        ep.raw = raw;
+                    }
+                }
+            }
+            // Pipeline stages and background jobs run in subshells
+            let piped = preceded_by == Some(ShellOperator::Pipe)
+                || segments
+                    .get(idx + 1)
+                    .is_some_and(|s| s.preceded_by == Some(ShellOperator::Pipe));
+            if !piped && !background {
+                cwd = changed_directory(trimmed, &stage, cwd);
+                update_exports(trimmed, &mut exported);
+            }
+            let flow_findings = flows.step(trimmed, preceded_by, &stage, depth);
+            out.paths.extend(stage.paths);
+            out.findings.extend(stage.findings);
+            out.findings.extend(flow_findings);
+        }
+    }
+
+    // Analyze what encoded payloads decode to
+    for payload in obfuscation::decode_payloads(command) {
+        extract_paths_recursive(&payload, out, depth + 1);
+    }
+}
+
+/// `ep`'s path as seen from `dir`, when it is relative to the working directory.
+fn under_directory(dir: &str, ep: &ExtractedPath) -> Option<String> {
+    let relative = !ep.raw.is_empty()
+        && ep.raw != "-"
+        && !ep.raw.starts_with(['/', '~', '$'])
+        && !matches!(
+            ep.context,
+            PathContext::CdImplicitHome | PathContext::CdDash
+        );
+    relative.then(|| format!("{}/{}", dir.trim_end_matches('/'), ep.raw))
+}
+
+/// The working directory after `sub_cmd`: the target of a `cd` (already
+/// resolved against `cwd` in `stage`), `~` for a bare `cd`, or unknown after
+/// `cd -`. Other commands leave it as it was.
+fn changed_directory(
+    sub_cmd: &str,
+    stage: &CommandAnalysis,
+    cwd: Option<String>,
+) -> Option<String> {
+    let tokens = shlex::split(sub_cmd).unwrap_or_default();
+    let start = unwrap_command(&tokens).start;
+    if tokens.get(start).is_none_or(|t| t != "cd") {
+        return cwd;
+    }
+    stage.paths.iter().rev().find_map(|ep| match ep.context {
+        PathContext::CdTarget => Some(Some(ep.raw.clone())),
+        PathContext::CdImplicitHome => Some(Some("~".to_string())),
+        PathContext::CdDash => Some(None),
+        _ => None,
+    })?
+}
+
+/// Track `export NAME=value` and `unset NAME` in `exported`.
+fn update_exports(sub_cmd: &str, exported: &mut Vec<String>) {
+    let tokens = shlex::split(sub_cmd).unwrap_or_default();
+    let Some((cmd, args)) = tokens.split_first() else {
+        return;
+    };
+    let name = |word: &str| word.split('=').next().unwrap_or_default().to_string();
+    match cmd.as_str() {
+        "export" => {
+            for assignment in args
+                .iter()
+                .filter(|a| a.contains('=') && !a.starts_with('-'))
+            {
+                exported.retain(|e| name(e) != name(assignment));
+                exported.push(assignment.clone());
+            }
+        }
+        "unset" => exported.retain(|e| !args.contains(&name(e))),
+        _ => {}
+    }
+}
+
+/// The environment a command runs with: what was exported, then its own
+/// `NAME=value` prefix, which wins.
+fn command_env(exported: &[String], prefix: &[String]) -> Vec<String> {
+    exported.iter().chain(prefix).cloned().collect()
+}
+
+/// Define a function cut out of the command and analyse its body, as
+/// though it ran where it is defined.
+fn define_function(
+    function: &Function,
+    out: &mut CommandAnalysis,
+    depth: usize,
+    scope: &mut Scope,
+) {
+    resource_analyzer::extract_function(&function.name, &function.body, &function.source, out);
+    extract_in_scope(
+        &function.body,
+        out,
+        depth + 1,
+        &mut scope.entering(&function.name),
+    );
+    scope.define_function(&function.name, &function.body);
+}
+
+/// When `stage` is a shell or interpreter reading its program from stdin,
+/// analyse the heredoc or here-string (`operator`) fed to it: shell bodies
+/// as commands, interpreter bodies as inline code. Heredocs fed to a
+/// database CLI are its SQL.
+fn extract_heredoc_code(
+    stage: &str,
+    exported: &[String],
+    operator: &str,
+    body: &str,
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let cleaned = strip_unquoted(&HERE_STRING_RE, &strip_unquoted(&REDIRECT_RE, stage));
+    let Some(tokens) = shlex::split(&cleaned) else {
+        return;
+    };
+    let start = command_start(&tokens);
+    let Some(cmd) = tokens.get(start) else {
+        return;
+    };
+    let name = cmd.rsplit('/').next().unwrap_or(cmd);
+    if DATABASE_COMMANDS.contains(&name) {
+        let env = command_env(exported, &tokens[..start]);
+        let args = &tokens[start + 1..];
+        let connect = database_analyzer::connect_target(name, stage.trim(), &env, args);
+        database_analyzer::extract_database_input(&connect, body, "heredoc", out, depth);
+        return;
+    }
+    if !reads_code_from_stdin(name, &tokens[start + 1..]) && name != "at" && name != "batch" {
+        return;
+    }
+    if SHELLS.contains(&name) || name == "at" || name == "batch" {
+        extract_paths_recursive(body, out, depth + 1);
+    } else {
+        interpreter_analyzer::extract_inline_code(name, operator, body, out, depth);
+    }
+}
+
+/// When `stage` is a database CLI reading the literal output of an `echo`
+/// or `printf` (`echo 'DROP TABLE t' | psql`), analyse that output as its
+/// SQL.
+fn extract_piped_sql(
+    stage: &str,
+    source: &str,
+    exported: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let cleaned = strip_unquoted(&REDIRECT_RE, stage);
+    let Some(tokens) = shlex::split(&cleaned) else {
+        return;
+    };
+    let start = command_start(&tokens);
+    let Some(cmd) = tokens.get(start) else {
+        return;
+    };
+    let name = cmd.rsplit('/').next().unwrap_or(cmd);
+    if DATABASE_COMMANDS.contains(&name)
+        && let Some(input) = literal_output(source)
+    {
+        let env = command_env(exported, &tokens[..start]);
+        let args = &tokens[start + 1..];
+        let connect = database_analyzer::connect_target(name, stage.trim(), &env, args);
+        database_analyzer::extract_database_input(&connect, &input, "pipe", out, depth);
+    }
+}
+
+/// What `echo …` or `printf FORMAT` prints, when it is fixed text: no
+/// expansions outside single quotes and no printf arguments to fill in.
+fn literal_output(source: &str) -> Option<String> {
+    let unquoted = unquoted_offsets(source);
+    let mut in_single_quote = false;
+    for (pos, ch) in source.char_indices() {
+        if ch == '\'' && (unquoted[pos] || in_single_quote) {
+            in_single_quote = !in_single_quote;
+        } else if (ch == '$' || ch == '`') && !in_single_quote {
+            return None;
+        }
+    }
+    let tokens = shlex::split(&strip_unquoted(&REDIRECT_RE, source))?;
+    let start = command_start(&tokens);
+    let (cmd, args) = tokens[start..].split_first()?;
+    let unescape = |text: &str| text.replace("\\n", "\n").replace("\\t", "\t");
+    match cmd.as_str() {
+        "echo" => {
+            let flags = args
+                .iter()
+                .take_while(|a| matches!(a.as_str(), "-n" | "-e" | "-E" | "-ne" | "-en"));
+            let escapes = flags.clone().any(|a| a.contains('e'));
+            let text = args[flags.count()..].join(" ");
+            Some(if escapes { unescape(&text) } else { text })
+        }
+        "printf" => match args {
+            [format] => Some(unescape(format)),
+            [dashes, format] if dashes == "--" => Some(unescape(format)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Record a finding when a pipeline stage runs a shell or interpreter that
+/// reads its program from stdin, i.e. executes whatever `source` produced.
+pub(crate) fn detect_code_from_stdin(stage: &str, source: &str, out: &mut CommandAnalysis) {
+    // `curl x | (sh)` and `curl x | { bash; }` feed the group's first command
+    let stage = stage
+        .trim()
+        .trim_start_matches(['(', '{'])
+        .trim_end_matches([')', '}']);
+    let cleaned = strip_unquoted(&REDIRECT_RE, stage);
+    let Some(tokens) = shlex::split(&cleaned) else {
+        return;
+    };
+    let start = command_start(&tokens);
+    let Some(cmd) = tokens.get(start) else {
+        return;
+    };
+    let name = cmd.rsplit('/').next().unwrap_or(cmd);
+    if reads_code_from_stdin(name, &tokens[start + 1..]) {
+        out.findings.push(Finding::PipeToInterpreter {
+            interpreter: name.to_string(),
+            source: source.to_string(),
+        });
+    }
+}
+
+/// Report a shell, interpreter, `source` or `.` whose script is a process
+/// substitution (`bash <(curl …)`): it runs whatever the substitution prints.
+fn detect_code_from_substitution(
+    cmd: &str,
+    args: &[String],
+    substituted: &[(String, String)],
+    out: &mut CommandAnalysis,
+) {
+    let name = cmd.rsplit('/').next().unwrap_or(cmd);
+    let script = match name {
+        "source" | "." => args.first().map(String::as_str),
+        _ => match program_source(name, args) {
+            Some(ProgramSource::Operand(script)) => Some(script),
+            _ => None,
+        },
+    };
+    if let Some((_, body)) = substituted
+        .iter()
+        .find(|(fd, _)| Some(fd.as_str()) == script)
+    {
+        out.findings.push(Finding::PipeToInterpreter {
+            interpreter: name.to_string(),
+            source: body.clone(),
+        });
+    }
+}
+
+/// Report `eval "$(curl …)"` and `sh -c "$(wget -O- …)"`: `code` runs
+/// whatever a network command in its substitutions downloads.
+fn detect_fetched_code(interpreter: &str, code: &str, out: &mut CommandAnalysis, depth: usize) {
+    for body in data_flow::substitutions(code) {
+        let mut fetch = CommandAnalysis::default();
+        extract_paths_recursive(&body, &mut fetch, depth + 1);
+        let downloads = fetch.findings.iter().any(|f| {
+            matches!(f, Finding::Fact(fact) if matches!(fact.name.as_str(), "network.request" | "network.connect"))
+        });
+        out.paths.extend(fetch.paths);
+        out.findings.extend(fetch.findings);
+        if downloads {
+            out.findings.push(Finding::PipeToInterpreter {
+                interpreter: interpreter.to_string(),
+                source: body,
             });
         }
     }
+}
 
-    // Split on shell operators and extract paths from each sub-command
-    let sub_commands = split_shell_operators(command);
-    for sub_cmd in &sub_commands {
-        let trimmed = sub_cmd.trim();
-        if trimmed.is_empty() {
+/// Where a shell or interpreter reads its program from.
+enum ProgramSource<'a> {
+    Stdin,
+    /// `-c`, `-e` and friends
+    Inline,
+    Operand(&'a str),
+}
+
+/// Whether `cmd args…` executes a program read from stdin: a shell or
+/// interpreter with no script operand, `-`, `/dev/stdin`, or `sh -s`.
+fn reads_code_from_stdin(cmd: &str, args: &[String]) -> bool {
+    matches!(program_source(cmd, args), Some(ProgramSource::Stdin))
+}
+
+/// Where `cmd args…` reads its program from, or None when `cmd` is not a
+/// shell or interpreter.
+fn program_source<'a>(cmd: &str, args: &'a [String]) -> Option<ProgramSource<'a>> {
+    let (code_flags, value_flags): (&[&str], &[&str]) = if SHELLS.contains(&cmd) {
+        (
+            &["-c"],
+            &["-o", "+o", "-O", "+O", "--rcfile", "--init-file"],
+        )
+    } else if STDIN_INTERPRETERS.contains(&cmd) {
+        (
+            &["-c", "-e", "-E", "--eval", "-p", "--print", "-m", "-r"],
+            &[
+                "-W",
+                "-X",
+                "-I",
+                "--require",
+                "--import",
+                "--loader",
+                "--input-type",
+            ],
+        )
+    } else {
+        return None;
+    };
+
+    let operand = |arg: &'a str| match arg {
+        "-" | "/dev/stdin" => ProgramSource::Stdin,
+        script => ProgramSource::Operand(script),
+    };
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        if code_flags.contains(&arg) {
+            return Some(ProgramSource::Inline);
+        }
+        if arg == "-s" && SHELLS.contains(&cmd) {
+            return Some(ProgramSource::Stdin);
+        }
+        if value_flags.contains(&arg) {
+            i += 2;
+            continue;
+        }
+        if arg == "--" {
+            return Some(args.get(i + 1).map_or(ProgramSource::Stdin, |a| operand(a)));
+        }
+        if arg != "-" && (arg.starts_with('-') || arg.starts_with('+')) {
+            i += 1;
             continue;
         }
-        extract_paths_from_sub_command(trimmed, paths, depth);
+        // A script operand — the program comes from a file, not stdin
+        return Some(operand(arg));
+    }
+    Some(ProgramSource::Stdin)
+}
+
+/// `command` with each `<(…)` and `>(…)` outside quotes replaced by the
+/// `/dev/fd` path bash passes in its place, and the (path, body) pairs.
+fn replace_process_substitutions(command: &str) -> (String, Vec<(String, String)>) {
+    let chars: Vec<char> = command.chars().collect();
+    let mut out = String::new();
+    let mut substituted = Vec::new();
+    let mut quote = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                out.push(c);
+                i += 1;
+            }
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '<' | '>')
+                if chars.get(i + 1) == Some(&'(')
+                    && i.checked_sub(1).is_none_or(|p| chars[p] != c) =>
+            {
+                let mut level = 0;
+                let mut j = i + 1;
+                while j < chars.len() {
+                    match chars[j] {
+                        '(' => level += 1,
+                        ')' => level -= 1,
+                        _ => {}
+                    }
+                    if level == 0 {
+                        break;
+                    }
+                    j += 1;
+                }
+                let fd = format!("/dev/fd/{}", 63 - substituted.len());
+                out.push_str(&fd);
+                substituted.push((fd, chars[i + 2..j.min(chars.len())].iter().collect()));
+                i = j + 1;
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(&c) = chars.get(i) {
+            out.push(c);
+        }
+        i += 1;
     }
+    (out, substituted)
 }
 
-fn extract_paths_from_sub_command(sub_cmd: &str, paths: &mut Vec<ExtractedPath>, depth: usize) {
-    let cleaned = REDIRECT_RE.replace_all(sub_cmd, "");
+fn extract_paths_from_sub_command(
+    sub_cmd: &str,
+    exported: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    // `x=$(cat /etc/shadow)` runs its substitution even with no command
+    for cap in unquoted_captures(&ASSIGNED_SUBSTITUTION_RE, sub_cmd) {
+        let open = cap.get(1).unwrap().start();
+        if let Some(body) = data_flow::substitutions(&sub_cmd[open..])
+            .into_iter()
+            .next()
+        {
+            extract_paths_recursive(&body, out, depth + 1);
+        }
+    }
+    // `<(cmd)` and `>(cmd)` run cmd alongside the command
+    let (replaced, substituted) = replace_process_substitutions(sub_cmd);
+    for (_, body) in &substituted {
+        extract_paths_recursive(body, out, depth + 1);
+    }
+    let cleaned = strip_unquoted(&REDIRECT_RE, &replaced);
     let tokens = match shlex::split(&cleaned) {
         Some(t) => t,
-        None => return,
+        None => {
+            out.findings.push(Finding::Unanalyzable {
+                command: sub_cmd.to_string(),
+                reason: "unbalanced quotes or escapes".to_string(),
+            });
+            return;
+        }
     };
     if tokens.is_empty() {
         return;
     }
 
-    // Skip env var prefixes and sudo/env
-    let mut start = 0;
-    while start < tokens.len() {
-        let t = &tokens[start];
-        if t.contains('=') && !t.starts_with('-') && !t.starts_with('/') {
-            start += 1;
-        } else if t == "sudo" || t == "env" {
-            start += 1;
-        } else {
-            break;
-        }
+    let unwrapped = unwrap_command(&tokens);
+    for raw in unwrapped.wrapper_paths {
+        out.paths.push(ExtractedPath {
+            raw,
+            context: PathContext::WrapperArg,
+        });
+    }
+    // `nohup cmd`, `setsid cmd` outlive the session
+    for via in unwrapped
+        .wrappers
+        .iter()
+        .filter(|w| *w == "nohup" || *w == "setsid")
+    {
+        out.findings
+            .push(Finding::Fact(persistence_analyzer::background_fact(
+                sub_cmd, via,
+            )));
     }
+    if let Some(inner) = &unwrapped.inline_command {
+        extract_paths_recursive(inner, out, depth + 1);
+    }
+    let start = unwrapped.start;
     if start >= tokens.len() {
         return;
     }
 
     let cmd_name = &tokens[start];
     let args = &tokens[start + 1..];
+    let env = command_env(exported, &tokens[..start]);
+    detect_code_from_substitution(cmd_name, args, &substituted, out);
+
+    if obfuscation::is_dynamic_word(cmd_name) {
+        out.findings.push(Finding::Unanalyzable {
+            command: sub_cmd.to_string(),
+            reason: "the command name is computed at runtime".to_string(),
+        });
+    }
 
     match cmd_name.as_str() {
         "cd" => {
             if args.is_empty() {
-                paths.push(ExtractedPath {
+                out.paths.push(ExtractedPath {
                     raw: String::new(),
                     context: PathContext::CdImplicitHome,
                 });
             } else if args[0] == "-" {
-                paths.push(ExtractedPath {
+                out.paths.push(ExtractedPath {
                     raw: "-".to_string(),
                     context: PathContext::CdDash,
                 });
             } else {
-                paths.push(ExtractedPath {
+                out.paths.push(ExtractedPath {
                     raw: args[0].clone(),
                     context: PathContext::CdTarget,
                 });
             }
         }
         "eval" => {
+            if args.iter().any(|a| a.contains('$') || a.contains('`')) {
+                out.findings.push(Finding::Unanalyzable {
+                    command: sub_cmd.to_string(),
+                    reason: "eval runs a string computed at runtime".to_string(),
+                });
+            }
+            detect_fetched_code(cmd_name, &args.join(" "), out, depth);
             if !args.is_empty() {
-                extract_paths_recursive(&args.join(" "), paths, depth + 1);
+                extract_paths_recursive(&args.join(" "), out, depth + 1);
             }
         }
         "bash" | "sh" | "zsh" | "dash" => {
-            if let Some(pos) = args.iter().position(|t| t == "-c") {
-                if let Some(inner) = args.get(pos + 1) {
-                    extract_paths_recursive(inner, paths, depth + 1);
-                    return;
+            if let Some(pos) = args.iter().position(|t| t == "-c")
+                && let Some(inner) = args.get(pos + 1)
+            {
+                if obfuscation::is_dynamic_word(inner) {
+                    out.findings.push(Finding::Unanalyzable {
+                        command: sub_cmd.to_string(),
+                        reason: format!("'{cmd_name} -c' runs a script computed at runtime"),
+                    });
                 }
+                detect_fetched_code(cmd_name, inner, out, depth);
+                extract_paths_recursive(inner, out, depth + 1);
+                return;
             }
             // Treat as script execution
             for arg in args {
                 if !arg.starts_with('-') {
-                    paths.push(ExtractedPath {
+                    out.paths.push(ExtractedPath {
                         raw: arg.clone(),
                         context: PathContext::ExecTarget,
                     });
@@ -274,15 +1270,33 @@ fn extract_paths_from_sub_command(sub_cmd: &str, paths: &mut Vec<ExtractedPath>,
             }
         }
         cmd if FILE_COMMANDS.contains(&cmd) => {
-            for arg in args {
-                if !arg.starts_with('-') {
-                    paths.push(ExtractedPath {
-                        raw: arg.clone(),
-                        context: PathContext::FileCommandArg,
-                    });
-                }
+            // `cat /dev/zero > f`, `head -c 10G /dev/urandom > f`
+            resource_analyzer::extract_resource(cmd, sub_cmd, args, out);
+            let operands: Vec<_> = args.iter().filter(|a| !a.starts_with('-')).collect();
+            for (idx, arg) in operands.iter().enumerate() {
+                let write = if READ_FILE_COMMANDS.contains(&cmd) {
+                    false
+                } else if cmd == "ln" {
+                    // A link lets later writes reach its target under another name
+                    true
+                } else if COPY_FILE_COMMANDS.contains(&cmd) {
+                    idx + 1 == operands.len()
+                } else {
+                    true
+                };
+                out.paths.push(ExtractedPath {
+                    raw: (*arg).clone(),
+                    context: if write {
+                        PathContext::FileCommandWrite
+                    } else {
+                        PathContext::FileCommandArg
+                    },
+                });
             }
         }
+        "find" => {
+            extract_find_paths(args, out, depth);
+        }
         cmd if SEARCH_COMMANDS.contains(&cmd) => {
             let mut i = 0;
             while i < args.len() {
@@ -299,7 +1313,7 @@ fn extract_paths_from_sub_command(sub_cmd: &str, paths: &mut Vec<ExtractedPath>,
                     i += 1;
                     continue;
                 }
-                paths.push(ExtractedPath {
+                out.paths.push(ExtractedPath {
                     raw: arg.clone(),
                     context: PathContext::SearchCommandArg,
                 });
@@ -307,86 +1321,133 @@ fn extract_paths_from_sub_command(sub_cmd: &str, paths: &mut Vec<ExtractedPath>,
             }
         }
         cmd if EXEC_COMMANDS.contains(&cmd) => {
-            // Check for inline code interpreters first
-            if INLINE_CODE_INTERPRETERS.contains(&cmd) {
-                let code_flags: &[&str] = match cmd {
-                    "node" => &["-e", "--eval"],
-                    _ => &["-c", "-e"],
-                };
-                if let Some(pos) = args.iter().position(|t| code_flags.contains(&t.as_str())) {
-                    if let Some(code_arg) = args.get(pos + 1) {
-                        for mat in PATH_IN_CODE_RE.find_iter(code_arg) {
-                            let path_str = mat.as_str();
-                            if !path_str.starts_with("/dev/") {
-                                paths.push(ExtractedPath {
-                                    raw: path_str.to_string(),
-                                    context: PathContext::InlineCodeRef {
-                                        interpreter: cmd.to_string(),
-                                        flag: args[pos].clone(),
-                                        code_snippet: code_arg.clone(),
-                                    },
-                                });
-                            }
-                        }
-                        return;
-                    }
-                }
-            }
-            // Normal exec: first non-flag arg is the script path
-            for arg in args {
-                if !arg.starts_with('-') {
-                    paths.push(ExtractedPath {
-                        raw: arg.clone(),
-                        context: PathContext::ExecTarget,
-                    });
-                    break;
-                }
+            // `bun add`, `bun x`, `python -m pip install`
+            package_analyzer::extract_package(cmd, sub_cmd, &env, args, out);
+            if listener_analyzer::extract_listener(cmd, sub_cmd, args, out) {
+                return;
             }
+            interpreter_analyzer::extract_interpreter(cmd, sub_cmd, args, out, depth);
         }
         cmd if DOWNLOAD_COMMANDS.contains(&cmd) => {
-            extract_download_paths(args, cmd, paths);
+            extract_download_paths(args, cmd, &mut out.paths);
+            network_analyzer::extract_request(cmd, sub_cmd, args, out);
+        }
+        cmd if NETWORK_COMMANDS.contains(&cmd) => {
+            network_analyzer::extract_network(cmd, sub_cmd, args, out, depth);
+        }
+        cmd if PIP_COMMANDS.contains(&cmd) => {
+            network_analyzer::extract_pip(cmd, sub_cmd, args, out);
+            package_analyzer::extract_package(cmd, sub_cmd, &env, args, out);
         }
         "sed" => {
-            extract_sed_paths(args, paths);
+            script_analyzer::extract_sed(sub_cmd, args, out, depth);
+        }
+        cmd if AWK_COMMANDS.contains(&cmd) => {
+            script_analyzer::extract_awk(sub_cmd, args, out, depth);
+        }
+        cmd if ARCHIVE_COMMANDS.contains(&cmd) => {
+            archive_analyzer::extract_archive(cmd, sub_cmd, args, out, depth);
+        }
+        cmd if DATABASE_COMMANDS.contains(&cmd) => {
+            database_analyzer::extract_database(cmd, sub_cmd, &env, args, out, depth);
+        }
+        cmd if INFRA_COMMANDS.contains(&cmd) => {
+            infra_analyzer::extract_infra(cmd, sub_cmd, &env, args, out);
+        }
+        cmd if PERSISTENCE_COMMANDS.contains(&cmd) => {
+            persistence_analyzer::extract_persistence(cmd, sub_cmd, args, out, depth);
+        }
+        cmd if PACKAGE_COMMANDS.contains(&cmd) => {
+            package_analyzer::extract_package(cmd, sub_cmd, &env, args, out);
+            extract_unknown_paths(args, &mut out.paths);
+        }
+        cmd if RESOURCE_COMMANDS.contains(&cmd) => {
+            resource_analyzer::extract_resource(cmd, sub_cmd, args, out);
+            extract_unknown_paths(args, &mut out.paths);
         }
         "dd" => {
+            resource_analyzer::extract_resource("dd", sub_cmd, args, out);
             let path_keys = ["if", "of"];
             for arg in args {
-                if let Some((key, value)) = arg.split_once('=') {
-                    if path_keys.contains(&key) {
-                        paths.push(ExtractedPath {
-                            raw: value.to_string(),
-                            context: PathContext::DdPath,
-                        });
-                    }
+                if let Some((key, value)) = arg.split_once('=')
+                    && path_keys.contains(&key)
+                {
+                    out.paths.push(ExtractedPath {
+                        raw: value.to_string(),
+                        context: if key == "of" {
+                            PathContext::DdOutput
+                        } else {
+                            PathContext::DdPath
+                        },
+                    });
                 }
             }
         }
+        "git" => {
+            git_analyzer::extract_git(sub_cmd, args, out, depth);
+        }
+        cmd if REMOTE_COMMANDS.contains(&cmd) => {
+            remote_analyzer::extract_remote(cmd, sub_cmd, args, out, depth);
+        }
+        cmd if CONTAINER_COMMANDS.contains(&cmd) => {
+            container_analyzer::extract_container(cmd, sub_cmd, args, out, depth);
+        }
+        cmd if SERVER_COMMANDS.contains(&cmd) || PACKAGE_RUNNERS.contains(&cmd) => {
+            package_analyzer::extract_package(cmd, sub_cmd, &env, args, out);
+            if !listener_analyzer::extract_listener(cmd, sub_cmd, args, out) {
+                extract_unknown_paths(args, &mut out.paths);
+            }
+        }
         _ => {
-            for arg in args {
-                if arg.starts_with('-') {
-                    // Check --flag=value patterns for embedded paths
-                    if let Some((_flag, value)) = arg.split_once('=') {
-                        if looks_like_path(value) {
-                            paths.push(ExtractedPath {
-                                raw: value.to_string(),
-                                context: PathContext::UnknownCommandArg,
-                            });
-                        }
-                    }
-                    continue;
-                }
-                if looks_like_path(arg) {
-                    paths.push(ExtractedPath {
-                        raw: arg.clone(),
-                        context: PathContext::UnknownCommandArg,
-                    });
-                }
+            extract_unknown_paths(args, &mut out.paths);
+        }
+    }
+}
+
+/// Guess paths among the arguments of a command with no dedicated handling.
+pub(crate) fn extract_unknown_paths(args: &[String], paths: &mut Vec<ExtractedPath>) {
+    for arg in args {
+        if arg.starts_with('-') {
+            // Check --flag=value patterns for embedded paths
+            if let Some((_flag, value)) = arg.split_once('=')
+                && looks_like_path(value)
+            {
+                paths.push(ExtractedPath {
+                    raw: value.to_string(),
+                    context: PathContext::UnknownCommandArg,
+                });
             }
+            continue;
+        }
+        if looks_like_path(arg) {
+            paths.push(ExtractedPath {
+                raw: arg.clone(),
+                context: PathContext::UnknownCommandArg,
+            });
         }
     }
 }
 
+/// For a bundle of short flags (`-vp22`, `-i key`), the first flag in
+/// `value_flags`, its value — the rest of the bundle or the next argument —
+/// and whether the next argument was consumed.
+pub(crate) fn short_option(
+    arg: &str,
+    next: Option<&String>,
+    value_flags: &str,
+) -> Option<(char, String, bool)> {
+    let flags = arg.strip_prefix('-')?;
+    let (pos, flag) = flags
+        .char_indices()
+        .find(|(_, c)| value_flags.contains(*c))?;
+    let attached = &flags[pos + flag.len_utf8()..];
+    if attached.is_empty() {
+        Some((flag, next?.clone(), true))
+    } else {
+        Some((flag, attached.to_string(), false))
+    }
+}
+
 /// Extract paths from download command arguments.
 fn extract_download_paths(args: &[String], cmd: &str, paths: &mut Vec<ExtractedPath>) {
     let output_flags: &[&str] = match cmd {
@@ -396,15 +1457,31 @@ fn extract_download_paths(args: &[String], cmd: &str, paths: &mut Vec<ExtractedP
     };
     let data_long_flags: &[&str] = match cmd {
         "curl" => &[
-            "--data", "--data-binary", "--data-raw", "--data-urlencode",
-            "--form", "--upload-file",
+            "--data",
+            "--data-binary",
+            "--data-raw",
+            "--data-urlencode",
+            "--form",
         ],
         _ => &[],
     };
     let data_short_flags: &[&str] = match cmd {
-        "curl" => &["-d", "-F", "-T"],
+        "curl" => &["-d", "-F"],
         _ => &[],
     };
+    // Options whose value is always a file to send, not inline data
+    let upload_file_flags: &[&str] = match cmd {
+        "curl" => &["-T", "--upload-file"],
+        "wget" => &["--post-file", "--body-file"],
+        _ => &[],
+    };
+    let upload_file = |raw: &str| {
+        // `-T -` and `-T .` upload stdin
+        (!matches!(raw, "" | "-" | ".")).then(|| ExtractedPath {
+            raw: raw.to_string(),
+            context: PathContext::UploadData,
+        })
+    };
 
     let mut i = 0;
     while i < args.len() {
@@ -419,14 +1496,31 @@ fn extract_download_paths(args: &[String], cmd: &str, paths: &mut Vec<ExtractedP
             i += 2;
             continue;
         }
+        if upload_file_flags.contains(&arg.as_str()) {
+            paths.extend(args.get(i + 1).and_then(|value| upload_file(value)));
+            i += 2;
+            continue;
+        }
+        if let Some(value) = upload_file_flags.iter().find_map(|flag| {
+            let attached = if flag.starts_with("--") {
+                format!("{flag}=")
+            } else {
+                flag.to_string()
+            };
+            arg.strip_prefix(&attached)
+        }) {
+            paths.extend(upload_file(value));
+            i += 1;
+            continue;
+        }
         if data_long_flags.contains(&arg.as_str()) || data_short_flags.contains(&arg.as_str()) {
-            if let Some(data_arg) = args.get(i + 1) {
-                if let Some(p) = extract_path_from_curl_data(data_arg) {
-                    paths.push(ExtractedPath {
-                        raw: p,
-                        context: PathContext::UploadData,
-                    });
-                }
+            if let Some(data_arg) = args.get(i + 1)
+                && let Some(p) = extract_path_from_curl_data(data_arg)
+            {
+                paths.push(ExtractedPath {
+                    raw: p,
+                    context: PathContext::UploadData,
+                });
             }
             i += 2;
             continue;
@@ -472,10 +1566,10 @@ fn extract_path_from_curl_data(data_arg: &str) -> Option<String> {
             return Some(at_path.to_string());
         }
     } else if data_arg.contains("=@") {
-        if let Some((_, path)) = data_arg.split_once("=@") {
-            if !path.is_empty() {
-                return Some(path.to_string());
-            }
+        if let Some((_, path)) = data_arg.split_once("=@")
+            && !path.is_empty()
+        {
+            return Some(path.to_string());
         }
     } else if looks_like_path(data_arg) {
         return Some(data_arg.to_string());
@@ -483,52 +1577,304 @@ fn extract_path_from_curl_data(data_arg: &str) -> Option<String> {
     None
 }
 
-/// Extract paths from sed arguments.
-fn extract_sed_paths(args: &[String], paths: &mut Vec<ExtractedPath>) {
-    let mut i = 0;
-    let mut skip_next = false;
+/// Extract paths from a `find` invocation: search roots, file-valued tests,
+/// output files, `-delete` targets, and the commands run by `-exec` and friends.
+fn extract_find_paths(args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let (mut roots, mut i) = find_roots(args);
+    for root in &roots {
+        out.paths.push(ExtractedPath {
+            raw: root.clone(),
+            context: PathContext::SearchCommandArg,
+        });
+    }
+    if roots.is_empty() {
+        roots.push(".".to_string());
+    }
+
     while i < args.len() {
-        if skip_next {
-            skip_next = false;
+        let arg = args[i].as_str();
+        if FIND_EXEC_FLAGS.contains(&arg) {
+            let end = find_exec_end(args, i);
+            for command in find_exec_commands(&args[i + 1..end], &roots) {
+                extract_paths_recursive(&command, out, depth + 1);
+            }
+            i = end + 1;
+            continue;
+        }
+        if arg == "-delete" {
+            for root in &roots {
+                out.paths.push(ExtractedPath {
+                    raw: root.clone(),
+                    context: PathContext::FindDelete,
+                });
+            }
             i += 1;
             continue;
         }
+        if FIND_OUTPUT_FLAGS.contains(&arg) || arg == "-fprintf" {
+            if let Some(file) = args.get(i + 1) {
+                out.paths.push(ExtractedPath {
+                    raw: file.clone(),
+                    context: PathContext::FindOutput,
+                });
+            }
+            // -fprintf also takes a format argument
+            i += if arg == "-fprintf" { 3 } else { 2 };
+            continue;
+        }
+        if FIND_PATH_FLAGS.contains(&arg) {
+            if let Some(file) = args.get(i + 1) {
+                out.paths.push(ExtractedPath {
+                    raw: file.clone(),
+                    context: PathContext::SearchCommandArg,
+                });
+            }
+            i += 2;
+            continue;
+        }
+        // -newerXY takes a reference file or time string
+        if FIND_VALUE_FLAGS.contains(&arg) || arg.starts_with("-newer") {
+            i += 2;
+            continue;
+        }
+        i += 1;
+    }
+}
+
+/// The commands a `find` invocation runs through `-exec` and friends.
+pub(crate) fn find_commands(args: &[String]) -> Vec<String> {
+    let (mut roots, mut i) = find_roots(args);
+    if roots.is_empty() {
+        roots.push(".".to_string());
+    }
+    let mut commands = Vec::new();
+    while i < args.len() {
+        if FIND_EXEC_FLAGS.contains(&args[i].as_str()) {
+            let end = find_exec_end(args, i);
+            commands.extend(find_exec_commands(&args[i + 1..end], &roots));
+            i = end;
+        }
+        i += 1;
+    }
+    commands
+}
+
+/// Skip `find`'s leading options (-H, -L, -P, -D debugopts, -Olevel) and
+/// collect the search roots, which run until the first expression token.
+/// Returns the roots and the index of that token.
+fn find_roots(args: &[String]) -> (Vec<String>, usize) {
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-H" | "-L" | "-P" => i += 1,
+            "-D" => i += 2,
+            a if a.starts_with("-O") => i += 1,
+            _ => break,
+        }
+    }
+    let mut roots = Vec::new();
+    while i < args.len() {
         let arg = &args[i];
-        if arg == "-e" || arg == "-f" {
-            skip_next = true;
+        if (arg.starts_with('-') && arg.len() > 1) || matches!(arg.as_str(), "(" | ")" | "!" | ",")
+        {
+            break;
+        }
+        roots.push(arg.clone());
+        i += 1;
+    }
+    (roots, i)
+}
+
+/// Index of the `;` or `+` ending the `-exec` at `start` (or the argument count).
+fn find_exec_end(args: &[String], start: usize) -> usize {
+    args[start + 1..]
+        .iter()
+        .position(|t| t == ";" || t == "+")
+        .map_or(args.len(), |p| start + 1 + p)
+}
+
+/// Build the command strings run by a `find -exec`, with `{}` standing for
+/// each search root.
+fn find_exec_commands(inner: &[String], roots: &[String]) -> Vec<String> {
+    if inner.is_empty() {
+        return Vec::new();
+    }
+    if !inner.iter().any(|t| t.contains("{}")) {
+        return shlex::try_join(inner.iter().map(String::as_str))
+            .into_iter()
+            .collect();
+    }
+    roots
+        .iter()
+        .filter_map(|root| {
+            let substituted: Vec<String> = inner.iter().map(|t| t.replace("{}", root)).collect();
+            shlex::try_join(substituted.iter().map(String::as_str)).ok()
+        })
+        .collect()
+}
+
+/// The result of peeling wrapper commands off a tokenized sub-command.
+#[derive(Debug, Default)]
+pub struct UnwrappedCommand {
+    /// Index of the wrapped command word (equal to the token count if there is none).
+    pub start: usize,
+    /// Names of the wrappers that were peeled off, outermost first.
+    pub wrappers: Vec<String>,
+    /// Path arguments consumed by the wrappers themselves (`flock FILE`, `time -o FILE`).
+    pub wrapper_paths: Vec<String>,
+    /// A shell command string run by a wrapper (`su -c CMD`, `flock -c CMD`, `watch CMD`).
+    pub inline_command: Option<String>,
+}
+
+/// Peel `VAR=value` assignments and wrapper commands (sudo, timeout, nohup,
+/// xargs, ...) off a tokenized sub-command, skipping each wrapper's own
+/// flags and positional arguments.
+pub fn unwrap_command(tokens: &[String]) -> UnwrappedCommand {
+    let mut result = UnwrappedCommand::default();
+    let mut i = 0;
+    while i < tokens.len() {
+        let t = &tokens[i];
+        let is_assignment = t.contains('=') && !t.starts_with('-') && !t.starts_with('/');
+        if is_assignment {
             i += 1;
             continue;
         }
-        if arg == "-i" || arg.starts_with("-i") {
+        let Some(spec) = WRAPPERS.iter().find(|w| w.name == t) else {
+            break;
+        };
+        result.wrappers.push(spec.name.to_string());
+        i = skip_wrapper_args(spec, tokens, i + 1, &mut result);
+        if result.inline_command.is_some() {
+            result.start = tokens.len();
+            return result;
+        }
+        if spec.joins_args && i < tokens.len() {
+            result.inline_command = Some(tokens[i..].join(" "));
+            result.start = tokens.len();
+            return result;
+        }
+    }
+    result.start = i.min(tokens.len());
+    result
+}
+
+/// Index of the actual command word in a tokenized sub-command, skipping
+/// `VAR=value` assignments and wrapper commands like `sudo` and `timeout`.
+pub fn command_start(tokens: &[String]) -> usize {
+    unwrap_command(tokens).start
+}
+
+/// Skip one wrapper's flags and positional arguments starting at `i`.
+/// Returns the index of the first token after them.
+fn skip_wrapper_args(
+    spec: &WrapperSpec,
+    tokens: &[String],
+    mut i: usize,
+    result: &mut UnwrappedCommand,
+) -> usize {
+    let mut positional_left = spec.positional;
+    while i < tokens.len() {
+        let t = tokens[i].as_str();
+        if t == "--" {
+            i += 1;
+            break;
+        }
+        if spec.command_flags.contains(&t) {
+            result.inline_command = tokens.get(i + 1).cloned();
+            return tokens.len();
+        }
+        if let Some((flag, value)) = t.split_once('=')
+            && flag.starts_with("--")
+        {
+            if spec.command_flags.contains(&flag) {
+                result.inline_command = Some(value.to_string());
+                return tokens.len();
+            }
+            if spec.path_flags.contains(&flag) {
+                result.wrapper_paths.push(value.to_string());
+            }
             i += 1;
             continue;
         }
-        if arg.starts_with('-') {
+        if spec.path_flags.contains(&t) {
+            if let Some(value) = tokens.get(i + 1) {
+                result.wrapper_paths.push(value.clone());
+            }
+            i += 2;
+            continue;
+        }
+        if spec.value_flags.contains(&t) {
+            i += 2;
+            continue;
+        }
+        if t.starts_with('-') && t.len() > 1 {
             i += 1;
             continue;
         }
-        if looks_like_path(arg) {
-            paths.push(ExtractedPath {
-                raw: arg.clone(),
-                context: PathContext::SedFile,
-            });
+        if positional_left == 0 {
+            break;
         }
+        if spec.positional_is_path && positional_left == spec.positional {
+            result.wrapper_paths.push(t.to_string());
+        }
+        positional_left -= 1;
         i += 1;
     }
+    i.min(tokens.len())
 }
 
 // ============================================================================
 // Shell operator splitting
 // ============================================================================
 
+/// A control operator separating sub-commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellOperator {
+    /// `&&`
+    And,
+    /// `||`
+    Or,
+    /// `;`
+    Sequence,
+    /// `|`
+    Pipe,
+}
+
+/// A sub-command together with the operator that connects it to the previous one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellSegment {
+    pub command: String,
+    /// Operator before this segment (None for the first).
+    pub preceded_by: Option<ShellOperator>,
+}
+
 /// Split a command string on shell operators (&&, ||, ;, |) while respecting quotes.
 pub fn split_shell_operators(command: &str) -> Vec<String> {
+    split_shell_segments(command)
+        .into_iter()
+        .map(|seg| seg.command)
+        .collect()
+}
+
+/// Split a command string on shell operators, keeping track of which operator
+/// joined each sub-command to the one before it.
+pub fn split_shell_segments(command: &str) -> Vec<ShellSegment> {
     let mut parts = Vec::new();
     let mut current = String::new();
+    let mut preceded_by = None;
     let mut chars = command.chars().peekable();
     let mut in_single_quote = false;
     let mut in_double_quote = false;
     let mut escape_next = false;
+    let mut substitution_depth = 0;
+
+    let mut finish = |current: &mut String, preceded_by: &mut Option<ShellOperator>, next| {
+        parts.push(ShellSegment {
+            command: std::mem::take(current),
+            preceded_by: *preceded_by,
+        });
+        *preceded_by = Some(next);
+    };
 
     while let Some(ch) = chars.next() {
         if escape_next {
@@ -560,21 +1906,44 @@ pub fn split_shell_operators(command: &str) -> Vec<String> {
             continue;
         }
 
+        // Operators inside `$(…)`, `<(…)` and `>(…)` belong to the substitution
+        match ch {
+            '(' if substitution_depth > 0 || current.ends_with(['$', '<', '>']) => {
+                substitution_depth += 1;
+                current.push(ch);
+                continue;
+            }
+            ')' if substitution_depth > 0 => {
+                substitution_depth -= 1;
+                current.push(ch);
+                continue;
+            }
+            _ if substitution_depth > 0 => {
+                current.push(ch);
+                continue;
+            }
+            _ => {}
+        }
+
         match ch {
             '&' if chars.peek() == Some(&'&') => {
                 chars.next(); // consume second '&'
-                parts.push(std::mem::take(&mut current));
+                finish(&mut current, &mut preceded_by, ShellOperator::And);
             }
             '|' if chars.peek() == Some(&'|') => {
                 chars.next(); // consume second '|'
-                parts.push(std::mem::take(&mut current));
+                finish(&mut current, &mut preceded_by, ShellOperator::Or);
             }
             '|' => {
                 // Single pipe — still a boundary for command analysis
-                parts.push(std::mem::take(&mut current));
+                finish(&mut current, &mut preceded_by, ShellOperator::Pipe);
             }
             ';' => {
-                parts.push(std::mem::take(&mut current));
+                finish(&mut current, &mut preceded_by, ShellOperator::Sequence);
+            }
+            // A newline ends a command, unless the line ended with an operator
+            '\n' if !current.trim().is_empty() => {
+                finish(&mut current, &mut preceded_by, ShellOperator::Sequence);
             }
             _ => {
                 current.push(ch);
@@ -583,12 +1952,205 @@ pub fn split_shell_operators(command: &str) -> Vec<String> {
     }
 
     if !current.trim().is_empty() {
-        parts.push(current);
+        parts.push(ShellSegment {
+            command: current,
+            preceded_by,
+        });
     }
 
     parts
 }
 
+/// Split a sub-command on unquoted background operators: `a & b` is `a`,
+/// started in the background, then `b`. Redirections (`2>&1`, `&>log`) and
+/// `|&` are not operators.
+pub(crate) fn split_background(command: &str) -> Vec<(String, bool)> {
+    let mut jobs = Vec::new();
+    let mut current = String::new();
+    let mut chars = command.chars().peekable();
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut escape_next = false;
+    let mut prev = None;
+
+    while let Some(ch) = chars.next() {
+        if escape_next {
+            escape_next = false;
+        } else if ch == '\\' && !in_single_quote {
+            escape_next = true;
+        } else if ch == '\'' && !in_double_quote {
+            in_single_quote = !in_single_quote;
+        } else if ch == '"' && !in_single_quote {
+            in_double_quote = !in_double_quote;
+        } else if ch == '&'
+            && !in_single_quote
+            && !in_double_quote
+            && !matches!(prev, Some('>' | '<'))
+            && chars.peek() != Some(&'>')
+        {
+            // `a |& b` leaves a leading `&` after the pipe split
+            if !current.trim().is_empty() {
+                jobs.push((std::mem::take(&mut current), true));
+            }
+            prev = Some(ch);
+            continue;
+        }
+        current.push(ch);
+        prev = Some(ch);
+    }
+    if !current.trim().is_empty() || jobs.is_empty() {
+        jobs.push((current, false));
+    }
+    jobs
+}
+
+// ============================================================================
+// Heredocs
+// ============================================================================
+
+/// A heredoc body cut out of a command. `marker` stands in for the `<<EOF`
+/// operator in the remaining command text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Heredoc {
+    pub marker: String,
+    pub delimiter: String,
+    pub body: String,
+}
+
+/// Cut heredoc bodies (`<<EOF … EOF`, `<<-'EOF'`) out of a command, leaving
+/// a marker token where each operator was. Operators without a terminating
+/// line (`$((1 << 2))`) are left alone.
+pub(crate) fn strip_heredocs(command: &str) -> (String, Vec<Heredoc>) {
+    let mut out = String::new();
+    let mut heredocs = Vec::new();
+    // Operators on the current line whose bodies start on the next one
+    let mut pending: Vec<(String, String, bool)> = Vec::new();
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut escape_next = false;
+    let mut pos = 0;
+    while pos < command.len() {
+        let rest = &command[pos..];
+        let ch = rest.chars().next().unwrap_or_default();
+        if escape_next {
+            escape_next = false;
+        } else if ch == '\\' && !in_single_quote {
+            escape_next = true;
+        } else if ch == '\'' && !in_double_quote {
+            in_single_quote = !in_single_quote;
+        } else if ch == '"' && !in_single_quote {
+            in_double_quote = !in_double_quote;
+        } else if !in_single_quote && !in_double_quote {
+            if rest.starts_with("<<") && !rest.starts_with("<<<") {
+                if let Some((delimiter, strip_tabs, len)) = heredoc_operator(&rest[2..])
+                    && has_terminator(&command[pos..], &delimiter, strip_tabs)
+                {
+                    let marker = format!("__clarg_heredoc_{}__", heredocs.len() + pending.len());
+                    out.push_str(&marker);
+                    pending.push((marker, delimiter, strip_tabs));
+                    pos += 2 + len;
+                    continue;
+                }
+            } else if ch == '\n' && !pending.is_empty() {
+                let line_end = out.trim_end();
+                let continues = ["|", "&&", "||"].iter().any(|op| line_end.ends_with(op));
+                pos += 1;
+                for (marker, delimiter, strip_tabs) in pending.drain(..) {
+                    let (body, consumed) =
+                        read_heredoc_body(&command[pos..], &delimiter, strip_tabs);
+                    pos += consumed;
+                    heredocs.push(Heredoc {
+                        marker,
+                        delimiter,
+                        body,
+                    });
+                }
+                // A newline ends the command; spell it as `;` for the segment splitter
+                out.push_str(if continues || command[pos..].trim().is_empty() {
+                    "\n"
+                } else {
+                    " ;\n"
+                });
+                continue;
+            }
+        }
+        out.push(ch);
+        pos += ch.len_utf8();
+    }
+    (out, heredocs)
+}
+
+/// Parse the delimiter after `<<`: whether `-` strips leading tabs, and how
+/// many bytes the operand took. Quotes around the delimiter are removed.
+fn heredoc_operator(after: &str) -> Option<(String, bool, usize)> {
+    let strip_tabs = after.starts_with('-');
+    let start = usize::from(strip_tabs);
+    let word_start =
+        start + after[start..].len() - after[start..].trim_start_matches([' ', '\t']).len();
+    let word: String = after[word_start..]
+        .chars()
+        .take_while(|c| !c.is_whitespace() && !";|&<>()".contains(*c))
+        .collect();
+    let delimiter: String = word
+        .chars()
+        .filter(|c| !matches!(c, '\'' | '"' | '\\'))
+        .collect();
+    if delimiter.is_empty() || delimiter.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((delimiter, strip_tabs, word_start + word.len()))
+}
+
+/// Whether a line after the operator's line ends the heredoc.
+fn has_terminator(from_operator: &str, delimiter: &str, strip_tabs: bool) -> bool {
+    from_operator.split_once('\n').is_some_and(|(_, body)| {
+        body.lines()
+            .any(|line| is_terminator(line, delimiter, strip_tabs))
+    })
+}
+
+/// The body up to the terminator line and the bytes consumed, terminator
+/// included.
+fn read_heredoc_body(text: &str, delimiter: &str, strip_tabs: bool) -> (String, usize) {
+    let mut body = String::new();
+    let mut consumed = 0;
+    for line in text.split_inclusive('\n') {
+        consumed += line.len();
+        let content = line.strip_suffix('\n').unwrap_or(line);
+        if is_terminator(content, delimiter, strip_tabs) {
+            break;
+        }
+        body.push_str(if strip_tabs {
+            line.trim_start_matches('\t')
+        } else {
+            line
+        });
+    }
+    (body, consumed)
+}
+
+fn is_terminator(line: &str, delimiter: &str, strip_tabs: bool) -> bool {
+    let line = if strip_tabs {
+        line.trim_start_matches('\t')
+    } else {
+        line
+    };
+    line.trim_end_matches('\r') == delimiter
+}
+
+/// Remove heredoc markers from a segment and return the heredocs they stand for.
+fn take_heredocs<'a>(segment: &str, heredocs: &'a [Heredoc]) -> (String, Vec<&'a Heredoc>) {
+    let mut text = segment.to_string();
+    let mut taken = Vec::new();
+    for heredoc in heredocs {
+        if text.contains(&heredoc.marker) {
+            text = text.replace(&heredoc.marker, "");
+            taken.push(heredoc);
+        }
+    }
+    (text.trim().to_string(), taken)
+}
+
 // ============================================================================
 // Utilities
 // ============================================================================
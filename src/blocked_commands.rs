@@ -1,6 +1,7 @@
 use eyre::{Result, WrapErr};
 use regex::Regex;
 
+use crate::normalize::normalize_command;
 use crate::util::truncate;
 
 pub struct BlockedCommandsRule {
@@ -23,6 +24,10 @@ impl BlockedCommandsRule {
     }
 
     /// Check if a command is blocked. Returns Some(reason) if blocked, None if allowed.
+    ///
+    /// Patterns are matched against the raw command first, then against the
+    /// normalized form of each sub-command so quoting and escape tricks
+    /// (`r''m`, `\sudo`, `$'\x72m'`) don't slip past.
     pub fn check(&self, command: &str) -> Option<String> {
         for (regex, original) in &self.patterns {
             if regex.is_match(command) {
@@ -33,6 +38,22 @@ impl BlockedCommandsRule {
                 ));
             }
         }
+
+        for form in normalize_command(command) {
+            if form == command {
+                continue;
+            }
+            for (regex, original) in &self.patterns {
+                if regex.is_match(&form) {
+                    return Some(format!(
+                        "Blocked by `clarg`: command '{}' is forbidden because its normalized form '{}' matched the pattern '{}'",
+                        truncate(command, 100),
+                        truncate(&form, 100),
+                        original
+                    ));
+                }
+            }
+        }
         None
     }
 }
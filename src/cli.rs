@@ -1,7 +1,9 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
-#[derive(Parser, Debug)]
+use crate::config::RuleAction;
+
+#[derive(Parser, Debug, Default)]
 #[command(
     name = "clarg",
     version,
@@ -12,11 +14,14 @@ use std::path::PathBuf;
                   Add to .claude/settings.json under hooks.PreToolUse\n\n\
                   EXAMPLES:\n  \
                   clarg -b '.env,*.secret' -c 'rm -rf' -i\n  \
-                  clarg config.yaml"
+                  clarg --preset destructive,git-safety\n  \
+                  clarg config.yaml\n  \
+                  clarg presets show destructive",
+    args_conflicts_with_subcommands = true
 )]
 pub struct Cli {
     /// YAML config path — mutually exclusive with all flags
-    #[arg(conflicts_with_all = ["block_access_to", "commands_forbidden", "log_to", "internal_access_only"])]
+    #[arg(conflicts_with_all = ["block_access_to", "commands_forbidden", "log_to", "internal_access_only", "pipe_to_shell", "secret_flow", "persistence", "obfuscation_threshold", "strict", "no_protect_git", "inspect_archives", "presets", "allow_hosts", "deny_hosts", "allow_databases", "deny_databases", "max_write_size"])]
     pub config_path: Option<PathBuf>,
 
     /// Gitignore-style file patterns to block (comma or space separated)
@@ -34,4 +39,77 @@ pub struct Cli {
     /// Block ALL filesystem access outside the project directory
     #[arg(short = 'i', long = "internal-access-only")]
     pub internal_access_only: bool,
+
+    /// Action when a command pipes data into a shell or interpreter (default: deny)
+    #[arg(long = "pipe-to-shell", value_enum)]
+    pub pipe_to_shell: Option<RuleAction>,
+
+    /// Action when credentials or environment variables flow to a network command (default: deny)
+    #[arg(long = "secret-flow", value_enum)]
+    pub secret_flow: Option<RuleAction>,
+
+    /// Action for background jobs, detached sessions, scheduled jobs, services and startup files no rule matches (default: ask)
+    #[arg(long = "persistence", value_enum)]
+    pub persistence: Option<RuleAction>,
+
+    /// Deny commands whose obfuscation score exceeds this (default: 3)
+    #[arg(long = "obfuscation-threshold")]
+    pub obfuscation_threshold: Option<u32>,
+
+    /// Deny bash commands that are too complex to verify (parse failures, deep nesting)
+    #[arg(short = 's', long = "strict")]
+    pub strict: bool,
+
+    /// Allow writes into .git directories (hooks, config, info/attributes)
+    #[arg(long = "no-protect-git")]
+    pub no_protect_git: bool,
+
+    /// List the members of local tar and zip archives before extraction and deny entries that escape
+    #[arg(long = "inspect-archives")]
+    pub inspect_archives: bool,
+
+    /// Built-in presets to enable (comma or space separated; see `clarg presets list`)
+    #[arg(short = 'p', long = "preset", value_delimiter = ',', num_args = 1..)]
+    pub presets: Vec<String>,
+
+    /// Host patterns or CIDR ranges that network and remote commands may connect to; others are denied (comma or space separated)
+    #[arg(long = "allow-hosts", value_delimiter = ',', num_args = 1..)]
+    pub allow_hosts: Vec<String>,
+
+    /// Host patterns or CIDR ranges that network and remote commands may never connect to (comma or space separated)
+    #[arg(long = "deny-hosts", value_delimiter = ',', num_args = 1..)]
+    pub deny_hosts: Vec<String>,
+
+    /// `database@host` patterns that database CLIs may connect to; others are denied (comma or space separated)
+    #[arg(long = "allow-databases", value_delimiter = ',', num_args = 1..)]
+    pub allow_databases: Vec<String>,
+
+    /// `database@host` patterns that database CLIs may never connect to (comma or space separated)
+    #[arg(long = "deny-databases", value_delimiter = ',', num_args = 1..)]
+    pub deny_databases: Vec<String>,
+
+    /// Largest file a single dd, fallocate, truncate or `yes > file` may write, e.g. 10G; larger and unbounded writes are denied
+    #[arg(long = "max-write-size")]
+    pub max_write_size: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Inspect the built-in presets
+    #[command(subcommand)]
+    Presets(PresetsCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PresetsCommand {
+    /// List the built-in presets
+    List,
+    /// Print exactly what a preset contains
+    Show {
+        /// Preset name
+        name: String,
+    },
 }
@@ -1,16 +1,87 @@
+use clap::ValueEnum;
 use eyre::{Result, WrapErr};
 use serde::Deserialize;
 use std::path::PathBuf;
 
 use crate::cli::Cli;
+use crate::databases::DatabasePolicy;
+use crate::facts::FactRule;
+use crate::hosts::HostPolicy;
+use crate::resources::ResourcePolicy;
+use crate::web::WebPolicy;
+
+/// What a built-in rule does when it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    Allow,
+    Ask,
+    #[default]
+    Deny,
+}
+
+/// Obfuscation score above which a bash command is denied.
+pub const DEFAULT_OBFUSCATION_THRESHOLD: u32 = 3;
 
 /// Unified configuration, built from either CLI args or a YAML file.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Config {
     pub block_access_to: Vec<String>,
     pub commands_forbidden: Vec<String>,
     pub log_to: Option<PathBuf>,
     pub internal_access_only: bool,
+    /// Action for pipelines that feed data into a shell or interpreter.
+    pub pipe_to_shell: RuleAction,
+    /// Action for credentials or environment dumps that flow to the network.
+    pub secret_flow: RuleAction,
+    /// Action for `persistence.*` facts (background jobs, detached sessions,
+    /// scheduled jobs, services, startup files) that no fact rule matches.
+    pub persistence: RuleAction,
+    /// Commands whose obfuscation score exceeds this are denied.
+    pub obfuscation_threshold: u32,
+    /// Deny bash commands the analyzer cannot fully understand.
+    pub strict: bool,
+    /// Deny writes into `.git` directories and the git dirs `.git` files point at.
+    pub protect_git: bool,
+    /// List the members of local archives before extraction and deny
+    /// entries that escape the extraction directory or hit protected paths.
+    pub inspect_archives: bool,
+    /// Names of built-in presets whose rules apply alongside the user's.
+    pub presets: Vec<String>,
+    /// Rules matched against facts the analyzers report (YAML only).
+    pub rules: Vec<FactRule>,
+    /// Hosts that remote and network commands may connect to.
+    pub hosts: HostPolicy,
+    /// Databases that database CLIs (psql, mysql, sqlite3, redis-cli) may connect to.
+    pub databases: DatabasePolicy,
+    /// Limits on how much `dd`, `fallocate`, `truncate` and the like may write.
+    pub resources: ResourcePolicy,
+    /// URL rules for WebFetch and domain checks for WebSearch (YAML only).
+    pub web: WebPolicy,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            block_access_to: Vec::new(),
+            commands_forbidden: Vec::new(),
+            log_to: None,
+            internal_access_only: false,
+            pipe_to_shell: RuleAction::default(),
+            secret_flow: RuleAction::default(),
+            persistence: RuleAction::Ask,
+            obfuscation_threshold: DEFAULT_OBFUSCATION_THRESHOLD,
+            strict: false,
+            protect_git: true,
+            inspect_archives: false,
+            presets: Vec::new(),
+            rules: Vec::new(),
+            hosts: HostPolicy::default(),
+            databases: DatabasePolicy::default(),
+            resources: ResourcePolicy::default(),
+            web: WebPolicy::default(),
+        }
+    }
 }
 
 /// Intermediate struct for YAML deserialization.
@@ -24,6 +95,44 @@ struct YamlConfig {
     log_to: Option<PathBuf>,
     #[serde(default)]
     internal_access_only: bool,
+    #[serde(default)]
+    pipe_to_shell: RuleAction,
+    #[serde(default)]
+    secret_flow: RuleAction,
+    #[serde(default = "default_ask")]
+    persistence: RuleAction,
+    #[serde(default = "default_obfuscation_threshold")]
+    obfuscation_threshold: u32,
+    #[serde(default)]
+    strict: bool,
+    #[serde(default = "default_true")]
+    protect_git: bool,
+    #[serde(default)]
+    inspect_archives: bool,
+    #[serde(default)]
+    presets: Vec<String>,
+    #[serde(default)]
+    rules: Vec<FactRule>,
+    #[serde(default)]
+    hosts: HostPolicy,
+    #[serde(default)]
+    databases: DatabasePolicy,
+    #[serde(default)]
+    resources: ResourcePolicy,
+    #[serde(default)]
+    web: WebPolicy,
+}
+
+fn default_obfuscation_threshold() -> u32 {
+    DEFAULT_OBFUSCATION_THRESHOLD
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_ask() -> RuleAction {
+    RuleAction::Ask
 }
 
 impl Config {
@@ -36,6 +145,29 @@ impl Config {
                 commands_forbidden: cli.commands_forbidden,
                 log_to: cli.log_to,
                 internal_access_only: cli.internal_access_only,
+                pipe_to_shell: cli.pipe_to_shell.unwrap_or_default(),
+                secret_flow: cli.secret_flow.unwrap_or_default(),
+                persistence: cli.persistence.unwrap_or(RuleAction::Ask),
+                obfuscation_threshold: cli
+                    .obfuscation_threshold
+                    .unwrap_or(DEFAULT_OBFUSCATION_THRESHOLD),
+                strict: cli.strict,
+                protect_git: !cli.no_protect_git,
+                inspect_archives: cli.inspect_archives,
+                presets: cli.presets,
+                rules: Vec::new(),
+                hosts: HostPolicy {
+                    allow: cli.allow_hosts,
+                    deny: cli.deny_hosts,
+                },
+                databases: DatabasePolicy {
+                    allow: cli.allow_databases,
+                    deny: cli.deny_databases,
+                },
+                resources: ResourcePolicy {
+                    max_write_size: cli.max_write_size,
+                },
+                web: WebPolicy::default(),
             })
         }
     }
@@ -50,6 +182,19 @@ impl Config {
             commands_forbidden: yaml.commands_forbidden,
             log_to: yaml.log_to,
             internal_access_only: yaml.internal_access_only,
+            pipe_to_shell: yaml.pipe_to_shell,
+            secret_flow: yaml.secret_flow,
+            persistence: yaml.persistence,
+            obfuscation_threshold: yaml.obfuscation_threshold,
+            strict: yaml.strict,
+            protect_git: yaml.protect_git,
+            inspect_archives: yaml.inspect_archives,
+            presets: yaml.presets,
+            rules: yaml.rules,
+            hosts: yaml.hosts,
+            databases: yaml.databases,
+            resources: yaml.resources,
+            web: yaml.web,
         })
     }
 }
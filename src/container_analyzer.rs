@@ -0,0 +1,495 @@
+use crate::bash_analyzer::{
+    CommandAnalysis, ExtractedPath, Finding, PathContext, extract_paths_recursive, short_option,
+};
+use crate::facts::Fact;
+
+/// Container CLIs that share docker's command line.
+pub(crate) const CONTAINER_COMMANDS: &[&str] = &["docker", "podman", "nerdctl"];
+
+/// Global options (before the subcommand) that consume the next argument.
+const GLOBAL_VALUE_FLAGS: &[&str] = &[
+    "-H",
+    "--host",
+    "-c",
+    "--context",
+    "--config",
+    "-l",
+    "--log-level",
+    "--tlscacert",
+    "--tlscert",
+    "--tlskey",
+    "--url",
+    "--connection",
+    "--root",
+    "--runroot",
+    "--storage-driver",
+    "--cgroup-manager",
+    "--namespace",
+    "-n",
+    "--address",
+    "-a",
+];
+
+/// `run`/`create`/`exec` flags that take no value. Every other flag is
+/// assumed to take a value: attached (`--flag=value`, `-vVALUE`) or the
+/// next argument.
+const BOOL_FLAGS: &[&str] = &[
+    "-d",
+    "--detach",
+    "-i",
+    "--interactive",
+    "-t",
+    "--tty",
+    "--rm",
+    "--privileged",
+    "--init",
+    "-P",
+    "--publish-all",
+    "--read-only",
+    "--no-healthcheck",
+    "--oom-kill-disable",
+    "-q",
+    "--quiet",
+    "--help",
+    "--disable-content-trust",
+    "--use-api-socket",
+    "--replace",
+    "--rmi",
+    "--read-only-tmpfs",
+    "--rootfs",
+    "--sig-proxy",
+    "--tls-verify",
+    "--http-proxy",
+    "--env-host",
+    "--no-hosts",
+];
+
+/// `build` flags that take no value.
+const BUILD_BOOL_FLAGS: &[&str] = &[
+    "-q",
+    "--quiet",
+    "--no-cache",
+    "--pull",
+    "--rm",
+    "--force-rm",
+    "--squash",
+    "--load",
+    "--push",
+    "--compress",
+    "--disable-content-trust",
+    "--help",
+];
+
+/// Characters allowed in a bundle of boolean short flags (`-it`, `-dit`).
+const BOOL_SHORT_CHARS: &str = "ditPq";
+
+/// Host-directory spellings that stand for the current directory in `-v` sources.
+const CWD_SPELLINGS: &[&str] = &["$PWD", "${PWD}", "$(pwd)", "`pwd`"];
+
+/// A host directory mounted into a container.
+struct BindMount {
+    source: String,
+    target: String,
+}
+
+/// Analyze a docker/podman/nerdctl invocation.
+pub(crate) fn extract_container(
+    runtime: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let Some((subcommand, rest)) = split_subcommand(args) else {
+        return;
+    };
+    match subcommand {
+        "run" | "create" => extract_run(runtime, sub_cmd, rest, out, depth),
+        "exec" => extract_exec(runtime, sub_cmd, rest, out, depth),
+        "cp" => extract_cp(rest, out),
+        "build" => extract_build(rest, out),
+        "buildx" if rest.first().is_some_and(|a| a == "build") => extract_build(&rest[1..], out),
+        _ => {}
+    }
+}
+
+/// The command a `run`, `create` or `exec` invocation runs inside the container.
+pub(crate) fn container_command(args: &[String]) -> Vec<String> {
+    match split_subcommand(args) {
+        Some(("run" | "create", rest)) => run_command(&parse_options(rest)),
+        Some(("exec", rest)) => parse_options(rest).operands.into_iter().skip(1).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Skip the global options and return the subcommand and its arguments.
+fn split_subcommand(args: &[String]) -> Option<(&str, &[String])> {
+    let mut i = 0;
+    while i < args.len() && args[i].starts_with('-') {
+        if GLOBAL_VALUE_FLAGS.contains(&args[i].as_str()) {
+            i += 1;
+        }
+        i += 1;
+    }
+    // `docker container run` is `docker run`
+    if args.get(i).is_some_and(|a| a == "container") {
+        i += 1;
+    }
+    let subcommand = args.get(i)?;
+    Some((subcommand.as_str(), &args[i + 1..]))
+}
+
+/// `run [OPTIONS] IMAGE [COMMAND] [ARG...]`
+fn extract_run(
+    runtime: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let parsed = parse_options(args);
+    let image = parsed.operands.first().cloned().unwrap_or_default();
+    let new_fact = |name: &str| {
+        Fact::new(name, sub_cmd)
+            .with("runtime", runtime)
+            .with("image", image.as_str())
+    };
+
+    let mut mounts = Vec::new();
+    let mut workdir = None;
+    for (flag, value) in &parsed.options {
+        match (flag.as_str(), value.as_deref()) {
+            ("--privileged", _) => out
+                .findings
+                .push(Finding::Fact(new_fact("container.privileged"))),
+            ("--pid", Some("host")) => out
+                .findings
+                .push(Finding::Fact(new_fact("container.pid-host"))),
+            ("--network" | "--net", Some("host")) => {
+                out.findings
+                    .push(Finding::Fact(new_fact("container.network-host")));
+            }
+            ("--cap-add", Some(cap)) => {
+                out.findings.push(Finding::Fact(
+                    new_fact("container.cap-add").with("cap", cap),
+                ));
+            }
+            ("-v" | "--volume", Some(spec)) => {
+                if let Some((mount, read_only)) = parse_volume(spec) {
+                    push_host_path(out, &mount.source, "container bind mount", !read_only);
+                    mounts.push(mount);
+                }
+            }
+            ("--mount", Some(spec)) => {
+                if let Some((mount, read_only)) = parse_mount(spec) {
+                    push_host_path(out, &mount.source, "container bind mount", !read_only);
+                    mounts.push(mount);
+                }
+            }
+            ("--env-file" | "--label-file", Some(path)) => {
+                push_host_path(out, path, "container env file", false);
+            }
+            ("--cidfile", Some(path)) => push_host_path(out, path, "container id file", true),
+            ("-w" | "--workdir", Some(dir)) => workdir = Some(dir.to_string()),
+            _ => {}
+        }
+    }
+
+    analyze_inner(
+        &run_command(&parsed),
+        &mounts,
+        workdir.as_deref(),
+        out,
+        depth,
+    );
+}
+
+/// The container command of a `run`: the `--entrypoint`, if any, followed by
+/// the arguments after the image.
+fn run_command(parsed: &ParsedOptions) -> Vec<String> {
+    let entrypoint = parsed
+        .options
+        .iter()
+        .rev()
+        .find_map(|(flag, value)| match flag.as_str() {
+            "--entrypoint" => value.clone(),
+            _ => None,
+        });
+    let mut command: Vec<String> = entrypoint.into_iter().collect();
+    command.extend(parsed.operands.iter().skip(1).cloned());
+    command
+}
+
+/// `exec [OPTIONS] CONTAINER COMMAND [ARG...]`
+fn extract_exec(
+    runtime: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let parsed = parse_options(args);
+    let container = parsed.operands.first().cloned().unwrap_or_default();
+    if parsed
+        .options
+        .iter()
+        .any(|(flag, _)| flag == "--privileged")
+    {
+        out.findings.push(Finding::Fact(
+            Fact::new("container.privileged", sub_cmd)
+                .with("runtime", runtime)
+                .with("container", container),
+        ));
+    }
+    for (flag, value) in &parsed.options {
+        if flag == "--env-file"
+            && let Some(path) = value
+        {
+            push_host_path(out, path, "container env file", false);
+        }
+    }
+    // The container's mounts aren't known, so its paths can't be mapped to the host
+    let command: Vec<String> = parsed.operands.iter().skip(1).cloned().collect();
+    analyze_inner(&command, &[], None, out, depth);
+}
+
+/// `cp SRC DEST` where one side is `CONTAINER:PATH`.
+fn extract_cp(args: &[String], out: &mut CommandAnalysis) {
+    let operands: Vec<_> = args.iter().filter(|a| !a.starts_with('-')).collect();
+    let [src, dest] = operands.as_slice() else {
+        return;
+    };
+    if !is_container_ref(src) {
+        push_host_path(out, src, "container copy source", false);
+    }
+    if !is_container_ref(dest) {
+        push_host_path(out, dest, "container copy destination", true);
+    }
+}
+
+/// `build [OPTIONS] PATH` — the context directory and `-f` Dockerfile are read.
+fn extract_build(args: &[String], out: &mut CommandAnalysis) {
+    // Unlike run, build accepts options after the context
+    let mut options = Vec::new();
+    let mut operands = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if let Some((flag, value)) = arg.split_once('=').filter(|_| arg.starts_with('-')) {
+            options.push((flag.to_string(), Some(value.to_string())));
+        } else if BUILD_BOOL_FLAGS.contains(&arg.as_str()) {
+            options.push((arg.clone(), None));
+        } else if arg.starts_with('-') && arg != "-" {
+            options.push((arg.clone(), args.get(i + 1).cloned()));
+            i += 1;
+        } else {
+            operands.push(arg.clone());
+        }
+        i += 1;
+    }
+    for (flag, value) in &options {
+        match (flag.as_str(), value) {
+            ("-f" | "--file", Some(path)) if path != "-" => {
+                push_host_path(out, path, "Dockerfile", false);
+            }
+            ("-o" | "--output", Some(dest)) => {
+                // `type=local,dest=out` or a bare directory
+                let dir = dest
+                    .split(',')
+                    .find_map(|kv| kv.strip_prefix("dest="))
+                    .unwrap_or(if dest.contains('=') { "" } else { dest });
+                if !dir.is_empty() && dir != "-" {
+                    push_host_path(out, dir, "container build output", true);
+                }
+            }
+            ("--iidfile" | "--metadata-file", Some(path)) => {
+                push_host_path(out, path, "container build output", true);
+            }
+            _ => {}
+        }
+    }
+    if let Some(context) = operands.first()
+        && !context.contains("://")
+        && context != "-"
+    {
+        push_host_path(out, context, "container build context", false);
+    }
+}
+
+/// Analyze the command a container runs. Its findings apply as-is; its paths
+/// are container paths, kept only when they fall inside a bind mount and
+/// translated to the host side.
+fn analyze_inner(
+    command: &[String],
+    mounts: &[BindMount],
+    workdir: Option<&str>,
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    if command.is_empty() {
+        return;
+    }
+    let Ok(joined) = shlex::try_join(command.iter().map(String::as_str)) else {
+        return;
+    };
+    let mut inner = CommandAnalysis::default();
+    extract_paths_recursive(&joined, &mut inner, depth + 1);
+    out.findings.extend(inner.findings);
+    for ep in inner.paths {
+        if let Some(raw) = to_host_path(&ep.raw, mounts, workdir) {
+            out.paths.push(ExtractedPath {
+                raw,
+                context: ep.context,
+            });
+        }
+    }
+}
+
+/// Translate a path inside the container to the host path it's mounted from.
+fn to_host_path(path: &str, mounts: &[BindMount], workdir: Option<&str>) -> Option<String> {
+    let absolute = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("{}/{}", workdir?.trim_end_matches('/'), path)
+    };
+    mounts.iter().rev().find_map(|m| {
+        let target = m.target.trim_end_matches('/');
+        let rest = absolute.strip_prefix(target)?;
+        if rest.is_empty() {
+            Some(m.source.clone())
+        } else {
+            rest.starts_with('/')
+                .then(|| format!("{}{}", m.source.trim_end_matches('/'), rest))
+        }
+    })
+}
+
+struct ParsedOptions {
+    /// (flag, value) in order; boolean flags have no value.
+    options: Vec<(String, Option<String>)>,
+    /// The first operand (image or container) and everything after it.
+    operands: Vec<String>,
+}
+
+/// Split options from operands. Options stop at the first operand, since what
+/// follows the image is the container's own command line.
+fn parse_options(args: &[String]) -> ParsedOptions {
+    let mut options = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            i += 1;
+            break;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            break;
+        }
+        if !arg.starts_with("--") {
+            // A bundle of boolean flags may end in one that takes a value,
+            // attached or as the next argument: `-itv /:/h`, `-v/:/h`, `-e=X`
+            let value_chars: String = arg[1..]
+                .chars()
+                .filter(|c| !BOOL_SHORT_CHARS.contains(*c))
+                .collect();
+            match short_option(arg, args.get(i + 1), &value_chars) {
+                Some((flag, value, consumed_next)) => {
+                    let value = value
+                        .strip_prefix('=')
+                        .map_or(value.clone(), str::to_string);
+                    options.push((format!("-{flag}"), Some(value)));
+                    if consumed_next {
+                        i += 1;
+                    }
+                }
+                None => options.push((arg.clone(), None)),
+            }
+        } else if let Some((flag, value)) = arg.split_once('=') {
+            options.push((flag.to_string(), Some(value.to_string())));
+        } else if BOOL_FLAGS.contains(&arg.as_str()) {
+            options.push((arg.clone(), None));
+        } else {
+            options.push((arg.clone(), args.get(i + 1).cloned()));
+            i += 1;
+        }
+        i += 1;
+    }
+    ParsedOptions {
+        options,
+        operands: args.get(i..).unwrap_or_default().to_vec(),
+    }
+}
+
+/// `-v SRC:DST[:OPTS]` — a bind mount when SRC is a host path rather than a
+/// named volume. Returns the mount and whether it is read-only.
+fn parse_volume(spec: &str) -> Option<(BindMount, bool)> {
+    let mut parts = spec.splitn(3, ':');
+    let source = host_source(parts.next()?)?;
+    let target = parts.next()?.to_string();
+    let read_only = parts
+        .next()
+        .is_some_and(|opts| opts.split(',').any(|o| o == "ro"));
+    Some((BindMount { source, target }, read_only))
+}
+
+/// `--mount type=bind,source=SRC,target=DST[,readonly]`
+fn parse_mount(spec: &str) -> Option<(BindMount, bool)> {
+    let mut kind = "volume";
+    let mut source = None;
+    let mut target = None;
+    let mut read_only = false;
+    for field in spec.split(',') {
+        let (key, value) = field.split_once('=').unwrap_or((field, ""));
+        match key {
+            "type" => kind = value,
+            "source" | "src" => source = Some(value),
+            "target" | "destination" | "dst" => target = Some(value),
+            "readonly" | "ro" => read_only = value.is_empty() || value == "true" || value == "1",
+            _ => {}
+        }
+    }
+    if kind != "bind" {
+        return None;
+    }
+    Some((
+        BindMount {
+            source: host_source(source?)?,
+            target: target?.to_string(),
+        },
+        read_only,
+    ))
+}
+
+/// A volume source that names a host path, with `$PWD`-style spellings
+/// of the current directory rewritten to `.`.
+fn host_source(source: &str) -> Option<String> {
+    for cwd in CWD_SPELLINGS {
+        if let Some(rest) = source.strip_prefix(cwd)
+            && (rest.is_empty() || rest.starts_with('/'))
+        {
+            return Some(format!(".{rest}"));
+        }
+    }
+    let is_path = source.starts_with('/')
+        || source.starts_with('.')
+        || source.starts_with('~')
+        || source.starts_with('$');
+    is_path.then(|| source.to_string())
+}
+
+/// `container:path` in `docker cp`, as opposed to a host path.
+fn is_container_ref(arg: &str) -> bool {
+    match arg.find(':') {
+        Some(colon) => {
+            !arg[..colon].contains('/') && !arg.starts_with('.') && !arg.starts_with('~')
+        }
+        None => false,
+    }
+}
+
+fn push_host_path(out: &mut CommandAnalysis, raw: &str, label: &'static str, write: bool) {
+    out.paths.push(ExtractedPath {
+        raw: raw.to_string(),
+        context: PathContext::ContainerHostPath { label, write },
+    });
+}
@@ -0,0 +1,423 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::bash_analyzer::{
+    CommandAnalysis, Finding, PathContext, REDIRECT_RE, ShellOperator, command_start,
+    extract_paths_recursive, split_shell_segments, strip_unquoted, unquoted_captures,
+};
+use crate::facts::Fact;
+
+/// Input redirections: `< file`, `0<file` (not heredocs or `<(…)`).
+static INPUT_REDIRECT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|[^<\d])\d*<\s*([^\s<(&|;]\S*)").unwrap());
+
+/// A leading variable assignment: `X=…`, `export X=…`, `local X=…`.
+static ASSIGNMENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:(?:export|local|readonly|declare|typeset)\s+(?:-\w+\s+)*)?([A-Za-z_][A-Za-z0-9_]*)=",
+    )
+    .unwrap()
+});
+
+/// A variable expansion: `$X`, `${X}`, `${X:-…}`.
+static VARIABLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{?([A-Za-z_][A-Za-z0-9_]*)").unwrap());
+
+/// File names that hold credentials wherever they are.
+const CREDENTIAL_FILES: &[&str] = &[
+    ".env",
+    ".netrc",
+    "_netrc",
+    ".git-credentials",
+    ".npmrc",
+    ".pypirc",
+    ".pgpass",
+    ".my.cnf",
+    ".vault-token",
+    ".htpasswd",
+    "id_rsa",
+    "id_dsa",
+    "id_ecdsa",
+    "id_ed25519",
+    "shadow",
+    "gshadow",
+];
+
+/// Extensions of key and keystore files.
+const CREDENTIAL_EXTENSIONS: &[&str] = &["pem", "key", "p12", "pfx", "jks", "keystore"];
+
+/// Words that mark a file or directory name as holding secrets.
+const CREDENTIAL_WORDS: &[&str] = &["secret", "credential"];
+
+/// Directories whose contents are credentials.
+const CREDENTIAL_DIRS: &[&str] = &[
+    ".ssh",
+    ".aws",
+    ".gnupg",
+    ".kube",
+    ".azure",
+    ".password-store",
+];
+
+/// Files in credential directories that are safe to share.
+const PUBLIC_FILES: &[&str] = &["known_hosts", "authorized_keys"];
+
+/// `.env` variants that hold placeholders rather than secrets.
+const ENV_TEMPLATES: &[&str] = &["example", "sample", "template", "dist"];
+
+/// Where data reaching a network sink was read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlowSource {
+    /// A file or directory, as written in the command.
+    Path(String),
+    /// Environment variables, dumped by `env`, `printenv`, `export -p`, ...
+    Environment,
+}
+
+/// Data read by one command, remembered until it reaches a sink.
+#[derive(Debug, Clone)]
+struct Taint {
+    source: FlowSource,
+    reader: String,
+}
+
+/// Follows data through one command list: down pipelines, into files that
+/// later commands read, and into shell variables.
+#[derive(Default)]
+pub(crate) struct FlowTracker {
+    stream: Vec<Taint>,
+    files: Vec<(String, Vec<Taint>)>,
+    vars: Vec<(String, Vec<Taint>)>,
+}
+
+impl FlowTracker {
+    /// Account for one segment of a command list, given what the analyzer
+    /// found in it. Returns a finding for every read that reaches a sink.
+    pub(crate) fn step(
+        &mut self,
+        segment: &str,
+        preceded_by: Option<ShellOperator>,
+        stage: &CommandAnalysis,
+        depth: usize,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let piped = std::mem::take(&mut self.stream);
+        let piped = if preceded_by == Some(ShellOperator::Pipe) {
+            piped
+        } else {
+            Vec::new()
+        };
+
+        // `read` everything the command reads, `sent` what a sink transmits
+        let mut read = Vec::new();
+        let mut sent = Vec::new();
+        for ep in stage
+            .paths
+            .iter()
+            .filter(|ep| reads_from(&ep.context, &ep.raw))
+        {
+            let taints = self.file_taints(&ep.raw, segment);
+            if matches!(
+                ep.context,
+                PathContext::UploadData | PathContext::RemoteTransfer { upload: true }
+            ) {
+                sent.extend(taints.iter().cloned());
+            }
+            read.extend(taints);
+        }
+        for cap in unquoted_captures(&INPUT_REDIRECT_RE, segment) {
+            let taints = self.file_taints(&cap[1], segment);
+            sent.extend(taints.iter().cloned());
+            read.extend(taints);
+        }
+        let mut expanded = Vec::new();
+        for body in substitutions(segment) {
+            let mut scratch = CommandAnalysis::default();
+            extract_paths_recursive(&body, &mut scratch, depth + 1);
+            for ep in scratch
+                .paths
+                .iter()
+                .filter(|ep| reads_from(&ep.context, &ep.raw))
+            {
+                expanded.extend(self.file_taints(&ep.raw, &body));
+            }
+            for seg in split_shell_segments(&body) {
+                if dumps_environment(seg.command.trim()) {
+                    expanded.push(Taint {
+                        source: FlowSource::Environment,
+                        reader: body.clone(),
+                    });
+                }
+            }
+            findings.extend(
+                scratch
+                    .findings
+                    .into_iter()
+                    .filter(|f| matches!(f, Finding::DataFlow { .. })),
+            );
+        }
+        for cap in VARIABLE_RE.captures_iter(segment) {
+            if let Some((_, taints)) = self.vars.iter().find(|(name, _)| name == &cap[1]) {
+                expanded.extend(taints.iter().cloned());
+            }
+        }
+        if dumps_environment(segment) {
+            read.push(Taint {
+                source: FlowSource::Environment,
+                reader: segment.to_string(),
+            });
+        }
+        sent.extend(expanded.iter().cloned());
+        read.extend(expanded.iter().cloned());
+
+        // Report data that reaches a sink
+        let mut is_sink = false;
+        let mut reported: Vec<FlowSource> = Vec::new();
+        for sink in stage.findings.iter().filter_map(|f| match f {
+            Finding::Fact(fact) => Some(fact),
+            _ => None,
+        }) {
+            let Some(reads_stdin) = sink_reads_stdin(sink) else {
+                continue;
+            };
+            is_sink = true;
+            let stdin = if reads_stdin { piped.as_slice() } else { &[] };
+            for taint in stdin.iter().chain(&sent) {
+                if !reported.contains(&taint.source) {
+                    reported.push(taint.source.clone());
+                    findings.push(Finding::DataFlow {
+                        source: taint.source.clone(),
+                        reader: taint.reader.clone(),
+                        sink: sink.clone(),
+                    });
+                }
+            }
+        }
+
+        // What flows on: to the next pipeline stage, into files and variables
+        let mut data = piped;
+        data.extend(read);
+        if !data.is_empty() {
+            let written = stage
+                .paths
+                .iter()
+                .filter(|ep| ep.context.is_write())
+                .map(|ep| ep.raw.as_str());
+            let redirects = unquoted_captures(&REDIRECT_RE, segment);
+            let redirected = redirects
+                .iter()
+                .filter_map(|cap| cap.get(1))
+                .map(|m| m.as_str());
+            for file in written.chain(redirected) {
+                self.files.push((file.to_string(), data.clone()));
+            }
+        }
+        if let Some(cap) = ASSIGNMENT_RE.captures(segment) {
+            self.assign(&cap[1], expanded);
+        } else if let Some(names) = read_builtin_targets(segment) {
+            for name in names {
+                self.assign(&name, data.clone());
+            }
+        }
+        if !is_sink {
+            self.stream = data;
+        }
+        findings
+    }
+
+    /// A read of `raw`: the path itself plus whatever earlier commands wrote to it.
+    fn file_taints(&self, raw: &str, reader: &str) -> Vec<Taint> {
+        let mut taints = vec![Taint {
+            source: FlowSource::Path(raw.to_string()),
+            reader: reader.to_string(),
+        }];
+        for (_, written) in self.files.iter().filter(|(file, _)| file == raw) {
+            taints.extend(written.iter().cloned());
+        }
+        taints
+    }
+
+    fn assign(&mut self, name: &str, taints: Vec<Taint>) {
+        self.vars.retain(|(var, _)| var != name);
+        if !taints.is_empty() {
+            self.vars.push((name.to_string(), taints));
+        }
+    }
+}
+
+/// Whether a path in this context is read by the command.
+fn reads_from(context: &PathContext, raw: &str) -> bool {
+    !matches!(
+        context,
+        PathContext::Redirection
+            | PathContext::CdTarget
+            | PathContext::CdImplicitHome
+            | PathContext::CdDash
+            | PathContext::DownloadOutput
+            | PathContext::DdOutput
+            | PathContext::FindOutput
+            | PathContext::RemoteTransfer { upload: false }
+            | PathContext::ArchivePath {
+                label: "extraction directory",
+                ..
+            }
+            | PathContext::DatabasePath {
+                label: "query output file" | "copy target file" | "database dump file",
+                ..
+            }
+    ) && raw != "-"
+        && raw != "/dev/stdin"
+}
+
+/// Whether a fact is a network sink. Returns whether the sink also sends
+/// what it reads on stdin, or None when the fact is not a sink.
+fn sink_reads_stdin(fact: &Fact) -> Option<bool> {
+    match fact.name.as_str() {
+        "network.connect" | "remote.exec" | "remote.shell" => Some(true),
+        "network.request" => Some(fact.attr("upload") == Some("true")),
+        "remote.upload" => Some(false),
+        // Whoever connects to a raw listener receives its stdin
+        "network.listen" => Some(matches!(
+            fact.attr("tool"),
+            Some("nc" | "ncat" | "netcat" | "socat")
+        )),
+        _ => None,
+    }
+}
+
+/// Whether a segment prints environment variables: `env` or `printenv` on
+/// their own, `set` with no arguments, `export -p`, `declare -x`.
+fn dumps_environment(segment: &str) -> bool {
+    let cleaned = strip_unquoted(&REDIRECT_RE, segment);
+    let Some(tokens) = shlex::split(&cleaned) else {
+        return false;
+    };
+    let Some(first) = tokens.iter().position(|t| !is_assignment(t)) else {
+        return false;
+    };
+    let name = tokens[first].rsplit('/').next().unwrap_or_default();
+    let args = &tokens[first + 1..];
+    match name {
+        "printenv" => true,
+        "env" => command_start(&tokens) >= tokens.len(),
+        "set" => args.is_empty(),
+        "export" | "declare" | "typeset" => args
+            .iter()
+            .all(|a| matches!(a.as_str(), "-p" | "-x" | "-px" | "-xp")),
+        _ => false,
+    }
+}
+
+fn is_assignment(token: &str) -> bool {
+    token.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Variables a `read` builtin assigns: `read -r TOKEN < file`.
+fn read_builtin_targets(segment: &str) -> Option<Vec<String>> {
+    let cleaned = strip_unquoted(&INPUT_REDIRECT_RE, segment);
+    let tokens = shlex::split(&cleaned)?;
+    let (cmd, args) = tokens.split_first()?;
+    if !matches!(cmd.as_str(), "read" | "mapfile" | "readarray") {
+        return None;
+    }
+    let mut names = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            // Options that take a value
+            "-a" | "-d" | "-n" | "-N" | "-p" | "-t" | "-u" | "-C" | "-c" | "-O" | "-s" => i += 1,
+            arg if arg.starts_with('-') => {}
+            name => names.push(name.to_string()),
+        }
+        i += 1;
+    }
+    Some(names)
+}
+
+/// Bodies of `$(…)` and `` `…` `` command substitutions outside single quotes.
+pub(crate) fn substitutions(segment: &str) -> Vec<String> {
+    let chars: Vec<char> = segment.chars().collect();
+    let mut bodies = Vec::new();
+    let mut in_single_quote = false;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '\'' => in_single_quote = !in_single_quote,
+            '$' if !in_single_quote
+                && chars.get(i + 1) == Some(&'(')
+                && chars.get(i + 2) != Some(&'(') =>
+            {
+                let start = i + 2;
+                let mut level = 1;
+                let mut j = start;
+                while j < chars.len() {
+                    match chars[j] {
+                        '(' => level += 1,
+                        ')' => {
+                            level -= 1;
+                            if level == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    j += 1;
+                }
+                bodies.push(chars[start..j.min(chars.len())].iter().collect());
+                i = j;
+            }
+            '`' if !in_single_quote => {
+                let start = i + 1;
+                let end = chars[start..]
+                    .iter()
+                    .position(|&c| c == '`')
+                    .map_or(chars.len(), |p| start + p);
+                bodies.push(chars[start..end].iter().collect());
+                i = end;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bodies
+}
+
+/// Whether a path names a credential file or directory: `.env`, ssh keys,
+/// `~/.aws`, `*.pem`, `/proc/*/environ`, names containing "secret", ...
+pub fn is_credential_path(raw: &str) -> bool {
+    let parts: Vec<&str> = raw
+        .split('/')
+        .filter(|p| !p.is_empty() && *p != ".")
+        .collect();
+    let Some(name) = parts.last() else {
+        return false;
+    };
+    let lower = name.to_ascii_lowercase();
+    if let Some(variant) = lower.strip_prefix(".env.") {
+        return !ENV_TEMPLATES.contains(&variant);
+    }
+    if CREDENTIAL_FILES.contains(&lower.as_str())
+        || CREDENTIAL_WORDS.iter().any(|word| lower.contains(word))
+        || lower
+            .rsplit_once('.')
+            .is_some_and(|(stem, ext)| !stem.is_empty() && CREDENTIAL_EXTENSIONS.contains(&ext))
+    {
+        return true;
+    }
+    if lower == "environ" && parts.contains(&"proc") {
+        return true;
+    }
+    if lower.ends_with(".pub") || PUBLIC_FILES.contains(&lower.as_str()) {
+        return false;
+    }
+    parts.iter().any(|part| CREDENTIAL_DIRS.contains(part))
+        || parts.windows(2).any(|w| {
+            matches!(
+                w,
+                [".docker", "config.json"] | [".config", "gcloud"] | [".config", "gh"]
+            )
+        })
+}
@@ -0,0 +1,131 @@
+use eyre::{Result, WrapErr};
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::facts::Fact;
+use crate::hosts::{self, HostPattern};
+use crate::util::truncate;
+
+/// Which databases the database CLIs may connect to, as written in YAML:
+///
+/// ```yaml
+/// databases:
+///   allow: ["app_dev@localhost", "*@127.0.0.1", "*.sqlite3"]
+///   deny: ["*@*.prod.example.com"]
+/// ```
+///
+/// Patterns are `database@host` globs. A pattern without `@` matches the
+/// database on any host; database names (and sqlite3 file paths) are
+/// case-sensitive, hosts are not and may be CIDR ranges.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DatabasePolicy {
+    /// When non-empty, only matching databases are allowed.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Databases that are always denied, even when allowed.
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl DatabasePolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+}
+
+struct DatabasePattern {
+    database: Regex,
+    host: Option<HostPattern>,
+}
+
+impl DatabasePattern {
+    fn matches(&self, database: &str, host: Option<&str>) -> bool {
+        self.database.is_match(database)
+            && match (&self.host, host) {
+                (None, _) => true,
+                (Some(pattern), Some(host)) => pattern.matches(host),
+                (Some(_), None) => false,
+            }
+    }
+}
+
+/// Checks the targets of `database.connect` facts against a [`DatabasePolicy`].
+pub struct DatabaseRule {
+    allow: Vec<DatabasePattern>,
+    deny: Vec<DatabasePattern>,
+}
+
+impl DatabaseRule {
+    pub fn new(policy: &DatabasePolicy) -> Result<Self> {
+        Ok(Self {
+            allow: compile(&policy.allow)?,
+            deny: compile(&policy.deny)?,
+        })
+    }
+
+    /// Returns Some(reason) when a `database.connect` fact targets a denied
+    /// database, None for allowed targets and other facts. Server
+    /// connections without a host go to `localhost`; a missing database
+    /// name matches as empty.
+    pub fn check(&self, fact: &Fact) -> Option<String> {
+        if fact.name != "database.connect" {
+            return None;
+        }
+        // A psql service's host and database are in a file clarg doesn't read
+        if let Some(service) = fact.attr("service")
+            && fact.attr("host").is_none()
+            && !self.allow.is_empty()
+        {
+            return Some(format!(
+                "Blocked by `clarg`: '{}' connects through the service '{}', whose database isn't known to be on the database allowlist",
+                truncate(&fact.command, 100),
+                service
+            ));
+        }
+        let database = fact.attr("database").unwrap_or_default();
+        let host = match fact.attr("host") {
+            Some(host) => Some(host),
+            None if fact.attr("tool") == Some("sqlite3") => None,
+            None => Some("localhost"),
+        };
+        let why = if self.deny.iter().any(|p| p.matches(database, host)) {
+            "is on the database denylist"
+        } else if !self.allow.is_empty() && !self.allow.iter().any(|p| p.matches(database, host)) {
+            "is not on the database allowlist"
+        } else {
+            return None;
+        };
+        let target = match host {
+            Some(host) => format!("{database}@{host}"),
+            None => database.to_string(),
+        };
+        Some(format!(
+            "Blocked by `clarg`: '{}' connects to database '{}', which {}",
+            truncate(&fact.command, 100),
+            target,
+            why
+        ))
+    }
+}
+
+/// Compile `database@host` patterns: the database part to a case-sensitive
+/// glob, the host part like a host policy pattern.
+fn compile(patterns: &[String]) -> Result<Vec<DatabasePattern>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            let (database, host) = match pattern.rsplit_once('@') {
+                Some((database, host)) => (database, Some(host)),
+                None => (pattern.as_str(), None),
+            };
+            let re = format!("^{}$", regex::escape(database).replace(r"\*", ".*"));
+            let database =
+                Regex::new(&re).wrap_err_with(|| format!("invalid database pattern: {pattern}"))?;
+            let host = match host {
+                Some(host) => hosts::compile(&[host.to_string()])?.pop(),
+                None => None,
+            };
+            Ok(DatabasePattern { database, host })
+        })
+        .collect()
+}
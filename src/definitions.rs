@@ -0,0 +1,284 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::bash_analyzer::{REDIRECT_RE, strip_unquoted, unquoted_captures, unwrap_command};
+
+/// The start of a function definition: `name() {`, `name () (`,
+/// `function name {`, `function name() {`. Captures the name (either
+/// group) and the opening brace or parenthesis.
+static FUNCTION_START_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^(?:function\s+([^\s(){};|&<>'"$=]+)(?:\s*\(\s*\))?|([^\s(){};|&<>'"$=]+)\s*\(\s*\))\s*([{(])"#,
+    )
+    .unwrap()
+});
+
+/// A function definition cut out of a command. `marker` stands in for the
+/// definition in the remaining command text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Function {
+    pub marker: String,
+    pub name: String,
+    pub body: String,
+    /// The definition as written, for messages.
+    pub source: String,
+}
+
+/// Cut function definitions out of a command, leaving a marker token where
+/// each one was so it is defined in order. Definitions whose body never
+/// closes are left alone.
+pub(crate) fn take_functions(command: &str) -> (String, Vec<Function>) {
+    let mut out = String::new();
+    let mut functions = Vec::new();
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut escape_next = false;
+    // Whether the next word is in command position
+    let mut at_command = true;
+    let mut pos = 0;
+    while pos < command.len() {
+        let rest = &command[pos..];
+        let ch = rest.chars().next().unwrap_or_default();
+        let escaped = escape_next;
+        if escape_next {
+            escape_next = false;
+        } else if ch == '\\' && !in_single_quote {
+            escape_next = true;
+        } else if ch == '\'' && !in_double_quote {
+            in_single_quote = !in_single_quote;
+        } else if ch == '"' && !in_single_quote {
+            in_double_quote = !in_double_quote;
+        } else if !in_single_quote
+            && !in_double_quote
+            && at_command
+            && let Some(cap) = FUNCTION_START_RE.captures(rest)
+            && let Some(len) = closing(&rest[cap[0].len()..], &cap[3])
+        {
+            let open = cap[0].len();
+            let marker = format!("__clarg_function_{}__", functions.len());
+            out.push_str(&marker);
+            functions.push(Function {
+                marker,
+                name: cap
+                    .get(1)
+                    .or(cap.get(2))
+                    .map_or("", |m| m.as_str())
+                    .to_string(),
+                body: rest[open..open + len].to_string(),
+                source: rest[..open + len + 1].to_string(),
+            });
+            pos += open + len + 1;
+            at_command = false;
+            continue;
+        }
+        let unquoted = !escaped && !in_single_quote && !in_double_quote;
+        if unquoted && ";&|({\n".contains(ch) {
+            at_command = true;
+        } else if !ch.is_whitespace() {
+            at_command = false;
+        }
+        out.push(ch);
+        pos += ch.len_utf8();
+    }
+    (out, functions)
+}
+
+/// Byte length of a body opened by `open` up to its matching close, or None
+/// if it never closes.
+fn closing(body: &str, open: &str) -> Option<usize> {
+    let (open, close) = if open == "{" { ('{', '}') } else { ('(', ')') };
+    let mut depth = 0;
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut escape_next = false;
+    for (pos, ch) in body.char_indices() {
+        if escape_next {
+            escape_next = false;
+        } else if ch == '\\' && !in_single_quote {
+            escape_next = true;
+        } else if ch == '\'' && !in_double_quote {
+            in_single_quote = !in_single_quote;
+        } else if ch == '"' && !in_single_quote {
+            in_double_quote = !in_double_quote;
+        } else if in_single_quote || in_double_quote {
+            continue;
+        } else if ch == open {
+            depth += 1;
+        } else if ch == close {
+            if depth == 0 {
+                return Some(pos);
+            }
+            depth -= 1;
+        }
+    }
+    None
+}
+
+/// Functions and aliases a command defined so far, so that calling one is
+/// analysed as what it runs.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Scope {
+    functions: Vec<(String, String)>,
+    aliases: Vec<(String, String)>,
+    /// Names being expanded, which aren't expanded again inside themselves
+    /// (`alias ls='ls -la'`, recursive functions).
+    expanding: Vec<String>,
+}
+
+/// What a call to a function or alias runs.
+pub(crate) struct Expansion {
+    pub name: String,
+    pub command: String,
+}
+
+impl Scope {
+    pub(crate) fn define_function(&mut self, name: &str, body: &str) {
+        self.functions.retain(|(n, _)| n != name);
+        self.functions.push((name.to_string(), body.to_string()));
+    }
+
+    /// If `sub_cmd` is an `alias` builtin, define its `name=value` operands
+    /// and return them.
+    pub(crate) fn define_aliases(&mut self, sub_cmd: &str) -> Option<Vec<(String, String)>> {
+        let tokens = shlex::split(&strip_unquoted(&REDIRECT_RE, sub_cmd))?;
+        let (cmd, args) = tokens.split_first()?;
+        if cmd != "alias" {
+            return None;
+        }
+        let defined: Vec<_> = args
+            .iter()
+            .filter(|a| !a.starts_with('-'))
+            .filter_map(|a| a.split_once('='))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        for (name, value) in &defined {
+            self.aliases.retain(|(n, _)| n != name);
+            self.aliases.push((name.clone(), value.clone()));
+        }
+        Some(defined)
+    }
+
+    /// The scope inside the expansion of `name`.
+    pub(crate) fn entering(&self, name: &str) -> Scope {
+        let mut inner = self.clone();
+        inner.expanding.push(name.to_string());
+        inner
+    }
+
+    /// What `sub_cmd` runs when its command word is an alias or a function:
+    /// the alias value followed by the rest of the words, or the function
+    /// body with its positional parameters replaced by the arguments.
+    pub(crate) fn expand(&self, sub_cmd: &str) -> Option<Expansion> {
+        let tokens = shlex::split(&strip_unquoted(&REDIRECT_RE, sub_cmd))?;
+        let unwrapped = unwrap_command(&tokens);
+        // `sudo f`, `nohup f` run a program, never a shell function
+        if !unwrapped.wrappers.is_empty() || unwrapped.inline_command.is_some() {
+            return None;
+        }
+        let name = tokens.get(unwrapped.start)?;
+        if self.expanding.contains(name) {
+            return None;
+        }
+        if let Some((_, value)) = self.aliases.iter().find(|(n, _)| n == name) {
+            let at = command_word(sub_cmd, name)?;
+            let command = format!("{}{}{}", &sub_cmd[..at], value, &sub_cmd[at + name.len()..]);
+            return Some(Expansion {
+                name: name.clone(),
+                command,
+            });
+        }
+        let (_, body) = self.functions.iter().find(|(n, _)| n == name)?;
+        let command = substitute_positional(body, &tokens[unwrapped.start + 1..]);
+        Some(Expansion {
+            name: name.clone(),
+            command,
+        })
+    }
+}
+
+/// Byte offset of the first unquoted occurrence of `name` as a whole word.
+fn command_word(sub_cmd: &str, name: &str) -> Option<usize> {
+    let re = Regex::new(&format!(
+        r"(?:^|\s)({})(?:$|[\s;&|<>])",
+        regex::escape(name)
+    ))
+    .ok()?;
+    unquoted_captures(&re, sub_cmd)
+        .first()
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.start())
+}
+
+/// `body` with `$1`, `${2}`, `$@`, `"$@"`, `$*` and `$#` replaced by what
+/// the call passes. Single-quoted text is left alone.
+pub(crate) fn substitute_positional(body: &str, args: &[String]) -> String {
+    let quote =
+        |arg: &String| shlex::try_quote(arg).map_or_else(|_| arg.clone(), |q| q.into_owned());
+    let joined = args.iter().map(quote).collect::<Vec<_>>().join(" ");
+    let mut out = String::new();
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut escape_next = false;
+    let mut pos = 0;
+    while pos < body.len() {
+        let rest = &body[pos..];
+        let ch = rest.chars().next().unwrap_or_default();
+        if !escape_next && !in_single_quote && !in_double_quote && rest.starts_with("\"$@\"") {
+            out.push_str(&joined);
+            pos += 4;
+            continue;
+        }
+        if escape_next {
+            escape_next = false;
+        } else if ch == '\\' && !in_single_quote {
+            escape_next = true;
+        } else if ch == '\'' && !in_double_quote {
+            in_single_quote = !in_single_quote;
+        } else if ch == '"' && !in_single_quote {
+            in_double_quote = !in_double_quote;
+        } else if ch == '$'
+            && !in_single_quote
+            && let Some((param, len)) = positional(&rest[1..])
+        {
+            let value = match param {
+                "@" | "*" if in_double_quote => args.join(" "),
+                "@" | "*" => joined.clone(),
+                "#" => args.len().to_string(),
+                n => {
+                    let index = n.parse::<usize>().unwrap_or_default();
+                    let arg = args.get(index.wrapping_sub(1)).cloned().unwrap_or_default();
+                    if in_double_quote { arg } else { quote(&arg) }
+                }
+            };
+            for c in value.chars() {
+                // Keep the value literal inside the double quotes
+                if in_double_quote && "\\\"$`".contains(c) {
+                    out.push('\\');
+                }
+                out.push(c);
+            }
+            pos += 1 + len;
+            continue;
+        }
+        out.push(ch);
+        pos += ch.len_utf8();
+    }
+    out
+}
+
+/// The positional parameter after a `$` (`1`, `{12}`, `@`), as its name and
+/// the bytes it took. `$0` is the shell's name, not an argument.
+fn positional(after: &str) -> Option<(&str, usize)> {
+    if let Some(braced) = after.strip_prefix('{') {
+        let name = &braced[..braced.find('}')?];
+        let valid = matches!(name, "@" | "*" | "#")
+            || (!name.is_empty() && name != "0" && name.chars().all(|c| c.is_ascii_digit()));
+        return valid.then_some((name, name.len() + 2));
+    }
+    let name = after.get(..1)?;
+    matches!(
+        name,
+        "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" | "@" | "*" | "#"
+    )
+    .then_some((name, 1))
+}
@@ -0,0 +1,131 @@
+use eyre::{Result, WrapErr};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::config::RuleAction;
+use crate::util::truncate;
+
+/// A structured observation about a command, e.g. `git.push.force` with
+/// `remote: origin`, that user and preset rules can match on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fact {
+    /// Dotted name, most general part first (`git.reset.hard`).
+    pub name: String,
+    pub attrs: BTreeMap<String, String>,
+    /// The sub-command the fact was observed in.
+    pub command: String,
+}
+
+impl Fact {
+    pub fn new(name: &str, command: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            attrs: BTreeMap::new(),
+            command: command.to_string(),
+        }
+    }
+
+    /// Add an attribute.
+    pub fn with(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.attrs.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs.get(key).map(String::as_str)
+    }
+}
+
+/// A rule as written in YAML:
+///
+/// ```yaml
+/// rules:
+///   - fact: git.push.force
+///     when: { remote: "^(origin|upstream)$" }
+///     action: deny
+/// ```
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct FactRule {
+    /// Fact name, with `*` matching any run of characters (`git.*`).
+    pub fact: String,
+    /// Attribute regexes that must all match.
+    #[serde(default)]
+    pub when: BTreeMap<String, String>,
+    #[serde(default)]
+    pub action: RuleAction,
+    /// Message shown instead of the generated reason.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+struct CompiledRule {
+    name: Regex,
+    when: Vec<(String, Regex)>,
+    action: RuleAction,
+    reason: Option<String>,
+}
+
+/// Ordered fact rules — the first rule that matches a fact decides.
+pub struct FactRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl FactRules {
+    pub fn new(rules: &[FactRule]) -> Result<Self> {
+        let compiled: Result<Vec<_>> = rules.iter().map(compile).collect();
+        Ok(Self { rules: compiled? })
+    }
+
+    /// The action and reason of the first rule matching `fact`, if any.
+    pub fn check(&self, fact: &Fact) -> Option<(RuleAction, String)> {
+        let rule = self.rules.iter().find(|rule| {
+            rule.name.is_match(&fact.name)
+                && rule
+                    .when
+                    .iter()
+                    .all(|(key, re)| fact.attr(key).is_some_and(|v| re.is_match(v)))
+        })?;
+        let reason = rule.reason.clone().unwrap_or_else(|| {
+            format!(
+                "Blocked by `clarg`: '{}' matched the rule for {}",
+                truncate(&fact.command, 100),
+                describe(fact)
+            )
+        });
+        Some((rule.action, reason))
+    }
+}
+
+fn compile(rule: &FactRule) -> Result<CompiledRule> {
+    let name = format!("^{}$", regex::escape(&rule.fact).replace(r"\*", ".*"));
+    let when: Result<Vec<_>> = rule
+        .when
+        .iter()
+        .map(|(key, pattern)| {
+            Regex::new(pattern)
+                .map(|re| (key.clone(), re))
+                .wrap_err_with(|| {
+                    format!(
+                        "invalid regex for '{key}' in rule '{}': {pattern}",
+                        rule.fact
+                    )
+                })
+        })
+        .collect();
+    Ok(CompiledRule {
+        name: Regex::new(&name).wrap_err_with(|| format!("invalid fact pattern: {}", rule.fact))?,
+        when: when?,
+        action: rule.action,
+        reason: rule.reason.clone(),
+    })
+}
+
+/// Name and attributes for messages: `'git.push.force' (remote=origin, via=-f)`
+fn describe(fact: &Fact) -> String {
+    if fact.attrs.is_empty() {
+        return format!("'{}'", fact.name);
+    }
+    let attrs: Vec<_> = fact.attrs.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    format!("'{}' ({})", fact.name, attrs.join(", "))
+}
@@ -0,0 +1,584 @@
+use crate::bash_analyzer::{
+    CommandAnalysis, ExtractedPath, Finding, PathContext, extract_paths_recursive,
+    extract_unknown_paths,
+};
+use crate::facts::Fact;
+use crate::network_analyzer::url_fact;
+
+/// Global git options that consume the next argument.
+const GLOBAL_VALUE_FLAGS: &[&str] = &[
+    "-C",
+    "-c",
+    "--git-dir",
+    "--work-tree",
+    "--namespace",
+    "--super-prefix",
+    "--config-env",
+    "--exec-path",
+    "--list-cmds",
+    "--attr-source",
+];
+
+/// `git clone` options that consume the next argument.
+const CLONE_VALUE_FLAGS: &[&str] = &[
+    "-o",
+    "--origin",
+    "-b",
+    "--branch",
+    "-u",
+    "--upload-pack",
+    "--reference",
+    "--reference-if-able",
+    "--separate-git-dir",
+    "--depth",
+    "--shallow-since",
+    "--shallow-exclude",
+    "-c",
+    "--config",
+    "--template",
+    "-j",
+    "--jobs",
+    "--filter",
+    "--server-option",
+    "--bundle-uri",
+    "--ref-format",
+];
+
+/// `git clone` options whose value is a path.
+const CLONE_PATH_FLAGS: &[&str] = &[
+    "--reference",
+    "--reference-if-able",
+    "--separate-git-dir",
+    "--template",
+];
+
+/// `git worktree add` options that consume the next argument.
+const WORKTREE_VALUE_FLAGS: &[&str] = &["-b", "-B", "--reason", "--orphan"];
+
+/// `git submodule add` options that consume the next argument.
+const SUBMODULE_VALUE_FLAGS: &[&str] = &["-b", "--branch", "--name", "--reference", "--depth"];
+
+/// Path labels for locations git creates or modifies.
+const WRITTEN_PATH_LABELS: &[&str] = &[
+    "git clone target",
+    "git worktree path",
+    "git submodule path",
+    "git config file",
+];
+
+/// Config keys whose value is a program git runs later.
+const EXEC_CONFIG_KEYS: &[&str] = &[
+    "core.fsmonitor",
+    "core.sshcommand",
+    "core.pager",
+    "core.editor",
+    "core.askpass",
+    "core.gitproxy",
+    "sequence.editor",
+    "gpg.program",
+    "credential.helper",
+    "diff.external",
+    "uploadpack.packobjectshook",
+];
+
+/// Suffixes of per-name config keys whose value is a program
+/// (`filter.<driver>.smudge`, `diff.<driver>.textconv`, ...).
+const EXEC_CONFIG_SUFFIXES: &[(&str, &str)] = &[
+    ("filter.", ".clean"),
+    ("filter.", ".smudge"),
+    ("filter.", ".process"),
+    ("diff.", ".textconv"),
+    ("diff.", ".command"),
+    ("merge.", ".driver"),
+    ("gpg.", ".program"),
+    ("credential.", ".helper"),
+];
+
+/// `git config` actions (new-style subcommands) other than `set`. Some read
+/// and some modify the config, but none takes a key/value pair.
+const CONFIG_NON_SET_ACTIONS: &[&str] = &[
+    "get",
+    "list",
+    "unset",
+    "rename-section",
+    "remove-section",
+    "edit",
+];
+
+/// `git fetch`/`pull`/`push`/`ls-remote` options that consume the next argument.
+const TRANSFER_VALUE_FLAGS: &[&str] = &[
+    "--depth",
+    "--deepen",
+    "--shallow-since",
+    "--shallow-exclude",
+    "--upload-pack",
+    "--receive-pack",
+    "--exec",
+    "-j",
+    "--jobs",
+    "--refmap",
+    "-o",
+    "--server-option",
+    "--push-option",
+    "--negotiation-tip",
+    "--repo",
+    "--recurse-submodules-default",
+];
+
+/// Git subcommands that accept `--no-verify` to skip hooks.
+const NO_VERIFY_SUBCOMMANDS: &[&str] = &["commit", "push", "merge", "am", "rebase", "pull"];
+
+/// Analyze a `git` invocation: resolve repository-relocating options and
+/// path arguments, and record risky operations as facts.
+pub(crate) fn extract_git(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    // Global options come before the subcommand
+    let mut dir: Option<String> = None;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if !arg.starts_with('-') {
+            break;
+        }
+        let (flag, inline) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f, Some(v.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        if !GLOBAL_VALUE_FLAGS.contains(&flag) {
+            i += 1;
+            continue;
+        }
+        let value = match inline {
+            Some(v) => v,
+            None => {
+                i += 1;
+                match args.get(i) {
+                    Some(v) => v.clone(),
+                    None => break,
+                }
+            }
+        };
+        match flag {
+            "-C" => {
+                let joined = join_dir(dir.as_deref(), &value);
+                push_path(out, &joined, "git -C directory");
+                dir = Some(joined);
+            }
+            "--git-dir" => push_path(out, &join_dir(dir.as_deref(), &value), "git --git-dir"),
+            "--work-tree" => push_path(out, &join_dir(dir.as_deref(), &value), "git --work-tree"),
+            "--exec-path" => push_path(out, &value, "git --exec-path"),
+            "-c" => {
+                if let Some((key, val)) = value.split_once('=') {
+                    config_facts(sub_cmd, key, val, "git -c", out);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let Some(subcommand) = args.get(i) else {
+        return;
+    };
+    let rest = &args[i + 1..];
+
+    let no_verify = rest.iter().any(|a| a == "--no-verify")
+        || (subcommand == "commit" && has_short_flag(rest, 'n'));
+    if no_verify && NO_VERIFY_SUBCOMMANDS.contains(&subcommand.as_str()) {
+        out.findings.push(Finding::Fact(
+            Fact::new("git.no-verify", sub_cmd).with("subcommand", subcommand.as_str()),
+        ));
+    }
+
+    match subcommand.as_str() {
+        "clone" => {
+            if let Some(repo) = positionals(rest, CLONE_VALUE_FLAGS).first() {
+                remote_fact(sub_cmd, subcommand, repo, out);
+            }
+            extract_clone(rest, dir.as_deref(), out);
+        }
+        "worktree" => extract_worktree(rest, dir.as_deref(), out),
+        "submodule" => extract_submodule(sub_cmd, rest, dir.as_deref(), out, depth),
+        "push" => {
+            if let Some(remote) = positionals(rest, TRANSFER_VALUE_FLAGS).first() {
+                remote_fact(sub_cmd, subcommand, remote, out);
+            }
+            push_facts(sub_cmd, rest, out);
+        }
+        "fetch" | "pull" | "ls-remote" => {
+            if let Some(remote) = positionals(rest, TRANSFER_VALUE_FLAGS).first() {
+                remote_fact(sub_cmd, subcommand, remote, out);
+            }
+            extract_rebased_paths(rest, dir.as_deref(), out);
+        }
+        "remote" => {
+            // remote add <name> <url>, remote set-url [--push|--add] <name> <url>
+            let operands = positionals(rest, &["-t", "-m", "--mirror"]);
+            if let [action, _, url, ..] = operands.as_slice()
+                && (action == "add" || action == "set-url")
+            {
+                remote_fact(sub_cmd, subcommand, url, out);
+            }
+        }
+        "reset" => {
+            if rest.iter().any(|a| a == "--hard") {
+                let mut fact = Fact::new("git.reset.hard", sub_cmd);
+                if let Some(target) = positionals(rest, &[]).first() {
+                    fact = fact.with("target", target.as_str());
+                }
+                out.findings.push(Finding::Fact(fact));
+            }
+            extract_rebased_paths(rest, dir.as_deref(), out);
+        }
+        "clean" => {
+            let forced = has_short_flag(rest, 'f') || rest.iter().any(|a| a == "--force");
+            let dry_run = has_short_flag(rest, 'n') || rest.iter().any(|a| a == "--dry-run");
+            if forced && !dry_run {
+                out.findings.push(Finding::Fact(
+                    Fact::new("git.clean.force", sub_cmd)
+                        .with("directories", has_short_flag(rest, 'd').to_string())
+                        .with(
+                            "ignored",
+                            (has_short_flag(rest, 'x') || has_short_flag(rest, 'X')).to_string(),
+                        ),
+                ));
+            }
+            extract_rebased_paths(rest, dir.as_deref(), out);
+        }
+        "checkout" | "restore" => {
+            discard_fact(sub_cmd, subcommand, rest, out);
+            extract_rebased_paths(rest, dir.as_deref(), out);
+        }
+        "branch" => {
+            let delete = rest.iter().any(|a| a == "--delete") || has_short_flag(rest, 'd');
+            let force = rest.iter().any(|a| a == "--force") || has_short_flag(rest, 'f');
+            if has_short_flag(rest, 'D') || (delete && force) {
+                let branches = positionals(rest, &[]);
+                out.findings.push(Finding::Fact(
+                    Fact::new("git.branch.force-delete", sub_cmd)
+                        .with("branch", branches.join(" ")),
+                ));
+            }
+        }
+        "filter-branch" | "filter-repo" => {
+            out.findings.push(Finding::Fact(Fact::new(
+                &format!("git.{subcommand}"),
+                sub_cmd,
+            )));
+        }
+        "config" => {
+            let mut values = positionals(
+                rest,
+                &["--file", "-f", "--blob", "--type", "--default", "--comment"],
+            );
+            if values.first().is_some_and(|v| v == "set") {
+                values.remove(0);
+            }
+            let sets = !values
+                .first()
+                .is_some_and(|v| CONFIG_NON_SET_ACTIONS.contains(&v.as_str()))
+                && !rest.iter().any(|a| {
+                    a.starts_with("--get") || a.starts_with("--unset") || a == "-l" || a == "--list"
+                });
+            if sets && let [key, value, ..] = values.as_slice() {
+                config_facts(sub_cmd, key, value, "git config", out);
+            }
+            for pair in rest.windows(2) {
+                if pair[0] == "--file" || pair[0] == "-f" {
+                    push_path(out, &join_dir(dir.as_deref(), &pair[1]), "git config file");
+                }
+            }
+        }
+        _ => extract_rebased_paths(rest, dir.as_deref(), out),
+    }
+}
+
+/// `git clone <repo> [<dir>]` — a local source and the target directory.
+fn extract_clone(args: &[String], dir: Option<&str>, out: &mut CommandAnalysis) {
+    let mut positional = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if let Some((flag, value)) = arg.split_once('=')
+            && CLONE_PATH_FLAGS.contains(&flag)
+        {
+            push_path(out, &join_dir(dir, value), "git clone option path");
+        } else if CLONE_VALUE_FLAGS.contains(&arg.as_str()) {
+            if CLONE_PATH_FLAGS.contains(&arg.as_str())
+                && let Some(value) = args.get(i + 1)
+            {
+                push_path(out, &join_dir(dir, value), "git clone option path");
+            }
+            i += 1;
+        } else if arg == "--" {
+            positional.extend(args[i + 1..].iter().cloned());
+            break;
+        } else if !arg.starts_with('-') {
+            positional.push(arg.clone());
+        }
+        i += 1;
+    }
+    if let Some(repo) = positional.first()
+        && is_local_repo(repo)
+    {
+        push_path(out, &join_dir(dir, repo), "git clone source");
+    }
+    if let Some(target) = positional.get(1) {
+        push_path(out, &join_dir(dir, target), "git clone target");
+    }
+}
+
+/// `git worktree add|move|remove …`
+fn extract_worktree(args: &[String], dir: Option<&str>, out: &mut CommandAnalysis) {
+    let Some(action) = args.first() else {
+        return;
+    };
+    let operands = positionals(&args[1..], WORKTREE_VALUE_FLAGS);
+    let paths: &[String] = match action.as_str() {
+        // add <path> [<commit-ish>]
+        "add" => operands.get(..1).unwrap_or(&[]),
+        // move <worktree> <new-path>, remove/lock/unlock/repair <worktree>
+        "move" | "remove" | "lock" | "unlock" | "repair" => &operands,
+        _ => &[],
+    };
+    for path in paths {
+        push_path(out, &join_dir(dir, path), "git worktree path");
+    }
+}
+
+/// `git submodule add <repo> [<path>]`, `git submodule foreach <command>`,
+/// and pathspecs for the other submodule actions.
+fn extract_submodule(
+    sub_cmd: &str,
+    args: &[String],
+    dir: Option<&str>,
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let Some(action) = args.iter().find(|a| !a.starts_with('-')) else {
+        return;
+    };
+    let after = &args[args.iter().position(|a| a == action).unwrap_or(0) + 1..];
+    match action.as_str() {
+        "add" => {
+            let operands = positionals(after, SUBMODULE_VALUE_FLAGS);
+            if let Some(repo) = operands.first() {
+                if is_local_repo(repo) {
+                    push_path(out, &join_dir(dir, repo), "git submodule source");
+                } else {
+                    remote_fact(sub_cmd, "submodule", repo, out);
+                }
+            }
+            if let Some(path) = operands.get(1) {
+                push_path(out, &join_dir(dir, path), "git submodule path");
+            }
+        }
+        // foreach runs a shell command in every submodule
+        "foreach" => {
+            let command: Vec<_> = after
+                .iter()
+                .filter(|a| *a != "--recursive")
+                .cloned()
+                .collect();
+            if !command.is_empty() {
+                extract_paths_recursive(&command.join(" "), out, depth + 1);
+            }
+        }
+        _ => {
+            for path in positionals(after, &["--reference", "--depth", "-j", "--jobs", "--name"]) {
+                push_path(out, &join_dir(dir, &path), "git submodule path");
+            }
+        }
+    }
+}
+
+/// A `network.git` fact for a remote given as a URL or `[user@]host:path`.
+/// Named remotes (`origin`) and local repositories are skipped.
+fn remote_fact(sub_cmd: &str, subcommand: &str, remote: &str, out: &mut CommandAnalysis) {
+    if is_local_repo(remote) {
+        return;
+    }
+    let fact = if remote.contains("://") {
+        url_fact("network.git", sub_cmd, "git", remote)
+    } else {
+        // scp-like syntax: [user@]host:path
+        remote.split_once(':').map(|(authority, _)| {
+            let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+            Fact::new("network.git", sub_cmd)
+                .with("tool", "git")
+                .with("url", remote)
+                .with("scheme", "ssh")
+                .with("host", host.to_ascii_lowercase())
+        })
+    };
+    if let Some(fact) = fact {
+        out.findings
+            .push(Finding::Fact(fact.with("subcommand", subcommand)));
+    }
+}
+
+/// `git push --force`, `-f`, `--force-with-lease`, `--mirror` and `+refspec`.
+fn push_facts(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let operands = positionals(
+        args,
+        &["--repo", "-o", "--push-option", "--receive-pack", "--exec"],
+    );
+    let via = args
+        .iter()
+        .find_map(|a| {
+            let flag = a.split_once('=').map_or(a.as_str(), |(f, _)| f);
+            matches!(
+                flag,
+                "--force" | "--force-with-lease" | "--force-if-includes" | "--mirror"
+            )
+            .then(|| flag.to_string())
+        })
+        .or_else(|| has_short_flag(args, 'f').then(|| "-f".to_string()))
+        .or_else(|| {
+            operands
+                .iter()
+                .skip(1)
+                .any(|r| r.starts_with('+'))
+                .then(|| "+refspec".to_string())
+        });
+    let Some(via) = via else {
+        return;
+    };
+    let mut fact = Fact::new("git.push.force", sub_cmd).with("via", via);
+    if let Some(remote) = operands.first() {
+        fact = fact.with("remote", remote.as_str());
+    }
+    if operands.len() > 1 {
+        fact = fact.with("refspec", operands[1..].join(" "));
+    }
+    out.findings.push(Finding::Fact(fact));
+}
+
+/// `git checkout -- <paths>`, `git checkout -f`, `git checkout .` and
+/// `git restore <paths>` throw away uncommitted changes.
+fn discard_fact(sub_cmd: &str, subcommand: &str, args: &[String], out: &mut CommandAnalysis) {
+    let dashdash = args.iter().position(|a| a == "--");
+    let paths: Vec<String> = match dashdash {
+        Some(pos) => args[pos + 1..].to_vec(),
+        None if subcommand == "restore" => positionals(args, &["-s", "--source"]),
+        None => args
+            .iter()
+            .filter(|a| *a == "." || *a == ":/")
+            .cloned()
+            .collect(),
+    };
+    let staged_only = subcommand == "restore"
+        && (args.iter().any(|a| a == "--staged" || a == "-S"))
+        && !args.iter().any(|a| a == "--worktree" || a == "-W");
+    let forced = subcommand == "checkout"
+        && (has_short_flag(args, 'f') || args.iter().any(|a| a == "--force"));
+    if (paths.is_empty() && !forced) || staged_only {
+        return;
+    }
+    let mut fact = Fact::new(&format!("git.{subcommand}.discard"), sub_cmd);
+    if !paths.is_empty() {
+        fact = fact.with("paths", paths.join(" "));
+    }
+    out.findings.push(Finding::Fact(fact));
+}
+
+/// Config values that make git run a program: `core.hooksPath` gets its own
+/// fact, other program-valued keys are `git.config.exec`. `source` tells a
+/// one-off `git -c` apart from a persisted `git config` write.
+fn config_facts(sub_cmd: &str, key: &str, value: &str, source: &str, out: &mut CommandAnalysis) {
+    let lower = key.to_ascii_lowercase();
+    let name = if lower == "core.hookspath" {
+        "git.config.hooks-path"
+    } else if is_exec_config_key(&lower, value) {
+        "git.config.exec"
+    } else {
+        return;
+    };
+    out.findings.push(Finding::Fact(
+        Fact::new(name, sub_cmd)
+            .with("key", key)
+            .with("value", value)
+            .with("source", source),
+    ));
+}
+
+fn is_exec_config_key(key: &str, value: &str) -> bool {
+    EXEC_CONFIG_KEYS.contains(&key)
+        || EXEC_CONFIG_SUFFIXES
+            .iter()
+            .any(|(prefix, suffix)| key.starts_with(prefix) && key.ends_with(suffix) && key.len() > prefix.len() + suffix.len())
+        || key.starts_with("pager.")
+        // Aliases starting with `!` run a shell command
+        || (key.starts_with("alias.") && value.starts_with('!'))
+}
+
+/// Generic path guessing for subcommand arguments, resolved against `-C`.
+fn extract_rebased_paths(args: &[String], dir: Option<&str>, out: &mut CommandAnalysis) {
+    let start = out.paths.len();
+    extract_unknown_paths(args, &mut out.paths);
+    if let Some(dir) = dir {
+        for ep in &mut out.paths[start..] {
+            ep.raw = join_dir(Some(dir), &ep.raw);
+        }
+    }
+}
+
+/// Non-flag arguments, skipping the values of `value_flags`.
+fn positionals(args: &[String], value_flags: &[&str]) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            out.extend(args[i + 1..].iter().cloned());
+            break;
+        }
+        if value_flags.contains(&arg.as_str()) {
+            i += 1;
+        } else if !arg.starts_with('-') {
+            out.push(arg.clone());
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Whether a bundle of short flags (`-fdx`) contains `flag`.
+fn has_short_flag(args: &[String], flag: char) -> bool {
+    args.iter()
+        .take_while(|a| *a != "--")
+        .any(|a| a.len() > 1 && a.starts_with('-') && !a.starts_with("--") && a[1..].contains(flag))
+}
+
+/// Resolve `path` against the directory set by `git -C`.
+fn join_dir(dir: Option<&str>, path: &str) -> String {
+    match dir {
+        Some(dir) if !path.starts_with('/') && !path.starts_with('~') && !path.starts_with('$') => {
+            format!("{}/{}", dir.trim_end_matches('/'), path)
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// A clone source that is a local path rather than a URL or `host:path`.
+fn is_local_repo(repo: &str) -> bool {
+    if repo.contains("://") {
+        return repo.starts_with("file://");
+    }
+    // scp-like syntax: a colon before the first slash
+    match (repo.find(':'), repo.find('/')) {
+        (Some(colon), Some(slash)) if colon < slash => false,
+        (Some(_), None) => false,
+        _ => true,
+    }
+}
+
+fn push_path(out: &mut CommandAnalysis, raw: &str, label: &'static str) {
+    let raw = raw.strip_prefix("file://").unwrap_or(raw);
+    out.paths.push(ExtractedPath {
+        raw: raw.to_string(),
+        context: PathContext::GitPath {
+            label,
+            write: WRITTEN_PATH_LABELS.contains(&label),
+        },
+    });
+}
@@ -0,0 +1,86 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::internalonly::normalize_path;
+
+/// Denies writes into git repository internals (`.git/hooks`, `.git/config`,
+/// `.git/info/attributes`, ...), which git would later run or obey outside
+/// the agent's control.
+pub struct GitInternalsRule {
+    /// Git directories that don't have a `.git` component in their path,
+    /// found by following `.git` files (worktrees, submodules,
+    /// `--separate-git-dir`).
+    linked_git_dirs: Vec<PathBuf>,
+}
+
+impl GitInternalsRule {
+    pub fn new(project_root: &Path) -> Self {
+        let mut linked_git_dirs = Vec::new();
+        for dir in project_root.ancestors() {
+            let dot_git = dir.join(".git");
+            if dot_git.is_dir() {
+                break;
+            }
+            if let Some(git_dir) = read_gitdir_file(&dot_git) {
+                if let Some(common) = read_commondir(&git_dir) {
+                    linked_git_dirs.push(common);
+                }
+                linked_git_dirs.push(git_dir);
+                break;
+            }
+        }
+        Self { linked_git_dirs }
+    }
+
+    /// Check a path that is about to be written. Returns Some(reason) if it
+    /// is inside a git directory, None if allowed.
+    pub fn check(&self, path: &Path) -> Option<String> {
+        let inside = in_dot_git(path).or_else(|| {
+            self.linked_git_dirs
+                .iter()
+                .find(|dir| path.starts_with(dir))
+                .cloned()
+        })?;
+        Some(format!(
+            "Blocked by `clarg`: '{}' is inside the git directory '{}' — writing repository internals can make git run code later",
+            path.display(),
+            inside.display()
+        ))
+    }
+}
+
+/// The `.git` directory (or file) a path is in or is, if any.
+fn in_dot_git(path: &Path) -> Option<PathBuf> {
+    let mut prefix = PathBuf::new();
+    for component in path.components() {
+        prefix.push(component);
+        if let Component::Normal(name) = component
+            && name.eq_ignore_ascii_case(".git")
+        {
+            return Some(prefix);
+        }
+    }
+    None
+}
+
+/// Follow a `.git` file (`gitdir: <path>`) to the git directory it names.
+fn read_gitdir_file(dot_git: &Path) -> Option<PathBuf> {
+    if !dot_git.is_file() {
+        return None;
+    }
+    let contents = std::fs::read_to_string(dot_git).ok()?;
+    let target = contents
+        .lines()
+        .find_map(|l| l.strip_prefix("gitdir:"))?
+        .trim();
+    Some(resolve_from(dot_git.parent()?, target))
+}
+
+/// The shared git directory of a linked worktree, from its `commondir` file.
+fn read_commondir(git_dir: &Path) -> Option<PathBuf> {
+    let contents = std::fs::read_to_string(git_dir.join("commondir")).ok()?;
+    Some(resolve_from(git_dir, contents.trim()))
+}
+
+fn resolve_from(base: &Path, target: &str) -> PathBuf {
+    normalize_path(&base.join(target))
+}
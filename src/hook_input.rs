@@ -26,6 +26,14 @@ impl HookInput {
         self.tool_input.get("command").and_then(|v| v.as_str())
     }
 
+    /// Extract `tool_input.run_in_background` (used by Bash).
+    pub fn run_in_background(&self) -> bool {
+        self.tool_input
+            .get("run_in_background")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
     /// Extract `tool_input.path` (used by Glob, Grep).
     pub fn search_path(&self) -> Option<&str> {
         self.tool_input.get("path").and_then(|v| v.as_str())
@@ -42,4 +50,32 @@ impl HookInput {
             .get("notebook_path")
             .and_then(|v| v.as_str())
     }
+
+    /// Extract `tool_input.url` (used by WebFetch).
+    pub fn url(&self) -> Option<&str> {
+        self.tool_input.get("url").and_then(|v| v.as_str())
+    }
+
+    /// Extract `tool_input.query` (used by WebSearch).
+    pub fn query(&self) -> Option<&str> {
+        self.tool_input.get("query").and_then(|v| v.as_str())
+    }
+
+    /// Extract `tool_input.allowed_domains` (used by WebSearch).
+    pub fn allowed_domains(&self) -> Vec<&str> {
+        self.string_list("allowed_domains")
+    }
+
+    /// Extract `tool_input.blocked_domains` (used by WebSearch).
+    pub fn blocked_domains(&self) -> Vec<&str> {
+        self.string_list("blocked_domains")
+    }
+
+    fn string_list(&self, key: &str) -> Vec<&str> {
+        self.tool_input
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default()
+    }
 }
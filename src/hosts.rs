@@ -0,0 +1,185 @@
+use eyre::{Result, WrapErr, bail};
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use std::net::{IpAddr, Ipv4Addr};
+
+use crate::facts::Fact;
+use crate::util::truncate;
+
+/// Where commands may send data or run code, as written in YAML:
+///
+/// ```yaml
+/// hosts:
+///   allow: ["github.com", "*.internal.example.com", "10.0.0.0/8"]
+///   deny: ["*.pastebin.com"]
+/// ```
+///
+/// Patterns are host globs (`*` matches anything) or CIDR ranges, which
+/// match IP literals.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HostPolicy {
+    /// When non-empty, only matching hosts are allowed.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Hosts that are always denied, even when allowed.
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl HostPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+}
+
+/// Fact attributes naming a host the command connects to, directly or on
+/// the way (ssh jump hosts, proxies, curl `--resolve`/`--connect-to`).
+const HOST_ATTRS: &[&str] = &["host", "jump", "proxy", "connect"];
+
+pub(crate) enum HostPattern {
+    Glob(Regex),
+    Cidr(IpAddr, u8),
+}
+
+impl HostPattern {
+    pub(crate) fn matches(&self, host: &str) -> bool {
+        match self {
+            HostPattern::Glob(re) => re.is_match(host),
+            HostPattern::Cidr(net, prefix) => {
+                parse_ip(host).is_some_and(|ip| in_cidr(ip, *net, *prefix))
+            }
+        }
+    }
+}
+
+/// Checks the host attributes of facts against a [`HostPolicy`].
+pub struct HostRule {
+    allow: Vec<HostPattern>,
+    deny: Vec<HostPattern>,
+}
+
+impl HostRule {
+    pub fn new(policy: &HostPolicy) -> Result<Self> {
+        Ok(Self {
+            allow: compile(&policy.allow)?,
+            deny: compile(&policy.deny)?,
+        })
+    }
+
+    /// Whether only listed hosts are allowed.
+    pub fn has_allowlist(&self) -> bool {
+        !self.allow.is_empty()
+    }
+
+    /// Check every host a fact names. Returns Some(reason) for the first
+    /// host that is denied, None if all are allowed.
+    pub fn check(&self, fact: &Fact) -> Option<String> {
+        let hosts = HOST_ATTRS
+            .iter()
+            .filter_map(|key| fact.attr(key))
+            .flat_map(|value| value.split(','));
+        for host in hosts {
+            let why = if self.deny.iter().any(|p| p.matches(host)) {
+                "is on the host denylist"
+            } else if !self.allow.is_empty() && !self.allow.iter().any(|p| p.matches(host)) {
+                "is not on the host allowlist"
+            } else {
+                continue;
+            };
+            return Some(format!(
+                "Blocked by `clarg`: '{}' connects to '{}', which {}",
+                truncate(&fact.command, 100),
+                host,
+                why
+            ));
+        }
+        None
+    }
+}
+
+/// Compile host globs (`*.example.com`) to case-insensitive regexes and
+/// `addr/prefix` patterns to CIDR ranges.
+pub(crate) fn compile(patterns: &[String]) -> Result<Vec<HostPattern>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            if let Some((addr, prefix)) = pattern.split_once('/') {
+                let net: IpAddr = addr
+                    .parse()
+                    .wrap_err_with(|| format!("invalid CIDR address in host pattern: {pattern}"))?;
+                let max = if net.is_ipv4() { 32 } else { 128 };
+                match prefix.parse::<u8>() {
+                    Ok(prefix) if prefix <= max => return Ok(HostPattern::Cidr(net, prefix)),
+                    _ => bail!("invalid CIDR prefix in host pattern: {pattern}"),
+                }
+            }
+            let re = format!("^{}$", regex::escape(pattern).replace(r"\*", ".*"));
+            RegexBuilder::new(&re)
+                .case_insensitive(true)
+                .build()
+                .map(HostPattern::Glob)
+                .wrap_err_with(|| format!("invalid host pattern: {pattern}"))
+        })
+        .collect()
+}
+
+/// An IP literal, including the bracketed IPv6 and the inet_aton IPv4 forms
+/// that tools resolve without DNS: one to four parts, each decimal, octal
+/// (`0251`) or hex (`0x7f`), the last filling the remaining bytes
+/// (`2130706433`, `0x7f000001`, `127.1`, `0251.0376.0251.0376`).
+pub(crate) fn parse_ip(host: &str) -> Option<IpAddr> {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = host.parse() {
+        return Some(ip);
+    }
+    let parts = host
+        .split('.')
+        .map(aton_part)
+        .collect::<Option<Vec<u32>>>()?;
+    let (last, leading) = parts.split_last()?;
+    if leading.len() > 3 || leading.iter().any(|&part| part > 255) {
+        return None;
+    }
+    let bits = 8 * (4 - leading.len() as u32);
+    if bits < 32 && *last >> bits != 0 {
+        return None;
+    }
+    let high = leading
+        .iter()
+        .fold(0u32, |n, &part| n << 8 | part)
+        .checked_shl(bits)
+        .unwrap_or(0);
+    Some(IpAddr::V4(Ipv4Addr::from(high | last)))
+}
+
+/// One part of an inet_aton address: `0x` hex, `0`-prefixed octal or decimal.
+fn aton_part(part: &str) -> Option<u32> {
+    let (digits, radix) = match part.strip_prefix("0x").or_else(|| part.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None if part.len() > 1 && part.starts_with('0') => (&part[1..], 8),
+        None => (part, 10),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    u32::from_str_radix(digits, radix).ok()
+}
+
+fn in_cidr(ip: IpAddr, net: IpAddr, prefix: u8) -> bool {
+    // IPv4-mapped IPv6 (::ffff:10.0.0.1) matches IPv4 ranges
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        v4 => v4,
+    };
+    match (ip, net) {
+        (IpAddr::V4(ip), IpAddr::V4(net)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(net) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(net)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(net) & mask
+        }
+        _ => false,
+    }
+}
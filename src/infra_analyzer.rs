@@ -0,0 +1,890 @@
+use crate::bash_analyzer::{CommandAnalysis, Finding};
+use crate::facts::Fact;
+
+/// Infrastructure CLIs whose subcommands and targets are reported as facts.
+pub(crate) const INFRA_COMMANDS: &[&str] = &[
+    "terraform",
+    "tofu",
+    "terragrunt",
+    "kubectl",
+    "helm",
+    "aws",
+    "gcloud",
+];
+
+/// Options and positional arguments of a CLI whose options may appear
+/// anywhere on the command line.
+struct Parsed {
+    /// (flag, value) in order; boolean flags have no value.
+    options: Vec<(String, Option<String>)>,
+    positionals: Vec<String>,
+}
+
+impl Parsed {
+    /// The last value given for any of `flags`.
+    fn value(&self, flags: &[&str]) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(flag, _)| flags.contains(&flag.as_str()))
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Every value given for any of `flags`.
+    fn values(&self, flags: &[&str]) -> Vec<&str> {
+        self.options
+            .iter()
+            .filter(|(flag, _)| flags.contains(&flag.as_str()))
+            .filter_map(|(_, value)| value.as_deref())
+            .collect()
+    }
+
+    /// Whether any of `flags` is set, and not to `false`.
+    fn has(&self, flags: &[&str]) -> bool {
+        self.options
+            .iter()
+            .rev()
+            .find(|(flag, _)| flags.contains(&flag.as_str()))
+            .is_some_and(|(_, value)| value.as_deref() != Some("false"))
+    }
+
+    fn positional(&self, index: usize) -> Option<&str> {
+        self.positionals.get(index).map(String::as_str)
+    }
+}
+
+/// Split options from positionals. Flags in `value_flags` consume the next
+/// argument, or an attached value (`--flag=value`, `-nvalue`).
+fn parse(args: &[String], value_flags: &[&str]) -> Parsed {
+    let mut options = Vec::new();
+    let mut positionals = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        i += 1;
+        if arg == "--" {
+            positionals.extend(args[i..].iter().cloned());
+            break;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            positionals.push(arg.clone());
+        } else if let Some((flag, value)) = arg.split_once('=') {
+            options.push((flag.to_string(), Some(value.to_string())));
+        } else if value_flags.contains(&arg.as_str()) {
+            options.push((arg.clone(), args.get(i).cloned()));
+            i += 1;
+        } else if let Some(short) = arg
+            .get(..2)
+            .filter(|f| !arg.starts_with("--") && value_flags.contains(f))
+        {
+            options.push((short.to_string(), Some(arg[2..].to_string())));
+        } else {
+            options.push((arg.clone(), None));
+        }
+    }
+    Parsed {
+        options,
+        positionals,
+    }
+}
+
+/// The value of `NAME=value` among the assignments before the command.
+fn env_var<'a>(env: &'a [String], names: &[&str]) -> Option<&'a str> {
+    names.iter().find_map(|name| {
+        env.iter()
+            .rev()
+            .find_map(|assignment| assignment.strip_prefix(name)?.strip_prefix('='))
+    })
+}
+
+/// Report a terraform, kubectl, helm, aws or gcloud invocation as an
+/// `infra.*` fact: its subcommand, target (context, profile, project or
+/// workspace) and whether it is destructive or read-only. `env` holds the
+/// `VAR=value` assignments before the command.
+pub(crate) fn extract_infra(
+    cmd: &str,
+    sub_cmd: &str,
+    env: &[String],
+    args: &[String],
+    out: &mut CommandAnalysis,
+) {
+    let fact = match cmd {
+        "terraform" | "tofu" | "terragrunt" => terraform_fact(cmd, sub_cmd, env, args),
+        "kubectl" => kubectl_fact(sub_cmd, args),
+        "helm" => helm_fact(sub_cmd, args),
+        "aws" => aws_fact(sub_cmd, env, args),
+        _ => gcloud_fact(sub_cmd, env, args),
+    };
+    if let Some(fact) = fact {
+        out.findings.push(Finding::Fact(fact));
+    }
+}
+
+/// Add the attributes every `infra.*` fact has.
+fn finish(
+    fact: Fact,
+    subcommand: &str,
+    target: Option<&str>,
+    destructive: bool,
+    read_only: bool,
+) -> Fact {
+    let fact = fact
+        .with("subcommand", subcommand)
+        .with("destructive", destructive.to_string())
+        .with("read_only", read_only.to_string());
+    match target.filter(|t| !t.is_empty()) {
+        Some(target) => fact.with("target", target),
+        None => fact,
+    }
+}
+
+fn with_opt(fact: Fact, key: &str, value: Option<&str>) -> Fact {
+    match value.filter(|v| !v.is_empty()) {
+        Some(value) => fact.with(key, value),
+        None => fact,
+    }
+}
+
+// ============================================================================
+// terraform
+// ============================================================================
+
+/// terraform options that take a separate value.
+const TERRAFORM_VALUE_FLAGS: &[&str] = &[
+    "-var",
+    "-var-file",
+    "-target",
+    "-replace",
+    "-state",
+    "-state-out",
+    "-backup",
+    "-out",
+    "-parallelism",
+    "-lock-timeout",
+    "-chdir",
+    "-backend-config",
+    "-from-module",
+];
+
+/// Subcommands with their own subcommand (`state rm`, `workspace delete`).
+const TERRAFORM_GROUPS: &[&str] = &["state", "workspace", "providers", "metadata", "env"];
+
+/// Subcommands that don't change infrastructure or state.
+const TERRAFORM_READ_ONLY: &[&str] = &[
+    "init",
+    "plan",
+    "validate",
+    "fmt",
+    "show",
+    "output",
+    "graph",
+    "providers",
+    "version",
+    "state list",
+    "state show",
+    "state pull",
+    "workspace list",
+    "workspace show",
+    "workspace select",
+    "console",
+    "get",
+    "login",
+    "logout",
+    "metadata functions",
+    "providers lock",
+    "providers mirror",
+    "providers schema",
+    "modules",
+    "help",
+];
+
+/// Subcommands that delete resources or rewrite state.
+const TERRAFORM_DESTRUCTIVE: &[&str] = &[
+    "destroy",
+    "state rm",
+    "state mv",
+    "state push",
+    "state replace-provider",
+    "taint",
+    "workspace delete",
+    "force-unlock",
+];
+
+/// `terraform [-chdir=DIR] subcommand [options] [args]`
+fn terraform_fact(tool: &str, sub_cmd: &str, env: &[String], args: &[String]) -> Option<Fact> {
+    // terraform accepts `--flag` for `-flag`
+    let args: Vec<String> = args
+        .iter()
+        .map(|a| {
+            a.strip_prefix("--")
+                .map_or_else(|| a.clone(), |flag| format!("-{flag}"))
+        })
+        .collect();
+    let parsed = parse(&args, TERRAFORM_VALUE_FLAGS);
+    let mut positionals = parsed.positionals.iter().map(String::as_str).peekable();
+    // terragrunt runs a terraform command across modules
+    if tool == "terragrunt" {
+        positionals.next_if(|p| *p == "run-all" || *p == "run");
+    }
+    let first = positionals.next()?;
+    let subcommand = match positionals.peek() {
+        Some(second) if TERRAFORM_GROUPS.contains(&first) => format!("{first} {second}"),
+        _ => first.to_string(),
+    };
+    let operand = positionals.nth(usize::from(subcommand.contains(' ')));
+
+    let destroy =
+        subcommand == "destroy" || (matches!(first, "apply" | "plan") && parsed.has(&["-destroy"]));
+    // Applying a saved plan doesn't prompt either
+    let auto_approve = matches!(first, "apply" | "destroy")
+        && (parsed.has(&["-auto-approve"]) || (first == "apply" && operand.is_some()));
+    let destructive = TERRAFORM_DESTRUCTIVE.contains(&subcommand.as_str())
+        || (first == "apply" && (destroy || auto_approve));
+    let read_only = TERRAFORM_READ_ONLY.contains(&subcommand.as_str());
+
+    let workspace = env_var(env, &["TF_WORKSPACE"]);
+    let chdir = parsed.value(&["-chdir"]);
+    let targets = parsed.values(&["-target"]).join(",");
+    let fact = Fact::new("infra.terraform", sub_cmd)
+        .with("tool", tool)
+        .with("auto_approve", auto_approve.to_string())
+        .with("destroy", destroy.to_string());
+    let fact = with_opt(fact, "workspace", workspace);
+    let fact = with_opt(fact, "chdir", chdir);
+    let fact = with_opt(fact, "targets", Some(&targets));
+    Some(finish(
+        fact,
+        &subcommand,
+        workspace.or(chdir.and_then(chdir_environment)),
+        destructive,
+        read_only,
+    ))
+}
+
+/// Words that name an environment in a directory path.
+const ENVIRONMENT_WORDS: &[&str] = &[
+    "prod",
+    "production",
+    "prd",
+    "live",
+    "preprod",
+    "staging",
+    "stage",
+    "stg",
+    "uat",
+    "qa",
+    "dev",
+    "development",
+    "test",
+    "sandbox",
+    "local",
+];
+
+/// The environment a `-chdir` directory stands for (`envs/prod`,
+/// `stacks/eu-prod`): its last segment containing an environment word.
+/// Other directories (`infra`, `../x`) are only where the config lives.
+fn chdir_environment(dir: &str) -> Option<&str> {
+    dir.rsplit('/').find(|segment| {
+        segment
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| ENVIRONMENT_WORDS.contains(&word.to_ascii_lowercase().as_str()))
+    })
+}
+
+// ============================================================================
+// kubectl
+// ============================================================================
+
+/// kubectl options that take a separate value.
+const KUBECTL_VALUE_FLAGS: &[&str] = &[
+    "-n",
+    "--namespace",
+    "--context",
+    "--cluster",
+    "--kubeconfig",
+    "--user",
+    "-s",
+    "--server",
+    "--token",
+    "--as",
+    "--as-group",
+    "--as-uid",
+    "--request-timeout",
+    "--certificate-authority",
+    "--client-certificate",
+    "--client-key",
+    "--tls-server-name",
+    "--cache-dir",
+    "-v",
+    "-l",
+    "--selector",
+    "-f",
+    "--filename",
+    "-k",
+    "--kustomize",
+    "-o",
+    "--output",
+    "--field-selector",
+    "--grace-period",
+    "--timeout",
+    "--cascade",
+    "-c",
+    "--container",
+    "--type",
+    "-p",
+    "--patch",
+    "--patch-file",
+    "--replicas",
+    "--current-replicas",
+    "--image",
+    "--port",
+    "--for",
+    "--sort-by",
+    "-L",
+    "--label-columns",
+    "--template",
+    "--field-manager",
+    "--subresource",
+    "--raw",
+    "--to-revision",
+    "--pod-selector",
+    "--chunk-size",
+    "--since",
+    "--since-time",
+    "--tail",
+    "--prune-allowlist",
+    "--selector-allowlist",
+    "--reason",
+    "--delete-emptydir-data-timeout",
+];
+
+/// Subcommands with their own subcommand (`rollout undo`, `config use-context`).
+const KUBECTL_GROUPS: &[&str] = &[
+    "rollout",
+    "config",
+    "auth",
+    "certificate",
+    "set",
+    "top",
+    "plugin",
+    "apply",
+];
+
+/// Subcommands that only read from the cluster or local config.
+const KUBECTL_READ_ONLY: &[&str] = &[
+    "get",
+    "describe",
+    "logs",
+    "explain",
+    "api-resources",
+    "api-versions",
+    "version",
+    "cluster-info",
+    "top",
+    "diff",
+    "wait",
+    "events",
+    "kustomize",
+    "completion",
+    "plugin",
+    "options",
+    "help",
+    "auth",
+    "rollout status",
+    "rollout history",
+    "config view",
+    "config get-contexts",
+    "config current-context",
+    "config get-clusters",
+    "config get-users",
+    "apply view-last-applied",
+];
+
+/// `kubectl [options] subcommand [resource] [name] [options]`
+fn kubectl_fact(sub_cmd: &str, args: &[String]) -> Option<Fact> {
+    let parsed = parse(args, KUBECTL_VALUE_FLAGS);
+    let first = parsed.positional(0)?;
+    let group =
+        KUBECTL_GROUPS.contains(&first) && parsed.positional(1).is_some_and(|p| !p.contains('/'));
+    let (subcommand, rest) = match parsed.positional(1) {
+        Some(second) if group => (format!("{first} {second}"), 2),
+        _ => (first.to_string(), 1),
+    };
+    let subcommand_prefix = subcommand.split(' ').next().unwrap_or_default();
+
+    // `pod/name`, `pods name`, `deploy/a deploy/b`
+    let (resource, name) = match parsed.positional(rest) {
+        Some(arg) if arg.contains('/') => {
+            let (kind, name) = arg.split_once('/').unwrap_or_default();
+            (Some(kubectl_kind(kind)), Some(name.to_string()))
+        }
+        Some(arg) if !group || subcommand_prefix == "rollout" => {
+            let name = parsed.positional(rest + 1).map(str::to_string);
+            (Some(kubectl_kind(arg)), name)
+        }
+        _ => (None, None),
+    };
+
+    let dry_run = parsed
+        .value(&["--dry-run"])
+        .is_some_and(|mode| mode != "none");
+    let read_only = dry_run
+        || KUBECTL_READ_ONLY.contains(&subcommand.as_str())
+        || KUBECTL_READ_ONLY.contains(&subcommand_prefix)
+            && subcommand_prefix != "rollout"
+            && subcommand_prefix != "config";
+    let destructive = !dry_run
+        && match subcommand.as_str() {
+            "delete" | "drain" | "rollout undo" => true,
+            "replace" => parsed.has(&["--force"]),
+            "apply" => parsed.has(&["--prune", "--force"]),
+            "scale" => parsed.value(&["--replicas"]) == Some("0"),
+            _ => false,
+        };
+    let namespace = if parsed.has(&["-A", "--all-namespaces"]) {
+        Some("*")
+    } else {
+        parsed.value(&["-n", "--namespace"])
+    };
+    let context = parsed.value(&["--context"]);
+
+    let fact = Fact::new("infra.kubectl", sub_cmd)
+        .with("tool", "kubectl")
+        .with("all", parsed.has(&["--all"]).to_string());
+    let fact = with_opt(fact, "context", context);
+    let fact = with_opt(fact, "cluster", parsed.value(&["--cluster"]));
+    let fact = with_opt(fact, "namespace", namespace);
+    let fact = with_opt(fact, "resource", resource.as_deref());
+    let fact = with_opt(fact, "name", name.as_deref());
+    let fact = with_opt(
+        fact,
+        "filename",
+        parsed.value(&["-f", "--filename", "-k", "--kustomize"]),
+    );
+    Some(finish(
+        fact,
+        &subcommand,
+        context.or(named_namespace(namespace)),
+        destructive,
+        read_only,
+    ))
+}
+
+/// A namespace given with `-n` as a target, unless it is `default` or all
+/// of them.
+fn named_namespace(namespace: Option<&str>) -> Option<&str> {
+    namespace.filter(|ns| !matches!(*ns, "default" | "*"))
+}
+
+/// Resource kind without short names, plurals or API group: `ns`,
+/// `namespaces` and `namespaces.v1` are all `namespace`.
+fn kubectl_kind(kind: &str) -> String {
+    let kind = kind
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let long = match kind.as_str() {
+        "ns" => "namespace",
+        "po" => "pod",
+        "deploy" => "deployment",
+        "svc" => "service",
+        "no" => "node",
+        "pv" => "persistentvolume",
+        "pvc" => "persistentvolumeclaim",
+        "crd" | "crds" => "customresourcedefinition",
+        "sts" => "statefulset",
+        "ds" => "daemonset",
+        "rs" => "replicaset",
+        "cm" => "configmap",
+        "sa" => "serviceaccount",
+        "ing" => "ingress",
+        "cj" => "cronjob",
+        "netpol" => "networkpolicy",
+        kind if kind.ends_with("ies") => return format!("{}y", &kind[..kind.len() - 3]),
+        kind if kind.ends_with("sses") || kind.ends_with("ches") => {
+            return kind[..kind.len() - 2].to_string();
+        }
+        kind if kind.ends_with('s') && !kind.ends_with("ss") => {
+            return kind[..kind.len() - 1].to_string();
+        }
+        kind => kind,
+    };
+    long.to_string()
+}
+
+// ============================================================================
+// helm
+// ============================================================================
+
+/// helm options that take a separate value.
+const HELM_VALUE_FLAGS: &[&str] = &[
+    "-n",
+    "--namespace",
+    "--kube-context",
+    "--kubeconfig",
+    "--kube-apiserver",
+    "--kube-token",
+    "--kube-as-user",
+    "--kube-as-group",
+    "--kube-ca-file",
+    "--registry-config",
+    "--repository-config",
+    "--repository-cache",
+    "--burst-limit",
+    "-f",
+    "--values",
+    "--set",
+    "--set-string",
+    "--set-file",
+    "--set-json",
+    "--set-literal",
+    "--version",
+    "--timeout",
+    "--description",
+    "--post-renderer",
+    "--post-renderer-args",
+    "--repo",
+    "--username",
+    "--password",
+    "-o",
+    "--output",
+    "--history-max",
+    "--max",
+    "--revision",
+    "--cascade",
+    "--deletion-propagation",
+    "--ca-file",
+    "--cert-file",
+    "--key-file",
+    "--keyring",
+    "--name-template",
+    "--labels",
+    "-l",
+    "--selector",
+];
+
+/// Subcommands with their own subcommand (`repo add`, `get values`).
+const HELM_GROUPS: &[&str] = &[
+    "repo",
+    "plugin",
+    "dependency",
+    "dep",
+    "get",
+    "show",
+    "inspect",
+    "registry",
+    "search",
+];
+
+/// Subcommands that don't change a cluster.
+const HELM_READ_ONLY: &[&str] = &[
+    "list",
+    "ls",
+    "status",
+    "get",
+    "show",
+    "inspect",
+    "template",
+    "search",
+    "repo",
+    "history",
+    "hist",
+    "lint",
+    "version",
+    "env",
+    "dependency",
+    "dep",
+    "pull",
+    "fetch",
+    "package",
+    "verify",
+    "completion",
+    "help",
+    "create",
+    "registry",
+    "plugin",
+];
+
+/// `helm subcommand [release] [chart] [options]`
+fn helm_fact(sub_cmd: &str, args: &[String]) -> Option<Fact> {
+    let parsed = parse(args, HELM_VALUE_FLAGS);
+    let first = parsed.positional(0)?;
+    let (subcommand, rest) = match parsed.positional(1) {
+        Some(second) if HELM_GROUPS.contains(&first) => (format!("{first} {second}"), 2),
+        _ => (first.to_string(), 1),
+    };
+    let dry_run = parsed.has(&["--dry-run"]);
+    let destructive =
+        !dry_run && matches!(first, "uninstall" | "delete" | "del" | "un" | "rollback");
+    let read_only = dry_run || HELM_READ_ONLY.contains(&first);
+    let release = match first {
+        "install" if parsed.has(&["--generate-name", "-g"]) => None,
+        "install" | "upgrade" | "uninstall" | "delete" | "del" | "un" | "rollback" | "status"
+        | "history" | "hist" | "test" => parsed.positional(rest),
+        _ if subcommand.starts_with("get ") => parsed.positional(rest),
+        _ => None,
+    };
+    let context = parsed.value(&["--kube-context"]);
+    let namespace = parsed.value(&["-n", "--namespace"]);
+
+    let fact = Fact::new("infra.helm", sub_cmd).with("tool", "helm");
+    let fact = with_opt(fact, "context", context);
+    let fact = with_opt(fact, "namespace", namespace);
+    let fact = with_opt(fact, "release", release);
+    Some(finish(
+        fact,
+        &subcommand,
+        context.or(named_namespace(namespace)),
+        destructive,
+        read_only,
+    ))
+}
+
+// ============================================================================
+// aws
+// ============================================================================
+
+/// aws options that take a separate value.
+const AWS_VALUE_FLAGS: &[&str] = &[
+    "--profile",
+    "--region",
+    "--output",
+    "--query",
+    "--endpoint-url",
+    "--cli-read-timeout",
+    "--cli-connect-timeout",
+    "--color",
+    "--ca-bundle",
+    "--cli-binary-format",
+    "--cli-input-json",
+    "--cli-input-yaml",
+    "--include",
+    "--exclude",
+    "--acl",
+    "--storage-class",
+    "--sse",
+    "--sse-kms-key-id",
+    "--expires",
+    "--content-type",
+    "--cache-control",
+    "--metadata",
+    "--grants",
+    "--page-size",
+];
+
+/// Operation prefixes that delete or shut down resources.
+const AWS_DESTRUCTIVE_PREFIXES: &[&str] = &[
+    "delete",
+    "terminate",
+    "remove",
+    "purge",
+    "deregister",
+    "destroy",
+    "reset",
+    "disable",
+    "detach",
+    "revoke",
+    "cancel",
+    "stop",
+];
+
+/// Operation prefixes that only read.
+const AWS_READ_PREFIXES: &[&str] = &[
+    "describe",
+    "get",
+    "list",
+    "head",
+    "lookup",
+    "search",
+    "scan",
+    "query",
+    "select",
+    "batch-get",
+    "estimate",
+    "test",
+    "validate",
+    "simulate",
+    "filter",
+    "wait",
+];
+
+/// `aws [options] service operation [options]`
+fn aws_fact(sub_cmd: &str, env: &[String], args: &[String]) -> Option<Fact> {
+    let parsed = parse(args, AWS_VALUE_FLAGS);
+    let service = parsed.positional(0)?;
+    let operation = parsed.positional(1).unwrap_or_default();
+    let dry_run = parsed.has(&["--dry-run", "--dryrun"]);
+    let starts_with_any = |prefixes: &[&str]| {
+        prefixes
+            .iter()
+            .any(|prefix| operation == *prefix || operation.starts_with(&format!("{prefix}-")))
+    };
+    let (destructive, read_only) = match (service, operation) {
+        ("s3", "rm" | "rb" | "mv") => (true, false),
+        ("s3", "sync") => (parsed.has(&["--delete"]), false),
+        ("s3", "ls" | "presign") | ("sts", _) | ("configure", "list" | "get" | "list-profiles") => {
+            (false, true)
+        }
+        (_, "help") | ("help", _) => (false, true),
+        _ => (
+            starts_with_any(AWS_DESTRUCTIVE_PREFIXES),
+            starts_with_any(AWS_READ_PREFIXES),
+        ),
+    };
+    let profile = parsed
+        .value(&["--profile"])
+        .or_else(|| env_var(env, &["AWS_PROFILE", "AWS_DEFAULT_PROFILE"]));
+    let region = parsed
+        .value(&["--region"])
+        .or_else(|| env_var(env, &["AWS_REGION", "AWS_DEFAULT_REGION"]));
+
+    let fact = Fact::new("infra.aws", sub_cmd)
+        .with("tool", "aws")
+        .with("service", service)
+        .with("recursive", parsed.has(&["--recursive"]).to_string());
+    let fact = with_opt(fact, "operation", Some(operation));
+    let fact = with_opt(fact, "profile", profile);
+    let fact = with_opt(fact, "region", region);
+    let subcommand = format!("{service} {operation}");
+    Some(finish(
+        fact,
+        subcommand.trim_end(),
+        profile,
+        destructive && !dry_run,
+        read_only || dry_run,
+    ))
+}
+
+// ============================================================================
+// gcloud
+// ============================================================================
+
+/// gcloud options that take a separate value.
+const GCLOUD_VALUE_FLAGS: &[&str] = &[
+    "--project",
+    "--configuration",
+    "--account",
+    "--zone",
+    "--region",
+    "--location",
+    "--format",
+    "--filter",
+    "--limit",
+    "--sort-by",
+    "--page-size",
+    "--verbosity",
+    "--impersonate-service-account",
+    "--billing-project",
+    "--flags-file",
+    "--trace-token",
+    "--cluster",
+    "--instance",
+    "--database",
+    "--member",
+    "--role",
+    "--image",
+    "--service-account",
+];
+
+/// Command verbs that delete or reset resources.
+const GCLOUD_DESTRUCTIVE_VERBS: &[&str] = &[
+    "delete",
+    "remove",
+    "rm",
+    "destroy",
+    "purge",
+    "reset",
+    "uninstall",
+];
+
+/// Command verbs that end the command group path.
+const GCLOUD_VERBS: &[&str] = &[
+    "list",
+    "describe",
+    "ls",
+    "cat",
+    "du",
+    "info",
+    "create",
+    "update",
+    "set",
+    "unset",
+    "add",
+    "deploy",
+    "apply",
+    "import",
+    "export",
+    "start",
+    "stop",
+    "resize",
+    "patch",
+    "ssh",
+    "scp",
+    "run",
+    "execute",
+    "submit",
+    "enable",
+    "disable",
+    "cp",
+    "mv",
+    "rsync",
+    "get-credentials",
+    "get-iam-policy",
+    "set-iam-policy",
+    "add-iam-policy-binding",
+    "remove-iam-policy-binding",
+    "read",
+    "print-access-token",
+    "print-identity-token",
+    "tail",
+    "activate",
+    "login",
+];
+
+/// `gcloud [group…] command [args] [options]`
+fn gcloud_fact(sub_cmd: &str, env: &[String], args: &[String]) -> Option<Fact> {
+    let parsed = parse(args, GCLOUD_VALUE_FLAGS);
+    parsed.positional(0)?;
+    // The first word is always a group (`gcloud run deploy`)
+    let verb_at = parsed.positionals.iter().skip(1).position(|p| {
+        GCLOUD_DESTRUCTIVE_VERBS.contains(&p.as_str())
+            || GCLOUD_VERBS.contains(&p.as_str())
+            || p.starts_with("get-")
+    });
+    let verb_at = verb_at.map(|at| at + 1);
+    let end = verb_at.map_or(parsed.positionals.len().min(2), |at| at + 1);
+    let subcommand = parsed.positionals[..end].join(" ");
+    let verb = verb_at.map_or("", |at| parsed.positionals[at].as_str());
+
+    let destructive =
+        GCLOUD_DESTRUCTIVE_VERBS.contains(&verb) || verb == "remove-iam-policy-binding";
+    let read_only = matches!(
+        verb,
+        "list" | "describe" | "ls" | "cat" | "du" | "info" | "read" | "tail"
+    ) || (verb.starts_with("get-") && verb != "get-credentials")
+        || verb.starts_with("print-")
+        || matches!(
+            parsed.positional(0),
+            Some("version" | "info" | "help" | "topic")
+        );
+    let project = parsed
+        .value(&["--project"])
+        .or_else(|| env_var(env, &["CLOUDSDK_CORE_PROJECT"]));
+    let configuration = parsed
+        .value(&["--configuration"])
+        .or_else(|| env_var(env, &["CLOUDSDK_ACTIVE_CONFIG_NAME"]));
+
+    let fact = Fact::new("infra.gcloud", sub_cmd)
+        .with("tool", "gcloud")
+        .with("quiet", parsed.has(&["-q", "--quiet"]).to_string());
+    let fact = with_opt(fact, "project", project);
+    let fact = with_opt(fact, "configuration", configuration);
+    Some(finish(
+        fact,
+        &subcommand,
+        project.or(configuration),
+        destructive,
+        read_only,
+    ))
+}
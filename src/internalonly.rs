@@ -67,11 +67,7 @@ pub fn resolve_target(path_str: &str, project_root: &Path) -> PathBuf {
 
 /// Check if a resolved path is inside the project root.
 /// Returns Some(reason) if it's outside, None if it's inside.
-pub fn check_path_containment(
-    target: &Path,
-    project_root: &Path,
-    context: &str,
-) -> Option<String> {
+pub fn check_path_containment(target: &Path, project_root: &Path, context: &str) -> Option<String> {
     if target.starts_with(project_root) {
         None
     } else {
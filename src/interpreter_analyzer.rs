@@ -0,0 +1,645 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::bash_analyzer::{
+    CommandAnalysis, ExtractedPath, Finding, PathContext, extract_paths_recursive,
+    extract_unknown_paths, looks_like_path,
+};
+use crate::facts::Fact;
+use crate::network_analyzer::split_host_port;
+use crate::script_analyzer;
+
+/// Regex to extract absolute paths and home paths from inline code strings.
+/// The path must start a word, so `../x` and URLs don't yield `/x`.
+static PATH_IN_CODE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:^|[^a-zA-Z0-9_.@/~:-])(/[a-zA-Z0-9_.@-][a-zA-Z0-9_.@/-]*|~/[a-zA-Z0-9_.@/-]+|\$HOME/[a-zA-Z0-9_.@/-]+)"#).unwrap()
+});
+
+/// Single- and double-quoted string literals on one line of code.
+static STRING_LITERAL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""((?:[^"\\\n]|\\.)*)"|'((?:[^'\\\n]|\\.)*)'"#).unwrap());
+
+/// Shell commands run from Ruby and Perl code: backticks, `%x{}` and `qx{}`.
+static SHELL_OUT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"`([^`]*)`|(?:%x|qx)(?:\{([^}]*)\}|\(([^)]*)\)|\[([^\]]*)\]|<([^>]*)>)").unwrap()
+});
+
+/// Interpreters with `/regex/` literals, where an unquoted `/…/` is not a path.
+const REGEX_LITERAL_INTERPRETERS: &[&str] = &["perl", "ruby", "node", "deno", "bun"];
+
+/// Interpreters whose inline code runs shell commands in backticks.
+const SHELL_OUT_INTERPRETERS: &[&str] = &["ruby", "perl"];
+
+/// How an interpreter takes its options before the script operand.
+struct InterpreterSpec {
+    names: &'static [&'static str],
+    /// Options that take a value: the next argument, the rest of a short
+    /// bundle (`-Ilib`) or `--flag=value`.
+    value_flags: &'static [&'static str],
+    /// Short options whose value can only be attached (`perl -i.bak`, `ruby -W0`).
+    attached_flags: &'static [&'static str],
+    /// Options whose value is a path, with its label and whether it is written.
+    path_flags: &'static [(&'static str, &'static str, bool)],
+    /// Options whose value is a module name or a path (`node -r`, `ruby -r`).
+    /// Only path-like values are checked.
+    module_path_flags: &'static [&'static str],
+    /// Options whose value is inline code, run instead of a script.
+    code_flags: &'static [&'static str],
+    /// Options that run a module instead of a script (`python -m`).
+    module_flags: &'static [&'static str],
+    /// Options that run the script named by their value (`php -f`).
+    script_flags: &'static [&'static str],
+    /// Subcommands followed by more options and a script (`deno run`, `bun test`).
+    run_subcommands: &'static [&'static str],
+    /// Subcommands followed by options and inline code (`deno eval`).
+    eval_subcommands: &'static [&'static str],
+    /// Subcommands that don't take a script; their arguments are guessed at.
+    other_subcommands: &'static [&'static str],
+    /// The script operand may be a package.json script name (`bun run dev`).
+    named_scripts: bool,
+    /// Operands after inline code are input files, edited in place with
+    /// `-i` (`perl -pi -e … file`).
+    code_reads_files: bool,
+}
+
+const DEFAULT_SPEC: InterpreterSpec = InterpreterSpec {
+    names: &[],
+    value_flags: &[],
+    attached_flags: &[],
+    path_flags: &[],
+    module_path_flags: &[],
+    code_flags: &[],
+    module_flags: &[],
+    script_flags: &[],
+    run_subcommands: &[],
+    eval_subcommands: &[],
+    other_subcommands: &[],
+    named_scripts: false,
+    code_reads_files: false,
+};
+
+const INTERPRETERS: &[InterpreterSpec] = &[
+    InterpreterSpec {
+        names: &["python", "python2", "python3"],
+        value_flags: &["-c", "-m", "-W", "-X", "-Q", "--check-hash-based-pycs"],
+        code_flags: &["-c"],
+        module_flags: &["-m"],
+        ..DEFAULT_SPEC
+    },
+    InterpreterSpec {
+        names: &["node"],
+        value_flags: &[
+            "-e",
+            "--eval",
+            "-p",
+            "--print",
+            "-r",
+            "--require",
+            "--import",
+            "--loader",
+            "--experimental-loader",
+            "-C",
+            "--conditions",
+            "--input-type",
+            "--title",
+            "--env-file",
+            "--env-file-if-exists",
+            "--experimental-policy",
+            "--openssl-config",
+            "--icu-data-dir",
+            "--watch-path",
+            "--redirect-warnings",
+            "--diagnostic-dir",
+            "--report-dir",
+            "--report-directory",
+            "--cpu-prof-dir",
+            "--heap-prof-dir",
+        ],
+        path_flags: &[
+            ("--env-file", "env file", false),
+            ("--env-file-if-exists", "env file", false),
+            ("--experimental-policy", "policy file", false),
+            ("--openssl-config", "OpenSSL config", false),
+            ("--icu-data-dir", "ICU data directory", false),
+            ("--watch-path", "watched path", false),
+            ("--redirect-warnings", "warnings file", true),
+            ("--diagnostic-dir", "diagnostic directory", true),
+            ("--report-dir", "report directory", true),
+            ("--report-directory", "report directory", true),
+            ("--cpu-prof-dir", "profile directory", true),
+            ("--heap-prof-dir", "profile directory", true),
+        ],
+        module_path_flags: &[
+            "-r",
+            "--require",
+            "--import",
+            "--loader",
+            "--experimental-loader",
+        ],
+        code_flags: &["-e", "--eval", "-p", "--print"],
+        ..DEFAULT_SPEC
+    },
+    InterpreterSpec {
+        names: &["ruby"],
+        value_flags: &[
+            "-I",
+            "-r",
+            "-C",
+            "-e",
+            "-E",
+            "-F",
+            "--encoding",
+            "--external-encoding",
+            "--internal-encoding",
+            "--enable",
+            "--disable",
+            "--dump",
+        ],
+        attached_flags: &["-i", "-x", "-W", "-0", "-T", "-K"],
+        path_flags: &[
+            ("-I", "load path", false),
+            ("-C", "working directory", false),
+        ],
+        module_path_flags: &["-r"],
+        code_flags: &["-e"],
+        code_reads_files: true,
+        ..DEFAULT_SPEC
+    },
+    InterpreterSpec {
+        names: &["perl"],
+        value_flags: &["-I", "-M", "-m", "-e", "-E"],
+        attached_flags: &["-i", "-x", "-d", "-D", "-C", "-F", "-V"],
+        path_flags: &[("-I", "include path", false)],
+        code_flags: &["-e", "-E"],
+        code_reads_files: true,
+        ..DEFAULT_SPEC
+    },
+    InterpreterSpec {
+        names: &["php"],
+        value_flags: &[
+            "-c", "-d", "-f", "-r", "-t", "-z", "-B", "-R", "-F", "-E", "-S",
+        ],
+        path_flags: &[
+            ("-c", "php.ini path", false),
+            ("-t", "document root", false),
+            ("-z", "Zend extension", false),
+        ],
+        code_flags: &["-r"],
+        script_flags: &["-f"],
+        ..DEFAULT_SPEC
+    },
+    InterpreterSpec {
+        names: &["lua"],
+        value_flags: &["-e", "-l"],
+        code_flags: &["-e"],
+        ..DEFAULT_SPEC
+    },
+    InterpreterSpec {
+        names: &["deno"],
+        value_flags: &[
+            "-c",
+            "--config",
+            "--import-map",
+            "--lock",
+            "--cert",
+            "--location",
+            "--seed",
+            "-L",
+            "--log-level",
+            "--ext",
+            "--v8-flags",
+        ],
+        eval_subcommands: &["eval"],
+        path_flags: &[
+            ("-c", "config file", false),
+            ("--config", "config file", false),
+            ("--import-map", "import map", false),
+            ("--lock", "lockfile", true),
+            ("--cert", "certificate", false),
+            ("--env-file", "env file", false),
+        ],
+        run_subcommands: &[
+            "run", "serve", "test", "bench", "compile", "install", "check", "cache", "info", "fmt",
+            "lint", "doc", "coverage",
+        ],
+        other_subcommands: &[
+            "repl",
+            "task",
+            "upgrade",
+            "init",
+            "jupyter",
+            "lsp",
+            "types",
+            "completions",
+            "publish",
+            "add",
+            "remove",
+            "outdated",
+            "clean",
+            "uninstall",
+        ],
+        ..DEFAULT_SPEC
+    },
+    InterpreterSpec {
+        names: &["bun"],
+        value_flags: &[
+            "--cwd",
+            "-c",
+            "--config",
+            "--env-file",
+            "-r",
+            "--preload",
+            "--tsconfig-override",
+            "-e",
+            "--eval",
+            "-p",
+            "--print",
+            "--main-fields",
+            "--extension-order",
+            "--jsx-factory",
+            "--jsx-fragment",
+            "--jsx-import-source",
+            "--jsx-runtime",
+            "--define",
+            "-d",
+            "--loader",
+            "-l",
+            "--port",
+            "--conditions",
+            "--filter",
+            "-F",
+        ],
+        path_flags: &[
+            ("--cwd", "working directory", false),
+            ("-c", "config file", false),
+            ("--config", "config file", false),
+            ("--env-file", "env file", false),
+            ("--tsconfig-override", "tsconfig", false),
+        ],
+        module_path_flags: &["-r", "--preload"],
+        code_flags: &["-e", "--eval", "-p", "--print"],
+        run_subcommands: &["run", "test", "build"],
+        other_subcommands: &[
+            "x",
+            "exec",
+            "install",
+            "i",
+            "add",
+            "a",
+            "remove",
+            "rm",
+            "update",
+            "upgrade",
+            "init",
+            "create",
+            "c",
+            "pm",
+            "link",
+            "unlink",
+            "outdated",
+            "publish",
+            "audit",
+            "info",
+            "patch",
+            "patch-commit",
+            "repl",
+            "why",
+        ],
+        named_scripts: true,
+        ..DEFAULT_SPEC
+    },
+];
+
+/// Deno permission flags: the long form, its short alias and the permission name.
+const DENO_PERMISSIONS: &[(&str, &str, &str)] = &[
+    ("--allow-all", "-A", "all"),
+    ("--allow-read", "-R", "read"),
+    ("--allow-write", "-W", "write"),
+    ("--allow-net", "-N", "net"),
+    ("--allow-env", "-E", "env"),
+    ("--allow-sys", "-S", "sys"),
+    ("--allow-import", "-I", "import"),
+    ("--allow-run", "", "run"),
+    ("--allow-ffi", "", "ffi"),
+];
+
+/// Permission scopes that reach the whole filesystem or home directory.
+const BROAD_SCOPES: &[&str] = &["/", "~", "~/", "$HOME", "$HOME/", "*"];
+
+/// Extract the script, path-valued options and inline code of an interpreter
+/// or `source` command. Deno permission flags become `interpreter.permission`
+/// facts.
+pub(crate) fn extract_interpreter(
+    cmd: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let spec = INTERPRETERS
+        .iter()
+        .find(|s| s.names.contains(&cmd))
+        .unwrap_or(&DEFAULT_SPEC);
+    let mut subcommand_seen = false;
+    let mut eval_subcommand = None;
+    let mut code_seen = false;
+    let mut in_place = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        if arg == "--" {
+            if let Some(script) = args.get(i + 1) {
+                push_script(spec, script, out);
+            }
+            return;
+        }
+        // The program comes from stdin
+        if arg == "-" {
+            return;
+        }
+        if code_seen {
+            if !arg.starts_with('-') && looks_like_path(arg) {
+                out.paths.push(ExtractedPath {
+                    raw: arg.to_string(),
+                    context: PathContext::ScriptPath { write: in_place },
+                });
+            }
+            i += 1;
+            continue;
+        }
+        if !arg.starts_with('-') {
+            if let Some(eval) = eval_subcommand {
+                extract_inline_code(cmd, eval, arg, out, depth);
+                return;
+            }
+            if !subcommand_seen && spec.eval_subcommands.contains(&arg) {
+                eval_subcommand = Some(arg);
+                i += 1;
+                continue;
+            }
+            if !subcommand_seen && spec.run_subcommands.contains(&arg) {
+                subcommand_seen = true;
+                i += 1;
+                continue;
+            }
+            if !subcommand_seen && spec.other_subcommands.contains(&arg) {
+                extract_unknown_paths(&args[i + 1..], &mut out.paths);
+                return;
+            }
+            push_script(spec, arg, out);
+            return;
+        }
+        let (options, consumed_next) = split_option(spec, arg, args.get(i + 1));
+        for (flag, value) in options {
+            in_place |= spec.code_reads_files && flag == "-i";
+            match option(cmd, sub_cmd, spec, &flag, value.as_deref(), out, depth) {
+                Next::Continue => {}
+                Next::Code if spec.code_reads_files => code_seen = true,
+                Next::Code | Next::Stop => return,
+                Next::Script(script) => {
+                    push_script(spec, &script, out);
+                    return;
+                }
+            }
+        }
+        if consumed_next {
+            i += 1;
+        }
+        i += 1;
+    }
+}
+
+/// What the option parser does after an option.
+enum Next {
+    Continue,
+    /// The option was inline code, run instead of a script operand.
+    Code,
+    /// The interpreter runs a module rather than a script operand.
+    Stop,
+    /// The option named the script to run.
+    Script(String),
+}
+
+/// Split one argument into options and their values, and say whether the
+/// next argument was consumed as a value.
+fn split_option(
+    spec: &InterpreterSpec,
+    arg: &str,
+    next: Option<&String>,
+) -> (Vec<(String, Option<String>)>, bool) {
+    if arg.starts_with("--") {
+        if let Some((flag, value)) = arg.split_once('=') {
+            return (vec![(flag.to_string(), Some(value.to_string()))], false);
+        }
+        if spec.value_flags.contains(&arg) {
+            return (vec![(arg.to_string(), next.cloned())], next.is_some());
+        }
+        return (vec![(arg.to_string(), None)], false);
+    }
+    // Deno's short permission flags take a scope after `=` (`-R=./data`)
+    if arg.len() > 2 && arg.as_bytes()[2] == b'=' {
+        return (
+            vec![(arg[..2].to_string(), Some(arg[3..].to_string()))],
+            false,
+        );
+    }
+    let mut options = Vec::new();
+    let bundle = &arg[1..];
+    for (pos, c) in bundle.char_indices() {
+        let flag = format!("-{c}");
+        let rest = &bundle[pos + c.len_utf8()..];
+        if spec.attached_flags.contains(&flag.as_str()) {
+            options.push((flag, Some(rest.to_string())));
+            return (options, false);
+        }
+        if spec.value_flags.contains(&flag.as_str()) {
+            if rest.is_empty() {
+                options.push((flag, next.cloned()));
+                return (options, next.is_some());
+            }
+            options.push((flag, Some(rest.to_string())));
+            return (options, false);
+        }
+        options.push((flag, None));
+    }
+    (options, false)
+}
+
+/// Record what a single option means.
+fn option(
+    cmd: &str,
+    sub_cmd: &str,
+    spec: &InterpreterSpec,
+    flag: &str,
+    value: Option<&str>,
+    out: &mut CommandAnalysis,
+    depth: usize,
+) -> Next {
+    if cmd == "deno"
+        && let Some(&(_, _, permission)) = DENO_PERMISSIONS
+            .iter()
+            .find(|(long, short, _)| flag == *long || (!short.is_empty() && flag == *short))
+    {
+        deno_permission(sub_cmd, permission, value, out);
+        return Next::Continue;
+    }
+    let Some(value) = value else {
+        return Next::Continue;
+    };
+    if spec.code_flags.contains(&flag) {
+        extract_inline_code(cmd, flag, value, out, depth);
+        return Next::Code;
+    }
+    if spec.module_flags.contains(&flag) {
+        return Next::Stop;
+    }
+    if spec.script_flags.contains(&flag) {
+        return Next::Script(value.to_string());
+    }
+    if let Some(&(_, label, write)) = spec.path_flags.iter().find(|(f, _, _)| *f == flag) {
+        push_path(value, label, write, out);
+    } else if spec.module_path_flags.contains(&flag) && is_explicit_path(value) {
+        push_path(value, "preloaded module", false, out);
+    }
+    Next::Continue
+}
+
+/// Whether a module specifier is a file path rather than a package name
+/// (`dotenv/config`).
+fn is_explicit_path(value: &str) -> bool {
+    value.starts_with(['/', '.', '~']) || value.starts_with("$HOME")
+}
+
+fn push_path(raw: &str, label: &'static str, write: bool, out: &mut CommandAnalysis) {
+    if raw.is_empty() {
+        return;
+    }
+    out.paths.push(ExtractedPath {
+        raw: raw.to_string(),
+        context: PathContext::InterpreterPath { label, write },
+    });
+}
+
+fn push_script(spec: &InterpreterSpec, script: &str, out: &mut CommandAnalysis) {
+    // `bun run dev` runs a package.json script, not a file
+    if spec.named_scripts && !looks_like_path(script) && !script.contains('.') {
+        return;
+    }
+    out.paths.push(ExtractedPath {
+        raw: script.to_string(),
+        context: PathContext::ExecTarget,
+    });
+}
+
+/// Record the paths inline code (`python -c`, a heredoc fed to `node`)
+/// mentions: absolute and home paths anywhere, and string literals that look
+/// like paths, which resolve against the working directory. Shell commands
+/// in Ruby and Perl backticks are analysed as commands.
+pub(crate) fn extract_inline_code(
+    cmd: &str,
+    flag: &str,
+    code: &str,
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    let literals: Vec<_> = STRING_LITERAL_RE
+        .captures_iter(code)
+        .filter_map(|cap| cap.get(1).or_else(|| cap.get(2)))
+        .collect();
+    let in_literal = |start: usize| literals.iter().any(|lit| lit.range().contains(&start));
+    // `/foo/` outside quotes is a regex literal in these languages
+    let bare_paths = !REGEX_LITERAL_INTERPRETERS.contains(&cmd);
+    let mentioned = PATH_IN_CODE_RE
+        .captures_iter(code)
+        .filter_map(|cap| cap.get(1))
+        .filter(|m| bare_paths || !m.as_str().starts_with('/') || in_literal(m.start()))
+        .map(|m| m.as_str());
+    // Words of a longer literal that are clearly paths count too, like the
+    // `..` in `os.system('rm -rf ..')`
+    let literal_words = literals.iter().flat_map(|m| {
+        let words: Vec<&str> = m.as_str().split_whitespace().collect();
+        let whole = words.len() == 1;
+        words
+            .into_iter()
+            .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+            .filter(move |word| whole || word.starts_with(['.', '~', '/']) || word.contains(".."))
+    });
+    let path_literals = literal_words.filter(|lit| is_path_literal(lit));
+    let mut seen: Vec<&str> = Vec::new();
+    for raw in mentioned.chain(path_literals) {
+        if raw.starts_with("/dev/") || seen.contains(&raw) {
+            continue;
+        }
+        seen.push(raw);
+        out.paths.push(ExtractedPath {
+            raw: raw.to_string(),
+            context: PathContext::InlineCodeRef {
+                interpreter: cmd.to_string(),
+                flag: flag.to_string(),
+                code_snippet: code.to_string(),
+            },
+        });
+    }
+    if cmd == "perl" {
+        script_analyzer::extract_perl_code(code, out, depth);
+    }
+    if SHELL_OUT_INTERPRETERS.contains(&cmd) {
+        for cap in SHELL_OUT_RE.captures_iter(code) {
+            if let Some(command) = cap.iter().skip(1).flatten().next() {
+                extract_paths_recursive(command.as_str(), out, depth + 1);
+            }
+        }
+    }
+}
+
+/// Whether a word of a string literal in code names a file: path-like,
+/// not a URL, and not just separators (`"/"`, `"//"`).
+fn is_path_literal(lit: &str) -> bool {
+    looks_like_path(lit)
+        && !lit.contains("://")
+        && !lit.chars().any(char::is_whitespace)
+        && lit.chars().any(|c| c.is_alphanumeric() || c == '.')
+}
+
+/// A deno `--allow-*` flag: an `interpreter.permission` fact, plus the read
+/// and write scopes as paths and the net scope as hosts.
+fn deno_permission(
+    sub_cmd: &str,
+    permission: &str,
+    scope: Option<&str>,
+    out: &mut CommandAnalysis,
+) {
+    let entries: Vec<&str> = scope
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .collect();
+    let broad = permission == "all"
+        || entries.is_empty()
+        || entries.iter().any(|e| BROAD_SCOPES.contains(e));
+    let mut fact = Fact::new("interpreter.permission", sub_cmd)
+        .with("tool", "deno")
+        .with("permission", permission)
+        .with("scope", entries.join(","))
+        .with("broad", broad.to_string());
+    match permission {
+        "read" | "write" => {
+            let label = if permission == "read" {
+                "--allow-read scope"
+            } else {
+                "--allow-write scope"
+            };
+            for entry in &entries {
+                push_path(entry, label, permission == "write", out);
+            }
+        }
+        "net" if !entries.is_empty() => {
+            let hosts: Vec<String> = entries.iter().map(|e| split_host_port(e).0).collect();
+            fact = fact.with("host", hosts.join(","));
+        }
+        _ => {}
+    }
+    out.findings.push(Finding::Fact(fact));
+}
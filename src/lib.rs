@@ -1,10 +1,36 @@
+pub mod archive_analyzer;
+pub mod archive_members;
 pub mod bash_analyzer;
 pub mod blocked_commands;
 pub mod blocked_files;
 pub mod cli;
 pub mod config;
+pub mod container_analyzer;
+pub mod data_flow;
+pub mod database_analyzer;
+pub mod databases;
+pub mod definitions;
+pub mod facts;
+pub mod git_analyzer;
+pub mod git_internals;
 pub mod hook_input;
+pub mod hosts;
+pub mod infra_analyzer;
 pub mod internalonly;
+pub mod interpreter_analyzer;
+pub mod listener_analyzer;
+pub mod network_analyzer;
+pub mod normalize;
+pub mod obfuscation;
 pub mod output;
+pub mod package_analyzer;
+pub mod persistence_analyzer;
+pub mod presets;
+pub mod remote_analyzer;
+pub mod resource_analyzer;
+pub mod resources;
 pub mod router;
+pub mod script_analyzer;
+pub mod sql;
 pub mod util;
+pub mod web;
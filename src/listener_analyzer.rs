@@ -0,0 +1,352 @@
+use crate::bash_analyzer::{CommandAnalysis, ExtractedPath, Finding, PathContext, short_option};
+use crate::facts::Fact;
+use crate::hosts::parse_ip;
+use crate::network_analyzer::split_host_port;
+
+/// Standalone file and application servers.
+pub(crate) const SERVER_COMMANDS: &[&str] =
+    &["serve", "http-server", "busybox", "flask", "uvicorn"];
+
+/// Package runners that fetch and run a server (`npx serve`).
+pub(crate) const PACKAGE_RUNNERS: &[&str] = &["npx", "bunx", "pnpx"];
+
+/// Address a server binds when none is given: every interface.
+const ALL_INTERFACES: &str = "0.0.0.0";
+
+/// Address of servers that bind loopback unless told otherwise.
+const LOOPBACK: &str = "127.0.0.1";
+
+/// A `network.listen` fact for a listener bound to `bind` (an address,
+/// `host:port` is not accepted). `loopback` says whether only this machine
+/// can connect.
+pub(crate) fn listen_fact(sub_cmd: &str, tool: &str, bind: &str, port: Option<&str>) -> Fact {
+    let bind = bind
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_ascii_lowercase();
+    let mut fact = Fact::new("network.listen", sub_cmd)
+        .with("tool", tool)
+        .with("loopback", is_loopback(&bind).to_string())
+        .with("bind", bind);
+    if let Some(port) = port.filter(|p| !p.is_empty()) {
+        fact = fact.with("port", port);
+    }
+    fact
+}
+
+fn is_loopback(bind: &str) -> bool {
+    bind == "localhost"
+        || bind.ends_with(".localhost")
+        || parse_ip(bind).is_some_and(|ip| match ip {
+            std::net::IpAddr::V6(v6) => v6
+                .to_ipv4_mapped()
+                .map_or(v6.is_loopback(), |v4| v4.is_loopback()),
+            v4 => v4.is_loopback(),
+        })
+}
+
+/// Recognise servers started by interpreters (`python -m http.server`,
+/// `php -S`, `ruby -run -e httpd`), standalone servers and `npx serve`.
+/// Records a `network.listen` fact and the directory served, if any, and
+/// returns whether the command is a server.
+pub(crate) fn extract_listener(
+    cmd: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+) -> bool {
+    let server = match cmd {
+        "python" | "python2" | "python3" => python_server(args),
+        "php" => php_server(args),
+        "ruby" => ruby_server(args),
+        "serve" => Some(serve_server(args)),
+        "http-server" => Some(http_server(args)),
+        "busybox" => match args.split_first() {
+            Some((applet, rest)) if applet == "httpd" => Some(busybox_httpd(rest)),
+            _ => None,
+        },
+        "flask" => match args.split_first() {
+            Some((sub, rest)) if sub == "run" => Some(app_server(rest, &["-h", "--host"], "5000")),
+            _ => None,
+        },
+        "uvicorn" => Some(app_server(args, &["--host"], "8000")),
+        runner if PACKAGE_RUNNERS.contains(&runner) => {
+            let Some(pos) = args.iter().position(|a| !a.starts_with('-')) else {
+                return false;
+            };
+            // `npx serve@14 dist`
+            let package = args[pos].split('@').next().unwrap_or_default();
+            return matches!(package, "serve" | "http-server")
+                && extract_listener(package, sub_cmd, &args[pos + 1..], out);
+        }
+        _ => None,
+    };
+    let Some(server) = server else {
+        return false;
+    };
+    let tool = match cmd {
+        "python2" | "python3" => "python",
+        other => other,
+    };
+    let mut fact = listen_fact(sub_cmd, tool, &server.bind, server.port.as_deref());
+    if let Some(dir) = server.dir {
+        fact = fact.with("dir", dir.as_str());
+        out.paths.push(ExtractedPath {
+            raw: dir,
+            context: PathContext::ServedDirectory,
+        });
+    }
+    out.findings.push(Finding::Fact(fact));
+    true
+}
+
+/// What a server command listens on and serves.
+struct Server {
+    bind: String,
+    port: Option<String>,
+    /// Directory whose files are served, for static file servers.
+    dir: Option<String>,
+}
+
+impl Server {
+    fn files(bind: &str, port: &str) -> Self {
+        Self {
+            bind: bind.to_string(),
+            port: Some(port.to_string()),
+            dir: Some(".".to_string()),
+        }
+    }
+}
+
+/// `python -m http.server [port] [-b addr] [-d dir]`, `python2 -m SimpleHTTPServer [port]`.
+fn python_server(args: &[String]) -> Option<Server> {
+    let mut i = 0;
+    let (module, rest) = loop {
+        let arg = args.get(i)?;
+        if arg == "-m" {
+            break (args.get(i + 1)?.as_str(), &args[(i + 2).min(args.len())..]);
+        }
+        if let Some(module) = arg.strip_prefix("-m") {
+            break (module, &args[i + 1..]);
+        }
+        // A script or `-c` code runs instead of a module
+        if !arg.starts_with('-') || arg.starts_with("-c") {
+            return None;
+        }
+        if arg == "-W" || arg == "-X" {
+            i += 1;
+        }
+        i += 1;
+    };
+    if !matches!(module, "http.server" | "SimpleHTTPServer") {
+        return None;
+    }
+    let mut server = Server::files(ALL_INTERFACES, "8000");
+    let mut i = 0;
+    while i < rest.len() {
+        let arg = rest[i].as_str();
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ if matches!(
+                arg,
+                "-b" | "--bind" | "-d" | "--directory" | "-p" | "--protocol"
+            ) =>
+            {
+                i += 1;
+                (arg, rest.get(i).cloned())
+            }
+            _ => (arg, None),
+        };
+        match (flag, value) {
+            ("-b" | "--bind", Some(addr)) => server.bind = addr,
+            ("-d" | "--directory", Some(dir)) => server.dir = Some(dir),
+            (port, None) if !port.starts_with('-') => server.port = Some(port.to_string()),
+            _ => {}
+        }
+        i += 1;
+    }
+    Some(server)
+}
+
+/// `php -S addr:port [-t docroot]`
+fn php_server(args: &[String]) -> Option<Server> {
+    let mut listen = None;
+    let mut dir = ".".to_string();
+    let mut i = 0;
+    while i < args.len() {
+        if let Some((flag, value, consumed_next)) =
+            short_option(&args[i], args.get(i + 1), "StcdfrBRFEz")
+        {
+            match flag {
+                'S' => listen = Some(value),
+                't' => dir = value,
+                _ => {}
+            }
+            if consumed_next {
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+    let (bind, port) = split_host_port(&listen?);
+    Some(Server {
+        bind,
+        port,
+        dir: Some(dir),
+    })
+}
+
+/// `ruby -run -e httpd [dir] [-p port] [-b addr]`
+fn ruby_server(args: &[String]) -> Option<Server> {
+    let requires_un = args
+        .iter()
+        .enumerate()
+        .any(|(i, a)| a == "-run" || (a == "-r" && args.get(i + 1).is_some_and(|m| m == "un")));
+    let pos = args.iter().enumerate().position(|(i, a)| {
+        a == "-ehttpd" || (a == "-e" && args.get(i + 1).is_some_and(|c| c == "httpd"))
+    })?;
+    if !requires_un {
+        return None;
+    }
+    let skip = if args[pos] == "-e" { 2 } else { 1 };
+    let mut server = Server::files(ALL_INTERFACES, "8080");
+    let rest = &args[pos + skip..];
+    let mut i = 0;
+    while i < rest.len() {
+        let arg = rest[i].as_str();
+        if let Some((flag, value, consumed_next)) = short_option(arg, rest.get(i + 1), "pb") {
+            match flag {
+                'p' => server.port = Some(value),
+                _ => server.bind = value,
+            }
+            if consumed_next {
+                i += 1;
+            }
+        } else if let Some(port) = arg.strip_prefix("--port=") {
+            server.port = Some(port.to_string());
+        } else if let Some(addr) = arg.strip_prefix("--bind-address=") {
+            server.bind = addr.to_string();
+        } else if !arg.starts_with('-') {
+            server.dir = Some(arg.to_string());
+        }
+        i += 1;
+    }
+    Some(server)
+}
+
+/// `serve [dir] [-l port|tcp://host:port] [-p port]`
+fn serve_server(args: &[String]) -> Server {
+    let mut server = Server::files(ALL_INTERFACES, "3000");
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ if matches!(arg, "-l" | "--listen" | "-p" | "--port" | "-c" | "--config") => {
+                i += 1;
+                (arg, args.get(i).cloned())
+            }
+            _ => (arg, None),
+        };
+        match (flag, value) {
+            ("-l" | "--listen", Some(endpoint)) => {
+                let endpoint = endpoint
+                    .split_once("://")
+                    .map_or(endpoint.as_str(), |(_, rest)| rest);
+                if endpoint.chars().all(|c| c.is_ascii_digit()) {
+                    server.port = Some(endpoint.to_string());
+                } else {
+                    let (bind, port) = split_host_port(endpoint);
+                    server.bind = bind;
+                    server.port = port;
+                }
+            }
+            ("-p" | "--port", Some(port)) => server.port = Some(port),
+            (dir, None) if !dir.starts_with('-') => server.dir = Some(dir.to_string()),
+            _ => {}
+        }
+        i += 1;
+    }
+    server
+}
+
+/// `http-server [dir] [-p port] [-a addr]`
+fn http_server(args: &[String]) -> Server {
+    let mut server = Server::files(ALL_INTERFACES, "8080");
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1).cloned();
+        match args[i].as_str() {
+            "-p" | "--port" => server.port = value,
+            "-a" | "--address" => server.bind = value.unwrap_or(server.bind),
+            "-c" | "-e" | "--ext" | "-s" | "-C" | "--cert" | "-K" | "--key" | "-P" | "--proxy"
+            | "-t" => {}
+            dir if !dir.starts_with('-') => {
+                server.dir = Some(dir.to_string());
+                i += 1;
+                continue;
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+    server
+}
+
+/// `busybox httpd [-p [addr:]port] [-h home]`
+fn busybox_httpd(args: &[String]) -> Server {
+    let mut server = Server::files(ALL_INTERFACES, "80");
+    let mut i = 0;
+    while i < args.len() {
+        if let Some((flag, value, consumed_next)) = short_option(&args[i], args.get(i + 1), "phcru")
+        {
+            match flag {
+                'p' => match value.rsplit_once(':') {
+                    Some((addr, port)) => {
+                        server.bind = addr.to_string();
+                        server.port = Some(port.to_string());
+                    }
+                    None => server.port = Some(value),
+                },
+                'h' => server.dir = Some(value),
+                _ => {}
+            }
+            if consumed_next {
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+    server
+}
+
+/// Application servers that bind loopback by default: `flask run`, `uvicorn`.
+fn app_server(args: &[String], host_flags: &[&str], default_port: &str) -> Server {
+    let mut server = Server {
+        bind: LOOPBACK.to_string(),
+        port: Some(default_port.to_string()),
+        dir: None,
+    };
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ if host_flags.contains(&arg) || matches!(arg, "-p" | "--port") => {
+                i += 1;
+                (arg, args.get(i).cloned())
+            }
+            _ => (arg, None),
+        };
+        match (flag, value) {
+            (host, Some(addr)) if host_flags.contains(&host) => server.bind = addr,
+            ("-p" | "--port", Some(port)) => server.port = Some(port),
+            _ => {}
+        }
+        i += 1;
+    }
+    server
+}
@@ -4,10 +4,11 @@ use std::path::PathBuf;
 use clap::Parser;
 use eyre::{Result, WrapErr, bail};
 
-use clarg::cli::Cli;
+use clarg::cli::{Cli, Command, PresetsCommand};
 use clarg::config::Config;
 use clarg::hook_input::HookInput;
-use clarg::output::{format_log_entry, log_message, output_deny, print_friendly_usage};
+use clarg::output::{format_log_entry, log_message, output_ask, output_deny, print_friendly_usage};
+use clarg::presets;
 use clarg::router::{RuleSet, Verdict};
 
 fn project_root(hook_input: &HookInput) -> PathBuf {
@@ -31,14 +32,13 @@ fn run(cli: Cli) -> Result<Verdict> {
     }
 
     // Parse JSON — fail closed on parse error
-    let hook_input: HookInput = serde_json::from_str(&input_str)
-        .wrap_err("failed to parse hook input JSON")?;
+    let hook_input: HookInput =
+        serde_json::from_str(&input_str).wrap_err("failed to parse hook input JSON")?;
 
     let root = project_root(&hook_input);
 
     // Build rule set
-    let ruleset = RuleSet::build(&config, &root)
-        .wrap_err("failed to build rule set")?;
+    let ruleset = RuleSet::build(&config, &root).wrap_err("failed to build rule set")?;
 
     // Evaluate
     let verdict = ruleset.evaluate(&hook_input);
@@ -50,8 +50,11 @@ fn run(cli: Cli) -> Result<Verdict> {
             log_message(log_path.as_deref(), &entry);
         }
         Verdict::Deny(reason) => {
-            let entry =
-                format_log_entry(&hook_input.tool_name, "deny", reason);
+            let entry = format_log_entry(&hook_input.tool_name, "deny", reason);
+            log_message(log_path.as_deref(), &entry);
+        }
+        Verdict::Ask(reason) => {
+            let entry = format_log_entry(&hook_input.tool_name, "ask", reason);
             log_message(log_path.as_deref(), &entry);
         }
     }
@@ -59,11 +62,35 @@ fn run(cli: Cli) -> Result<Verdict> {
     Ok(verdict)
 }
 
+/// Handle `clarg presets …`, printing to stdout.
+fn run_presets(command: PresetsCommand) -> Result<()> {
+    match command {
+        PresetsCommand::List => {
+            for preset in presets::PRESETS {
+                println!("{:<12} {}", preset.name, preset.rules()?.description);
+            }
+        }
+        PresetsCommand::Show { name } => {
+            print!("{}", presets::find(&name)?.source);
+        }
+    }
+    Ok(())
+}
+
 fn main() {
     color_eyre::install().ok();
 
     // Parse CLI args first so --help/-V work even from a TTY
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+
+    // Subcommands are for humans — run them before the TTY check
+    if let Some(Command::Presets(command)) = cli.command.take() {
+        if let Err(e) = run_presets(command) {
+            eprintln!("clarg: {e:#}");
+            std::process::exit(1);
+        }
+        std::process::exit(0);
+    }
 
     // TTY check — if user ran clarg interactively with no meaningful args, show friendly usage
     if std::io::stdin().is_terminal() {
@@ -77,6 +104,11 @@ fn main() {
             output_deny(&reason);
             std::process::exit(2);
         }
+        // Claude Code only reads the JSON decision on exit 0
+        Ok(Verdict::Ask(reason)) => {
+            output_ask(&reason);
+            std::process::exit(0);
+        }
         Err(e) => {
             // Fail closed: any internal error blocks the operation
             let reason = format!(
@@ -0,0 +1,751 @@
+use crate::bash_analyzer::{
+    CommandAnalysis, ExtractedPath, Finding, PathContext, extract_paths_recursive,
+    extract_unknown_paths, short_option,
+};
+use crate::facts::Fact;
+use crate::listener_analyzer::listen_fact;
+
+/// Commands that open raw connections to a host.
+pub(crate) const NETWORK_COMMANDS: &[&str] = &["nc", "ncat", "netcat", "socat", "telnet", "ftp"];
+
+/// Package installers whose index URL is checked.
+pub(crate) const PIP_COMMANDS: &[&str] = &["pip", "pip3"];
+
+/// curl short options that take a value.
+const CURL_SHORT_VALUE_FLAGS: &str = "AbcCdDeEFHKmoPQrtTuUwxXyYz";
+
+/// curl long options that take a value when not written `--opt=value`.
+const CURL_VALUE_FLAGS: &[&str] = &[
+    "--data",
+    "--data-ascii",
+    "--data-binary",
+    "--data-raw",
+    "--data-urlencode",
+    "--json",
+    "--form",
+    "--form-string",
+    "--upload-file",
+    "--output",
+    "--output-dir",
+    "--header",
+    "--proxy-header",
+    "--user",
+    "--user-agent",
+    "--referer",
+    "--cookie",
+    "--cookie-jar",
+    "--request",
+    "--proxy",
+    "--proxy-user",
+    "--preproxy",
+    "--max-time",
+    "--connect-timeout",
+    "--url",
+    "--resolve",
+    "--connect-to",
+    "--cacert",
+    "--capath",
+    "--cert",
+    "--key",
+    "--config",
+    "--write-out",
+    "--dump-header",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "--range",
+    "--continue-at",
+    "--oauth2-bearer",
+    "--interface",
+    "--limit-rate",
+    "--max-filesize",
+    "--time-cond",
+    "--trace",
+    "--trace-ascii",
+    "--stderr",
+    "--noproxy",
+    "--netrc-file",
+    "--unix-socket",
+    "--abstract-unix-socket",
+    "--variable",
+    "--expand-url",
+    "--max-redirs",
+    "--local-port",
+    "--dns-servers",
+];
+
+/// curl options that send data to the server.
+const CURL_UPLOAD_FLAGS: &[&str] = &[
+    "-d",
+    "-F",
+    "-T",
+    "--data",
+    "--data-ascii",
+    "--data-binary",
+    "--data-raw",
+    "--data-urlencode",
+    "--json",
+    "--form",
+    "--form-string",
+    "--upload-file",
+];
+
+/// wget short options that take a value.
+const WGET_SHORT_VALUE_FLAGS: &str = "OoaPeitTwUBQlARDIX";
+
+/// wget long options that take a value when not written `--opt=value`.
+const WGET_VALUE_FLAGS: &[&str] = &[
+    "--output-document",
+    "--output-file",
+    "--append-output",
+    "--directory-prefix",
+    "--execute",
+    "--input-file",
+    "--tries",
+    "--timeout",
+    "--wait",
+    "--user-agent",
+    "--header",
+    "--post-data",
+    "--post-file",
+    "--body-data",
+    "--body-file",
+    "--method",
+    "--user",
+    "--password",
+    "--http-user",
+    "--http-password",
+    "--referer",
+    "--load-cookies",
+    "--save-cookies",
+    "--base",
+    "--quota",
+    "--level",
+    "--accept",
+    "--reject",
+    "--domains",
+    "--exclude-domains",
+    "--include-directories",
+    "--exclude-directories",
+    "--ca-certificate",
+    "--certificate",
+    "--private-key",
+    "--bind-address",
+];
+
+/// wget options that send data to the server.
+const WGET_UPLOAD_FLAGS: &[&str] = &[
+    "--post-data",
+    "--post-file",
+    "--body-data",
+    "--body-file",
+    "--method",
+];
+
+/// nc/ncat short options that take a value.
+const NC_SHORT_VALUE_FLAGS: &str = "eciIMmOPpqsTVwXxo";
+
+/// ncat long options that take a value.
+const NC_VALUE_FLAGS: &[&str] = &[
+    "--exec",
+    "--sh-exec",
+    "--lua-exec",
+    "--proxy",
+    "--proxy-type",
+    "--proxy-auth",
+    "--source",
+    "--source-port",
+    "--wait",
+    "--idle-timeout",
+    "--output",
+    "--hex-dump",
+    "--allow",
+    "--allowfile",
+    "--deny",
+    "--denyfile",
+    "--max-conns",
+];
+
+/// socat options that take a value.
+const SOCAT_VALUE_FLAGS: &[&str] = &["-t", "-T", "-b", "-lf", "-lp"];
+
+/// socat address types that connect to `host:port`.
+const SOCAT_CONNECT_TYPES: &[&str] = &[
+    "tcp",
+    "tcp4",
+    "tcp6",
+    "tcp-connect",
+    "tcp4-connect",
+    "tcp6-connect",
+    "udp",
+    "udp4",
+    "udp6",
+    "udp-connect",
+    "udp4-connect",
+    "udp6-connect",
+    "udp-sendto",
+    "udp4-sendto",
+    "udp6-sendto",
+    "udp-datagram",
+    "sctp",
+    "sctp-connect",
+    "openssl",
+    "openssl-connect",
+    "ssl",
+    "dtls",
+    "openssl-dtls-client",
+];
+
+/// socat address types that connect to `proxy:host:port`.
+const SOCAT_PROXY_TYPES: &[&str] = &[
+    "socks",
+    "socks4",
+    "socks4a",
+    "socks5",
+    "proxy",
+    "proxy-connect",
+];
+
+/// socat address types that run a local command.
+const SOCAT_EXEC_TYPES: &[&str] = &["exec", "system"];
+
+/// socat address types that open a local file.
+const SOCAT_FILE_TYPES: &[&str] = &["open", "create", "gopen", "file", "pipe"];
+
+/// telnet options that take a value.
+const TELNET_VALUE_FLAGS: &[&str] = &["-l", "-e", "-n", "-b", "-k", "-X", "-S"];
+
+/// ftp options that take a value.
+const FTP_VALUE_FLAGS: &[&str] = &["-P", "-o", "-r", "-T", "-q", "-s"];
+
+/// pip options that take a package index or find-links URL.
+const PIP_INDEX_FLAGS: &[&str] = &[
+    "-i",
+    "--index-url",
+    "--extra-index-url",
+    "-f",
+    "--find-links",
+];
+
+/// A `scheme://[user@]host[:port][/path][?query]` URL.
+pub(crate) struct Url {
+    pub(crate) scheme: String,
+    pub(crate) host: String,
+    pub(crate) port: Option<String>,
+    /// Path from the first `/`, or `/` when there is none.
+    pub(crate) path: String,
+    pub(crate) query: Option<String>,
+}
+
+/// Parse a URL. Returns None for anything without `scheme://` or a host.
+pub(crate) fn parse_url(raw: &str) -> Option<Url> {
+    let (scheme, rest) = raw.split_once("://")?;
+    if scheme.is_empty()
+        || !scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    {
+        return None;
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let (host, port) = match host_port.strip_prefix('[') {
+        // IPv6 literal: [::1]:8080
+        Some(bracketed) => {
+            let (host, after) = bracketed.split_once(']')?;
+            (host, after.strip_prefix(':'))
+        }
+        None => match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (host_port, None),
+        },
+    };
+    if host.is_empty() {
+        return None;
+    }
+    let tail = rest[authority.len()..]
+        .split('#')
+        .next()
+        .unwrap_or_default();
+    let (path, query) = match tail.split_once('?') {
+        Some((path, query)) => (path, Some(query.to_string())),
+        None => (tail, None),
+    };
+    Some(Url {
+        scheme: scheme.to_ascii_lowercase(),
+        host: host.to_ascii_lowercase(),
+        port: port.filter(|p| !p.is_empty()).map(str::to_string),
+        path: if path.is_empty() {
+            "/".to_string()
+        } else {
+            path.to_string()
+        },
+        query,
+    })
+}
+
+/// A fact for a connection to `url`, with `tool`, `url`, `scheme`, `host`
+/// and `port` attributes. None if `url` has no host.
+pub(crate) fn url_fact(name: &str, sub_cmd: &str, tool: &str, url: &str) -> Option<Fact> {
+    let parsed = parse_url(url)?;
+    let mut fact = Fact::new(name, sub_cmd)
+        .with("tool", tool)
+        .with("url", url)
+        .with("scheme", parsed.scheme)
+        .with("host", parsed.host);
+    if let Some(port) = parsed.port {
+        fact = fact.with("port", port);
+    }
+    Some(fact)
+}
+
+/// The URLs `curl` or `wget` fetch, as `network.request` facts. Output and
+/// upload paths are handled by the download path extraction.
+pub(crate) fn extract_request(
+    cmd: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+) {
+    let (short_value_flags, value_flags, upload_flags) = match cmd {
+        "curl" => (CURL_SHORT_VALUE_FLAGS, CURL_VALUE_FLAGS, CURL_UPLOAD_FLAGS),
+        _ => (WGET_SHORT_VALUE_FLAGS, WGET_VALUE_FLAGS, WGET_UPLOAD_FLAGS),
+    };
+    let mut urls = Vec::new();
+    let mut upload = false;
+    let mut method = None;
+    let mut proxy = None;
+    let mut connect = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            urls.extend(args[i + 1..].iter().cloned());
+            break;
+        }
+        let (flag, value) = if arg.starts_with("--") {
+            match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None if value_flags.contains(&arg.as_str()) => {
+                    i += 1;
+                    (arg.clone(), args.get(i).cloned())
+                }
+                None => (arg.clone(), None),
+            }
+        } else if arg.starts_with('-') && arg.len() > 1 {
+            match short_option(arg, args.get(i + 1), short_value_flags) {
+                Some((flag, value, consumed_next)) => {
+                    if consumed_next {
+                        i += 1;
+                    }
+                    (format!("-{flag}"), Some(value))
+                }
+                None => (arg.clone(), None),
+            }
+        } else {
+            urls.push(arg.clone());
+            i += 1;
+            continue;
+        };
+        if upload_flags.contains(&flag.as_str()) {
+            upload = true;
+        }
+        match (cmd, flag.as_str(), value) {
+            ("curl", "--url", Some(url)) => urls.push(url),
+            ("curl", "-X" | "--request", Some(m)) | ("wget", "--method", Some(m)) => {
+                method = Some(m);
+            }
+            ("curl", "-x" | "--proxy" | "--preproxy", Some(p)) => proxy = Some(p),
+            ("curl", "--connect-to", Some(spec)) => connect.extend(connect_to_host(&spec)),
+            ("curl", "--resolve", Some(spec)) => connect.extend(resolve_addresses(&spec)),
+            ("curl", "-K" | "--config", Some(_)) | ("wget", "-i" | "--input-file", Some(_)) => {
+                out.findings.push(Finding::Unanalyzable {
+                    command: sub_cmd.to_string(),
+                    reason: format!("{cmd} reads its URLs from a file"),
+                });
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    for url in urls {
+        // Both tools default to http:// when the scheme is left out
+        let full = if url.contains("://") {
+            url.clone()
+        } else {
+            format!("http://{url}")
+        };
+        let Some(mut fact) = url_fact("network.request", sub_cmd, cmd, &full) else {
+            continue;
+        };
+        if upload {
+            fact = fact.with("upload", "true");
+        }
+        if let Some(method) = &method {
+            fact = fact.with("method", method.to_ascii_uppercase());
+        }
+        if let Some(proxy) = &proxy {
+            let full = if proxy.contains("://") {
+                proxy.clone()
+            } else {
+                format!("http://{proxy}")
+            };
+            if let Some(parsed) = parse_url(&full) {
+                fact = fact.with("proxy", parsed.host);
+            }
+        }
+        if !connect.is_empty() {
+            fact = fact.with("connect", connect.join(","));
+        }
+        out.findings.push(Finding::Fact(fact));
+    }
+}
+
+/// The host curl connects to for `--connect-to HOST1:PORT1:HOST2:PORT2`.
+/// An empty HOST2 keeps the URL's host, which is already checked.
+fn connect_to_host(spec: &str) -> Option<String> {
+    let fields = split_fields(spec);
+    let host = fields.get(2)?;
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+/// The addresses curl connects to for `--resolve [+]HOST:PORT:ADDR[,ADDR]…`.
+/// `-HOST:PORT` removes an entry and connects nowhere.
+fn resolve_addresses(spec: &str) -> Vec<String> {
+    if spec.starts_with('-') {
+        return Vec::new();
+    }
+    let fields = split_fields(spec);
+    let addrs = fields
+        .get(2..)
+        .map(|rest| rest.join(":"))
+        .unwrap_or_default();
+    addrs
+        .split(',')
+        .map(|addr| {
+            addr.trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_ascii_lowercase()
+        })
+        .filter(|addr| !addr.is_empty())
+        .collect()
+}
+
+/// Split a curl `HOST:PORT:…` spec on colons outside `[v6]` brackets,
+/// dropping the brackets.
+fn split_fields(spec: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut bracketed = false;
+    for c in spec.chars() {
+        match c {
+            '[' => bracketed = true,
+            ']' => bracketed = false,
+            ':' if !bracketed => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+pub(crate) fn extract_network(
+    cmd: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    match cmd {
+        "nc" | "ncat" | "netcat" => extract_nc(cmd, sub_cmd, args, out),
+        "socat" => extract_socat(sub_cmd, args, out, depth),
+        "telnet" => {
+            if let [host, rest @ ..] = positionals(args, TELNET_VALUE_FLAGS).as_slice() {
+                out.findings.push(Finding::Fact(connect_fact(
+                    sub_cmd,
+                    cmd,
+                    host,
+                    rest.first(),
+                )));
+            }
+        }
+        "ftp" => {
+            let operands = positionals(args, FTP_VALUE_FLAGS);
+            // `ftp host [port]`, or URLs to fetch
+            if operands.iter().any(|o| o.contains("://")) {
+                for url in &operands {
+                    if let Some(fact) = url_fact("network.connect", sub_cmd, cmd, url) {
+                        out.findings.push(Finding::Fact(fact));
+                    }
+                }
+            } else if let [host, rest @ ..] = operands.as_slice() {
+                out.findings.push(Finding::Fact(connect_fact(
+                    sub_cmd,
+                    cmd,
+                    host,
+                    rest.first(),
+                )));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `nc [options] host port`, or `nc -l [-p port] [-s addr] [addr] [port]`
+/// for a listener. Unix sockets (`-U`) don't touch the network.
+fn extract_nc(cmd: &str, sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let mut operands = Vec::new();
+    let mut listen = false;
+    let mut unix = false;
+    let mut exec = None;
+    let mut proxy = None;
+    let mut port = None;
+    let mut source = None;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg.starts_with("--") {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None if NC_VALUE_FLAGS.contains(&arg.as_str()) => {
+                    i += 1;
+                    (arg.as_str(), args.get(i).cloned())
+                }
+                None => (arg.as_str(), None),
+            };
+            match (flag, value) {
+                ("--listen", _) => listen = true,
+                ("--unixsock", _) => unix = true,
+                ("--source", Some(v)) => source = Some(v),
+                ("--source-port", Some(v)) => port = Some(v),
+                ("--exec" | "--sh-exec" | "--lua-exec", Some(v)) => exec = Some(v),
+                ("--proxy", Some(v)) => proxy = Some(v),
+                _ => {}
+            }
+        } else if arg.starts_with('-') && arg.len() > 1 {
+            let bundle = &arg[1..];
+            let value_at = bundle
+                .find(|c| NC_SHORT_VALUE_FLAGS.contains(c))
+                .unwrap_or(bundle.len());
+            listen |= bundle[..value_at].contains('l');
+            unix |= bundle[..value_at].contains('U');
+            if let Some((flag, value, consumed_next)) =
+                short_option(arg, args.get(i + 1), NC_SHORT_VALUE_FLAGS)
+            {
+                match flag {
+                    'e' | 'c' => exec = Some(value),
+                    'x' => proxy = Some(value),
+                    'p' => port = Some(value),
+                    's' => source = Some(value),
+                    _ => {}
+                }
+                if consumed_next {
+                    i += 1;
+                }
+            }
+        } else {
+            operands.push(arg.clone());
+        }
+        i += 1;
+    }
+    if unix {
+        return;
+    }
+    if listen {
+        // `nc -l 4444`, `nc -l 0.0.0.0 4444`, `nc -l -p 4444`
+        let (bind, listen_port) = match operands.as_slice() {
+            [addr, p, ..] => (Some(addr.clone()), Some(p.clone())),
+            [p] if p.chars().all(|c| c.is_ascii_digit()) => (None, Some(p.clone())),
+            [addr] => (Some(addr.clone()), None),
+            [] => (None, None),
+        };
+        let bind = bind.or(source).unwrap_or_else(|| "0.0.0.0".to_string());
+        let mut fact = listen_fact(sub_cmd, cmd, &bind, listen_port.or(port).as_deref());
+        if let Some(exec) = exec {
+            fact = fact.with("exec", exec);
+        }
+        out.findings.push(Finding::Fact(fact));
+        return;
+    }
+    let Some(host) = operands.first() else {
+        return;
+    };
+    let mut fact = connect_fact(sub_cmd, cmd, host, operands.get(1));
+    if let Some(exec) = exec {
+        fact = fact.with("exec", exec);
+    }
+    if let Some(proxy) = proxy {
+        fact = fact.with("proxy", split_host_port(&proxy).0);
+    }
+    out.findings.push(Finding::Fact(fact));
+}
+
+/// `socat [options] <address> <address>` — each address that connects out
+/// is a fact; `EXEC:`/`SYSTEM:` commands are analysed and file addresses
+/// are local paths.
+fn extract_socat(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let addresses: Vec<_> = positionals(args, SOCAT_VALUE_FLAGS)
+        .iter()
+        .filter_map(|a| socat_parts(a))
+        .collect();
+    // A connection piped into a local command (a reverse shell)
+    let exec = addresses
+        .iter()
+        .find(|(kind, ..)| SOCAT_EXEC_TYPES.contains(&kind.as_str()))
+        .map(|(_, parts, _)| parts.join(":"));
+    for (kind, parts, options) in &addresses {
+        let kind = kind.as_str();
+        if kind.ends_with("-listen") || kind.ends_with("-recvfrom") {
+            let bind = options
+                .iter()
+                .find_map(|o| o.strip_prefix("bind="))
+                .map_or("0.0.0.0", |b| b);
+            let mut fact = listen_fact(sub_cmd, "socat", bind, parts.first().map(String::as_str));
+            if let Some(exec) = &exec {
+                fact = fact.with("exec", exec.as_str());
+            }
+            out.findings.push(Finding::Fact(fact));
+            continue;
+        }
+        let target = if SOCAT_CONNECT_TYPES.contains(&kind) {
+            parts.first().map(|h| (h.clone(), parts.get(1), None))
+        } else if SOCAT_PROXY_TYPES.contains(&kind) {
+            parts
+                .get(1)
+                .map(|h| (h.clone(), parts.get(2), parts.first()))
+        } else if SOCAT_EXEC_TYPES.contains(&kind) {
+            extract_paths_recursive(&parts.join(":"), out, depth + 1);
+            None
+        } else if SOCAT_FILE_TYPES.contains(&kind) {
+            if let Some(path) = parts.first() {
+                out.paths.push(ExtractedPath {
+                    raw: path.clone(),
+                    context: PathContext::FileCommandWrite,
+                });
+            }
+            None
+        } else {
+            None
+        };
+        if let Some((host, port, proxy)) = target {
+            let mut fact = connect_fact(sub_cmd, "socat", &host, port);
+            if let Some(proxy) = proxy {
+                fact = fact.with("proxy", proxy.as_str());
+            }
+            if let Some(exec) = &exec {
+                fact = fact.with("exec", exec.as_str());
+            }
+            out.findings.push(Finding::Fact(fact));
+        }
+    }
+}
+
+/// Split a socat address into its lowercased type, its `:`-separated
+/// parameters and its `,option` suffixes. Bracketed IPv6 hosts stay whole.
+fn socat_parts(address: &str) -> Option<(String, Vec<String>, Vec<String>)> {
+    let (kind, rest) = address.split_once(':')?;
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut bracket = false;
+    let mut chars = rest.chars();
+    for c in chars.by_ref() {
+        match c {
+            '[' => bracket = true,
+            ']' => bracket = false,
+            ':' if !bracket => parts.push(std::mem::take(&mut current)),
+            ',' if !bracket => break,
+            _ => current.push(c),
+        }
+    }
+    parts.push(current);
+    let options = chars
+        .as_str()
+        .split(',')
+        .filter(|o| !o.is_empty())
+        .map(str::to_string)
+        .collect();
+    Some((kind.to_ascii_lowercase(), parts, options))
+}
+
+/// `pip install -i URL`, `--extra-index-url` and URL `--find-links` as
+/// `network.package-index` facts; other arguments are checked as paths.
+pub(crate) fn extract_pip(cmd: &str, sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let mut rest = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if PIP_INDEX_FLAGS.contains(&flag) => {
+                (flag, Some(value.to_string()))
+            }
+            _ if PIP_INDEX_FLAGS.contains(&arg.as_str()) => {
+                i += 1;
+                (arg.as_str(), args.get(i).cloned())
+            }
+            _ => {
+                rest.push(arg.clone());
+                i += 1;
+                continue;
+            }
+        };
+        if let Some(value) = value {
+            match url_fact("network.package-index", sub_cmd, cmd, &value) {
+                Some(fact) => out.findings.push(Finding::Fact(fact.with("option", flag))),
+                // --find-links also takes a local directory
+                None => rest.push(value),
+            }
+        }
+        i += 1;
+    }
+    extract_unknown_paths(&rest, &mut out.paths);
+}
+
+pub(crate) fn connect_fact(sub_cmd: &str, tool: &str, host: &str, port: Option<&String>) -> Fact {
+    let mut fact = Fact::new("network.connect", sub_cmd)
+        .with("tool", tool)
+        .with(
+            "host",
+            host.trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_ascii_lowercase(),
+        );
+    if let Some(port) = port {
+        fact = fact.with("port", port.as_str());
+    }
+    fact
+}
+
+/// Split `host:port` or `[v6]:port`.
+pub(crate) fn split_host_port(spec: &str) -> (String, Option<String>) {
+    if let Some(bracketed) = spec.strip_prefix('[')
+        && let Some((host, after)) = bracketed.split_once(']')
+    {
+        return (
+            host.to_string(),
+            after.strip_prefix(':').map(str::to_string),
+        );
+    }
+    match spec.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => (host.to_string(), Some(port.to_string())),
+        _ => (spec.to_string(), None),
+    }
+}
+
+/// Non-flag arguments, skipping the values of `value_flags`.
+fn positionals(args: &[String], value_flags: &[&str]) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            out.extend(args[i + 1..].iter().cloned());
+            break;
+        }
+        if value_flags.contains(&arg.as_str()) {
+            i += 1;
+        } else if !arg.starts_with('-') || arg == "-" {
+            out.push(arg.clone());
+        }
+        i += 1;
+    }
+    out
+}
@@ -0,0 +1,293 @@
+use crate::bash_analyzer::{MAX_RECURSION, find_commands, split_shell_operators, unwrap_command};
+use crate::container_analyzer::{CONTAINER_COMMANDS, container_command};
+use crate::definitions::{self, Scope};
+use crate::obfuscation::decode_payloads;
+use crate::remote_analyzer::ssh_commands;
+use crate::script_analyzer::{AWK_COMMANDS, awk_commands, sed_commands};
+
+/// Produce normalized forms of every sub-command in a bash command string.
+///
+/// Each sub-command is tokenized the way the shell would see it (quotes and
+/// escapes removed, `$'...'` strings decoded) and re-joined with single spaces.
+/// When the command is behind a wrapper like `sudo` or `timeout`, the unwrapped
+/// form is emitted as well. Calls to functions and aliases the command defines
+/// are expanded to what they run. `eval` and `sh -c` payloads, the commands
+/// run by `find -exec`, `docker exec`/`run`, `ssh` and sed/awk scripts, and
+/// base64/hex/printf encoded payloads are normalized recursively.
+pub fn normalize_command(command: &str) -> Vec<String> {
+    let mut forms = Vec::new();
+    normalize_recursive(command, &mut forms, 0);
+    forms
+}
+
+fn normalize_recursive(command: &str, forms: &mut Vec<String>, depth: usize) {
+    normalize_in_scope(command, forms, depth, &mut Scope::default());
+}
+
+/// Normalize `command` with the functions and aliases in `scope` defined.
+/// Function bodies and alias values are normalized where they are defined
+/// and again, with their arguments, wherever they are called.
+fn normalize_in_scope(command: &str, forms: &mut Vec<String>, depth: usize, scope: &mut Scope) {
+    if depth > MAX_RECURSION {
+        return;
+    }
+
+    let (without_functions, functions) = definitions::take_functions(command);
+    for sub_cmd in split_shell_operators(&without_functions) {
+        let trimmed = sub_cmd.trim();
+        let first_word = trimmed.split_whitespace().next();
+        if let Some(function) = functions.iter().find(|f| first_word == Some(&f.marker)) {
+            normalize_in_scope(
+                &function.body,
+                forms,
+                depth + 1,
+                &mut scope.entering(&function.name),
+            );
+            scope.define_function(&function.name, &function.body);
+            continue;
+        }
+        if let Some(aliases) = scope.define_aliases(trimmed) {
+            for (name, value) in aliases {
+                normalize_in_scope(&value, forms, depth + 1, &mut scope.entering(&name));
+            }
+        }
+        if let Some(call) = scope.expand(trimmed) {
+            normalize_in_scope(
+                &call.command,
+                forms,
+                depth + 1,
+                &mut scope.entering(&call.name),
+            );
+        }
+
+        let tokens = match shell_words(trimmed) {
+            Some(t) if !t.is_empty() => t,
+            _ => continue,
+        };
+
+        push_unique(forms, tokens.join(" "));
+
+        let unwrapped = unwrap_command(&tokens);
+        if let Some(inner) = &unwrapped.inline_command {
+            normalize_recursive(inner, forms, depth + 1);
+        }
+        let start = unwrapped.start;
+        if start >= tokens.len() {
+            continue;
+        }
+        if start > 0 {
+            push_unique(forms, tokens[start..].join(" "));
+        }
+
+        let args = &tokens[start + 1..];
+        match tokens[start].as_str() {
+            "eval" if !args.is_empty() => {
+                normalize_recursive(&args.join(" "), forms, depth + 1);
+            }
+            "bash" | "sh" | "zsh" | "dash" => {
+                if let Some(pos) = args.iter().position(|t| t == "-c")
+                    && let Some(inner) = args.get(pos + 1)
+                {
+                    normalize_recursive(inner, forms, depth + 1);
+                }
+            }
+            "find" => {
+                for inner in find_commands(args) {
+                    normalize_recursive(&inner, forms, depth + 1);
+                }
+            }
+            "sed" => {
+                for inner in sed_commands(args) {
+                    normalize_recursive(&inner, forms, depth + 1);
+                }
+            }
+            cmd if AWK_COMMANDS.contains(&cmd) => {
+                for inner in awk_commands(args) {
+                    normalize_recursive(&inner, forms, depth + 1);
+                }
+            }
+            "ssh" => {
+                for inner in ssh_commands(args) {
+                    normalize_recursive(&inner, forms, depth + 1);
+                }
+            }
+            cmd if CONTAINER_COMMANDS.contains(&cmd) => {
+                let inner = container_command(args);
+                if let Ok(joined) = shlex::try_join(inner.iter().map(String::as_str)) {
+                    normalize_recursive(&joined, forms, depth + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for payload in decode_payloads(command) {
+        normalize_recursive(&payload, forms, depth + 1);
+    }
+}
+
+fn push_unique(forms: &mut Vec<String>, form: String) {
+    if !form.is_empty() && !forms.contains(&form) {
+        forms.push(form);
+    }
+}
+
+// ============================================================================
+// Shell word splitting
+// ============================================================================
+
+/// Split a string into words following shell quoting rules.
+///
+/// Unlike `shlex::split`, this decodes ANSI-C `$'...'` strings and treats
+/// `$"..."` like a plain double-quoted string. Returns None on unbalanced quotes.
+pub fn shell_words(input: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    // Line continuation
+                    Some('\n') => {}
+                    Some(next) => current.push(next),
+                    None => current.push('\\'),
+                }
+            }
+            '\'' => {
+                in_word = true;
+                read_single_quoted(&mut chars, &mut current)?;
+            }
+            '"' => {
+                in_word = true;
+                read_double_quoted(&mut chars, &mut current)?;
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                read_ansi_c_quoted(&mut chars, &mut current)?;
+            }
+            '$' if chars.peek() == Some(&'"') => {
+                chars.next();
+                in_word = true;
+                read_double_quoted(&mut chars, &mut current)?;
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+    Some(words)
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+fn read_single_quoted(chars: &mut Chars, out: &mut String) -> Option<()> {
+    for ch in chars.by_ref() {
+        if ch == '\'' {
+            return Some(());
+        }
+        out.push(ch);
+    }
+    None
+}
+
+fn read_double_quoted(chars: &mut Chars, out: &mut String) -> Option<()> {
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => return Some(()),
+            '\\' => match chars.next() {
+                Some(next @ ('$' | '`' | '"' | '\\')) => out.push(next),
+                Some('\n') => {}
+                Some(next) => {
+                    out.push('\\');
+                    out.push(next);
+                }
+                None => return None,
+            },
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+fn read_ansi_c_quoted(chars: &mut Chars, out: &mut String) -> Option<()> {
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' => return Some(()),
+            '\\' => {
+                let esc = chars.next()?;
+                match esc {
+                    'a' => out.push('\x07'),
+                    'b' => out.push('\x08'),
+                    'e' | 'E' => out.push('\x1b'),
+                    'f' => out.push('\x0c'),
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    'v' => out.push('\x0b'),
+                    '\\' | '\'' | '"' | '?' => out.push(esc),
+                    'x' => push_code_point(out, read_digits(chars, 16, 2)),
+                    'u' => push_code_point(out, read_digits(chars, 16, 4)),
+                    'U' => push_code_point(out, read_digits(chars, 16, 8)),
+                    '0'..='7' => {
+                        let rest = read_digits(chars, 8, 2);
+                        let first = esc.to_digit(8).unwrap_or(0);
+                        let value = match rest {
+                            Some((v, n)) => first * 8u32.pow(n) + v,
+                            None => first,
+                        };
+                        push_code_point(out, Some((value, 1)));
+                    }
+                    'c' => {
+                        let ctl = chars.next()?;
+                        out.push(char::from((ctl as u8) & 0x1f));
+                    }
+                    other => {
+                        out.push('\\');
+                        out.push(other);
+                    }
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+/// Read up to `max` digits in `radix`. Returns (value, digit count) or None if no digits.
+fn read_digits(chars: &mut Chars, radix: u32, max: u32) -> Option<(u32, u32)> {
+    let mut value = 0u32;
+    let mut count = 0;
+    while count < max {
+        match chars.peek().and_then(|c| c.to_digit(radix)) {
+            Some(d) => {
+                value = value * radix + d;
+                count += 1;
+                chars.next();
+            }
+            None => break,
+        }
+    }
+    (count > 0).then_some((value, count))
+}
+
+fn push_code_point(out: &mut String, digits: Option<(u32, u32)>) {
+    if let Some(c) = digits.and_then(|(v, _)| char::from_u32(v)) {
+        out.push(c);
+    }
+}
@@ -0,0 +1,257 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::bash_analyzer::{split_shell_operators, unwrap_command};
+
+/// Regex-detected obfuscation techniques: (description, weight, pattern).
+static TECHNIQUES: LazyLock<Vec<(&'static str, u32, Regex)>> = LazyLock::new(|| {
+    [
+        ("eval of dynamic content", 3, r#"\beval\s+[^;|&]*[$`]"#),
+        (
+            "base64 decoding",
+            2,
+            r"\bbase64\b[^;|&]*\s(?:-[a-zA-Z]*[dD][a-zA-Z]*|--decode)\b",
+        ),
+        (
+            "base64 decoding",
+            2,
+            r"\bopenssl\s+(?:enc\s+)?[^;|&]*-(?:base64|a)\b[^;|&]*\s-d\b",
+        ),
+        ("hex decoding", 2, r"\bxxd\b[^;|&]*\s-[a-zA-Z]*r"),
+        (
+            "escaped printf",
+            2,
+            r"\bprintf\b[^;|&]*\\(?:x[0-9a-fA-F]|[0-7]{3})",
+        ),
+        ("variable indirection", 2, r"\$\{![A-Za-z_]"),
+        // An IFS set to something other than empty or a newline, or $IFS used
+        // as a separator; `while IFS= read -r line` is the idiomatic line loop
+        (
+            "IFS manipulation",
+            2,
+            r#"\bIFS=(?:[^\s;&|'"$]|'[^'\n]|"[^"\n]|\$'(?:[^\\']|\\[^n]))|\$\{?IFS\b"#,
+        ),
+        ("ANSI-C hex escapes", 1, r"\$'[^']*\\x[0-9a-fA-F]"),
+        ("string reversal", 1, r"\|\s*rev\b"),
+    ]
+    .into_iter()
+    .map(|(name, weight, pattern)| (name, weight, Regex::new(pattern).unwrap()))
+    .collect()
+});
+
+/// Weight for a sub-command whose command word comes from a variable or a
+/// command substitution (`$cmd args`, `$(printf …) -rf /`).
+const DYNAMIC_COMMAND_WEIGHT: u32 = 2;
+
+/// Base64 literal candidates: at least 8 characters of the base64 alphabet.
+static BASE64_LITERAL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z0-9+/]{8,}={0,2}").unwrap());
+
+/// Hex literal candidates: at least 4 bytes of plain hex digits.
+static HEX_LITERAL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:[0-9a-fA-F]{2}){4,}\b").unwrap());
+
+/// Quoted printf arguments containing byte escapes.
+static PRINTF_ESCAPED_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bprintf\s+(?:--\s+)?(?:'([^']*)'|"([^"]*)")"#).unwrap());
+
+/// Places where a command's output is run as code: piped into a shell or
+/// interpreter, or passed to `eval`, `source`/`.`, `sh -c` or `sh <(…)`.
+static RUNS_OUTPUT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\|\s*(?:sudo\s+)?(?:\S*/)?(?:sh|bash|zsh|dash|ksh|python[0-9.]*|perl|ruby|node|php)\b|\b(?:eval|source)\s|(?:^|[;&|(]\s*)\.\s|\b(?:sh|bash|zsh|dash|ksh)\s+(?:-\S+\s+)*(?:-c\s|<\()",
+    )
+    .unwrap()
+});
+
+/// How obfuscated a command looks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObfuscationScore {
+    pub score: u32,
+    /// Descriptions of the techniques that contributed to the score.
+    pub techniques: Vec<String>,
+}
+
+/// Score a command for obfuscation techniques. Each technique counts once.
+pub fn score(command: &str) -> ObfuscationScore {
+    let mut result = ObfuscationScore::default();
+    for (name, weight, regex) in TECHNIQUES.iter() {
+        if regex.is_match(command) && !result.techniques.iter().any(|t| t == name) {
+            result.score += weight;
+            result.techniques.push(name.to_string());
+        }
+    }
+    if has_dynamic_command_word(command) {
+        result.score += DYNAMIC_COMMAND_WEIGHT;
+        result
+            .techniques
+            .push("command name from a variable or substitution".to_string());
+    }
+    result
+}
+
+/// Decode base64, hex and printf-escaped literals in a command when it also
+/// contains the matching decoder and runs the output (`| sh`, `eval`, as a
+/// command name), returning the decoded text of each payload that looks like
+/// a shell command. Decoded text that is only printed is data.
+pub fn decode_payloads(command: &str) -> Vec<String> {
+    let mut payloads = Vec::new();
+    if !RUNS_OUTPUT_RE.is_match(command) && !has_dynamic_command_word(command) {
+        return payloads;
+    }
+    let techniques = score(command).techniques;
+    let uses = |name: &str| techniques.iter().any(|t| t == name);
+
+    if uses("base64 decoding") {
+        for mat in BASE64_LITERAL_RE.find_iter(command) {
+            if let Some(text) = decode_base64(mat.as_str()).and_then(printable_text) {
+                payloads.push(text);
+            }
+        }
+    }
+    if uses("hex decoding") {
+        for mat in HEX_LITERAL_RE.find_iter(command) {
+            if let Some(text) = decode_hex(mat.as_str()).and_then(printable_text) {
+                payloads.push(text);
+            }
+        }
+    }
+    if uses("escaped printf") {
+        for cap in PRINTF_ESCAPED_RE.captures_iter(command) {
+            let format = cap.get(1).or_else(|| cap.get(2)).map_or("", |m| m.as_str());
+            if let Some(text) = printable_text(decode_printf_escapes(format)) {
+                payloads.push(text);
+            }
+        }
+    }
+
+    payloads.retain(|p| !command.contains(p.as_str()));
+    payloads.dedup();
+    payloads
+}
+
+/// Whether any sub-command's command word is a variable or a command substitution.
+fn has_dynamic_command_word(command: &str) -> bool {
+    split_shell_operators(command).iter().any(|sub_cmd| {
+        let Some(tokens) = shlex::split(sub_cmd.trim()) else {
+            return false;
+        };
+        let start = unwrap_command(&tokens).start;
+        tokens.get(start).is_some_and(|word| is_dynamic_word(word))
+    })
+}
+
+/// Whether a shell word starts with a variable expansion or command
+/// substitution, so its value is only known at runtime.
+pub fn is_dynamic_word(word: &str) -> bool {
+    word.starts_with('`')
+        || word
+            .strip_prefix('$')
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(|c| c == '(' || c == '{' || c == '_' || c.is_ascii_alphabetic())
+}
+
+// ============================================================================
+// Decoders
+// ============================================================================
+
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let trimmed = input.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(trimmed.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in trimmed.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+fn decode_hex(input: &str) -> Option<Vec<u8>> {
+    (0..input.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(input.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Decode the byte escapes printf understands in its format string.
+fn decode_printf_escapes(format: &str) -> Vec<u8> {
+    let bytes = format.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' || i + 1 >= bytes.len() {
+            out.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        let digits = |start: usize, max: usize, radix: u32| {
+            let end = (start..bytes.len().min(start + max))
+                .find(|&j| !(bytes[j] as char).is_digit(radix))
+                .unwrap_or(bytes.len().min(start + max));
+            let value = std::str::from_utf8(&bytes[start..end])
+                .ok()
+                .and_then(|s| u32::from_str_radix(s, radix).ok());
+            (value, end)
+        };
+        match bytes[i + 1] {
+            b'x' => match digits(i + 2, 2, 16) {
+                (Some(v), end) => {
+                    out.push(v as u8);
+                    i = end;
+                }
+                (None, _) => {
+                    out.extend_from_slice(b"\\x");
+                    i += 2;
+                }
+            },
+            b'0'..=b'7' => {
+                let (value, end) = digits(i + 1, 3, 8);
+                out.push(value.unwrap_or(0) as u8);
+                i = end;
+            }
+            b'n' => {
+                out.push(b'\n');
+                i += 2;
+            }
+            b't' => {
+                out.push(b'\t');
+                i += 2;
+            }
+            b'\\' => {
+                out.push(b'\\');
+                i += 2;
+            }
+            other => {
+                out.push(b'\\');
+                out.push(other);
+                i += 2;
+            }
+        }
+    }
+    out
+}
+
+/// Accept decoded bytes only if they are UTF-8 text without control characters.
+fn printable_text(bytes: Vec<u8>) -> Option<String> {
+    let text = String::from_utf8(bytes).ok()?;
+    let trimmed = text.trim();
+    let printable = !trimmed.is_empty()
+        && trimmed
+            .chars()
+            .all(|c| !c.is_control() || c == '\n' || c == '\t');
+    printable.then(|| trimmed.to_string())
+}
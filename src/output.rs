@@ -10,6 +10,12 @@ pub fn output_deny(reason: &str) {
     eprintln!("{}", reason);
 }
 
+/// Write the structured ask JSON to stdout so Claude Code prompts the user.
+pub fn output_ask(reason: &str) {
+    let json = ask_json(reason);
+    println!("{}", json);
+}
+
 /// Log a message. If `log_path` is Some, append to that file; otherwise write to stderr.
 pub fn log_message(log_path: Option<&Path>, msg: &str) {
     if let Some(path) = log_path {
@@ -66,10 +72,19 @@ Run `clarg --help` for all options.
 
 /// Build the deny output JSON as a Value.
 pub fn deny_json(reason: &str) -> serde_json::Value {
+    decision_json("deny", reason)
+}
+
+/// Build the ask output JSON as a Value.
+pub fn ask_json(reason: &str) -> serde_json::Value {
+    decision_json("ask", reason)
+}
+
+fn decision_json(decision: &str, reason: &str) -> serde_json::Value {
     json!({
         "hookSpecificOutput": {
             "hookEventName": "PreToolUse",
-            "permissionDecision": "deny",
+            "permissionDecision": decision,
             "permissionDecisionReason": reason
         }
     })
@@ -0,0 +1,896 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::bash_analyzer::{CommandAnalysis, Finding};
+use crate::facts::Fact;
+use crate::network_analyzer::parse_url;
+
+/// Package managers and runners whose installs and package runs are
+/// reported as facts. pip, the `npx`-style runners and bun are routed here
+/// from their own handlers.
+pub(crate) const PACKAGE_COMMANDS: &[&str] = &["npm", "pnpm", "yarn", "uv", "uvx", "pipx", "cargo"];
+
+/// An exact version: `1.2.3`, `v1.2.3-rc.1`, `=1.2.3`.
+static EXACT_VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[=v]?\d+(\.\d+)*([-+][0-9A-Za-z.+-]+)?$").unwrap());
+
+/// A git commit hash, full or abbreviated.
+static COMMIT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[0-9a-f]{7,40}$").unwrap());
+
+/// Which registry ecosystem a package comes from.
+#[derive(Clone, Copy)]
+enum Ecosystem {
+    Npm,
+    Pypi,
+    Crates,
+}
+
+impl Ecosystem {
+    fn as_str(self) -> &'static str {
+        match self {
+            Ecosystem::Npm => "npm",
+            Ecosystem::Pypi => "pypi",
+            Ecosystem::Crates => "crates",
+        }
+    }
+}
+
+/// A parsed package specifier: `eslint@9.1.0`, `ruff==0.4.2`, `git+https://…`.
+struct Spec {
+    package: String,
+    version: Option<String>,
+    /// `registry`, `git`, `url` or `path`
+    source: &'static str,
+    url: Option<String>,
+    pinned: bool,
+}
+
+impl Spec {
+    fn registry(package: &str, version: Option<&str>, pinned: bool) -> Self {
+        Self {
+            package: package.to_string(),
+            version: version.filter(|v| !v.is_empty()).map(str::to_string),
+            source: "registry",
+            url: None,
+            pinned,
+        }
+    }
+
+    fn path(raw: &str) -> Self {
+        Self {
+            package: raw.to_string(),
+            version: None,
+            source: "path",
+            url: None,
+            // Nothing is fetched
+            pinned: true,
+        }
+    }
+
+    /// A git or archive URL. Git URLs are pinned by a commit hash.
+    fn url(package: &str, url: &str, git: bool, rev: Option<&str>) -> Self {
+        Self {
+            package: package.to_string(),
+            version: rev.map(str::to_string),
+            source: if git { "git" } else { "url" },
+            url: Some(url.to_string()),
+            pinned: git && rev.is_some_and(|rev| COMMIT.is_match(rev)),
+        }
+    }
+}
+
+/// How a package operation was invoked.
+struct Operation<'a> {
+    name: &'static str,
+    tool: &'a str,
+    ecosystem: Ecosystem,
+    global: bool,
+    registry: Option<String>,
+}
+
+impl Operation<'_> {
+    fn push(&self, sub_cmd: &str, spec: Spec, out: &mut CommandAnalysis) {
+        let mut fact = Fact::new(self.name, sub_cmd)
+            .with("tool", self.tool)
+            .with("manager", self.ecosystem.as_str())
+            .with("package", spec.package)
+            .with("pinned", spec.pinned.to_string())
+            .with("source", spec.source)
+            .with("global", self.global.to_string());
+        if let Some(version) = spec.version {
+            fact = fact.with("version", version);
+        }
+        // Hosts the package is fetched from, checked against the host policy
+        let mut hosts = Vec::new();
+        if let Some(url) = &spec.url {
+            fact = fact.with("url", url.as_str());
+            hosts.extend(url_host(url));
+        }
+        if let Some(registry) = &self.registry {
+            fact = fact.with("registry", registry.as_str());
+            hosts.extend(parse_url(registry).map(|u| u.host));
+        }
+        hosts.dedup();
+        if !hosts.is_empty() {
+            fact = fact.with("host", hosts.join(","));
+        }
+        out.findings.push(Finding::Fact(fact));
+    }
+}
+
+/// The host of a URL, including `git+https://` and scp-like `git@host:path`.
+fn url_host(url: &str) -> Option<String> {
+    let url = url.strip_prefix("git+").unwrap_or(url);
+    if let Some(parsed) = parse_url(url) {
+        return Some(parsed.host);
+    }
+    let (authority, _) = url.split_once(':')?;
+    let host = authority.rsplit('@').next()?;
+    (!host.is_empty() && !host.contains('/')).then(|| host.to_string())
+}
+
+/// The value of `NAME=value` among the assignments before the command.
+fn env_var<'a>(env: &'a [String], names: &[&str]) -> Option<&'a str> {
+    names.iter().find_map(|name| {
+        env.iter()
+            .rev()
+            .find_map(|assignment| assignment.strip_prefix(name)?.strip_prefix('='))
+    })
+}
+
+/// Options and positionals, split like [`crate::infra_analyzer`] does but
+/// stopping at the first positional when `stop_at_operand` is set: what
+/// follows the package a runner fetches is that package's own command line.
+struct Parsed {
+    options: Vec<(String, Option<String>)>,
+    positionals: Vec<String>,
+}
+
+impl Parsed {
+    fn value(&self, flags: &[&str]) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(flag, _)| flags.contains(&flag.as_str()))
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    fn values(&self, flags: &[&str]) -> Vec<&str> {
+        self.options
+            .iter()
+            .filter(|(flag, _)| flags.contains(&flag.as_str()))
+            .filter_map(|(_, value)| value.as_deref())
+            .collect()
+    }
+
+    fn has(&self, flags: &[&str]) -> bool {
+        self.options
+            .iter()
+            .any(|(flag, _)| flags.contains(&flag.as_str()))
+    }
+}
+
+fn parse(args: &[String], value_flags: &[&str], stop_at_operand: bool) -> Parsed {
+    let mut options = Vec::new();
+    let mut positionals = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        i += 1;
+        if arg == "--" || (stop_at_operand && !positionals.is_empty()) {
+            let from = if arg == "--" { i } else { i - 1 };
+            positionals.extend(args[from..].iter().cloned());
+            break;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            positionals.push(arg.clone());
+        } else if let Some((flag, value)) = arg
+            .split_once('=')
+            .filter(|(flag, _)| flag.starts_with("--"))
+        {
+            options.push((flag.to_string(), Some(value.to_string())));
+        } else if value_flags.contains(&arg.as_str()) {
+            options.push((arg.clone(), args.get(i).cloned()));
+            i += 1;
+        } else {
+            options.push((arg.clone(), None));
+        }
+    }
+    Parsed {
+        options,
+        positionals,
+    }
+}
+
+/// Report the packages a package manager installs (`package.install`) or a
+/// runner fetches and runs (`package.exec`). `env` holds the `VAR=value`
+/// assignments before the command. Local paths among the arguments are left
+/// to the caller.
+pub(crate) fn extract_package(
+    cmd: &str,
+    sub_cmd: &str,
+    env: &[String],
+    args: &[String],
+    out: &mut CommandAnalysis,
+) {
+    match cmd {
+        "npx" | "pnpx" | "bunx" => npm_exec(cmd, sub_cmd, env, args, out),
+        "npm" | "pnpm" | "yarn" | "bun" => npm_family(cmd, sub_cmd, env, args, out),
+        "pip" | "pip3" => pip(cmd, sub_cmd, env, args, out),
+        "uv" | "uvx" => uv(cmd, sub_cmd, env, args, out),
+        "pipx" => pipx(sub_cmd, env, args, out),
+        "cargo" => cargo(sub_cmd, args, out),
+        // `python -m pip install …`
+        python if python.starts_with("python") => match args {
+            [m, module, rest @ ..]
+                if m == "-m" && matches!(module.as_str(), "pip" | "pip3" | "pipx" | "uv") =>
+            {
+                extract_package(module, sub_cmd, env, rest, out)
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+// ============================================================================
+// npm, pnpm, yarn, bun
+// ============================================================================
+
+/// npm-family options that take a separate value.
+const NPM_VALUE_FLAGS: &[&str] = &[
+    "--registry",
+    "--prefix",
+    "--cache",
+    "--userconfig",
+    "--tag",
+    "--omit",
+    "--include",
+    "--location",
+    "--workspace",
+    "--filter",
+    "--dir",
+    "-C",
+    "--cwd",
+    "--package",
+    "-p",
+    "--call",
+    "-c",
+    "--shell",
+    "--save-prefix",
+    "--network-timeout",
+    "--modules-folder",
+    "--store-dir",
+    "--config",
+    "--backend",
+    "--global-dir",
+];
+
+fn npm_registry(parsed: &Parsed, env: &[String]) -> Option<String> {
+    parsed
+        .value(&["--registry"])
+        .or_else(|| env_var(env, &["NPM_CONFIG_REGISTRY", "npm_config_registry"]))
+        .map(str::to_string)
+}
+
+/// `npx [options] package[@version] [args]`, `npx -p package command`
+fn npm_exec(tool: &str, sub_cmd: &str, env: &[String], args: &[String], out: &mut CommandAnalysis) {
+    let parsed = parse(args, NPM_VALUE_FLAGS, true);
+    let explicit = parsed.values(&["--package", "-p"]);
+    let specs: Vec<&str> = if explicit.is_empty() {
+        parsed
+            .positionals
+            .first()
+            .map(String::as_str)
+            .into_iter()
+            .collect()
+    } else {
+        explicit
+    };
+    if specs.is_empty() {
+        return;
+    }
+    let op = Operation {
+        name: "package.exec",
+        tool,
+        ecosystem: Ecosystem::Npm,
+        global: false,
+        registry: npm_registry(&parsed, env),
+    };
+    for spec in specs {
+        op.push(sub_cmd, npm_spec(spec), out);
+    }
+}
+
+/// `npm install`, `pnpm add`, `yarn global add`, `bun x`, `npm exec`,
+/// `npm init <initializer>`, ...
+fn npm_family(
+    tool: &str,
+    sub_cmd: &str,
+    env: &[String],
+    args: &[String],
+    out: &mut CommandAnalysis,
+) {
+    // Option values before the subcommand aren't the subcommand: `npm --prefix ../ i x`
+    let mut pos = 0;
+    while args.get(pos).is_some_and(|a| a.starts_with('-')) {
+        let takes_value = !args[pos].contains('=') && NPM_VALUE_FLAGS.contains(&args[pos].as_str());
+        pos += if takes_value { 2 } else { 1 };
+    }
+    let Some(subcommand) = args.get(pos).map(String::as_str) else {
+        return;
+    };
+    let rest = &args[pos + 1..];
+    match (tool, subcommand) {
+        ("npm", "exec" | "x") | ("pnpm" | "yarn", "dlx") | ("bun", "x") => {
+            return npm_exec(tool, sub_cmd, env, rest, out);
+        }
+        // `npm init vite@latest` runs `create-vite@latest`
+        (_, "init" | "create") => {
+            let parsed = parse(rest, NPM_VALUE_FLAGS, true);
+            let Some(initializer) = parsed.positionals.first() else {
+                return;
+            };
+            // `bun init` and `npm init` with no initializer don't fetch anything
+            let (name, version) = split_npm_name(initializer);
+            let package = match name.split_once('/') {
+                Some((scope, name)) => format!("{scope}/create-{name}"),
+                None if name.starts_with('@') => format!("{name}/create"),
+                None => format!("create-{name}"),
+            };
+            let spec = match version {
+                Some(version) => npm_spec(&format!("{package}@{version}")),
+                None => npm_spec(&package),
+            };
+            let op = Operation {
+                name: "package.exec",
+                tool,
+                ecosystem: Ecosystem::Npm,
+                global: false,
+                registry: npm_registry(&parsed, env),
+            };
+            op.push(sub_cmd, spec, out);
+            return;
+        }
+        _ => {}
+    }
+
+    let (is_global, rest) = match (tool, subcommand, rest.first()) {
+        ("yarn", "global", Some(add)) if add == "add" => (true, &rest[1..]),
+        ("npm", "install" | "i" | "in" | "ins" | "inst" | "add" | "isntall", _)
+        | ("pnpm", "add" | "install" | "i", _)
+        | ("yarn", "add", _)
+        | ("bun", "add" | "a" | "install" | "i", _) => (false, rest),
+        _ => return,
+    };
+    // Options may also come before the subcommand: `npm -g i x`
+    let parsed = parse(rest, NPM_VALUE_FLAGS, false);
+    let before = parse(&args[..pos], NPM_VALUE_FLAGS, false);
+    let global = is_global
+        || [&parsed, &before]
+            .iter()
+            .any(|p| p.has(&["-g", "--global"]) || p.value(&["--location"]) == Some("global"));
+    if parsed.positionals.is_empty() {
+        // Installing the project's own dependencies
+        return;
+    }
+    let op = Operation {
+        name: "package.install",
+        tool,
+        ecosystem: Ecosystem::Npm,
+        global,
+        registry: npm_registry(&parsed, env).or_else(|| npm_registry(&before, env)),
+    };
+    for spec in &parsed.positionals {
+        op.push(sub_cmd, npm_spec(spec), out);
+    }
+}
+
+/// Split `name@version` and `@scope/name@version`.
+fn split_npm_name(raw: &str) -> (&str, Option<&str>) {
+    let at = if let Some(scoped) = raw.strip_prefix('@') {
+        scoped.find('@').map(|at| at + 1)
+    } else {
+        raw.find('@')
+    };
+    match at {
+        Some(at) => (&raw[..at], Some(&raw[at + 1..])),
+        None => (raw, None),
+    }
+}
+
+/// An npm package specifier: `name`, `name@1.2.3`, `@scope/name@^1`,
+/// `alias@npm:name@1`, git URLs and `github:` shorthands, tarball URLs,
+/// local paths.
+fn npm_spec(raw: &str) -> Spec {
+    if !is_npm_source(raw)
+        && let (name, Some(source)) = split_npm_name(raw)
+        && is_npm_source(source)
+    {
+        // `name@<git, URL or path>` installs that source under `name`
+        return Spec {
+            package: name.to_string(),
+            ..npm_spec(source)
+        };
+    }
+    if NPM_GIT_PREFIXES.iter().any(|p| raw.starts_with(p)) || raw.starts_with("git@") {
+        let (url, rev) = match raw.split_once('#') {
+            Some((url, rev)) => (url, Some(rev)),
+            None => (raw, None),
+        };
+        return Spec::url(&repo_name(url), url, true, rev);
+    }
+    if raw.starts_with("http://") || raw.starts_with("https://") {
+        return Spec::url(&repo_name(raw), raw, false, None);
+    }
+    if raw.starts_with("file:") || is_local_path(raw) || raw.ends_with(".tgz") {
+        return Spec::path(raw);
+    }
+    let (name, version) = split_npm_name(raw);
+    // GitHub shorthand: `user/repo#ref`
+    if !name.starts_with('@') && name.contains('/') {
+        let (repo, rev) = match raw.split_once('#') {
+            Some((repo, rev)) => (repo, Some(rev)),
+            None => (raw, None),
+        };
+        return Spec::url(&repo_name(repo), &format!("github:{repo}"), true, rev);
+    }
+    match version.and_then(|v| v.strip_prefix("npm:")) {
+        Some(target) => {
+            let (name, version) = split_npm_name(target);
+            Spec::registry(
+                name,
+                version,
+                version.is_some_and(|v| EXACT_VERSION.is_match(v)),
+            )
+        }
+        None => Spec::registry(
+            name,
+            version,
+            version.is_some_and(|v| EXACT_VERSION.is_match(v)),
+        ),
+    }
+}
+
+/// Prefixes of npm git specifiers.
+const NPM_GIT_PREFIXES: &[&str] = &[
+    "git+",
+    "git://",
+    "github:",
+    "gitlab:",
+    "bitbucket:",
+    "gist:",
+];
+
+/// Whether an npm specifier names a git repository, URL or local path
+/// rather than a registry package.
+fn is_npm_source(raw: &str) -> bool {
+    NPM_GIT_PREFIXES.iter().any(|p| raw.starts_with(p))
+        || ["git@", "http://", "https://", "file:"]
+            .iter()
+            .any(|p| raw.starts_with(p))
+        || is_local_path(raw)
+}
+
+/// The last path segment of a repository or archive URL, without `.git`
+/// or an archive extension.
+fn repo_name(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let last = path
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or(path);
+    [".git", ".tgz", ".tar.gz", ".whl", ".zip", ".crate"]
+        .iter()
+        .find_map(|ext| last.strip_suffix(ext))
+        .unwrap_or(last)
+        .to_string()
+}
+
+fn is_local_path(raw: &str) -> bool {
+    raw == "." || raw == ".." || ["./", "../", "/", "~"].iter().any(|p| raw.starts_with(p))
+}
+
+// ============================================================================
+// pip, uv, pipx
+// ============================================================================
+
+/// pip and uv options that take a separate value.
+const PIP_VALUE_FLAGS: &[&str] = &[
+    "-r",
+    "--requirement",
+    "-c",
+    "--constraint",
+    "-e",
+    "--editable",
+    "-i",
+    "--index-url",
+    "--extra-index-url",
+    "--index",
+    "--default-index",
+    "-f",
+    "--find-links",
+    "-t",
+    "--target",
+    "--prefix",
+    "--root",
+    "--platform",
+    "--python-version",
+    "--implementation",
+    "--abi",
+    "--src",
+    "--upgrade-strategy",
+    "--progress-bar",
+    "--log",
+    "--proxy",
+    "--retries",
+    "--timeout",
+    "--exists-action",
+    "--trusted-host",
+    "--cert",
+    "--client-cert",
+    "--cache-dir",
+    "--no-binary",
+    "--only-binary",
+    "--config-settings",
+    "-C",
+    "--python",
+    "-p",
+    "--from",
+    "--with",
+    "-w",
+    "--with-editable",
+    "--with-requirements",
+    "--directory",
+    "--project",
+    "--config-file",
+    "--spec",
+    "--pip-args",
+    "--suffix",
+    "--preinstall",
+    "--group",
+    "--optional",
+    "--extra",
+    "--package",
+    "--index-strategy",
+    "--keyring-provider",
+];
+
+/// Options that install outside a virtual environment.
+const PIP_GLOBAL_FLAGS: &[&str] = &[
+    "--user",
+    "--break-system-packages",
+    "--system",
+    "-t",
+    "--target",
+    "--prefix",
+    "--root",
+];
+
+fn pip_registry(parsed: &Parsed, env: &[String]) -> Option<String> {
+    parsed
+        .value(&["-i", "--index-url", "--default-index", "--index"])
+        .or_else(|| env_var(env, &["PIP_INDEX_URL", "UV_INDEX_URL", "UV_DEFAULT_INDEX"]))
+        .map(str::to_string)
+}
+
+/// `pip install [options] spec…`
+fn pip(tool: &str, sub_cmd: &str, env: &[String], args: &[String], out: &mut CommandAnalysis) {
+    let parsed = parse(args, PIP_VALUE_FLAGS, false);
+    if parsed.positionals.first().map(String::as_str) != Some("install") {
+        return;
+    }
+    pip_install(
+        tool,
+        sub_cmd,
+        env,
+        &parsed,
+        &parsed.positionals[1..],
+        false,
+        out,
+    )
+}
+
+fn pip_install(
+    tool: &str,
+    sub_cmd: &str,
+    env: &[String],
+    parsed: &Parsed,
+    specs: &[String],
+    global: bool,
+    out: &mut CommandAnalysis,
+) {
+    let editable = parsed.values(&["-e", "--editable"]);
+    if specs.is_empty() && editable.is_empty() {
+        return;
+    }
+    let op = Operation {
+        name: "package.install",
+        tool,
+        ecosystem: Ecosystem::Pypi,
+        global: global || parsed.has(PIP_GLOBAL_FLAGS),
+        registry: pip_registry(parsed, env),
+    };
+    for spec in specs.iter().map(String::as_str).chain(editable) {
+        op.push(sub_cmd, pypi_spec(spec), out);
+    }
+}
+
+/// `uvx [options] tool[@version] [args]`, `uv tool run|install`,
+/// `uv pip install`, `uv add`
+fn uv(tool: &str, sub_cmd: &str, env: &[String], args: &[String], out: &mut CommandAnalysis) {
+    if tool == "uvx" {
+        return uv_run(tool, sub_cmd, env, args, out);
+    }
+    let parsed = parse(args, PIP_VALUE_FLAGS, false);
+    let positionals: Vec<&str> = parsed.positionals.iter().map(String::as_str).collect();
+    match positionals.as_slice() {
+        ["tool", "run", ..] | ["x", ..] => {
+            let at = args
+                .iter()
+                .position(|a| a == "run" || a == "x")
+                .unwrap_or(0);
+            uv_run(tool, sub_cmd, env, &args[at + 1..], out)
+        }
+        ["tool", "install", ..] => pip_install(
+            tool,
+            sub_cmd,
+            env,
+            &parsed,
+            &parsed.positionals[2..],
+            true,
+            out,
+        ),
+        ["pip", "install", ..] => pip_install(
+            tool,
+            sub_cmd,
+            env,
+            &parsed,
+            &parsed.positionals[2..],
+            false,
+            out,
+        ),
+        ["add", ..] => pip_install(
+            tool,
+            sub_cmd,
+            env,
+            &parsed,
+            &parsed.positionals[1..],
+            false,
+            out,
+        ),
+        _ => {}
+    }
+}
+
+fn uv_run(tool: &str, sub_cmd: &str, env: &[String], args: &[String], out: &mut CommandAnalysis) {
+    let parsed = parse(args, PIP_VALUE_FLAGS, true);
+    // With --from the first operand is a command of that package
+    let from = parsed.value(&["--from"]);
+    let main = from.or_else(|| parsed.positionals.first().map(String::as_str));
+    let Some(main) = main else {
+        return;
+    };
+    let op = Operation {
+        name: "package.exec",
+        tool,
+        ecosystem: Ecosystem::Pypi,
+        global: false,
+        registry: pip_registry(&parsed, env),
+    };
+    op.push(sub_cmd, pypi_spec(main), out);
+    for with in parsed.values(&["--with", "-w"]) {
+        for spec in with.split(',') {
+            op.push(sub_cmd, pypi_spec(spec), out);
+        }
+    }
+}
+
+/// `pipx run [--spec spec] app[==version]`, `pipx install spec…`,
+/// `pipx inject venv spec…`
+fn pipx(sub_cmd: &str, env: &[String], args: &[String], out: &mut CommandAnalysis) {
+    let Some(pos) = args.iter().position(|a| !a.starts_with('-')) else {
+        return;
+    };
+    let rest = &args[pos + 1..];
+    let (name, stop, skip) = match args[pos].as_str() {
+        "run" => ("package.exec", true, 0),
+        "install" => ("package.install", false, 0),
+        "inject" => ("package.install", false, 1),
+        _ => return,
+    };
+    let parsed = parse(rest, PIP_VALUE_FLAGS, stop);
+    let mut specs: Vec<&str> = parsed
+        .positionals
+        .iter()
+        .skip(skip)
+        .map(String::as_str)
+        .collect();
+    if stop {
+        specs.truncate(1);
+        if let Some(spec) = parsed.value(&["--spec"]) {
+            specs = vec![spec];
+        }
+    }
+    if specs.is_empty() {
+        return;
+    }
+    let registry = parsed
+        .value(&["--index-url"])
+        .or_else(|| env_var(env, &["PIP_INDEX_URL"]))
+        .map(str::to_string);
+    let op = Operation {
+        name,
+        tool: "pipx",
+        ecosystem: Ecosystem::Pypi,
+        // pipx puts apps on the user's PATH
+        global: name == "package.install",
+        registry,
+    };
+    for spec in specs {
+        op.push(sub_cmd, pypi_spec(spec), out);
+    }
+}
+
+/// A Python requirement: `name`, `name==1.2`, `name[extra]>=1`,
+/// `name @ git+https://…`, `git+https://…@rev#egg=name`, wheel URLs and
+/// local paths. uvx also accepts `name@1.2`.
+fn pypi_spec(raw: &str) -> Spec {
+    let raw = raw.split(';').next().unwrap_or(raw).trim();
+    // PEP 508 direct reference: `name @ url`
+    if let Some((name, url)) = raw.split_once('@')
+        && url.trim().contains("://")
+    {
+        let name = name.trim().split('[').next().unwrap_or_default();
+        let mut spec = pypi_url(url.trim());
+        if !name.is_empty() {
+            spec.package = name.to_string();
+        }
+        return spec;
+    }
+    if raw.contains("://") {
+        return pypi_url(raw);
+    }
+    if is_local_path(raw)
+        || raw.contains('/')
+        || [".whl", ".tar.gz", ".zip"].iter().any(|e| raw.ends_with(e))
+    {
+        return Spec::path(raw);
+    }
+    let end = raw
+        .find(|c: char| "[<>=!~@ (".contains(c))
+        .unwrap_or(raw.len());
+    let name = &raw[..end];
+    let rest = raw[end..].trim_start();
+    // Skip extras
+    let rest = match rest.strip_prefix('[') {
+        Some(extras) => extras
+            .split_once(']')
+            .map_or("", |(_, rest)| rest)
+            .trim_start(),
+        None => rest,
+    };
+    let pinned = if let Some(version) = rest.strip_prefix('@') {
+        EXACT_VERSION.is_match(version)
+    } else if let Some(version) = rest.strip_prefix("===") {
+        !version.is_empty()
+    } else if let Some(version) = rest.strip_prefix("==") {
+        !version.is_empty() && !version.contains(['*', ','])
+    } else {
+        false
+    };
+    Spec::registry(name, Some(rest), pinned)
+}
+
+/// `git+https://host/repo@rev#egg=name` or an archive URL.
+fn pypi_url(url: &str) -> Spec {
+    let (location, fragment) = url.split_once('#').unwrap_or((url, ""));
+    let egg = fragment
+        .split('&')
+        .find_map(|part| part.strip_prefix("egg="))
+        .map(str::to_string);
+    if !location.starts_with("git+") {
+        return Spec::url(
+            &egg.unwrap_or_else(|| repo_name(location)),
+            url,
+            false,
+            None,
+        );
+    }
+    // The revision follows the last `@` in the path, not one in `user@host`
+    let scheme_end = location.find("://").map_or(0, |i| i + 3);
+    let (repo, rev) = match location[scheme_end..].rfind('@') {
+        Some(at) if location[scheme_end..scheme_end + at].contains('/') => (
+            &location[..scheme_end + at],
+            Some(&location[scheme_end + at + 1..]),
+        ),
+        _ => (location, None),
+    };
+    Spec::url(&egg.unwrap_or_else(|| repo_name(repo)), url, true, rev)
+}
+
+// ============================================================================
+// cargo
+// ============================================================================
+
+/// `cargo install` and `cargo binstall` options that take a separate value.
+const CARGO_VALUE_FLAGS: &[&str] = &[
+    "--version",
+    "--vers",
+    "--git",
+    "--branch",
+    "--tag",
+    "--rev",
+    "--path",
+    "--root",
+    "--index",
+    "--registry",
+    "--features",
+    "-F",
+    "--target",
+    "--target-dir",
+    "-j",
+    "--jobs",
+    "--profile",
+    "--config",
+    "-Z",
+    "--bin",
+    "--example",
+    "--color",
+    "--manifest-path",
+    "--install-path",
+    "--pkg-url",
+    "--pkg-fmt",
+    "--bin-dir",
+    "--strategies",
+];
+
+/// `cargo install [options] crate[@version]…`
+fn cargo(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let parsed = parse(args, CARGO_VALUE_FLAGS, false);
+    let Some(subcommand) = parsed.positionals.first() else {
+        return;
+    };
+    if subcommand != "install" && subcommand != "binstall" {
+        return;
+    }
+    let crates = &parsed.positionals[1..];
+    let registry = parsed.value(&["--index", "--registry"]).map(str::to_string);
+    let op = Operation {
+        name: "package.install",
+        tool: "cargo",
+        ecosystem: Ecosystem::Crates,
+        // Binaries go to ~/.cargo/bin
+        global: true,
+        registry,
+    };
+    if let Some(git) = parsed.value(&["--git"]) {
+        let rev = parsed.value(&["--rev"]);
+        let mut spec = Spec::url(&repo_name(git), git, true, rev);
+        if let Some(name) = crates.first() {
+            spec.package = name.clone();
+        }
+        if rev.is_none() {
+            spec.version = parsed.value(&["--tag", "--branch"]).map(str::to_string);
+        }
+        op.push(sub_cmd, spec, out);
+        return;
+    }
+    if let Some(path) = parsed.value(&["--path"]) {
+        op.push(sub_cmd, Spec::path(path), out);
+        return;
+    }
+    let flag_version = parsed.value(&["--version", "--vers"]);
+    for name in crates {
+        let (name, version) = match name.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (name.as_str(), flag_version),
+        };
+        // A bare version without an operator installs exactly that version
+        let pinned = version.is_some_and(|v| {
+            EXACT_VERSION.is_match(v) && v.trim_start_matches(['=', 'v']).split('.').count() == 3
+        });
+        op.push(sub_cmd, Spec::registry(name, version, pinned), out);
+    }
+}
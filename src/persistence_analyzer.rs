@@ -0,0 +1,490 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::bash_analyzer::{CommandAnalysis, Finding, extract_paths_recursive};
+use crate::facts::Fact;
+
+/// Commands that detach, schedule or install something that outlives the
+/// session.
+pub(crate) const PERSISTENCE_COMMANDS: &[&str] = &[
+    "screen",
+    "tmux",
+    "crontab",
+    "at",
+    "batch",
+    "systemctl",
+    "systemd-run",
+    "launchctl",
+    "disown",
+];
+
+/// Home-relative files and directories read at login, shell start or boot.
+static HOME_STARTUP: LazyLock<Vec<(&'static str, Regex)>> = LazyLock::new(|| {
+    [
+        ("shell", r"^\.(bashrc|bash_profile|bash_login|bash_logout|profile|zshrc|zprofile|zshenv|zlogin|kshrc|cshrc|tcshrc|xinitrc|xprofile|xsessionrc)$"),
+        ("shell", r"^\.config/fish/(config\.fish$|conf\.d(/|$))"),
+        ("shell", r"^\.config/environment\.d(/|$)"),
+        ("autostart", r"^\.config/autostart(/|$)"),
+        ("systemd", r"^\.(config|local/share)/systemd/user(/|$)"),
+        ("launchd", r"^Library/LaunchAgents(/|$)"),
+        ("ssh", r"^\.ssh/(rc|authorized_keys2?)$"),
+    ]
+    .into_iter()
+    .map(|(kind, re)| (kind, Regex::new(re).unwrap()))
+    .collect()
+});
+
+/// System-wide files and directories read at login, shell start or boot.
+static SYSTEM_STARTUP: LazyLock<Vec<(&'static str, Regex)>> = LazyLock::new(|| {
+    [
+        (
+            "shell",
+            r"^/etc/(profile|bash\.bashrc|bashrc|zshrc|zshenv|zprofile|environment)$",
+        ),
+        ("shell", r"^/etc/(profile\.d|zsh)(/|$)"),
+        ("cron", r"^/etc/(crontab|anacrontab)$"),
+        ("cron", r"^/etc/cron\.[a-z]+(/|$)"),
+        ("cron", r"^/var/spool/cron(/|$)"),
+        ("systemd", r"^/(etc|lib|usr/lib)/systemd/(system|user)(/|$)"),
+        ("init", r"^/etc/(rc\.local|init\.d(/|$))"),
+        ("autostart", r"^/etc/xdg/autostart(/|$)"),
+        ("launchd", r"^/Library/(LaunchAgents|LaunchDaemons)(/|$)"),
+        ("preload", r"^/etc/ld\.so\.preload$"),
+    ]
+    .into_iter()
+    .map(|(kind, re)| (kind, Regex::new(re).unwrap()))
+    .collect()
+});
+
+/// Home directories spelled out in a path.
+static HOME_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(~|\$HOME|\$\{HOME\}|/root|/home/[^/]+|/Users/[^/]+)/").unwrap()
+});
+
+/// The kind of startup file `path` is (`shell`, `autostart`, `systemd`,
+/// `cron`, ...), or None for other paths. Relative paths are never startup
+/// files: the working directory is the project.
+pub fn startup_file(path: &str) -> Option<&'static str> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let relative = HOME_PREFIX
+        .find(path)
+        .map(|m| &path[m.end()..])
+        .or_else(|| {
+            path.strip_prefix(&format!("{home}/"))
+                .filter(|_| !home.is_empty())
+        });
+    match relative {
+        Some(rest) => HOME_STARTUP
+            .iter()
+            .find(|(_, re)| re.is_match(rest))
+            .map(|(kind, _)| *kind),
+        None => SYSTEM_STARTUP
+            .iter()
+            .find(|(_, re)| re.is_match(path))
+            .map(|(kind, _)| *kind),
+    }
+}
+
+/// A `persistence.startup-file` fact when `path` is a startup file written
+/// by `command`.
+pub fn startup_file_fact(command: &str, path: &str) -> Option<Fact> {
+    let kind = startup_file(path)?;
+    Some(
+        Fact::new("persistence.startup-file", command)
+            .with("path", path)
+            .with("kind", kind),
+    )
+}
+
+/// A `persistence.background` fact: `command` keeps running without the
+/// session waiting for it, started `via` `&`, `nohup`, `setsid`, `disown`
+/// or the Bash tool's `run_in_background`.
+pub fn background_fact(command: &str, via: &str) -> Fact {
+    Fact::new("persistence.background", command)
+        .with("via", via)
+        .with("command", command.trim())
+}
+
+/// What a `persistence.*` fact does, for messages: `runs in the background
+/// (via nohup)`, `writes the startup file '~/.bashrc'`, ...
+pub fn describe(fact: &Fact) -> String {
+    let attr = |key| fact.attr(key).unwrap_or_default();
+    match fact.name.as_str() {
+        "persistence.background" => format!("runs in the background (via {})", attr("via")),
+        "persistence.detach" => format!("starts a detached {} session", attr("tool")),
+        "persistence.schedule" => format!("schedules a job with {}", attr("tool")),
+        "persistence.service" => format!(
+            "starts or enables a service ({} {})",
+            attr("tool"),
+            attr("action")
+        ),
+        "persistence.startup-file" => format!("writes the startup file '{}'", attr("path")),
+        _ => "persists".to_string(),
+    }
+}
+
+/// Short options of a getopt-style command, with bundles (`-dmS name`) and
+/// attached values (`-Sname`) split apart, and the index of the first operand.
+struct ShortOptions {
+    flags: Vec<(char, Option<String>)>,
+    rest: usize,
+}
+
+impl ShortOptions {
+    fn has(&self, flag: char) -> bool {
+        self.flags.iter().any(|(f, _)| *f == flag)
+    }
+
+    fn value(&self, flag: char) -> Option<&str> {
+        self.flags
+            .iter()
+            .rev()
+            .find(|(f, _)| *f == flag)
+            .and_then(|(_, value)| value.as_deref())
+    }
+}
+
+/// `value_letters` take a value; `long_values` are whole words that take
+/// one (screen's `-Logfile`).
+fn short_options(args: &[String], value_letters: &str, long_values: &[&str]) -> ShortOptions {
+    let mut flags = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        i += 1;
+        if arg == "--" {
+            break;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            i -= 1;
+            break;
+        }
+        if long_values.contains(&arg.as_str()) || arg.starts_with("--") {
+            i += usize::from(long_values.contains(&arg.as_str()));
+            continue;
+        }
+        for (pos, flag) in arg.char_indices().skip(1) {
+            if value_letters.contains(flag) {
+                let attached = &arg[pos + flag.len_utf8()..];
+                let value = if attached.is_empty() {
+                    i += 1;
+                    args.get(i - 1).cloned()
+                } else {
+                    Some(attached.to_string())
+                };
+                flags.push((flag, value));
+                break;
+            }
+            flags.push((flag, None));
+        }
+    }
+    ShortOptions {
+        flags,
+        rest: i.min(args.len()),
+    }
+}
+
+/// Report commands that detach a session, schedule a job, start or enable
+/// a service, or disown a job. Commands they run are analysed too.
+pub(crate) fn extract_persistence(
+    cmd: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    match cmd {
+        "screen" => extract_screen(sub_cmd, args, out, depth),
+        "tmux" => extract_tmux(sub_cmd, args, out, depth),
+        "crontab" => extract_crontab(sub_cmd, args, out),
+        "at" | "batch" => extract_at(cmd, sub_cmd, args, out),
+        "systemctl" => extract_systemctl(sub_cmd, args, out),
+        "systemd-run" => extract_systemd_run(sub_cmd, args, out, depth),
+        "launchctl" => extract_launchctl(sub_cmd, args, out, depth),
+        "disown" => out
+            .findings
+            .push(Finding::Fact(background_fact(sub_cmd, "disown"))),
+        _ => {}
+    }
+}
+
+/// `screen -dmS name cmd…`: `-d -m` starts the session detached.
+fn extract_screen(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let options = short_options(args, "SctpehTX", &["-Logfile"]);
+    let command = &args[options.rest..];
+    if (options.has('d') || options.has('D')) && options.has('m') {
+        let mut fact = Fact::new("persistence.detach", sub_cmd)
+            .with("tool", "screen")
+            .with("command", command.join(" "));
+        if let Some(session) = options.value('S') {
+            fact = fact.with("session", session);
+        }
+        out.findings.push(Finding::Fact(fact));
+    }
+    if !command.is_empty() {
+        extract_paths_recursive(&command.join(" "), out, depth + 1);
+    }
+}
+
+/// `tmux new -d -s name cmd…`; windows and panes (`new-window`,
+/// `split-window`) run their command too.
+fn extract_tmux(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let global = short_options(args, "LSfcT", &[]);
+    if let Some(command) = global.value('c') {
+        extract_paths_recursive(command, out, depth + 1);
+    }
+    let Some(subcommand) = args.get(global.rest) else {
+        return;
+    };
+    let rest = &args[global.rest + 1..];
+    let options = short_options(rest, "sncteFxylfb", &[]);
+    let command = &rest[options.rest..];
+    match subcommand.as_str() {
+        "new" | "new-session" => {
+            if options.has('d') {
+                let mut fact = Fact::new("persistence.detach", sub_cmd)
+                    .with("tool", "tmux")
+                    .with("command", command.join(" "));
+                if let Some(session) = options.value('s') {
+                    fact = fact.with("session", session);
+                }
+                out.findings.push(Finding::Fact(fact));
+            }
+        }
+        "new-window" | "neww" | "split-window" | "splitw" | "respawn-pane" | "respawnp" => {}
+        _ => return,
+    }
+    if !command.is_empty() {
+        extract_paths_recursive(&command.join(" "), out, depth + 1);
+    }
+}
+
+/// `crontab file`, `crontab -` and `crontab -e` install a crontab; `-l`
+/// and `-r` only list or remove it.
+fn extract_crontab(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let options = short_options(args, "uT", &[]);
+    if options.has('l') || options.has('r') || options.has('T') {
+        return;
+    }
+    let file = match args.get(options.rest) {
+        Some(file) => file.as_str(),
+        None if options.has('e') => "editor",
+        None => "-",
+    };
+    let mut fact = Fact::new("persistence.schedule", sub_cmd)
+        .with("tool", "crontab")
+        .with("file", file);
+    if let Some(user) = options.value('u') {
+        fact = fact.with("user", user);
+    }
+    out.findings.push(Finding::Fact(fact));
+}
+
+/// `at [-f file] time`, `batch`: the job is read from stdin or `-f`.
+/// Options may follow the time (`at now + 1 minute -f job.sh`).
+fn extract_at(cmd: &str, sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let mut option_args = Vec::new();
+    let mut time = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg.starts_with('-') && arg.len() > 1 {
+            option_args.push(arg.clone());
+            if matches!(arg.as_str(), "-f" | "-q" | "-t") {
+                option_args.extend(args.get(i + 1).cloned());
+                i += 1;
+            }
+        } else {
+            time.push(arg.as_str());
+        }
+        i += 1;
+    }
+    let options = short_options(&option_args, "qft", &[]);
+    // Listing, removing and printing jobs
+    if ['l', 'd', 'r', 'c'].into_iter().any(|f| options.has(f)) {
+        return;
+    }
+    let mut fact = Fact::new("persistence.schedule", sub_cmd).with("tool", cmd);
+    let time = options
+        .value('t')
+        .map_or_else(|| time.join(" "), str::to_string);
+    if !time.is_empty() {
+        fact = fact.with("time", time);
+    }
+    fact = fact.with("file", options.value('f').unwrap_or("-"));
+    out.findings.push(Finding::Fact(fact));
+}
+
+/// systemctl operations that start a service or make one start later.
+const SYSTEMCTL_ACTIONS: &[&str] = &[
+    "enable",
+    "reenable",
+    "link",
+    "start",
+    "restart",
+    "add-wants",
+    "add-requires",
+];
+
+/// systemctl options that take a separate value.
+const SYSTEMCTL_VALUE_FLAGS: &[&str] = &[
+    "-M",
+    "--machine",
+    "-H",
+    "--host",
+    "--root",
+    "-t",
+    "--type",
+    "-p",
+    "--property",
+    "-s",
+    "--signal",
+    "--state",
+    "-o",
+    "--output",
+    "-n",
+    "--lines",
+];
+
+/// `systemctl [--user] enable [--now] unit…`
+fn extract_systemctl(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let mut positionals = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        i += 1;
+        if SYSTEMCTL_VALUE_FLAGS.contains(&arg.as_str()) {
+            i += 1;
+        } else if !arg.starts_with('-') {
+            positionals.push(arg.as_str());
+        }
+    }
+    let Some((action, units)) = positionals.split_first() else {
+        return;
+    };
+    if !SYSTEMCTL_ACTIONS.contains(action) {
+        return;
+    }
+    let fact = Fact::new("persistence.service", sub_cmd)
+        .with("tool", "systemctl")
+        .with("action", *action)
+        .with("user", args.iter().any(|a| a == "--user").to_string())
+        .with("now", args.iter().any(|a| a == "--now").to_string())
+        .with("units", units.join(","));
+    out.findings.push(Finding::Fact(fact));
+}
+
+/// systemd-run options that take a separate value.
+const SYSTEMD_RUN_VALUE_FLAGS: &[&str] = &[
+    "-u",
+    "--unit",
+    "-p",
+    "--property",
+    "--description",
+    "--slice",
+    "-E",
+    "--setenv",
+    "--on-active",
+    "--on-boot",
+    "--on-startup",
+    "--on-unit-active",
+    "--on-unit-inactive",
+    "--on-calendar",
+    "--on-clock-change",
+    "--on-timezone-change",
+    "--timer-property",
+    "-M",
+    "--machine",
+    "-H",
+    "--host",
+    "--working-directory",
+    "--uid",
+    "--gid",
+    "--nice",
+    "--path-property",
+    "--socket-property",
+    "--service-type",
+];
+
+/// `systemd-run [--user] [--on-calendar=…] cmd…` runs the command as a
+/// transient service or timer; `--scope`, `--wait` and `--pty` wait for it.
+fn extract_systemd_run(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let mut options = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        if arg == "--" {
+            i += 1;
+            break;
+        }
+        if !arg.starts_with('-') {
+            break;
+        }
+        let flag = arg.split_once('=').map_or(arg, |(flag, _)| flag);
+        options.push(flag);
+        i += if SYSTEMD_RUN_VALUE_FLAGS.contains(&arg) {
+            2
+        } else {
+            1
+        };
+    }
+    let command = &args[i.min(args.len())..];
+    let waits = [
+        "--scope", "--wait", "--pty", "-t", "--pipe", "-P", "--shell", "-S",
+    ];
+    if !options.iter().any(|o| waits.contains(o)) {
+        let timer = options.iter().any(|o| o.starts_with("--on-"));
+        let name = if timer {
+            "persistence.schedule"
+        } else {
+            "persistence.service"
+        };
+        let fact = Fact::new(name, sub_cmd)
+            .with("tool", "systemd-run")
+            .with("action", "run")
+            .with("user", options.contains(&"--user").to_string())
+            .with("command", command.join(" "));
+        out.findings.push(Finding::Fact(fact));
+    }
+    if !command.is_empty() {
+        extract_paths_recursive(&command.join(" "), out, depth + 1);
+    }
+}
+
+/// launchctl subcommands that load, enable or start a job.
+const LAUNCHCTL_ACTIONS: &[&str] = &[
+    "load",
+    "bootstrap",
+    "enable",
+    "submit",
+    "start",
+    "kickstart",
+];
+
+/// `launchctl load -w plist`, `launchctl submit -l label -- cmd…`
+fn extract_launchctl(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let Some(action) = args
+        .first()
+        .filter(|a| LAUNCHCTL_ACTIONS.contains(&a.as_str()))
+    else {
+        return;
+    };
+    let rest = &args[1..];
+    let operands: Vec<&str> = rest
+        .iter()
+        .take_while(|a| *a != "--")
+        .filter(|a| !a.starts_with('-'))
+        .map(String::as_str)
+        .collect();
+    let fact = Fact::new("persistence.service", sub_cmd)
+        .with("tool", "launchctl")
+        .with("action", action.as_str())
+        .with("units", operands.join(","));
+    out.findings.push(Finding::Fact(fact));
+    if action == "submit"
+        && let Some(pos) = rest.iter().position(|a| a == "--")
+        && pos + 1 < rest.len()
+    {
+        extract_paths_recursive(&rest[pos + 1..].join(" "), out, depth + 1);
+    }
+}
@@ -0,0 +1,89 @@
+use eyre::{Result, WrapErr, bail};
+use serde::Deserialize;
+
+use crate::facts::FactRule;
+
+/// A named, built-in bundle of rules.
+pub struct Preset {
+    pub name: &'static str,
+    /// The preset's YAML source, exactly as compiled into the binary.
+    pub source: &'static str,
+}
+
+/// All built-in presets, in display order.
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        name: "destructive",
+        source: include_str!("presets/destructive.yaml"),
+    },
+    Preset {
+        name: "privilege",
+        source: include_str!("presets/privilege.yaml"),
+    },
+    Preset {
+        name: "git-safety",
+        source: include_str!("presets/git-safety.yaml"),
+    },
+    Preset {
+        name: "disk-wipe",
+        source: include_str!("presets/disk-wipe.yaml"),
+    },
+    Preset {
+        name: "fork-bomb",
+        source: include_str!("presets/fork-bomb.yaml"),
+    },
+    Preset {
+        name: "shutdown",
+        source: include_str!("presets/shutdown.yaml"),
+    },
+    Preset {
+        name: "listeners",
+        source: include_str!("presets/listeners.yaml"),
+    },
+    Preset {
+        name: "database",
+        source: include_str!("presets/database.yaml"),
+    },
+    Preset {
+        name: "infra",
+        source: include_str!("presets/infra.yaml"),
+    },
+    Preset {
+        name: "packages",
+        source: include_str!("presets/packages.yaml"),
+    },
+    Preset {
+        name: "resources",
+        source: include_str!("presets/resources.yaml"),
+    },
+];
+
+/// The rules contained in a preset.
+#[derive(Deserialize, Debug, Default)]
+pub struct PresetRules {
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub commands_forbidden: Vec<String>,
+    #[serde(default)]
+    pub rules: Vec<FactRule>,
+}
+
+/// Look up a built-in preset by name.
+pub fn find(name: &str) -> Result<&'static Preset> {
+    match PRESETS.iter().find(|p| p.name == name) {
+        Some(preset) => Ok(preset),
+        None => {
+            let known: Vec<_> = PRESETS.iter().map(|p| p.name).collect();
+            bail!("unknown preset '{name}' (available: {})", known.join(", "))
+        }
+    }
+}
+
+impl Preset {
+    /// Parse the preset's rules.
+    pub fn rules(&self) -> Result<PresetRules> {
+        serde_yaml::from_str(self.source)
+            .wrap_err_with(|| format!("failed to parse built-in preset '{}'", self.name))
+    }
+}
@@ -0,0 +1,485 @@
+use crate::bash_analyzer::{
+    CommandAnalysis, ExtractedPath, Finding, PathContext, extract_paths_recursive, short_option,
+};
+use crate::facts::Fact;
+
+/// Remote shell and copy commands.
+pub(crate) const REMOTE_COMMANDS: &[&str] = &["ssh", "scp", "rsync"];
+
+/// ssh options that take a value (`-p 22` or `-p22`).
+const SSH_VALUE_FLAGS: &str = "BbcDEeFIiJLlmOopQRSWw";
+
+/// scp options that take a value.
+const SCP_VALUE_FLAGS: &str = "cDFiJloPSX";
+
+/// rsync short options that take a value.
+const RSYNC_SHORT_VALUE_FLAGS: &str = "efTBM";
+
+/// rsync long options that take a value when not written `--opt=value`.
+const RSYNC_VALUE_FLAGS: &[&str] = &[
+    "--rsh",
+    "--exclude",
+    "--include",
+    "--exclude-from",
+    "--include-from",
+    "--files-from",
+    "--filter",
+    "--log-file",
+    "--password-file",
+    "--partial-dir",
+    "--temp-dir",
+    "--backup-dir",
+    "--compare-dest",
+    "--link-dest",
+    "--copy-dest",
+    "--chmod",
+    "--chown",
+    "--bwlimit",
+    "--timeout",
+    "--port",
+    "--rsync-path",
+    "--max-size",
+    "--min-size",
+    "--block-size",
+    "--suffix",
+    "--out-format",
+    "--log-file-format",
+    "--info",
+    "--debug",
+    "--remote-option",
+    "--usermap",
+    "--groupmap",
+    "--iconv",
+    "--checksum-choice",
+    "--compress-choice",
+    "--compress-level",
+    "--sockopts",
+    "--address",
+    "--protocol",
+    "--max-delete",
+    "--modify-window",
+    "--contimeout",
+    "--stop-after",
+    "--stop-at",
+    "--write-batch",
+    "--read-batch",
+    "--only-write-batch",
+    "--outbuf",
+    "--skip-compress",
+];
+
+/// rsync options whose value is a local file it reads.
+const RSYNC_READ_FILES: &[&str] = &[
+    "--exclude-from",
+    "--include-from",
+    "--files-from",
+    "--password-file",
+    "--read-batch",
+];
+
+/// rsync options whose value is a local file it writes.
+const RSYNC_WRITE_FILES: &[&str] = &["--log-file", "--write-batch", "--only-write-batch"];
+
+/// A `[user@]host:path` operand.
+struct RemoteSpec {
+    user: Option<String>,
+    host: String,
+    path: String,
+}
+
+pub(crate) fn extract_remote(
+    cmd: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    match cmd {
+        "ssh" => extract_ssh(sub_cmd, args, out, depth),
+        "scp" => extract_scp(sub_cmd, args, out),
+        "rsync" => extract_rsync(sub_cmd, args, out),
+        _ => {}
+    }
+}
+
+/// An ssh command line split into the options clarg looks at, the
+/// destination and the remote command.
+#[derive(Default)]
+struct SshArgs {
+    login: Option<String>,
+    port: Option<String>,
+    jumps: Vec<String>,
+    host_name: Option<String>,
+    proxy_command: Option<String>,
+    /// (path, is_write) for the identity, config and log files.
+    files: Vec<(String, bool)>,
+    destination: Option<String>,
+    command: Vec<String>,
+}
+
+/// `ssh [options] [user@]host [command]` — the remote command is analysed
+/// for findings; its paths are on the remote host, so they're dropped. A
+/// `-o ProxyCommand` runs locally and is analysed like any other command.
+fn extract_ssh(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let parsed = parse_ssh(args);
+    for (path, write) in &parsed.files {
+        push_local(out, path, *write);
+    }
+    let Some(destination) = &parsed.destination else {
+        return;
+    };
+    let destination = destination.strip_prefix("ssh://").unwrap_or(destination);
+    let (user, host_port) = parse_host(destination);
+    let (host, uri_port) = split_port(&host_port);
+    // `HostName` is where ssh connects; the destination is only its alias
+    let host = parsed
+        .host_name
+        .map_or(host.clone(), |name| name.replace("%h", &host));
+    let user = user.or(parsed.login);
+    let port = parsed.port.or(uri_port);
+    let command = &parsed.command;
+
+    let mut fact = Fact::new(
+        if command.is_empty() {
+            "remote.shell"
+        } else {
+            "remote.exec"
+        },
+        sub_cmd,
+    )
+    .with("tool", "ssh")
+    .with("host", host.as_str());
+    if let Some(user) = user {
+        fact = fact.with("user", user);
+    }
+    if let Some(port) = &port {
+        fact = fact.with("port", port);
+    }
+    if !parsed.jumps.is_empty() {
+        fact = fact.with("jump", parsed.jumps.join(","));
+    }
+    if let Some(proxy) = parsed.proxy_command {
+        let proxy = proxy
+            .replace("%h", &host)
+            .replace("%p", port.as_deref().unwrap_or("22"));
+        let mut inner = CommandAnalysis::default();
+        extract_paths_recursive(&proxy, &mut inner, depth + 1);
+        let hosts: Vec<&str> = inner
+            .findings
+            .iter()
+            .filter_map(|f| match f {
+                Finding::Fact(fact) => fact.attr("host"),
+                _ => None,
+            })
+            .collect();
+        // A proxy that names no host still routes the connection somewhere
+        let via = if hosts.is_empty() {
+            proxy.clone()
+        } else {
+            hosts.join(",")
+        };
+        fact = fact
+            .with("proxy", via)
+            .with("proxy_command", proxy.as_str());
+        out.paths.extend(inner.paths);
+        out.findings.extend(inner.findings);
+    }
+    if !command.is_empty() {
+        // ssh joins its arguments with spaces and hands them to the remote shell
+        let remote = command.join(" ");
+        fact = fact.with("command", remote.as_str());
+        let mut inner = CommandAnalysis::default();
+        extract_paths_recursive(&remote, &mut inner, depth + 1);
+        out.findings.extend(inner.findings);
+    }
+    out.findings.push(Finding::Fact(fact));
+}
+
+/// The commands an ssh invocation runs: the remote command and a local
+/// `-o ProxyCommand`.
+pub(crate) fn ssh_commands(args: &[String]) -> Vec<String> {
+    let parsed = parse_ssh(args);
+    if parsed.destination.is_none() {
+        return Vec::new();
+    }
+    let mut commands: Vec<String> = parsed.proxy_command.into_iter().collect();
+    if !parsed.command.is_empty() {
+        commands.push(parsed.command.join(" "));
+    }
+    commands
+}
+
+/// Parse ssh's options up to the destination.
+fn parse_ssh(args: &[String]) -> SshArgs {
+    let mut parsed = SshArgs::default();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if !arg.starts_with('-') || arg == "-" {
+            break;
+        }
+        if arg == "--" {
+            i += 1;
+            break;
+        }
+        if let Some((flag, value, consumed_next)) =
+            short_option(arg, args.get(i + 1), SSH_VALUE_FLAGS)
+        {
+            match flag {
+                'l' => parsed.login = Some(value),
+                'p' => parsed.port = Some(value),
+                'J' => parsed
+                    .jumps
+                    .extend(value.split(',').map(|j| parse_host(j).1)),
+                'i' | 'F' => parsed.files.push((value, false)),
+                'E' => parsed.files.push((value, true)),
+                'o' => match ssh_config_option(&value) {
+                    Some((key, value)) if key == "proxyjump" && value != "none" => {
+                        parsed
+                            .jumps
+                            .extend(value.split(',').map(|j| parse_host(j).1));
+                    }
+                    // ssh keeps the first value it is given for each keyword
+                    Some((key, value)) if key == "hostname" => {
+                        parsed.host_name.get_or_insert(value);
+                    }
+                    Some((key, value)) if key == "proxycommand" && value != "none" => {
+                        parsed.proxy_command.get_or_insert(value);
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+            if consumed_next {
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+    parsed.destination = args.get(i).cloned();
+    parsed.command = args.get(i + 1..).unwrap_or_default().to_vec();
+    parsed
+}
+
+/// A `-o Key=Value` or `-o "Key Value"` ssh option as its lowercased keyword
+/// and value.
+fn ssh_config_option(option: &str) -> Option<(String, String)> {
+    let (key, value) = option.split_once(|c: char| c == '=' || c.is_whitespace())?;
+    let value = value.trim_start_matches(|c: char| c == '=' || c.is_whitespace());
+    Some((key.to_ascii_lowercase(), value.to_string()))
+}
+
+/// `scp [options] source... target`
+fn extract_scp(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let mut operands = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            operands.extend(args[i + 1..].iter().cloned());
+            break;
+        }
+        if arg.starts_with('-') && arg != "-" {
+            if let Some((flag, value, consumed_next)) =
+                short_option(arg, args.get(i + 1), SCP_VALUE_FLAGS)
+            {
+                if matches!(flag, 'i' | 'F') {
+                    push_local(out, &value, false);
+                }
+                if consumed_next {
+                    i += 1;
+                }
+            }
+        } else {
+            operands.push(arg.clone());
+        }
+        i += 1;
+    }
+    transfer(sub_cmd, "scp", &operands, false, out);
+}
+
+/// `rsync [options] source... [dest]`
+fn extract_rsync(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let mut operands = Vec::new();
+    let mut delete = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            operands.extend(args[i + 1..].iter().cloned());
+            break;
+        }
+        if arg.starts_with("--") {
+            let (flag, value) = match arg.split_once('=') {
+                Some((f, v)) => (f, Some(v.to_string())),
+                None if RSYNC_VALUE_FLAGS.contains(&arg.as_str()) => {
+                    i += 1;
+                    (arg.as_str(), args.get(i).cloned())
+                }
+                None => (arg.as_str(), None),
+            };
+            if flag.starts_with("--delete") || flag == "--remove-source-files" {
+                delete = true;
+            }
+            if let Some(value) = value {
+                if RSYNC_READ_FILES.contains(&flag) {
+                    push_local(out, &value, false);
+                } else if RSYNC_WRITE_FILES.contains(&flag) {
+                    push_local(out, &value, true);
+                }
+            }
+        } else if arg.starts_with('-') && arg != "-" {
+            if let Some((_, _, true)) = short_option(arg, args.get(i + 1), RSYNC_SHORT_VALUE_FLAGS)
+            {
+                i += 1;
+            }
+        } else {
+            operands.push(arg.clone());
+        }
+        i += 1;
+    }
+    transfer(sub_cmd, "rsync", &operands, delete, out);
+}
+
+/// Classify the operands of a copy: the last is the destination, the rest
+/// are sources. Remote sources are downloads, a remote destination is an
+/// upload, and all-local copies read their sources and write the destination.
+fn transfer(
+    sub_cmd: &str,
+    tool: &str,
+    operands: &[String],
+    delete: bool,
+    out: &mut CommandAnalysis,
+) {
+    let [sources @ .., dest] = operands else {
+        return;
+    };
+    // rsync with a single operand lists the source
+    if sources.is_empty() {
+        if let Some(remote) = parse_remote(dest) {
+            out.findings.push(Finding::Fact(remote_fact(
+                "remote.download",
+                sub_cmd,
+                tool,
+                &remote,
+            )));
+        } else {
+            push_local(out, dest, false);
+        }
+        return;
+    }
+
+    let dest_remote = parse_remote(dest);
+    for source in sources {
+        match (parse_remote(source), &dest_remote) {
+            (Some(remote), _) => {
+                let mut fact = remote_fact("remote.download", sub_cmd, tool, &remote);
+                if delete {
+                    fact = fact.with("delete", "true");
+                }
+                out.findings.push(Finding::Fact(fact));
+            }
+            (None, Some(_)) => out.paths.push(ExtractedPath {
+                raw: source.clone(),
+                context: PathContext::RemoteTransfer { upload: true },
+            }),
+            (None, None) => push_local(out, source, false),
+        }
+    }
+    match dest_remote {
+        Some(remote) => {
+            let mut fact = remote_fact("remote.upload", sub_cmd, tool, &remote);
+            let local: Vec<_> = sources
+                .iter()
+                .filter(|s| parse_remote(s).is_none())
+                .cloned()
+                .collect();
+            if !local.is_empty() {
+                fact = fact.with("local", local.join(" "));
+            }
+            if delete {
+                fact = fact.with("delete", "true");
+            }
+            out.findings.push(Finding::Fact(fact));
+        }
+        None if sources.iter().any(|s| parse_remote(s).is_some()) => {
+            out.paths.push(ExtractedPath {
+                raw: dest.clone(),
+                context: PathContext::RemoteTransfer { upload: false },
+            })
+        }
+        None => push_local(out, dest, true),
+    }
+}
+
+fn remote_fact(name: &str, sub_cmd: &str, tool: &str, remote: &RemoteSpec) -> Fact {
+    let mut fact = Fact::new(name, sub_cmd)
+        .with("tool", tool)
+        .with("host", remote.host.as_str())
+        .with("path", remote.path.as_str());
+    if let Some(user) = &remote.user {
+        fact = fact.with("user", user.as_str());
+    }
+    fact
+}
+
+/// Parse `[user@]host:path`, `host::module/path`, `scp://[user@]host[:port]/path`
+/// and `rsync://[user@]host[:port]/module/path`. Local paths return None.
+fn parse_remote(operand: &str) -> Option<RemoteSpec> {
+    for scheme in ["scp://", "rsync://", "ssh://"] {
+        if let Some(rest) = operand.strip_prefix(scheme) {
+            let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+            let (user, host_port) = parse_host(authority);
+            return Some(RemoteSpec {
+                user,
+                host: split_port(&host_port).0,
+                path: format!("/{path}"),
+            });
+        }
+    }
+    // IPv6 literals are bracketed, so look for the colon after them: [::1]:/path
+    let search_from = operand.find(']').map_or(0, |close| close + 1);
+    let colon = search_from + operand[search_from..].find(':')?;
+    // A slash before the colon makes it a local path (`./a:b`, `/tmp/x:y`)
+    if operand[..colon].contains('/') || colon == 0 {
+        return None;
+    }
+    let (user, host) = parse_host(&operand[..colon]);
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let path = operand[colon + 1..].trim_start_matches(':').to_string();
+    Some(RemoteSpec { user, host, path })
+}
+
+/// Split `user@host` into its parts.
+fn parse_host(spec: &str) -> (Option<String>, String) {
+    match spec.rsplit_once('@') {
+        Some((user, host)) => (Some(user.to_string()), host.to_string()),
+        None => (None, spec.to_string()),
+    }
+}
+
+/// Split `host:port` (from an ssh:// URI) into its parts.
+fn split_port(host: &str) -> (String, Option<String>) {
+    match host.rsplit_once(':') {
+        Some((h, p)) if !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()) => (
+            h.trim_start_matches('[').trim_end_matches(']').to_string(),
+            Some(p.to_string()),
+        ),
+        _ => (host.to_string(), None),
+    }
+}
+
+/// A local file named by a transfer operand or an option (`-i key`, `--log-file`).
+fn push_local(out: &mut CommandAnalysis, raw: &str, write: bool) {
+    out.paths.push(ExtractedPath {
+        raw: raw.to_string(),
+        context: if write {
+            PathContext::FileCommandWrite
+        } else {
+            PathContext::FileCommandArg
+        },
+    });
+}
@@ -0,0 +1,333 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::bash_analyzer::{CommandAnalysis, Finding, REDIRECT_RE, unquoted_captures};
+use crate::facts::Fact;
+use crate::resources::parse_size;
+
+/// Commands that can fill the disk or load the machine, besides `dd`,
+/// `cat` and `head`, which have their own arms.
+pub(crate) const RESOURCE_COMMANDS: &[&str] =
+    &["fallocate", "truncate", "yes", "stress", "stress-ng"];
+
+/// Devices that never run out of data.
+const ENDLESS_SOURCES: &[&str] = &["/dev/zero", "/dev/urandom", "/dev/random"];
+
+/// A loop whose condition never changes: `while true`, `while :`,
+/// `until false`, `for ((;;))`.
+static LOOP_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?s)\b(?:(while|until)\s+(true|false|:|\[\s*1\s*\]|\(\(\s*1\s*\)\))|for\s*\(\(\s*;\s*;\s*\)\))\s*;?\s*do\b(.*?)\bdone\b",
+    )
+    .unwrap()
+});
+
+/// Commands in a loop body that pace it.
+static PACING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(sleep|wait|read|timeout|inotifywait)\b").unwrap());
+
+/// Commands in a loop body that end it.
+static EXIT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(break|exit|return)\b").unwrap());
+
+/// Record a fork bomb: a function whose body pipes into or backgrounds
+/// calls to itself (`:(){ :|:& };:`), so every call starts two more.
+pub(crate) fn extract_function(
+    name: &str,
+    body: &str,
+    definition: &str,
+    out: &mut CommandAnalysis,
+) {
+    let calls_itself = body
+        .split(|c: char| c.is_whitespace() || "|&;".contains(c))
+        .any(|w| w == name);
+    if calls_itself && (body.contains('|') || body.contains('&')) {
+        out.findings.push(Finding::Fact(
+            Fact::new("resource.fork-bomb", definition.trim()).with("function", name),
+        ));
+    }
+}
+
+/// Record endless loops in `command`, before it is split into sub-commands
+/// and the loop is lost.
+pub(crate) fn scan_loops(command: &str, out: &mut CommandAnalysis) {
+    for cap in unquoted_captures(&LOOP_RE, command) {
+        let condition = match (
+            cap.get(1).map(|m| m.as_str()),
+            cap.get(2).map(|m| m.as_str()),
+        ) {
+            (Some("while"), Some("false")) => continue,
+            (Some("until"), Some(c)) if c != "false" => continue,
+            (Some(keyword), Some(c)) => format!("{keyword} {c}"),
+            _ => "for ((;;))".to_string(),
+        };
+        let body = cap[3].trim().trim_start_matches(';').trim();
+        out.findings.push(Finding::Fact(
+            Fact::new("resource.loop", command.trim())
+                .with("condition", condition)
+                .with("sleeps", PACING_RE.is_match(body).to_string())
+                .with("breaks", EXIT_RE.is_match(body).to_string())
+                .with("body", body),
+        ));
+    }
+}
+
+/// Record what `cmd args…` writes or loads: `resource.disk-fill` for dd,
+/// fallocate, truncate and endless sources redirected to a file, and
+/// `resource.stress` for stress tools.
+pub(crate) fn extract_resource(
+    cmd: &str,
+    sub_cmd: &str,
+    args: &[String],
+    out: &mut CommandAnalysis,
+) {
+    match cmd {
+        "dd" => extract_dd(sub_cmd, args, out),
+        "fallocate" => extract_fallocate(sub_cmd, args, out),
+        "truncate" => extract_truncate(sub_cmd, args, out),
+        "yes" => {
+            if let Some(path) = stdout_file(sub_cmd) {
+                out.findings
+                    .push(Finding::Fact(disk_fill(sub_cmd, "yes", &path, None)));
+            }
+        }
+        "cat" => {
+            if let Some(source) = args.iter().find(|a| ENDLESS_SOURCES.contains(&a.as_str()))
+                && let Some(path) = stdout_file(sub_cmd)
+            {
+                let fact = disk_fill(sub_cmd, "cat", &path, None).with("source", source);
+                out.findings.push(Finding::Fact(fact));
+            }
+        }
+        "head" => extract_head(sub_cmd, args, out),
+        "stress" | "stress-ng" => extract_stress(cmd, sub_cmd, args, out),
+        _ => {}
+    }
+}
+
+/// `dd if=/dev/zero of=x bs=1G count=100` writes count × ibs bytes after
+/// skipping seek × obs; with an endless input and no count it never stops.
+fn extract_dd(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let operand = |key: &str| {
+        args.iter()
+            .filter_map(|a| a.split_once('='))
+            .filter(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+            .next_back()
+    };
+    let Some(path) = operand("of")
+        .map(str::to_string)
+        .or_else(|| stdout_file(sub_cmd))
+    else {
+        return;
+    };
+    if path.starts_with("/dev/") {
+        return;
+    }
+    let block = operand("bs").and_then(parse_size);
+    let ibs = block
+        .or_else(|| operand("ibs").and_then(parse_size))
+        .unwrap_or(512);
+    let obs = block
+        .or_else(|| operand("obs").and_then(parse_size))
+        .unwrap_or(512);
+    let flag =
+        |key: &str, value: &str| operand(key).is_some_and(|f| f.split(',').any(|f| f == value));
+    let source = operand("if").unwrap_or("-");
+    let size = match operand("count") {
+        Some(count) => {
+            let Some(count) = parse_size(count) else {
+                return;
+            };
+            let count = if flag("iflag", "count_bytes") {
+                count
+            } else {
+                count.saturating_mul(ibs)
+            };
+            let seek = operand("seek")
+                .or_else(|| operand("oseek"))
+                .and_then(parse_size)
+                .unwrap_or(0);
+            let seek = if flag("oflag", "seek_bytes") {
+                seek
+            } else {
+                seek.saturating_mul(obs)
+            };
+            Some(count.saturating_add(seek))
+        }
+        None if ENDLESS_SOURCES.contains(&source) => None,
+        None => return,
+    };
+    out.findings.push(Finding::Fact(
+        disk_fill(sub_cmd, "dd", &path, size).with("source", source),
+    ));
+}
+
+/// `fallocate -l 500G file` reserves offset + length bytes.
+fn extract_fallocate(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let options = sized_options(args, &[('l', "--length"), ('o', "--offset")]);
+    let length = options.value('l').and_then(parse_size);
+    let offset = options.value('o').and_then(parse_size).unwrap_or(0);
+    if let (Some(length), Some(path)) = (length, options.operands.last()) {
+        out.findings.push(Finding::Fact(disk_fill(
+            sub_cmd,
+            "fallocate",
+            path,
+            Some(length.saturating_add(offset)),
+        )));
+    }
+}
+
+/// `truncate -s 100G file…` sets each file's size; `+` grows it by that
+/// much, while `-`, `<` and `/` only shrink it.
+fn extract_truncate(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let options = sized_options(args, &[('s', "--size"), ('r', "--reference")]);
+    let Some(size) = options.value('s') else {
+        return;
+    };
+    if size.starts_with(['-', '<', '/']) {
+        return;
+    }
+    let Some(size) = parse_size(size.trim_start_matches(['+', '>', '%'])) else {
+        return;
+    };
+    for path in &options.operands {
+        out.findings.push(Finding::Fact(disk_fill(
+            sub_cmd,
+            "truncate",
+            path,
+            Some(size),
+        )));
+    }
+}
+
+/// `head -c 10G /dev/zero > file` writes that many bytes of an endless
+/// source.
+fn extract_head(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let options = sized_options(args, &[('c', "--bytes"), ('n', "--lines")]);
+    let Some(source) = options
+        .operands
+        .iter()
+        .find(|a| ENDLESS_SOURCES.contains(&a.as_str()))
+    else {
+        return;
+    };
+    let Some(path) = stdout_file(sub_cmd) else {
+        return;
+    };
+    if let Some(size) = options.value('c').and_then(parse_size) {
+        let fact = disk_fill(sub_cmd, "head", &path, Some(size)).with("source", source);
+        out.findings.push(Finding::Fact(fact));
+    }
+}
+
+/// `stress --cpu 8 --vm 2 --timeout 60s`, `stress-ng -c 0 -t 10`.
+fn extract_stress(cmd: &str, sub_cmd: &str, args: &[String], out: &mut CommandAnalysis) {
+    let options = sized_options(
+        args,
+        &[
+            ('c', "--cpu"),
+            ('i', "--io"),
+            ('m', "--vm"),
+            ('d', "--hdd"),
+            ('t', "--timeout"),
+        ],
+    );
+    let mut fact = Fact::new("resource.stress", sub_cmd).with("tool", cmd);
+    for (letter, name) in [
+        ('c', "cpu"),
+        ('i', "io"),
+        ('m', "vm"),
+        ('d', "hdd"),
+        ('t', "timeout"),
+    ] {
+        if let Some(value) = options.value(letter) {
+            fact = fact.with(name, value);
+        }
+    }
+    out.findings.push(Finding::Fact(fact));
+}
+
+fn disk_fill(sub_cmd: &str, tool: &str, path: &str, size: Option<u64>) -> Fact {
+    let fact = Fact::new("resource.disk-fill", sub_cmd)
+        .with("tool", tool)
+        .with("path", path)
+        .with("unbounded", size.is_none().to_string());
+    match size {
+        Some(size) => fact.with("size", size.to_string()),
+        None => fact,
+    }
+}
+
+/// The file stdout is redirected to, if any: `> f`, `>> f`, `1> f`, `&> f`.
+fn stdout_file(sub_cmd: &str) -> Option<String> {
+    unquoted_captures(&REDIRECT_RE, sub_cmd)
+        .into_iter()
+        .filter(|cap| {
+            let op = cap.get(0).unwrap().as_str();
+            !op.starts_with(|c: char| c.is_ascii_digit()) || op.starts_with("1>")
+        })
+        .map(|cap| cap[1].to_string())
+        .next_back()
+        .filter(|target| !target.starts_with("/dev/") && !target.starts_with('&'))
+}
+
+/// Options that take a value, by short letter, and the operands.
+struct SizedOptions {
+    values: Vec<(char, String)>,
+    operands: Vec<String>,
+}
+
+impl SizedOptions {
+    fn value(&self, letter: char) -> Option<&str> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(l, _)| *l == letter)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Parse `-l 5G`, `-l5G`, `--length 5G` and `--length=5G` for each
+/// `(letter, long name)` in `valued`; other options are flags.
+fn sized_options(args: &[String], valued: &[(char, &str)]) -> SizedOptions {
+    let mut options = SizedOptions {
+        values: Vec::new(),
+        operands: Vec::new(),
+    };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            options.operands.extend(iter.by_ref().cloned());
+        } else if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            if let Some((letter, _)) = valued
+                .iter()
+                .find(|(_, l)| l.strip_prefix("--") == Some(name))
+                && let Some(value) = inline.or_else(|| iter.next().cloned())
+            {
+                options.values.push((*letter, value));
+            }
+        } else if let Some(bundle) = arg.strip_prefix('-').filter(|b| !b.is_empty()) {
+            for (pos, letter) in bundle.char_indices() {
+                if valued.iter().any(|(l, _)| *l == letter) {
+                    let rest = &bundle[pos + letter.len_utf8()..];
+                    let value = if rest.is_empty() {
+                        iter.next().cloned()
+                    } else {
+                        Some(rest.to_string())
+                    };
+                    if let Some(value) = value {
+                        options.values.push((letter, value));
+                    }
+                    break;
+                }
+            }
+        } else {
+            options.operands.push(arg.clone());
+        }
+    }
+    options
+}
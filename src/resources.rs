@@ -0,0 +1,116 @@
+use eyre::{Result, eyre};
+use serde::Deserialize;
+
+use crate::facts::Fact;
+use crate::util::truncate;
+
+/// Limits on what a command may consume, as written in YAML:
+///
+/// ```yaml
+/// resources:
+///   max_write_size: 10G
+/// ```
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ResourcePolicy {
+    /// Largest file a single `dd`, `fallocate`, `truncate`, `head -c` or
+    /// `yes > file` may write, with a size suffix (`512M`, `10G`, `1TB`).
+    #[serde(default)]
+    pub max_write_size: Option<String>,
+}
+
+impl ResourcePolicy {
+    pub fn is_empty(&self) -> bool {
+        self.max_write_size.is_none()
+    }
+}
+
+/// Checks `resource.disk-fill` facts against a [`ResourcePolicy`].
+pub struct ResourceRule {
+    max_write_size: u64,
+}
+
+impl ResourceRule {
+    pub fn new(policy: &ResourcePolicy) -> Result<Self> {
+        let raw = policy.max_write_size.as_deref().unwrap_or_default();
+        let max_write_size =
+            parse_size(raw).ok_or_else(|| eyre!("invalid max_write_size: {raw}"))?;
+        Ok(Self { max_write_size })
+    }
+
+    /// Returns Some(reason) when a `resource.disk-fill` fact writes more than
+    /// the limit, or writes until the disk is full.
+    pub fn check(&self, fact: &Fact) -> Option<String> {
+        if fact.name != "resource.disk-fill" {
+            return None;
+        }
+        let path = fact.attr("path").unwrap_or_default();
+        let size = fact.attr("size").and_then(|s| s.parse::<u64>().ok());
+        let what = match size {
+            Some(size) if size > self.max_write_size => {
+                format!("writes {} to '{path}'", format_size(size))
+            }
+            None if fact.attr("unbounded") == Some("true") => {
+                format!("writes to '{path}' until the disk is full")
+            }
+            _ => return None,
+        };
+        Some(format!(
+            "Blocked by `clarg`: '{}' {}, over the {} write limit",
+            truncate(&fact.command, 100),
+            what,
+            format_size(self.max_write_size)
+        ))
+    }
+}
+
+/// Parse a size with an optional suffix, as dd, fallocate and truncate
+/// take them: `K`/`KiB` are 1024, `KB` is 1000, and so on up to `E`; dd's
+/// `c`, `w` and `b` are 1, 2 and 512 bytes, and `2x512K` multiplies.
+pub fn parse_size(raw: &str) -> Option<u64> {
+    raw.split('x').try_fold(1u64, |total, factor| {
+        let factor = factor.trim();
+        let digits = factor
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(factor.len());
+        if digits == 0 {
+            return None;
+        }
+        // Numbers too big for u64 are still sizes, just huge ones
+        let number = factor[..digits].parse().unwrap_or(u64::MAX);
+        let multiplier = match &factor[digits..] {
+            "" | "c" => 1,
+            "w" => 2,
+            "b" => 512,
+            suffix => {
+                let (unit, rest) = suffix.split_at(1);
+                let power = "KMGTPE".find(&unit.to_ascii_uppercase())? as u32 + 1;
+                let base: u64 = match rest {
+                    "" | "iB" => 1024,
+                    "B" => 1000,
+                    _ => return None,
+                };
+                base.checked_pow(power)?
+            }
+        };
+        Some(total.saturating_mul(number.saturating_mul(multiplier)))
+    })
+}
+
+/// A byte count for messages: `100 GiB`, `512 MiB`, `300 bytes`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    let mut unit = None;
+    let mut value = bytes as f64;
+    for name in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = Some(name);
+    }
+    match unit {
+        None => format!("{bytes} bytes"),
+        Some(unit) if value.fract() == 0.0 => format!("{value} {unit}"),
+        Some(unit) => format!("{value:.1} {unit}"),
+    }
+}
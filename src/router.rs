@@ -1,18 +1,49 @@
 use eyre::Result;
+use std::io::Read;
 use std::path::Path;
 
-use crate::bash_analyzer::{self, PathContext};
+use crate::archive_members::{self, Link, member_target};
+use crate::bash_analyzer::{self, Finding, PathContext};
 use crate::blocked_commands::BlockedCommandsRule;
 use crate::blocked_files::BlockedFilesRule;
-use crate::config::Config;
+use crate::config::{Config, RuleAction};
+use crate::data_flow::{self, FlowSource};
+use crate::database_analyzer;
+use crate::databases::DatabaseRule;
+use crate::facts::{Fact, FactRules};
+use crate::git_internals::GitInternalsRule;
 use crate::hook_input::HookInput;
-use crate::internalonly::{check_path_containment, resolve_project_root, resolve_target};
+use crate::hosts::HostRule;
+use crate::internalonly::{
+    check_path_containment, normalize_path, resolve_project_root, resolve_target,
+};
+use crate::network_analyzer::parse_url;
+use crate::persistence_analyzer;
+use crate::presets;
+use crate::resources::ResourceRule;
 use crate::util::truncate;
+use crate::web::WebRule;
+
+/// Bytes of a SQL script read before the rest is ignored.
+const MAX_SCRIPT_BYTES: u64 = 1024 * 1024;
 
 #[derive(Debug)]
 pub enum Verdict {
     Allow,
     Deny(String),
+    /// Let the user decide, with the reason shown in the permission prompt.
+    Ask(String),
+}
+
+impl RuleAction {
+    /// The verdict for a matching rule, or None when the rule only allows.
+    fn verdict(self, reason: String) -> Option<Verdict> {
+        match self {
+            RuleAction::Allow => None,
+            RuleAction::Ask => Some(Verdict::Ask(reason)),
+            RuleAction::Deny => Some(Verdict::Deny(reason)),
+        }
+    }
 }
 
 pub struct RuleSet {
@@ -21,13 +52,24 @@ pub struct RuleSet {
     internal_access_only: bool,
     blocked_files: Option<BlockedFilesRule>,
     blocked_commands: Option<BlockedCommandsRule>,
+    git_internals: Option<GitInternalsRule>,
+    inspect_archives: bool,
+    fact_rules: FactRules,
+    hosts: Option<HostRule>,
+    databases: Option<DatabaseRule>,
+    resources: Option<ResourceRule>,
+    web: WebRule,
+    pipe_to_shell: RuleAction,
+    secret_flow: RuleAction,
+    persistence: RuleAction,
+    obfuscation_threshold: u32,
+    strict: bool,
 }
 
 impl RuleSet {
     pub fn build(config: &Config, raw_project_root: &Path) -> Result<Self> {
         // Canonicalize the project root when any filesystem rule needs it.
-        let needs_canonical =
-            config.internal_access_only || !config.block_access_to.is_empty();
+        let needs_canonical = config.internal_access_only || !config.block_access_to.is_empty();
         let project_root = if needs_canonical {
             resolve_project_root(raw_project_root)?
         } else {
@@ -43,8 +85,40 @@ impl RuleSet {
             None
         };
 
-        let blocked_commands = if !config.commands_forbidden.is_empty() {
-            Some(BlockedCommandsRule::new(&config.commands_forbidden)?)
+        // Preset rules apply alongside the user's own. User fact rules come
+        // first so they can override a preset's action.
+        let mut commands_forbidden = config.commands_forbidden.clone();
+        let mut fact_rules = config.rules.clone();
+        for name in &config.presets {
+            let rules = presets::find(name)?.rules()?;
+            commands_forbidden.extend(rules.commands_forbidden);
+            fact_rules.extend(rules.rules);
+        }
+
+        let blocked_commands = if !commands_forbidden.is_empty() {
+            Some(BlockedCommandsRule::new(&commands_forbidden)?)
+        } else {
+            None
+        };
+
+        let git_internals = config
+            .protect_git
+            .then(|| GitInternalsRule::new(&project_root));
+
+        let hosts = if !config.hosts.is_empty() {
+            Some(HostRule::new(&config.hosts)?)
+        } else {
+            None
+        };
+
+        let databases = if !config.databases.is_empty() {
+            Some(DatabaseRule::new(&config.databases)?)
+        } else {
+            None
+        };
+
+        let resources = if !config.resources.is_empty() {
+            Some(ResourceRule::new(&config.resources)?)
         } else {
             None
         };
@@ -54,6 +128,18 @@ impl RuleSet {
             internal_access_only: config.internal_access_only,
             blocked_files,
             blocked_commands,
+            git_internals,
+            inspect_archives: config.inspect_archives,
+            fact_rules: FactRules::new(&fact_rules)?,
+            hosts,
+            databases,
+            resources,
+            web: WebRule::new(&config.web)?,
+            pipe_to_shell: config.pipe_to_shell,
+            secret_flow: config.secret_flow,
+            persistence: config.persistence,
+            obfuscation_threshold: config.obfuscation_threshold,
+            strict: config.strict,
         })
     }
 
@@ -63,21 +149,25 @@ impl RuleSet {
             "bash" => self.evaluate_bash(input),
             "read" | "write" | "edit" | "notebookedit" => {
                 let path = input.file_path().or_else(|| input.notebook_path());
+                let write = tool_name_lower != "read";
                 match path {
-                    Some(p) => self.evaluate_path_tool(p),
+                    Some(p) => self.evaluate_path_tool(p, write),
                     None => Verdict::Allow,
                 }
             }
             "glob" | "grep" => match input.search_path() {
-                Some(p) => self.evaluate_path_tool(p),
+                Some(p) => self.evaluate_path_tool(p, false),
                 None => Verdict::Allow,
             },
+            "webfetch" => match input.url() {
+                Some(url) => self.evaluate_webfetch(url),
+                None => Verdict::Allow,
+            },
+            "websearch" => self.evaluate_websearch(input),
             // Known non-filesystem tools — always allow
-            "webfetch" | "websearch" | "task" | "askuserquestion"
-            | "todowrite" | "skill" | "sendmessage" | "teamcreate"
-            | "teamdelete" | "enterplanmode" | "exitplanmode"
-            | "taskcreate" | "taskget" | "taskupdate" | "tasklist"
-            | "taskoutput" | "taskstop" => Verdict::Allow,
+            "task" | "askuserquestion" | "todowrite" | "skill" | "sendmessage" | "teamcreate"
+            | "teamdelete" | "enterplanmode" | "exitplanmode" | "taskcreate" | "taskget"
+            | "taskupdate" | "tasklist" | "taskoutput" | "taskstop" => Verdict::Allow,
             // Unknown tools — allow by default
             _ => Verdict::Allow,
         }
@@ -89,12 +179,13 @@ impl RuleSet {
             None => return Verdict::Allow,
         };
 
-        // Single extraction pass — used by both internal-only and blocked-files checks
-        let paths = bash_analyzer::extract_paths(command);
+        // Single analysis pass — used by the path checks and the finding rules
+        let analysis = bash_analyzer::analyze_command(command);
+        let paths = &analysis.paths;
 
         // Check internal-only (path containment)
         if self.internal_access_only {
-            for ep in &paths {
+            for ep in paths {
                 match &ep.context {
                     PathContext::CdImplicitHome => {
                         return Verdict::Deny(
@@ -112,13 +203,7 @@ impl RuleSet {
                         code_snippet,
                     } => {
                         let resolved = resolve_target(&ep.raw, &self.project_root);
-                        if check_path_containment(
-                            &resolved,
-                            &self.project_root,
-                            "path",
-                        )
-                        .is_some()
-                        {
+                        if check_path_containment(&resolved, &self.project_root, "path").is_some() {
                             return Verdict::Deny(format!(
                                 "Blocked by `clarg`: '{} {} \"{}\"' references external path '{}'",
                                 interpreter,
@@ -129,8 +214,7 @@ impl RuleSet {
                         }
                     }
                     _ => {
-                        let resolved =
-                            resolve_target(&ep.raw, &self.project_root);
+                        let resolved = resolve_target(&ep.raw, &self.project_root);
                         if let Some(reason) = check_path_containment(
                             &resolved,
                             &self.project_root,
@@ -145,7 +229,7 @@ impl RuleSet {
 
         // Check blocked files against extracted paths
         if let Some(rule) = &self.blocked_files {
-            for ep in &paths {
+            for ep in paths {
                 // Skip non-path contexts
                 if matches!(
                     ep.context,
@@ -153,54 +237,386 @@ impl RuleSet {
                 ) {
                     continue;
                 }
-                let resolved =
-                    resolve_target(&ep.raw, &self.project_root);
+                let resolved = resolve_target(&ep.raw, &self.project_root);
                 // Only check paths under the project root — the gitignore
                 // matcher requires paths to be under its root.
-                if resolved.starts_with(&self.project_root) {
-                    if let Some(reason) = rule.check(&resolved) {
-                        return Verdict::Deny(reason);
-                    }
+                if resolved.starts_with(&self.project_root)
+                    && let Some(reason) = rule.check(&resolved)
+                {
+                    return Verdict::Deny(reason);
                 }
             }
         }
 
+        // Check writes into git internals
+        if let Some(rule) = &self.git_internals {
+            // Whether inline code writes a path it names isn't known, so it counts as one
+            let writes = paths.iter().filter(|ep| {
+                ep.context.is_write() || matches!(ep.context, PathContext::InlineCodeRef { .. })
+            });
+            for ep in writes {
+                let resolved = resolve_target(&ep.raw, &self.project_root);
+                if let Some(reason) = rule.check(&resolved) {
+                    return Verdict::Deny(reason);
+                }
+            }
+            if let Some(fact) = analysis.findings.iter().find_map(|f| match f {
+                Finding::Fact(fact) if sets_git_program(fact) => Some(fact),
+                _ => None,
+            }) {
+                return Verdict::Deny(format!(
+                    "Blocked by `clarg`: '{}' sets git config '{}', which makes git run a program later — repository internals are protected",
+                    truncate(&fact.command, 100),
+                    fact.attr("key").unwrap_or_default()
+                ));
+            }
+        }
+
+        // Check the members of archives being extracted
+        if self.inspect_archives
+            && let Some(reason) = analysis.findings.iter().find_map(|f| match f {
+                Finding::Fact(fact) if fact.name == "archive.extract" => self.check_archive(fact),
+                _ => None,
+            })
+        {
+            return Verdict::Deny(reason);
+        }
+
         // Check blocked commands
-        if let Some(rule) = &self.blocked_commands {
-            if let Some(reason) = rule.check(command) {
-                return Verdict::Deny(reason);
+        if let Some(rule) = &self.blocked_commands
+            && let Some(reason) = rule.check(command)
+        {
+            return Verdict::Deny(reason);
+        }
+
+        // Statements in the SQL scripts database CLIs run count like inline ones
+        let script_findings: Vec<Finding> = analysis
+            .findings
+            .iter()
+            .filter_map(|f| match f {
+                Finding::Fact(fact) if fact.name == "database.connect" => Some(fact),
+                _ => None,
+            })
+            .flat_map(|connect| self.database_scripts(connect))
+            .collect();
+
+        // The Bash tool's own background mode
+        let background = input.run_in_background().then(|| {
+            Finding::Fact(persistence_analyzer::background_fact(
+                command,
+                "run_in_background",
+            ))
+        });
+
+        // Check findings — a deny wins over any ask
+        let findings = analysis
+            .findings
+            .iter()
+            .chain(&script_findings)
+            .chain(&background);
+        strongest(findings.map(|finding| match finding {
+            Finding::PipeToInterpreter {
+                interpreter,
+                source,
+            } => self.pipe_to_shell.verdict(format!(
+                "Blocked by `clarg`: output of '{}' is piped into '{}' — the executed code cannot be analyzed",
+                truncate(source, 80),
+                interpreter
+            )),
+            Finding::Obfuscation { score, techniques } => {
+                (*score > self.obfuscation_threshold).then(|| {
+                    Verdict::Deny(format!(
+                        "Blocked by `clarg`: command looks obfuscated ({}; score {} > threshold {}) — it cannot be reliably analyzed",
+                        techniques.join(", "),
+                        score,
+                        self.obfuscation_threshold
+                    ))
+                })
             }
+            Finding::Unanalyzable { command, reason } => self.strict.then(|| {
+                Verdict::Deny(format!(
+                    "Blocked by `clarg`: '{}' is too complex to verify ({}) — strict mode denies commands it cannot fully analyze",
+                    truncate(command, 80),
+                    reason
+                ))
+            }),
+            Finding::Fact(fact) => self.evaluate_fact(fact),
+            Finding::DataFlow {
+                source,
+                reader,
+                sink,
+            } => self.is_secret(source).then(|| {
+                let what = match source {
+                    FlowSource::Path(raw) => format!("'{raw}'"),
+                    FlowSource::Environment => "the environment".to_string(),
+                };
+                let to = sink
+                    .attr("host")
+                    .map_or_else(|| "the network".to_string(), |host| format!("'{host}'"));
+                self.secret_flow.verdict(format!(
+                    "Blocked by `clarg`: {} read by '{}' is sent to {} by '{}' — secrets must not leave the machine",
+                    what,
+                    truncate(reader, 80),
+                    to,
+                    truncate(&sink.command, 80)
+                ))
+            })
+            .flatten(),
+        }))
+    }
+
+    /// Whether a flow source holds secrets: the environment, a credential
+    /// file, or a blocked file.
+    fn is_secret(&self, source: &FlowSource) -> bool {
+        let FlowSource::Path(raw) = source else {
+            return true;
+        };
+        if data_flow::is_credential_path(raw) {
+            return true;
         }
+        let resolved = resolve_target(raw, &self.project_root);
+        self.blocked_files.as_ref().is_some_and(|rule| {
+            resolved.starts_with(&self.project_root) && rule.check(&resolved).is_some()
+        })
+    }
 
-        Verdict::Allow
+    /// List the members of a local archive being extracted and check where
+    /// each lands. Returns Some(reason) for a member or link that escapes the
+    /// extraction directory, or that writes a blocked file or git internals.
+    fn check_archive(&self, fact: &Fact) -> Option<String> {
+        let archive = fact.attr("archive")?;
+        let members = (archive != "-")
+            .then(|| archive_members::list_members(&resolve_target(archive, &self.project_root)))
+            .flatten();
+        let Some(members) = members else {
+            // Compressed with something other than gzip, piped in, or not found
+            return self.strict.then(|| {
+                format!(
+                    "Blocked by `clarg`: '{}' is too complex to verify (the members of '{}' can't be listed) — strict mode denies commands it cannot fully analyze",
+                    truncate(&fact.command, 80),
+                    if archive == "-" { "stdin" } else { archive }
+                )
+            });
+        };
+        let dest = resolve_target(fact.attr("dest").unwrap_or("."), &self.project_root);
+        let absolute_names = fact.attr("absolute_names") == Some("true");
+        let strip = fact
+            .attr("strip_components")
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0);
+        let escapes = |target: &Path, what: &str| {
+            (!target.starts_with(&dest)).then(|| {
+                format!(
+                    "Blocked by `clarg`: {} of '{}' would extract to '{}', outside the extraction directory '{}'",
+                    what,
+                    archive,
+                    target.display(),
+                    dest.display()
+                )
+            })
+        };
+        for member in &members {
+            let Some(target) = member_target(&dest, &member.name, absolute_names, strip) else {
+                continue;
+            };
+            let what = format!("archive member '{}'", member.name);
+            let link = match &member.link {
+                Some(Link::Symbolic(link)) => {
+                    Some(normalize_path(&target.parent().unwrap_or(&dest).join(link)))
+                }
+                Some(Link::Hard(link)) => member_target(&dest, link, absolute_names, strip),
+                None => None,
+            };
+            let reason = escapes(&target, &what)
+                .or_else(|| link.and_then(|l| escapes(&l, &format!("the link target of {what}"))))
+                .or_else(|| {
+                    self.git_internals
+                        .as_ref()
+                        .and_then(|rule| rule.check(&target))
+                })
+                .or_else(|| {
+                    self.blocked_files
+                        .as_ref()
+                        .filter(|_| target.starts_with(&self.project_root))
+                        .and_then(|rule| rule.check(&target))
+                });
+            if reason.is_some() {
+                return reason;
+            }
+        }
+        None
+    }
+
+    /// Read the local SQL scripts a `database.connect` fact runs and analyze
+    /// them as input to its CLI. Missing files are skipped; large ones are
+    /// read up to a limit.
+    fn database_scripts(&self, connect: &Fact) -> Vec<Finding> {
+        let Some(scripts) = connect.attr("script") else {
+            return Vec::new();
+        };
+        scripts
+            .split(',')
+            .filter_map(|script| {
+                let file = std::fs::File::open(resolve_target(script, &self.project_root)).ok()?;
+                let mut bytes = Vec::new();
+                file.take(MAX_SCRIPT_BYTES).read_to_end(&mut bytes).ok()?;
+                Some(
+                    database_analyzer::analyze_script(connect, &String::from_utf8_lossy(&bytes))
+                        .findings,
+                )
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Check a fact against the host, database and resource policies, then
+    /// the fact rules.
+    fn evaluate_fact(&self, fact: &Fact) -> Option<Verdict> {
+        let policy = self
+            .hosts
+            .as_ref()
+            .and_then(|h| h.check(fact))
+            .or_else(|| self.databases.as_ref().and_then(|d| d.check(fact)))
+            .or_else(|| self.resources.as_ref().and_then(|r| r.check(fact)));
+        if let Some(reason) = policy {
+            return Some(Verdict::Deny(reason));
+        }
+        match self.fact_rules.check(fact) {
+            Some((action, reason)) => action.verdict(reason),
+            None if fact.name.starts_with("persistence.") => self.persistence.verdict(format!(
+                "Blocked by `clarg`: '{}' {} — it keeps running or runs again after the session ends",
+                truncate(&fact.command, 100),
+                persistence_analyzer::describe(fact)
+            )),
+            None => None,
+        }
+    }
+
+    /// Evaluate a WebFetch URL against the web rules, then as a `web.fetch` fact.
+    fn evaluate_webfetch(&self, url: &str) -> Verdict {
+        let web = self
+            .web
+            .check_url(url)
+            .and_then(|(action, reason)| action.verdict(reason));
+        let mut fact = Fact::new("web.fetch", url).with("url", url);
+        if let Some(parsed) = parse_url(url) {
+            fact = fact
+                .with("scheme", parsed.scheme)
+                .with("host", parsed.host)
+                .with("path", parsed.path)
+                .with("query", parsed.query.unwrap_or_default());
+            if let Some(port) = parsed.port {
+                fact = fact.with("port", port);
+            }
+        }
+        strongest([web, self.evaluate_fact(&fact)])
+    }
+
+    /// Evaluate a WebSearch: each `allowed_domains` entry is checked like a
+    /// fetched host, a search without them is checked against the web and
+    /// host allowlists, and the search is a `web.search` fact.
+    fn evaluate_websearch(&self, input: &HookInput) -> Verdict {
+        let query = input.query().unwrap_or_default();
+        let allowed = input.allowed_domains();
+        // Without allowed_domains any site may come back
+        let unrestricted = allowed.is_empty();
+        let web_allowlist = unrestricted
+            .then(|| self.web.check_unrestricted_search(query))
+            .flatten()
+            .and_then(|(action, reason)| action.verdict(reason));
+        let host_allowlist = (unrestricted && self.hosts.as_ref().is_some_and(HostRule::has_allowlist)).then(|| {
+            Verdict::Deny(format!(
+                "Blocked by `clarg`: the search '{query}' sets no allowed_domains, so it may reach hosts outside the host allowlist"
+            ))
+        });
+        let domains = allowed.iter().flat_map(|domain| {
+            let web = self
+                .web
+                .check_domain(domain)
+                .and_then(|(action, reason)| action.verdict(reason));
+            let host = Fact::new("web.search", query).with("host", *domain);
+            [
+                web,
+                self.hosts
+                    .as_ref()
+                    .and_then(|h| h.check(&host))
+                    .map(Verdict::Deny),
+            ]
+        });
+        let fact = Fact::new("web.search", query)
+            .with("query", query)
+            .with("allowed_domains", allowed.join(","))
+            .with("blocked_domains", input.blocked_domains().join(","));
+        let fact_rule = self.fact_rules.check(&fact).and_then(|(a, r)| a.verdict(r));
+        strongest(domains.chain([web_allowlist, host_allowlist, fact_rule]))
     }
 
     /// Evaluate a single-path tool (Read, Write, Edit, NotebookEdit, Glob, Grep).
-    fn evaluate_path_tool(&self, path: &str) -> Verdict {
-        if !self.internal_access_only && self.blocked_files.is_none() {
+    fn evaluate_path_tool(&self, path: &str, write: bool) -> Verdict {
+        // Writing a startup file runs its contents at every login or boot
+        let startup = write
+            .then(|| persistence_analyzer::startup_file_fact(path, path))
+            .flatten()
+            .and_then(|fact| self.evaluate_fact(&fact));
+        match self.check_path_tool(path, write) {
+            Verdict::Allow => strongest([startup]),
+            denied => denied,
+        }
+    }
+
+    /// Check a file tool's path against the filesystem rules.
+    fn check_path_tool(&self, path: &str, write: bool) -> Verdict {
+        let protects_git = write && self.git_internals.is_some();
+        if !self.internal_access_only && self.blocked_files.is_none() && !protects_git {
             return Verdict::Allow;
         }
 
         let resolved = resolve_target(path, &self.project_root);
 
+        // Check git internals
+        if write
+            && let Some(rule) = &self.git_internals
+            && let Some(reason) = rule.check(&resolved)
+        {
+            return Verdict::Deny(reason);
+        }
+
         // Check internal-only
-        if self.internal_access_only {
-            if let Some(reason) = check_path_containment(
-                &resolved,
-                &self.project_root,
-                "path",
-            ) {
-                return Verdict::Deny(reason);
-            }
+        if self.internal_access_only
+            && let Some(reason) = check_path_containment(&resolved, &self.project_root, "path")
+        {
+            return Verdict::Deny(reason);
         }
 
         // Check blocked files
-        if let Some(rule) = &self.blocked_files {
-            if let Some(reason) = rule.check(&resolved) {
-                return Verdict::Deny(reason);
-            }
+        if let Some(rule) = &self.blocked_files
+            && let Some(reason) = rule.check(&resolved)
+        {
+            return Verdict::Deny(reason);
         }
 
         Verdict::Allow
     }
 }
+
+/// Combine rule outcomes: the first deny wins, otherwise the first ask.
+fn strongest(verdicts: impl IntoIterator<Item = Option<Verdict>>) -> Verdict {
+    let mut ask = None;
+    for verdict in verdicts.into_iter().flatten() {
+        match verdict {
+            Verdict::Deny(reason) => return Verdict::Deny(reason),
+            v => {
+                ask.get_or_insert(v);
+            }
+        }
+    }
+    ask.unwrap_or(Verdict::Allow)
+}
+
+/// A persisted `git config` write of a key whose value git runs as a program.
+fn sets_git_program(fact: &Fact) -> bool {
+    matches!(
+        fact.name.as_str(),
+        "git.config.hooks-path" | "git.config.exec"
+    ) && fact.attr("source") == Some("git config")
+}
@@ -0,0 +1,828 @@
+use crate::bash_analyzer::{
+    CommandAnalysis, ExtractedPath, Finding, PathContext, detect_code_from_stdin,
+    extract_paths_recursive, looks_like_path,
+};
+
+/// awk implementations analysed like `awk`.
+pub(crate) const AWK_COMMANDS: &[&str] = &["awk", "gawk", "mawk", "nawk"];
+
+/// What a sed, awk or perl script does outside its input and output streams.
+#[derive(Debug, PartialEq)]
+enum ScriptOp {
+    /// A file the script reads (`r file`, `getline < "file"`).
+    Read(String),
+    /// A file the script writes (`w file`, `print > "file"`).
+    Write(String),
+    /// A shell command the script runs (`e cmd`, `system("cmd")`).
+    Run(String),
+    /// A shell command the script's output is piped into (`print | "sh"`).
+    PipeTo(String),
+    /// A file or command computed at runtime.
+    Dynamic(&'static str),
+}
+
+/// Record what the ops found in a script of `tool` mean: paths for files,
+/// analysed commands for `system()` and pipes, and `Unanalyzable` for
+/// targets computed at runtime.
+fn apply_ops(
+    tool: &'static str,
+    sub_cmd: &str,
+    ops: Vec<ScriptOp>,
+    out: &mut CommandAnalysis,
+    depth: usize,
+) {
+    for op in ops {
+        match op {
+            ScriptOp::Read(raw) | ScriptOp::Write(raw)
+                if raw.starts_with("/dev/") || raw == "-" => {}
+            ScriptOp::Read(raw) => out.paths.push(ExtractedPath {
+                raw,
+                context: PathContext::ScriptPath { write: false },
+            }),
+            ScriptOp::Write(raw) => out.paths.push(ExtractedPath {
+                raw,
+                context: PathContext::ScriptPath { write: true },
+            }),
+            ScriptOp::Run(command) => extract_paths_recursive(&command, out, depth + 1),
+            ScriptOp::PipeTo(command) => {
+                detect_code_from_stdin(&command, sub_cmd, out);
+                extract_paths_recursive(&command, out, depth + 1);
+            }
+            ScriptOp::Dynamic(what) => out.findings.push(Finding::Unanalyzable {
+                command: sub_cmd.to_string(),
+                reason: format!("{tool} {what} computed at runtime"),
+            }),
+        }
+    }
+}
+
+/// The shell commands among a script's ops, for matching against forbidden
+/// command patterns.
+fn commands(ops: impl IntoIterator<Item = ScriptOp>) -> Vec<String> {
+    ops.into_iter()
+        .filter_map(|op| match op {
+            ScriptOp::Run(command) | ScriptOp::PipeTo(command) => Some(command),
+            _ => None,
+        })
+        .collect()
+}
+
+// ============================================================================
+// sed
+// ============================================================================
+
+/// A sed command line split into its scripts, script files and operands.
+#[derive(Default)]
+struct SedArgs {
+    in_place: bool,
+    sandbox: bool,
+    scripts: Vec<String>,
+    script_files: Vec<String>,
+    operands: Vec<String>,
+}
+
+/// Extract the file operands of sed, its `-f` script files, and the files
+/// and commands its scripts use (`w`, `r`, `e`, `s///w`, `s///e`).
+pub(crate) fn extract_sed(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let SedArgs {
+        in_place,
+        sandbox,
+        scripts,
+        script_files,
+        operands,
+    } = parse_sed(args);
+    for file in script_files {
+        out.paths.push(ExtractedPath {
+            raw: file,
+            context: PathContext::SedFile,
+        });
+    }
+    for arg in operands.into_iter().filter(|a| looks_like_path(a)) {
+        out.paths.push(ExtractedPath {
+            raw: arg,
+            context: if in_place {
+                PathContext::SedInPlace
+            } else {
+                PathContext::SedFile
+            },
+        });
+    }
+    // --sandbox rejects scripts with e, r and w
+    if !sandbox {
+        let ops = scripts.iter().flat_map(|s| sed_script_ops(s)).collect();
+        apply_ops("sed", sub_cmd, ops, out, depth);
+    }
+}
+
+/// The shell commands a sed invocation's scripts run.
+pub(crate) fn sed_commands(args: &[String]) -> Vec<String> {
+    let parsed = parse_sed(args);
+    if parsed.sandbox {
+        return Vec::new();
+    }
+    commands(parsed.scripts.iter().flat_map(|s| sed_script_ops(s)))
+}
+
+/// Parse sed's options; without `-e` or `-f` the first operand is the script.
+fn parse_sed(args: &[String]) -> SedArgs {
+    let mut parsed = SedArgs::default();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        let next = args.get(i + 1);
+        i += 1;
+        if arg == "--" {
+            parsed.operands.extend(args[i..].iter().cloned());
+            break;
+        }
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            let value = match name {
+                "expression" | "file" | "line-length" if value.is_none() => {
+                    i += 1;
+                    next.cloned()
+                }
+                _ => value,
+            };
+            match (name, value) {
+                ("expression", Some(script)) => parsed.scripts.push(script),
+                ("file", Some(file)) => parsed.script_files.push(file),
+                ("in-place", _) => parsed.in_place = true,
+                ("sandbox", _) => parsed.sandbox = true,
+                _ => {}
+            }
+            continue;
+        }
+        let Some(bundle) = arg.strip_prefix('-').filter(|b| !b.is_empty()) else {
+            parsed.operands.push(arg.to_string());
+            continue;
+        };
+        for (pos, c) in bundle.char_indices() {
+            let attached = &bundle[pos + c.len_utf8()..];
+            match c {
+                // `-i.bak`: the rest is the backup suffix
+                'i' => {
+                    parsed.in_place = true;
+                    break;
+                }
+                'e' | 'f' | 'l' => {
+                    let value = if attached.is_empty() {
+                        i += 1;
+                        next.cloned()
+                    } else {
+                        Some(attached.to_string())
+                    };
+                    match (c, value) {
+                        ('e', Some(script)) => parsed.scripts.push(script),
+                        ('f', Some(file)) => parsed.script_files.push(file),
+                        _ => {}
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+    // Without -e/-f the first operand is the script, not a file
+    if parsed.scripts.is_empty() && parsed.script_files.is_empty() && !parsed.operands.is_empty() {
+        parsed.scripts.push(parsed.operands.remove(0));
+    }
+    parsed
+}
+
+/// Parse a sed script far enough to find its `r`/`R`, `w`/`W` and `e`
+/// commands and the `w` and `e` flags of `s`. Parsing stops at anything
+/// unrecognised.
+fn sed_script_ops(script: &str) -> Vec<ScriptOp> {
+    let chars: Vec<char> = script.chars().collect();
+    let mut ops = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == ';' || c == '}' || c == '!' {
+            i += 1;
+            continue;
+        }
+        if c == '#' {
+            i = line_end(&chars, i);
+            continue;
+        }
+        // Addresses: `1`, `$`, `/re/`, `\%re%`, `first~step`, `addr1,+N`
+        if c.is_ascii_digit() || c == '$' || c == ',' || c == '~' || c == '+' {
+            i += 1;
+            continue;
+        }
+        if c == '/' || c == '\\' {
+            let delim_at = if c == '\\' { i + 1 } else { i };
+            let Some(&delim) = chars.get(delim_at) else {
+                break;
+            };
+            i = skip_delimited(&chars, delim_at + 1, delim);
+            // Address flags: `/re/I`, `/re/M`
+            while chars.get(i).is_some_and(|c| matches!(c, 'I' | 'M')) {
+                i += 1;
+            }
+            continue;
+        }
+        i += 1;
+        match c {
+            '{' | '=' | 'd' | 'D' | 'g' | 'G' | 'h' | 'H' | 'x' | 'n' | 'N' | 'p' | 'P' | 'z'
+            | 'F' => {}
+            'l' | 'q' | 'Q' | 'L' => {
+                while chars
+                    .get(i)
+                    .is_some_and(|c| c.is_ascii_digit() || *c == ' ')
+                {
+                    i += 1;
+                }
+            }
+            ':' | 'b' | 't' | 'T' | 'v' => {
+                while chars.get(i).is_some_and(|c| !matches!(c, ';' | '\n' | '}')) {
+                    i += 1;
+                }
+            }
+            'a' | 'i' | 'c' => {
+                // Text runs to the end of the line, continued by a trailing backslash
+                loop {
+                    let end = line_end(&chars, i);
+                    let continued = end > i && chars[end - 1] == '\\';
+                    i = end + 1;
+                    if !continued || i >= chars.len() {
+                        break;
+                    }
+                }
+            }
+            'r' | 'R' | 'w' | 'W' | 'e' => {
+                let end = line_end(&chars, i);
+                let operand: String = chars[i..end].iter().collect::<String>().trim().to_string();
+                i = end;
+                ops.push(match c {
+                    'r' | 'R' => ScriptOp::Read(operand),
+                    'w' | 'W' => ScriptOp::Write(operand),
+                    // `e` alone runs the pattern space
+                    _ if operand.is_empty() => ScriptOp::Dynamic("'e' runs text"),
+                    _ => ScriptOp::Run(operand),
+                });
+            }
+            's' | 'y' => {
+                let Some(&delim) = chars.get(i) else {
+                    break;
+                };
+                let replacement_start = skip_delimited(&chars, i + 1, delim);
+                i = skip_delimited(&chars, replacement_start, delim);
+                if c == 'y' {
+                    continue;
+                }
+                let replacement: String = chars
+                    [replacement_start..i.saturating_sub(1).max(replacement_start)]
+                    .iter()
+                    .collect();
+                while let Some(&flag) = chars.get(i) {
+                    match flag {
+                        'w' => {
+                            let end = line_end(&chars, i + 1);
+                            let file: String = chars[i + 1..end].iter().collect();
+                            ops.push(ScriptOp::Write(file.trim().to_string()));
+                            i = end;
+                            break;
+                        }
+                        'e' => {
+                            // The rest of the line comes from the input, but a
+                            // literal replacement is a command the script chose
+                            if let Some(command) = literal_replacement(&replacement, delim) {
+                                ops.push(ScriptOp::Run(command));
+                            }
+                            ops.push(ScriptOp::Dynamic("'s///e' runs text"));
+                            i += 1;
+                        }
+                        'g' | 'p' | 'i' | 'I' | 'm' | 'M' => i += 1,
+                        d if d.is_ascii_digit() => i += 1,
+                        _ => break,
+                    }
+                }
+            }
+            _ => break,
+        }
+    }
+    ops
+}
+
+/// The text of an `s` replacement with its escaped delimiters restored, or
+/// None if it copies the match (`&`, `\1`) or is empty.
+fn literal_replacement(replacement: &str, delim: char) -> Option<String> {
+    let mut text = String::new();
+    let mut chars = replacement.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => return None,
+            '\\' => match chars.next() {
+                Some(d) if d.is_ascii_digit() => return None,
+                Some('n') => text.push('\n'),
+                Some(next) if next == delim || next == '&' || next == '\\' => text.push(next),
+                Some(next) => {
+                    text.push('\\');
+                    text.push(next);
+                }
+                None => text.push('\\'),
+            },
+            c => text.push(c),
+        }
+    }
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Index of the newline ending the line that contains `from` (or the end).
+fn line_end(chars: &[char], from: usize) -> usize {
+    chars[from.min(chars.len())..]
+        .iter()
+        .position(|&c| c == '\n')
+        .map_or(chars.len(), |p| from + p)
+}
+
+/// Index just past the next unescaped `delim` at or after `from`.
+fn skip_delimited(chars: &[char], from: usize, delim: char) -> usize {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == '\\' {
+            i += 2;
+            continue;
+        }
+        if chars[i] == delim {
+            return i + 1;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+// ============================================================================
+// awk
+// ============================================================================
+
+/// An awk command line split into its programs, program files and operands.
+#[derive(Default)]
+struct AwkArgs {
+    in_place: bool,
+    programs: Vec<String>,
+    program_files: Vec<String>,
+    operands: Vec<String>,
+}
+
+/// Extract awk's program files and input files, and the files and commands
+/// its program uses: output redirections, `getline < file`, `cmd | getline`,
+/// `system()` and `print | cmd`. `gawk -i inplace` makes the inputs writes.
+pub(crate) fn extract_awk(sub_cmd: &str, args: &[String], out: &mut CommandAnalysis, depth: usize) {
+    let AwkArgs {
+        in_place,
+        programs,
+        program_files,
+        operands,
+    } = parse_awk(args);
+    for file in program_files {
+        out.paths.push(ExtractedPath {
+            raw: file,
+            context: PathContext::ScriptPath { write: false },
+        });
+    }
+    // `var=value` operands are assignments, not files
+    let files = operands.into_iter().filter(|a| {
+        !a.split_once('=')
+            .is_some_and(|(var, _)| is_awk_identifier(var))
+    });
+    for file in files.filter(|a| looks_like_path(a)) {
+        out.paths.push(ExtractedPath {
+            raw: file,
+            context: PathContext::ScriptPath { write: in_place },
+        });
+    }
+    let ops = programs.iter().flat_map(|p| awk_program_ops(p)).collect();
+    apply_ops("awk", sub_cmd, ops, out, depth);
+}
+
+/// The shell commands an awk invocation's programs run.
+pub(crate) fn awk_commands(args: &[String]) -> Vec<String> {
+    commands(
+        parse_awk(args)
+            .programs
+            .iter()
+            .flat_map(|p| awk_program_ops(p)),
+    )
+}
+
+/// Parse awk's options; without `-f` or `-e` the first operand is the program.
+fn parse_awk(args: &[String]) -> AwkArgs {
+    let mut parsed = AwkArgs::default();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        let next = args.get(i + 1).cloned();
+        i += 1;
+        if arg == "--" {
+            parsed.operands.extend(args[i..].iter().cloned());
+            break;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            parsed.operands.push(arg.to_string());
+            continue;
+        }
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ if matches!(
+                arg,
+                "--file"
+                    | "--source"
+                    | "--include"
+                    | "--load"
+                    | "--assign"
+                    | "--field-separator"
+                    | "--exec"
+            ) =>
+            {
+                i += 1;
+                (arg, next)
+            }
+            _ if arg.starts_with("--") => (arg, None),
+            // Short options take their value attached or as the next argument
+            _ => {
+                let (flag, attached) = arg.split_at(2.min(arg.len()));
+                if matches!(flag, "-f" | "-e" | "-i" | "-l" | "-v" | "-F" | "-E") {
+                    if attached.is_empty() {
+                        i += 1;
+                        (flag, next)
+                    } else {
+                        (flag, Some(attached.to_string()))
+                    }
+                } else {
+                    (flag, None)
+                }
+            }
+        };
+        match (flag, value) {
+            ("-f" | "--file", Some(file)) => parsed.program_files.push(file),
+            // `-E file` is the program and ends the options
+            ("-E" | "--exec", Some(file)) => {
+                parsed.program_files.push(file);
+                parsed.operands.extend(args[i..].iter().cloned());
+                break;
+            }
+            ("-e" | "--source", Some(program)) => parsed.programs.push(program),
+            ("-i" | "--include", Some(lib)) if lib == "inplace" => parsed.in_place = true,
+            ("-i" | "--include", Some(lib)) if looks_like_path(&lib) => {
+                parsed.program_files.push(lib)
+            }
+            _ => {}
+        }
+    }
+    if parsed.programs.is_empty() && parsed.program_files.is_empty() && !parsed.operands.is_empty()
+    {
+        parsed.programs.push(parsed.operands.remove(0));
+    }
+    parsed
+}
+
+fn is_awk_identifier(word: &str) -> bool {
+    word.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A lexical token of an awk program.
+#[derive(Debug, PartialEq)]
+enum AwkToken {
+    Str(String),
+    Word(String),
+    Op(String),
+    Regex,
+}
+
+const AWK_TWO_CHAR_OPS: &[&str] = &[
+    "&&", "||", ">>", "|&", ">=", "<=", "==", "!=", "++", "--", "+=", "-=", "*=", "/=", "%=", "^=",
+    "**",
+];
+
+fn awk_tokens(program: &str) -> Vec<AwkToken> {
+    let chars: Vec<char> = program.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\\' && chars.get(i + 1) == Some(&'\n') {
+            i += 2;
+            continue;
+        }
+        if c == ' ' || c == '\t' || c == '\r' {
+            i += 1;
+            continue;
+        }
+        if c == '#' {
+            i = line_end(&chars, i);
+            continue;
+        }
+        if c == '"' {
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                    s.push(match chars[i] {
+                        'n' => '\n',
+                        't' => '\t',
+                        other => other,
+                    });
+                } else {
+                    s.push(chars[i]);
+                }
+                i += 1;
+            }
+            tokens.push(AwkToken::Str(s));
+            i += 1;
+            continue;
+        }
+        // A slash after an operand divides; anywhere else it starts a regex
+        let after_operand = matches!(
+            tokens.last(),
+            Some(AwkToken::Str(_) | AwkToken::Word(_) | AwkToken::Regex)
+        ) || matches!(tokens.last(), Some(AwkToken::Op(op)) if op == ")" || op == "]");
+        if c == '/' && !after_operand {
+            i = skip_delimited(&chars, i + 1, '/');
+            tokens.push(AwkToken::Regex);
+            continue;
+        }
+        if c.is_alphanumeric() || c == '_' || c == '$' || c == '.' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$' | '.'))
+            {
+                i += 1;
+            }
+            tokens.push(AwkToken::Word(chars[start..i].iter().collect()));
+            continue;
+        }
+        let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+        if AWK_TWO_CHAR_OPS.contains(&two.as_str()) {
+            tokens.push(AwkToken::Op(two));
+            i += 2;
+        } else {
+            tokens.push(AwkToken::Op(c.to_string()));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+fn is_statement_end(token: &AwkToken) -> bool {
+    matches!(token, AwkToken::Op(op) if matches!(op.as_str(), ";" | "\n" | "{" | "}"))
+}
+
+/// Tokens from `from` up to the end of the statement or a closing paren
+/// at the same level.
+fn expression_end(tokens: &[AwkToken], from: usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = from;
+    while i < tokens.len() {
+        match &tokens[i] {
+            AwkToken::Op(op) if op == "(" => depth += 1,
+            AwkToken::Op(op) if op == ")" => {
+                if depth == 0 {
+                    return i;
+                }
+                depth -= 1;
+            }
+            t if depth == 0
+                && (is_statement_end(t) || matches!(t, AwkToken::Op(op) if op == ",")) =>
+            {
+                return i;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    tokens.len()
+}
+
+/// The string an expression is, if it is a single literal.
+fn literal(tokens: &[AwkToken]) -> Option<String> {
+    match tokens {
+        [AwkToken::Str(s)] => Some(s.clone()),
+        [AwkToken::Op(open), AwkToken::Str(s), AwkToken::Op(close)]
+            if open == "(" && close == ")" =>
+        {
+            Some(s.clone())
+        }
+        _ => None,
+    }
+}
+
+fn awk_program_ops(program: &str) -> Vec<ScriptOp> {
+    let tokens = awk_tokens(program);
+    let mut ops = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let AwkToken::Word(word) = token else {
+            continue;
+        };
+        match word.as_str() {
+            "print" | "printf" => {
+                // The first unparenthesised `>`, `>>` or `|` redirects the output
+                let mut depth = 0usize;
+                let mut j = i + 1;
+                while j < tokens.len() && !(depth == 0 && is_statement_end(&tokens[j])) {
+                    match &tokens[j] {
+                        AwkToken::Op(op) if op == "(" => depth += 1,
+                        AwkToken::Op(op) if op == ")" => depth = depth.saturating_sub(1),
+                        AwkToken::Op(op)
+                            if depth == 0 && matches!(op.as_str(), ">" | ">>" | "|" | "|&") =>
+                        {
+                            let end = expression_end(&tokens, j + 1);
+                            let target = literal(&tokens[j + 1..end]);
+                            ops.push(match (op.as_str(), target) {
+                                (">" | ">>", Some(file)) => ScriptOp::Write(file),
+                                (">" | ">>", None) => ScriptOp::Dynamic("output file"),
+                                (_, Some(command)) => ScriptOp::PipeTo(command),
+                                (_, None) => ScriptOp::Dynamic("output command"),
+                            });
+                            break;
+                        }
+                        _ => {}
+                    }
+                    j += 1;
+                }
+            }
+            "getline" => {
+                // `getline [var] < file`
+                let mut j = i + 1;
+                if matches!(tokens.get(j), Some(AwkToken::Word(_))) {
+                    j += 1;
+                }
+                if matches!(tokens.get(j), Some(AwkToken::Op(op)) if op == "<") {
+                    ops.push(match tokens.get(j + 1) {
+                        Some(AwkToken::Str(file)) => ScriptOp::Read(file.clone()),
+                        _ => ScriptOp::Dynamic("getline file"),
+                    });
+                }
+                // `cmd | getline`
+                if i >= 1 && matches!(&tokens[i - 1], AwkToken::Op(op) if op == "|" || op == "|&") {
+                    let start = tokens[..i - 1]
+                        .iter()
+                        .rposition(
+                            |t| matches!(t, AwkToken::Op(op) if !matches!(op.as_str(), ")" | "]")),
+                        )
+                        .map_or(0, |p| p + 1);
+                    ops.push(match literal(&tokens[start..i - 1]) {
+                        Some(command) => ScriptOp::Run(command),
+                        None => ScriptOp::Dynamic("getline command"),
+                    });
+                }
+            }
+            "system" if matches!(tokens.get(i + 1), Some(AwkToken::Op(op)) if op == "(") => {
+                let end = expression_end(&tokens, i + 2);
+                ops.push(match literal(&tokens[i + 2..end]) {
+                    Some(command) => ScriptOp::Run(command),
+                    None => ScriptOp::Dynamic("system() command"),
+                });
+            }
+            _ => {}
+        }
+    }
+    ops
+}
+
+// ============================================================================
+// perl
+// ============================================================================
+
+/// Find the files perl inline code opens and the commands it runs through
+/// `open` pipes, `system` and `exec`.
+pub(crate) fn extract_perl_code(code: &str, out: &mut CommandAnalysis, depth: usize) {
+    apply_ops("perl", code, perl_code_ops(code), out, depth);
+}
+
+/// Three-argument `open` modes, ignoring `:encoding(...)` layers.
+const PERL_OPEN_MODES: &[&str] = &["<", ">", ">>", "+<", "+>", "+>>", "|-", "-|"];
+
+fn perl_code_ops(code: &str) -> Vec<ScriptOp> {
+    let mut ops = Vec::new();
+    for args in perl_calls(code, "open") {
+        // The first argument is the handle
+        let literals: Vec<Option<String>> = args.iter().skip(1).map(|a| perl_literal(a)).collect();
+        match literals.as_slice() {
+            [Some(mode), target, ..]
+                if PERL_OPEN_MODES.contains(&mode.split(':').next().unwrap_or_default().trim()) =>
+            {
+                let mode = mode.split(':').next().unwrap_or_default().trim();
+                ops.push(match (mode, target) {
+                    ("|-", Some(cmd)) => ScriptOp::PipeTo(cmd.clone()),
+                    ("-|", Some(cmd)) => ScriptOp::Run(cmd.clone()),
+                    ("|-" | "-|", None) => ScriptOp::Dynamic("open command"),
+                    (_, None) => ScriptOp::Dynamic("open file"),
+                    (m, Some(file)) if m.contains('>') => ScriptOp::Write(file.clone()),
+                    (_, Some(file)) => ScriptOp::Read(file.clone()),
+                });
+            }
+            // Two-argument open: the mode is part of the string
+            [Some(spec)] => {
+                let spec = spec.trim();
+                ops.push(if let Some(cmd) = spec.strip_prefix('|') {
+                    ScriptOp::PipeTo(cmd.trim().to_string())
+                } else if let Some(cmd) = spec.strip_suffix('|') {
+                    ScriptOp::Run(cmd.trim().to_string())
+                } else if let Some(file) =
+                    spec.strip_prefix(">>").or_else(|| spec.strip_prefix('>'))
+                {
+                    ScriptOp::Write(file.trim().to_string())
+                } else {
+                    ScriptOp::Read(spec.trim_start_matches('<').trim().to_string())
+                });
+            }
+            _ => {}
+        }
+    }
+    for args in perl_calls(code, "system").chain(perl_calls(code, "exec")) {
+        let literals: Option<Vec<String>> = args.iter().map(|a| perl_literal(a)).collect();
+        match literals {
+            Some(words) if !words.is_empty() => ops.push(ScriptOp::Run(words.join(" "))),
+            _ if args.is_empty() => {}
+            _ => ops.push(ScriptOp::Dynamic("system() command")),
+        }
+    }
+    ops
+}
+
+/// Calls of the perl builtin `name` and their comma-separated arguments,
+/// with or without parens, up to the closing paren or the end of the
+/// statement (`or die` ends it too).
+fn perl_calls<'a>(code: &'a str, name: &'static str) -> impl Iterator<Item = Vec<String>> + 'a {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    code.match_indices(name).filter_map(move |(pos, _)| {
+        let after = &code[pos + name.len()..];
+        if code[..pos].ends_with(is_word) || after.starts_with(is_word) {
+            return None;
+        }
+        Some(perl_call_args(after))
+    })
+}
+
+fn perl_call_args(after_name: &str) -> Vec<String> {
+    let text = after_name.trim_start();
+    let (text, parens) = match text.strip_prefix('(') {
+        Some(inner) => (inner, true),
+        None => (text, false),
+    };
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut escaped = false;
+    let mut depth = 0usize;
+    for (idx, c) in text.char_indices() {
+        if let Some(q) = quote {
+            current.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if depth == 0
+            && !parens
+            && [" or ", " || ", " and ", " && "]
+                .iter()
+                .any(|op| text[idx..].starts_with(op))
+        {
+            break;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => depth -= 1,
+            ';' | ')' | '}' => break,
+            ',' if depth == 0 => {
+                args.push(std::mem::take(&mut current).trim().to_string());
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        args.push(current.trim().to_string());
+    }
+    args
+}
+
+/// The value of a quoted perl string with no interpolated variables.
+fn perl_literal(arg: &str) -> Option<String> {
+    let arg = arg.trim();
+    let quote = arg.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let inner = arg.strip_prefix(quote)?.strip_suffix(quote)?;
+    if quote == '"' && (inner.contains('$') || inner.contains('@')) {
+        return None;
+    }
+    Some(inner.replace(&format!("\\{quote}"), &quote.to_string()))
+}
@@ -0,0 +1,500 @@
+/// What a SQL statement does, coarsely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementKind {
+    /// Schema changes: CREATE, DROP, ALTER, TRUNCATE, RENAME
+    Ddl,
+    /// Data changes: INSERT, UPDATE, DELETE, MERGE, COPY, LOAD DATA
+    Dml,
+    /// Server, user and file administration: GRANT, SHUTDOWN, VACUUM,
+    /// ATTACH, `COPY … PROGRAM`, `INTO OUTFILE`, FLUSHALL
+    Admin,
+    /// Queries: SELECT, SHOW, EXPLAIN
+    Read,
+    /// Transactions and session settings: BEGIN, COMMIT, SET, USE
+    Session,
+    Other,
+}
+
+impl StatementKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatementKind::Ddl => "ddl",
+            StatementKind::Dml => "dml",
+            StatementKind::Admin => "admin",
+            StatementKind::Read => "read",
+            StatementKind::Session => "session",
+            StatementKind::Other => "other",
+        }
+    }
+}
+
+/// A classified statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    /// Leading keywords, upper-cased: `DROP TABLE`, `DELETE`, `GRANT`
+    pub verb: String,
+    pub kind: StatementKind,
+    /// Table, database or other object the statement names, when found
+    pub object: Option<String>,
+    /// A DELETE or UPDATE without a WHERE clause, which touches every row
+    pub unbounded: bool,
+    /// Client-side file the statement opens (sqlite `ATTACH`, mysql
+    /// `LOAD DATA LOCAL INFILE`)
+    pub file: Option<String>,
+}
+
+impl Statement {
+    fn new(verb: impl Into<String>, kind: StatementKind) -> Self {
+        Self {
+            verb: verb.into(),
+            kind,
+            object: None,
+            unbounded: false,
+            file: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    /// A quoted identifier ("name", `name`)
+    Ident(String),
+    /// A string literal ('text', $$text$$)
+    Str(String),
+    Punct(char),
+}
+
+/// Split SQL into statements and classify each one. Comments, string
+/// literals and dollar-quoted bodies are skipped when looking for `;`.
+pub fn classify(sql: &str) -> Vec<Statement> {
+    tokenize(sql)
+        .iter()
+        .flat_map(|tokens| classify_statement(tokens))
+        .collect()
+}
+
+/// Classify a statement, and before it the data-modifying statements in the
+/// bodies of its `WITH` queries (`WITH x AS (DELETE FROM t RETURNING *)
+/// SELECT 1` deletes).
+fn classify_statement(tokens: &[Token]) -> Vec<Statement> {
+    let mut statements: Vec<Statement> = cte_bodies(tokens)
+        .into_iter()
+        .flat_map(classify_statement)
+        .filter(|statement| statement.kind != StatementKind::Read)
+        .collect();
+    statements.extend(classify_tokens(tokens));
+    statements
+}
+
+/// The parenthesised bodies of the `name AS (…)` queries of a `WITH`.
+fn cte_bodies(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut bodies = Vec::new();
+    if keyword(tokens, 0).as_deref() != Some("WITH") {
+        return bodies;
+    }
+    let end = find_top_level(tokens, 1, WITH_MAIN_VERBS).unwrap_or(tokens.len());
+    let mut i = 1;
+    while i < end {
+        if tokens[i] != Token::Punct('(') {
+            i += 1;
+            continue;
+        }
+        let mut depth = 0usize;
+        let mut close = end;
+        for (j, token) in tokens.iter().enumerate().take(end).skip(i) {
+            match token {
+                Token::Punct('(') => depth += 1,
+                Token::Punct(')') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = j;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        // `name (col, …) AS (…)`: only the body follows AS
+        if matches!(
+            keyword(tokens, i - 1).as_deref(),
+            Some("AS" | "MATERIALIZED")
+        ) {
+            bodies.push(&tokens[i + 1..close]);
+        }
+        i = close + 1;
+    }
+    bodies
+}
+
+fn tokenize(sql: &str) -> Vec<Vec<Token>> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ';' => {
+                statements.push(std::mem::take(&mut tokens));
+                i += 1;
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            '\'' | '"' | '`' => {
+                let (text, end) = quoted(&chars, i + 1, c);
+                tokens.push(if c == '\'' {
+                    Token::Str(text)
+                } else {
+                    Token::Ident(text)
+                });
+                i = end;
+            }
+            '$' => match dollar_tag(&chars, i) {
+                Some(tag) => {
+                    let start = i + tag.len();
+                    let body: String = chars[start..].iter().collect();
+                    let len = body.find(&tag).unwrap_or(body.len());
+                    tokens.push(Token::Str(body[..len].to_string()));
+                    i = start + body[..len].chars().count() + tag.chars().count();
+                }
+                None => {
+                    tokens.push(Token::Punct(c));
+                    i += 1;
+                }
+            },
+            c if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '$'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Word(chars[start..i].iter().collect()));
+            }
+            c if c.is_whitespace() => i += 1,
+            c => {
+                tokens.push(Token::Punct(c));
+                i += 1;
+            }
+        }
+    }
+    statements.push(tokens);
+    statements.retain(|s| !s.is_empty());
+    statements
+}
+
+/// Text up to the closing `quote` (doubled quotes escape it) and the index after it.
+fn quoted(chars: &[char], mut i: usize, quote: char) -> (String, usize) {
+    let mut text = String::new();
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                text.push(quote);
+                i += 2;
+                continue;
+            }
+            return (text, i + 1);
+        }
+        if chars[i] == '\\' && quote == '\'' && i + 1 < chars.len() {
+            text.push(chars[i + 1]);
+            i += 2;
+            continue;
+        }
+        text.push(chars[i]);
+        i += 1;
+    }
+    (text, i)
+}
+
+/// A PostgreSQL dollar-quote opener at `i` (`$$`, `$body$`).
+fn dollar_tag(chars: &[char], i: usize) -> Option<String> {
+    let len = chars[i + 1..]
+        .iter()
+        .position(|c| !(c.is_alphanumeric() || *c == '_'))?;
+    (chars[i + 1 + len] == '$' && !chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        .then(|| chars[i..i + len + 2].iter().collect())
+}
+
+/// Upper-cased keyword at `i`, if the token there is a bare word.
+fn keyword(tokens: &[Token], i: usize) -> Option<String> {
+    match tokens.get(i) {
+        Some(Token::Word(word)) => Some(word.to_ascii_uppercase()),
+        _ => None,
+    }
+}
+
+/// Name of the object at `i`: a bare or quoted identifier.
+fn name(tokens: &[Token], i: usize) -> Option<String> {
+    match tokens.get(i) {
+        Some(Token::Word(word) | Token::Ident(word)) => Some(word.clone()),
+        _ => None,
+    }
+}
+
+/// Index of the first keyword in `words` outside parentheses, from `from`.
+fn find_top_level(tokens: &[Token], from: usize, words: &[&str]) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(from) {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => depth = depth.saturating_sub(1),
+            Token::Word(word)
+                if depth == 0 && words.iter().any(|w| word.eq_ignore_ascii_case(w)) =>
+            {
+                return Some(i);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Skip keywords in `skip` starting at `i`.
+fn skip_keywords(tokens: &[Token], mut i: usize, skip: &[&str]) -> usize {
+    while keyword(tokens, i).is_some_and(|k| skip.contains(&k.as_str())) {
+        i += 1;
+    }
+    i
+}
+
+/// Modifiers between CREATE/DROP/ALTER and the object type.
+const DDL_MODIFIERS: &[&str] = &[
+    "OR",
+    "REPLACE",
+    "TEMP",
+    "TEMPORARY",
+    "UNIQUE",
+    "MATERIALIZED",
+    "GLOBAL",
+    "LOCAL",
+    "UNLOGGED",
+    "CONCURRENTLY",
+    "EXTERNAL",
+    "FOREIGN",
+    "VIRTUAL",
+    "ONLINE",
+    "OFFLINE",
+    "RECURSIVE",
+    "TRUSTED",
+    "PROCEDURAL",
+];
+
+/// Object types whose DDL administers the server rather than a schema.
+const ADMIN_OBJECTS: &[&str] = &[
+    "USER",
+    "ROLE",
+    "SYSTEM",
+    "SERVER",
+    "TABLESPACE",
+    "EXTENSION",
+    "PUBLICATION",
+    "SUBSCRIPTION",
+    "INSTANCE",
+    "RESOURCE",
+];
+
+const ADMIN_VERBS: &[&str] = &[
+    "GRANT",
+    "REVOKE",
+    "SHUTDOWN",
+    "KILL",
+    "FLUSH",
+    "PURGE",
+    "INSTALL",
+    "UNINSTALL",
+    "VACUUM",
+    "REINDEX",
+    "CLUSTER",
+    "CHECKPOINT",
+    "PRAGMA",
+    "ATTACH",
+    "DETACH",
+    "CHANGE",
+    "STOP",
+    "REASSIGN",
+    "SECURITY",
+    "IMPORT",
+];
+
+/// Verbs that start the main statement after the queries of a `WITH`.
+const WITH_MAIN_VERBS: &[&str] = &["SELECT", "INSERT", "UPDATE", "DELETE", "MERGE"];
+
+const READ_VERBS: &[&str] = &[
+    "SELECT", "SHOW", "VALUES", "TABLE", "HELP", "DESCRIBE", "DESC", "FETCH",
+];
+
+const SESSION_VERBS: &[&str] = &[
+    "BEGIN",
+    "COMMIT",
+    "ROLLBACK",
+    "SAVEPOINT",
+    "RELEASE",
+    "END",
+    "ABORT",
+    "USE",
+    "SET",
+    "DISCARD",
+    "DEALLOCATE",
+    "PREPARE",
+    "RESET",
+    "START",
+    "LISTEN",
+    "UNLISTEN",
+    "NOTIFY",
+];
+
+fn classify_tokens(tokens: &[Token]) -> Option<Statement> {
+    let first = keyword(tokens, 0)?;
+    let statement = match first.as_str() {
+        // EXPLAIN ANALYZE runs the statement
+        "EXPLAIN" | "DESCRIBE" | "DESC" => match keyword(tokens, 1).as_deref() {
+            Some("ANALYZE") if tokens.len() > 2 => return classify_tokens(&tokens[2..]),
+            _ => Statement::new(first, StatementKind::Read),
+        },
+        "WITH" => {
+            let main = find_top_level(tokens, 1, WITH_MAIN_VERBS)?;
+            return classify_tokens(&tokens[main..]);
+        }
+        "CREATE" | "DROP" | "ALTER" => {
+            let i = skip_keywords(tokens, 1, DDL_MODIFIERS);
+            let Some(object_type) = keyword(tokens, i) else {
+                return Some(Statement::new(first, StatementKind::Ddl));
+            };
+            let kind = if ADMIN_OBJECTS.contains(&object_type.as_str()) {
+                StatementKind::Admin
+            } else {
+                StatementKind::Ddl
+            };
+            let mut statement = Statement::new(format!("{first} {object_type}"), kind);
+            statement.object = name(
+                tokens,
+                skip_keywords(tokens, i + 1, &["IF", "NOT", "EXISTS", "ONLY"]),
+            );
+            statement
+        }
+        "TRUNCATE" => {
+            let mut statement = Statement::new(first, StatementKind::Ddl);
+            statement.object = name(tokens, skip_keywords(tokens, 1, &["TABLE", "ONLY"]));
+            statement
+        }
+        "RENAME" | "COMMENT" => Statement::new(first, StatementKind::Ddl),
+        "DELETE" => {
+            let mut statement = Statement::new(first, StatementKind::Dml);
+            statement.object = name(
+                tokens,
+                skip_keywords(
+                    tokens,
+                    1,
+                    &["LOW_PRIORITY", "QUICK", "IGNORE", "FROM", "ONLY"],
+                ),
+            );
+            statement.unbounded = find_top_level(tokens, 1, &["WHERE"]).is_none();
+            statement
+        }
+        "UPDATE" => {
+            let mut statement = Statement::new(first, StatementKind::Dml);
+            statement.object = name(
+                tokens,
+                skip_keywords(tokens, 1, &["LOW_PRIORITY", "IGNORE", "ONLY", "OR"]),
+            );
+            statement.unbounded = find_top_level(tokens, 1, &["WHERE"]).is_none();
+            statement
+        }
+        "INSERT" | "REPLACE" | "UPSERT" | "MERGE" => {
+            let mut statement = Statement::new(first, StatementKind::Dml);
+            statement.object =
+                find_top_level(tokens, 1, &["INTO"]).and_then(|i| name(tokens, i + 1));
+            statement
+        }
+        "COPY" => {
+            // COPY … FROM/TO PROGRAM runs a command on the server
+            let program = find_top_level(tokens, 1, &["PROGRAM"]).is_some();
+            let mut statement = if program {
+                Statement::new("COPY PROGRAM", StatementKind::Admin)
+            } else {
+                Statement::new(first, StatementKind::Dml)
+            };
+            statement.object = name(tokens, 1);
+            statement
+        }
+        "LOAD" => match keyword(tokens, 1).as_deref() {
+            Some(what @ ("DATA" | "XML")) => {
+                let mut statement = Statement::new(format!("LOAD {what}"), StatementKind::Dml);
+                if find_top_level(tokens, 2, &["LOCAL"]).is_some()
+                    && let Some(i) = find_top_level(tokens, 2, &["INFILE"])
+                    && let Some(Token::Str(file)) = tokens.get(i + 1)
+                {
+                    statement.file = Some(file.clone());
+                }
+                statement.object =
+                    find_top_level(tokens, 2, &["TABLE"]).and_then(|i| name(tokens, i + 1));
+                statement
+            }
+            _ => Statement::new(first, StatementKind::Admin),
+        },
+        "SELECT" => {
+            match find_top_level(tokens, 1, &["OUTFILE", "DUMPFILE"])
+                .and_then(|i| keyword(tokens, i))
+            {
+                Some(target) => {
+                    Statement::new(format!("SELECT INTO {target}"), StatementKind::Admin)
+                }
+                None => Statement::new(first, StatementKind::Read),
+            }
+        }
+        "SET"
+            if matches!(
+                keyword(tokens, 1).as_deref(),
+                Some("GLOBAL" | "PERSIST" | "PERSIST_ONLY")
+            ) =>
+        {
+            Statement::new("SET GLOBAL", StatementKind::Admin)
+        }
+        // START SLAVE/REPLICA/GROUP_REPLICATION, but not START TRANSACTION
+        "START" if keyword(tokens, 1).is_some_and(|k| k != "TRANSACTION") => Statement::new(
+            format!("START {}", keyword(tokens, 1).unwrap_or_default()),
+            StatementKind::Admin,
+        ),
+        "RESET"
+            if matches!(
+                keyword(tokens, 1).as_deref(),
+                Some("MASTER" | "SLAVE" | "REPLICA" | "PERSIST" | "BINARY")
+            ) =>
+        {
+            Statement::new(
+                format!("RESET {}", keyword(tokens, 1).unwrap_or_default()),
+                StatementKind::Admin,
+            )
+        }
+        "ATTACH" => {
+            let mut statement = Statement::new(first, StatementKind::Admin);
+            let i = skip_keywords(tokens, 1, &["DATABASE"]);
+            if let Some(Token::Str(file)) = tokens.get(i) {
+                statement.file = Some(file.clone());
+            }
+            statement.object = find_top_level(tokens, i, &["AS"]).and_then(|i| name(tokens, i + 1));
+            statement
+        }
+        "PRAGMA" => {
+            let mut statement = Statement::new(first, StatementKind::Admin);
+            statement.object = name(tokens, 1);
+            statement
+        }
+        verb if ADMIN_VERBS.contains(&verb) => Statement::new(first, StatementKind::Admin),
+        verb if READ_VERBS.contains(&verb) => Statement::new(first, StatementKind::Read),
+        verb if SESSION_VERBS.contains(&verb) => Statement::new(first, StatementKind::Session),
+        _ => Statement::new(first, StatementKind::Other),
+    };
+    Some(statement)
+}
@@ -0,0 +1,300 @@
+use eyre::Result;
+use serde::Deserialize;
+use std::net::IpAddr;
+
+use crate::config::RuleAction;
+use crate::hosts::{self, HostPattern};
+use crate::network_analyzer::parse_url;
+
+/// Rules for WebFetch URLs and WebSearch domains, as written in YAML:
+///
+/// ```yaml
+/// web:
+///   schemes: [https]
+///   allow: ["docs.rs", "github.com/rust-lang/"]
+///   deny: ["*.pastebin.com"]
+///   private_addresses: deny
+///   query_strings: ask
+/// ```
+///
+/// `allow` and `deny` entries are a host glob, optionally followed by a
+/// path prefix (`github.com/rust-lang/`). Private addresses and query
+/// strings are allowed unless configured.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WebPolicy {
+    /// URL schemes WebFetch may use.
+    #[serde(default = "default_schemes")]
+    pub schemes: Vec<String>,
+    /// When non-empty, only matching URLs may be fetched.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// URLs that are never fetched, even when allowed.
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Action for loopback, private and link-local addresses and internal
+    /// hostnames (`localhost`, `*.internal`, single-label names).
+    #[serde(default = "default_action")]
+    pub private_addresses: RuleAction,
+    /// Action for URLs with a query string to hosts not on `allow`.
+    #[serde(default = "default_action")]
+    pub query_strings: RuleAction,
+}
+
+impl Default for WebPolicy {
+    fn default() -> Self {
+        Self {
+            schemes: default_schemes(),
+            allow: Vec::new(),
+            deny: Vec::new(),
+            private_addresses: default_action(),
+            query_strings: default_action(),
+        }
+    }
+}
+
+fn default_schemes() -> Vec<String> {
+    vec!["http".to_string(), "https".to_string()]
+}
+
+fn default_action() -> RuleAction {
+    RuleAction::Allow
+}
+
+/// Hostname suffixes that only resolve inside a private network.
+const INTERNAL_SUFFIXES: &[&str] = &[
+    ".localhost",
+    ".local",
+    ".internal",
+    ".lan",
+    ".localdomain",
+    ".home.arpa",
+    ".intranet",
+];
+
+/// A compiled `host[/path-prefix]` entry.
+struct UrlPattern {
+    host: HostPattern,
+    path_prefix: Option<String>,
+}
+
+impl UrlPattern {
+    fn matches(&self, host: &str, path: &str) -> bool {
+        self.host.matches(host)
+            && self
+                .path_prefix
+                .as_ref()
+                .is_none_or(|prefix| normalize_path(path).starts_with(prefix.as_str()))
+    }
+}
+
+/// A URL path as the server sees it: percent-escapes decoded, `\` read as
+/// `/`, and `.` and `..` segments resolved, so `/rust-lang/%2e%2e/evil` is
+/// `/evil`.
+fn normalize_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    let decoded = String::from_utf8_lossy(&decoded).replace('\\', "/");
+    let mut segments: Vec<&str> = Vec::new();
+    let mut parts = decoded.split('/').skip(1).peekable();
+    while let Some(segment) = parts.next() {
+        let last = parts.peek().is_none();
+        match segment {
+            "." | ".." => {
+                if segment == ".." {
+                    segments.pop();
+                }
+                if last {
+                    segments.push("");
+                }
+            }
+            _ => segments.push(segment),
+        }
+    }
+    format!("/{}", segments.join("/"))
+}
+
+/// Checks WebFetch URLs and WebSearch domains against a [`WebPolicy`].
+pub struct WebRule {
+    schemes: Vec<String>,
+    allow: Vec<UrlPattern>,
+    deny: Vec<UrlPattern>,
+    private_addresses: RuleAction,
+    query_strings: RuleAction,
+}
+
+impl WebRule {
+    pub fn new(policy: &WebPolicy) -> Result<Self> {
+        Ok(Self {
+            schemes: policy
+                .schemes
+                .iter()
+                .map(|s| s.to_ascii_lowercase())
+                .collect(),
+            allow: compile(&policy.allow)?,
+            deny: compile(&policy.deny)?,
+            private_addresses: policy.private_addresses,
+            query_strings: policy.query_strings,
+        })
+    }
+
+    /// Check a WebFetch URL. Returns the action and reason of the strictest
+    /// rule it breaks, or None if it may be fetched.
+    pub fn check_url(&self, url: &str) -> Option<(RuleAction, String)> {
+        let Some(parsed) = parse_url(url) else {
+            return Some((
+                RuleAction::Deny,
+                format!("Blocked by `clarg`: '{url}' is not a URL with a host"),
+            ));
+        };
+        if !self.schemes.contains(&parsed.scheme) {
+            return Some((
+                RuleAction::Deny,
+                format!(
+                    "Blocked by `clarg`: '{url}' uses the scheme '{}' (allowed: {})",
+                    parsed.scheme,
+                    self.schemes.join(", ")
+                ),
+            ));
+        }
+        let mut found = self.check_host(&parsed.host, &parsed.path, url);
+        if found.is_none()
+            && parsed.query.as_deref().is_some_and(|q| !q.is_empty())
+            && !self
+                .allow
+                .iter()
+                .any(|p| p.matches(&parsed.host, &parsed.path))
+        {
+            found = self.query_strings.blocks().then(|| {
+                (
+                    self.query_strings,
+                    format!(
+                        "Blocked by `clarg`: '{url}' sends a query string to '{}', which is not on the web allowlist — data can leave through URLs",
+                        parsed.host
+                    ),
+                )
+            });
+        }
+        found
+    }
+
+    /// Check a WebSearch `allowed_domains` entry.
+    pub fn check_domain(&self, domain: &str) -> Option<(RuleAction, String)> {
+        let host = domain.to_ascii_lowercase();
+        self.check_host(&host, "/", domain)
+    }
+
+    /// Check a WebSearch without `allowed_domains`. It may return any site
+    /// (`blocked_domains` only removes some), so it is refused when there
+    /// is a web allowlist.
+    pub fn check_unrestricted_search(&self, query: &str) -> Option<(RuleAction, String)> {
+        (!self.allow.is_empty()).then(|| {
+            (
+                RuleAction::Deny,
+                format!(
+                    "Blocked by `clarg`: the search '{query}' sets no allowed_domains, so it may reach sites outside the web allowlist"
+                ),
+            )
+        })
+    }
+
+    fn check_host(&self, host: &str, path: &str, subject: &str) -> Option<(RuleAction, String)> {
+        if self.deny.iter().any(|p| p.matches(host, path)) {
+            return Some((
+                RuleAction::Deny,
+                format!("Blocked by `clarg`: '{subject}' is on the web denylist"),
+            ));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|p| p.matches(host, path)) {
+            return Some((
+                RuleAction::Deny,
+                format!("Blocked by `clarg`: '{subject}' is not on the web allowlist"),
+            ));
+        }
+        if self.private_addresses.blocks() && is_internal(host) {
+            return Some((
+                self.private_addresses,
+                format!(
+                    "Blocked by `clarg`: '{subject}' points at '{host}', a private, loopback or internal address"
+                ),
+            ));
+        }
+        None
+    }
+}
+
+impl RuleAction {
+    fn blocks(self) -> bool {
+        self != RuleAction::Allow
+    }
+}
+
+fn compile(patterns: &[String]) -> Result<Vec<UrlPattern>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            // CIDR ranges keep their `/prefix`; other entries may end in a path
+            let (host, path_prefix) = match pattern.split_once('/') {
+                Some((host, prefix)) if hosts::parse_ip(host).is_none() => {
+                    (host.to_string(), Some(format!("/{prefix}")))
+                }
+                _ => (pattern.clone(), None),
+            };
+            let host = hosts::compile(&[host])?.remove(0);
+            Ok(UrlPattern { host, path_prefix })
+        })
+        .collect()
+}
+
+/// Loopback, private, link-local and unique-local addresses, and hostnames
+/// that only resolve inside a private network.
+fn is_internal(host: &str) -> bool {
+    if let Some(ip) = hosts::parse_ip(host) {
+        return match ip {
+            IpAddr::V4(v4) => is_internal_v4(v4),
+            IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+                Some(v4) => is_internal_v4(v4),
+                None => {
+                    let first = v6.segments()[0];
+                    v6.is_loopback()
+                        || v6.is_unspecified()
+                        || (first & 0xfe00) == 0xfc00 // unique local fc00::/7
+                        || (first & 0xffc0) == 0xfe80 // link-local fe80::/10
+                }
+            },
+        };
+    }
+    let host = host.trim_end_matches('.');
+    host == "localhost"
+        || !host.contains('.')
+        || INTERNAL_SUFFIXES
+            .iter()
+            .any(|suffix| host.ends_with(suffix))
+}
+
+fn is_internal_v4(ip: std::net::Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        // shared address space (carrier-grade NAT) 100.64.0.0/10
+        || (a == 100 && (b & 0xc0) == 64)
+}
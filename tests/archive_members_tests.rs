@@ -0,0 +1,5 @@
+// Test entry point for archive_members module tests
+// Tests listing tar, gzip-compressed tar and zip members and where they extract to
+
+#[path = "archive_members_tests/mod.rs"]
+mod archive_members_tests;
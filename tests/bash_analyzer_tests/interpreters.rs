@@ -0,0 +1,294 @@
+use clarg::bash_analyzer::{Finding, PathContext, analyze_command, extract_paths};
+use clarg::facts::Fact;
+
+use super::blocked;
+
+fn scripts(cmd: &str) -> Vec<String> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter(|p| matches!(p.context, PathContext::ExecTarget))
+        .map(|p| p.raw)
+        .collect()
+}
+
+fn option_paths(cmd: &str) -> Vec<(String, &'static str, bool)> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter_map(|p| match p.context {
+            PathContext::InterpreterPath { label, write } => Some((p.raw, label, write)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn permissions(cmd: &str) -> Vec<Fact> {
+    analyze_command(cmd)
+        .findings
+        .into_iter()
+        .filter_map(|f| match f {
+            Finding::Fact(fact) if fact.name == "interpreter.permission" => Some(fact),
+            _ => None,
+        })
+        .collect()
+}
+
+// ============================================================================
+// Script operand after value-taking options
+// ============================================================================
+
+#[test]
+fn test_python_value_flags_skipped() {
+    assert_eq!(scripts("python -W ignore x.py"), vec!["x.py"]);
+    assert_eq!(
+        scripts("python3 -X dev -u -W error::DeprecationWarning x.py"),
+        vec!["x.py"]
+    );
+    assert_eq!(scripts("python -Wignore /tmp/x.py"), vec!["/tmp/x.py"]);
+    assert!(blocked("python -W ignore /tmp/x.py"));
+}
+
+#[test]
+fn test_python_module_has_no_script() {
+    assert!(scripts("python -m pytest tests/").is_empty());
+    assert!(scripts("python3 -u -m pip install requests").is_empty());
+    assert!(scripts("python -mvenv .venv").is_empty());
+}
+
+#[test]
+fn test_double_dash_ends_options() {
+    assert_eq!(scripts("python -- -weird.py"), vec!["-weird.py"]);
+}
+
+#[test]
+fn test_stdin_program_has_no_script() {
+    assert!(scripts("python - arg").is_empty());
+}
+
+#[test]
+fn test_script_arguments_not_parsed() {
+    assert_eq!(scripts("python x.py -m y /etc/z"), vec!["x.py"]);
+    assert!(option_paths("node app.js --require /abs/hook.js").is_empty());
+}
+
+#[test]
+fn test_perl_bundled_code_flag() {
+    assert!(scripts("perl -ne 'print' file.txt").is_empty());
+    assert!(scripts("perl -pi.bak -e 's/a/b/' f").is_empty());
+    assert_eq!(scripts("perl -Mstrict -w script.pl"), vec!["script.pl"]);
+}
+
+#[test]
+fn test_php_f_names_script() {
+    assert_eq!(
+        scripts("php -d display_errors=1 -f /tmp/x.php"),
+        vec!["/tmp/x.php"]
+    );
+    assert!(blocked("php -f /tmp/x.php"));
+}
+
+#[test]
+fn test_source_unchanged() {
+    assert_eq!(scripts("source ./env.sh"), vec!["./env.sh"]);
+    assert_eq!(scripts("tsx src/main.ts"), vec!["src/main.ts"]);
+}
+
+// ============================================================================
+// Path-valued options
+// ============================================================================
+
+#[test]
+fn test_node_require_path() {
+    assert_eq!(
+        scripts("node --require /abs/hook.js app.js"),
+        vec!["app.js"]
+    );
+    assert_eq!(
+        option_paths("node --require /abs/hook.js app.js"),
+        vec![("/abs/hook.js".to_string(), "preloaded module", false)]
+    );
+    assert!(blocked("node --require /abs/hook.js app.js"));
+    assert!(blocked("node --import=/abs/hook.mjs app.js"));
+}
+
+#[test]
+fn test_node_require_module_name_ignored() {
+    assert!(option_paths("node -r dotenv/config app.js").is_empty());
+    assert!(option_paths("node -r ts-node/register app.ts").is_empty());
+    assert_eq!(scripts("node -r dotenv/config app.js"), vec!["app.js"]);
+}
+
+#[test]
+fn test_node_env_file_and_report_dir() {
+    assert_eq!(
+        option_paths("node --env-file=../.env app.js"),
+        vec![("../.env".to_string(), "env file", false)]
+    );
+    assert_eq!(
+        option_paths("node --report-dir /var/reports app.js"),
+        vec![("/var/reports".to_string(), "report directory", true)]
+    );
+}
+
+#[test]
+fn test_ruby_load_path() {
+    assert_eq!(
+        option_paths("ruby -I ../lib app.rb"),
+        vec![("../lib".to_string(), "load path", false)]
+    );
+    assert_eq!(
+        option_paths("ruby -Ilib -rjson app.rb"),
+        vec![("lib".to_string(), "load path", false)]
+    );
+    assert_eq!(scripts("ruby -I ../lib app.rb"), vec!["app.rb"]);
+    assert!(blocked("ruby -I ../lib app.rb"));
+    assert!(!blocked("ruby -Ilib app.rb"));
+}
+
+#[test]
+fn test_ruby_chdir_and_require_path() {
+    assert_eq!(
+        option_paths("ruby -C /srv app.rb"),
+        vec![("/srv".to_string(), "working directory", false)]
+    );
+    assert_eq!(
+        option_paths("ruby -r ./helper.rb app.rb"),
+        vec![("./helper.rb".to_string(), "preloaded module", false)]
+    );
+}
+
+#[test]
+fn test_perl_include_path() {
+    assert_eq!(
+        option_paths("perl -I/etc x.pl"),
+        vec![("/etc".to_string(), "include path", false)]
+    );
+    assert_eq!(scripts("perl -I/etc x.pl"), vec!["x.pl"]);
+    assert!(blocked("perl -I/etc x.pl"));
+}
+
+#[test]
+fn test_php_config_path() {
+    assert_eq!(
+        option_paths("php -c /etc/php.ini x.php"),
+        vec![("/etc/php.ini".to_string(), "php.ini path", false)]
+    );
+    assert_eq!(scripts("php -c /etc/php.ini x.php"), vec!["x.php"]);
+}
+
+#[test]
+fn test_bun_cwd() {
+    assert_eq!(
+        option_paths("bun --cwd ../other run dev"),
+        vec![("../other".to_string(), "working directory", false)]
+    );
+    assert!(blocked("bun --cwd ../other run dev"));
+    assert!(!blocked("bun --cwd packages/app run dev"));
+}
+
+#[test]
+fn test_bun_named_scripts_and_files() {
+    assert!(scripts("bun run dev").is_empty());
+    assert_eq!(scripts("bun run script.ts"), vec!["script.ts"]);
+    assert_eq!(scripts("bun /tmp/script.js"), vec!["/tmp/script.js"]);
+    assert_eq!(scripts("bun test ./src"), vec!["./src"]);
+}
+
+#[test]
+fn test_bun_other_subcommands() {
+    assert!(scripts("bun install").is_empty());
+    assert!(scripts("bun add zod").is_empty());
+    assert!(blocked("bun add /tmp/pkg"));
+}
+
+// ============================================================================
+// deno
+// ============================================================================
+
+#[test]
+fn test_deno_subcommand_options() {
+    assert_eq!(
+        scripts("deno run --config deno.json main.ts"),
+        vec!["main.ts"]
+    );
+    assert_eq!(scripts("deno main.ts"), vec!["main.ts"]);
+    assert!(scripts("deno task dev").is_empty());
+    assert_eq!(
+        option_paths("deno run -c /etc/deno.json main.ts"),
+        vec![("/etc/deno.json".to_string(), "config file", false)]
+    );
+}
+
+#[test]
+fn test_deno_allow_all() {
+    for cmd in [
+        "deno run -A x.ts",
+        "deno run --allow-all x.ts",
+        "deno run -qA x.ts",
+    ] {
+        let facts = permissions(cmd);
+        assert_eq!(facts.len(), 1, "{cmd}");
+        assert_eq!(facts[0].attr("tool"), Some("deno"));
+        assert_eq!(facts[0].attr("permission"), Some("all"));
+        assert_eq!(facts[0].attr("broad"), Some("true"));
+    }
+}
+
+#[test]
+fn test_deno_allow_read_root() {
+    let facts = permissions("deno run --allow-read=/ x.ts");
+    assert_eq!(facts[0].attr("permission"), Some("read"));
+    assert_eq!(facts[0].attr("scope"), Some("/"));
+    assert_eq!(facts[0].attr("broad"), Some("true"));
+    assert_eq!(
+        option_paths("deno run --allow-read=/ x.ts"),
+        vec![("/".to_string(), "--allow-read scope", false)]
+    );
+    assert!(blocked("deno run --allow-read=/ x.ts"));
+}
+
+#[test]
+fn test_deno_scoped_permissions() {
+    let facts = permissions("deno run --allow-read=./data,./config --allow-write=out x.ts");
+    assert_eq!(facts.len(), 2);
+    assert_eq!(facts[0].attr("scope"), Some("./data,./config"));
+    assert_eq!(facts[0].attr("broad"), Some("false"));
+    assert_eq!(facts[1].attr("permission"), Some("write"));
+    assert_eq!(
+        option_paths("deno run --allow-write=out x.ts"),
+        vec![("out".to_string(), "--allow-write scope", true)]
+    );
+    assert!(!blocked(
+        "deno run --allow-read=./data --allow-write=out x.ts"
+    ));
+}
+
+#[test]
+fn test_deno_unscoped_permission_is_broad() {
+    let facts = permissions("deno run --allow-read --allow-env x.ts");
+    assert_eq!(facts.len(), 2);
+    assert!(
+        facts
+            .iter()
+            .all(|f| f.attr("broad") == Some("true") && f.attr("scope") == Some(""))
+    );
+    assert_eq!(
+        permissions("deno run -R=~ x.ts")[0].attr("broad"),
+        Some("true")
+    );
+}
+
+#[test]
+fn test_deno_allow_net_hosts() {
+    let facts = permissions("deno run --allow-net=api.example.com:443,[::1]:8000 x.ts");
+    assert_eq!(facts[0].attr("host"), Some("api.example.com,::1"));
+    assert_eq!(facts[0].attr("broad"), Some("false"));
+    assert_eq!(
+        permissions("deno run --allow-net x.ts")[0].attr("host"),
+        None
+    );
+}
+
+#[test]
+fn test_deno_flags_after_script_belong_to_script() {
+    assert!(permissions("deno run x.ts --allow-all").is_empty());
+}
@@ -0,0 +1,173 @@
+use clarg::bash_analyzer::{Finding, PathContext, analyze_command, extract_paths};
+use clarg::facts::Fact;
+
+fn listen(cmd: &str) -> Fact {
+    analyze_command(cmd)
+        .findings
+        .into_iter()
+        .find_map(|f| match f {
+            Finding::Fact(fact) if fact.name == "network.listen" => Some(fact),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("no 'network.listen' fact for: {cmd}"))
+}
+
+fn no_listen(cmd: &str) -> bool {
+    !analyze_command(cmd)
+        .findings
+        .iter()
+        .any(|f| matches!(f, Finding::Fact(fact) if fact.name == "network.listen"))
+}
+
+fn served(cmd: &str) -> Vec<String> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter(|p| matches!(p.context, PathContext::ServedDirectory))
+        .map(|p| p.raw)
+        .collect()
+}
+
+// ============================================================================
+// Interpreter file servers
+// ============================================================================
+
+#[test]
+fn test_python_http_server_defaults() {
+    let f = listen("python3 -m http.server");
+    assert_eq!(f.attr("tool"), Some("python"));
+    assert_eq!(f.attr("bind"), Some("0.0.0.0"));
+    assert_eq!(f.attr("port"), Some("8000"));
+    assert_eq!(f.attr("loopback"), Some("false"));
+    assert_eq!(f.attr("dir"), Some("."));
+    assert_eq!(served("python3 -m http.server"), vec!["."]);
+}
+
+#[test]
+fn test_python_http_server_options() {
+    let f = listen("python3 -m http.server 9000 --bind 127.0.0.1 --directory=/");
+    assert_eq!(f.attr("port"), Some("9000"));
+    assert_eq!(f.attr("bind"), Some("127.0.0.1"));
+    assert_eq!(f.attr("loopback"), Some("true"));
+    assert_eq!(served("python -m http.server -d /"), vec!["/"]);
+    assert_eq!(
+        listen("python -W ignore -mhttp.server -b ::1").attr("loopback"),
+        Some("true")
+    );
+    assert_eq!(
+        listen("python2 -m SimpleHTTPServer 8080").attr("port"),
+        Some("8080")
+    );
+}
+
+#[test]
+fn test_python_script_is_not_a_server() {
+    assert!(no_listen("python3 app.py -m http.server"));
+    assert!(no_listen("python3 -m pytest"));
+    assert!(no_listen("python3 -c 'import http.server'"));
+    // The module name isn't mistaken for a script
+    assert!(
+        extract_paths("python3 -m http.server")
+            .iter()
+            .all(|p| p.raw != "http.server")
+    );
+}
+
+#[test]
+fn test_php_builtin_server() {
+    let f = listen("php -S 0.0.0.0:80");
+    assert_eq!(f.attr("bind"), Some("0.0.0.0"));
+    assert_eq!(f.attr("port"), Some("80"));
+    assert_eq!(served("php -S localhost:8000 -t public"), vec!["public"]);
+    assert_eq!(
+        listen("php -S localhost:8000").attr("loopback"),
+        Some("true")
+    );
+    assert!(no_listen("php artisan migrate"));
+}
+
+#[test]
+fn test_ruby_un_httpd() {
+    let f = listen("ruby -run -ehttpd /srv -p8000");
+    assert_eq!(f.attr("port"), Some("8000"));
+    assert_eq!(f.attr("dir"), Some("/srv"));
+    assert_eq!(
+        listen("ruby -r un -e httpd . -b 127.0.0.1").attr("loopback"),
+        Some("true")
+    );
+    assert!(no_listen("ruby -e httpd"));
+}
+
+// ============================================================================
+// Standalone servers
+// ============================================================================
+
+#[test]
+fn test_serve_and_npx() {
+    let f = listen("npx serve /");
+    assert_eq!(f.attr("tool"), Some("serve"));
+    assert_eq!(f.attr("dir"), Some("/"));
+    assert_eq!(f.attr("port"), Some("3000"));
+    let f = listen("npx serve@14 -l tcp://127.0.0.1:5000 dist");
+    assert_eq!(f.attr("bind"), Some("127.0.0.1"));
+    assert_eq!(f.attr("port"), Some("5000"));
+    assert_eq!(listen("serve -l 8080 build").attr("port"), Some("8080"));
+    assert!(no_listen("npx eslint ."));
+}
+
+#[test]
+fn test_http_server_and_busybox() {
+    let f = listen("http-server ./dist -p 9000 -a 127.0.0.1");
+    assert_eq!(f.attr("port"), Some("9000"));
+    assert_eq!(f.attr("loopback"), Some("true"));
+    assert_eq!(served("http-server ./dist -p 9000"), vec!["./dist"]);
+    let f = listen("busybox httpd -f -p 0.0.0.0:8080 -h /srv");
+    assert_eq!(f.attr("bind"), Some("0.0.0.0"));
+    assert_eq!(f.attr("dir"), Some("/srv"));
+    assert!(no_listen("busybox ls"));
+}
+
+#[test]
+fn test_app_servers_default_to_loopback() {
+    assert_eq!(listen("flask run").attr("loopback"), Some("true"));
+    assert_eq!(
+        listen("flask run --host=0.0.0.0").attr("loopback"),
+        Some("false")
+    );
+    let f = listen("uvicorn app:app --host 0.0.0.0 --port 80");
+    assert_eq!(f.attr("port"), Some("80"));
+    assert_eq!(f.attr("loopback"), Some("false"));
+    assert_eq!(listen("uvicorn main:app").attr("dir"), None);
+}
+
+// ============================================================================
+// Raw listeners
+// ============================================================================
+
+#[test]
+fn test_nc_listen() {
+    let f = listen("nc -lvnp 4444 -e /bin/sh");
+    assert_eq!(f.attr("tool"), Some("nc"));
+    assert_eq!(f.attr("port"), Some("4444"));
+    assert_eq!(f.attr("bind"), Some("0.0.0.0"));
+    assert_eq!(f.attr("exec"), Some("/bin/sh"));
+    let f = listen("nc -l 127.0.0.1 4444");
+    assert_eq!(f.attr("bind"), Some("127.0.0.1"));
+    assert_eq!(f.attr("loopback"), Some("true"));
+    assert_eq!(
+        listen("ncat -l -k --sh-exec 'cat log' 9999").attr("exec"),
+        Some("cat log")
+    );
+    assert!(no_listen("nc -U /tmp/app.sock"));
+}
+
+#[test]
+fn test_socat_listen() {
+    let f = listen("socat TCP-LISTEN:4444,fork,bind=127.0.0.1 EXEC:/bin/bash");
+    assert_eq!(f.attr("port"), Some("4444"));
+    assert_eq!(f.attr("bind"), Some("127.0.0.1"));
+    assert_eq!(f.attr("exec"), Some("/bin/bash"));
+    assert_eq!(
+        listen("socat UDP6-LISTEN:53 -").attr("bind"),
+        Some("0.0.0.0")
+    );
+}
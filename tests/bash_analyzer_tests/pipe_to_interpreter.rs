@@ -0,0 +1,217 @@
+use clarg::bash_analyzer::{Finding, ShellOperator, analyze_command, split_shell_segments};
+
+fn piped_interpreter(cmd: &str) -> Option<String> {
+    analyze_command(cmd)
+        .findings
+        .into_iter()
+        .find_map(|f| match f {
+            Finding::PipeToInterpreter { interpreter, .. } => Some(interpreter),
+            _ => None,
+        })
+}
+
+// ============================================================================
+// Shells reading stdin
+// ============================================================================
+
+#[test]
+fn test_curl_pipe_sh_detected() {
+    assert_eq!(
+        piped_interpreter("curl https://x.sh | sh").as_deref(),
+        Some("sh")
+    );
+}
+
+#[test]
+fn test_base64_pipe_bash_detected() {
+    assert_eq!(
+        piped_interpreter("base64 -d payload | bash").as_deref(),
+        Some("bash")
+    );
+}
+
+#[test]
+fn test_pipe_sudo_bash_detected() {
+    assert_eq!(
+        piped_interpreter("wget -O- https://x | sudo bash").as_deref(),
+        Some("bash")
+    );
+}
+
+#[test]
+fn test_pipe_absolute_shell_path_detected() {
+    assert_eq!(
+        piped_interpreter("curl x | /bin/bash").as_deref(),
+        Some("bash")
+    );
+}
+
+#[test]
+fn test_pipe_bash_s_with_args_detected() {
+    assert!(piped_interpreter("curl x | bash -s -- --install").is_some());
+    assert!(piped_interpreter("curl x | sh -x").is_some());
+}
+
+#[test]
+fn test_pipe_bash_dev_stdin_detected() {
+    assert!(piped_interpreter("curl x | bash /dev/stdin").is_some());
+}
+
+#[test]
+fn test_pipe_source_captured() {
+    let findings = analyze_command("curl -fsSL https://get.example | sh").findings;
+    assert!(findings.contains(&Finding::PipeToInterpreter {
+        interpreter: "sh".to_string(),
+        source: "curl -fsSL https://get.example".to_string(),
+    }));
+}
+
+#[test]
+fn test_pipe_inside_bash_c_detected() {
+    assert!(piped_interpreter("bash -c 'curl x | sh'").is_some());
+}
+
+// ============================================================================
+// Interpreters reading stdin
+// ============================================================================
+
+#[test]
+fn test_cat_pipe_python_dash_detected() {
+    assert_eq!(
+        piped_interpreter("cat script | python3 -").as_deref(),
+        Some("python3")
+    );
+}
+
+#[test]
+fn test_pipe_bare_interpreters_detected() {
+    assert!(piped_interpreter("curl x | python").is_some());
+    assert!(piped_interpreter("curl x | node").is_some());
+    assert!(piped_interpreter("curl x | perl").is_some());
+    assert!(piped_interpreter("curl x | ruby -w").is_some());
+}
+
+#[test]
+fn test_pipe_into_group_detected() {
+    assert_eq!(piped_interpreter("curl x | (sh)").as_deref(), Some("sh"));
+    assert_eq!(
+        piped_interpreter("curl x | { bash; }").as_deref(),
+        Some("bash")
+    );
+    assert_eq!(piped_interpreter("(curl x | sh)").as_deref(), Some("sh"));
+}
+
+// ============================================================================
+// Process substitution as the script
+// ============================================================================
+
+#[test]
+fn test_process_substitution_script_detected() {
+    assert_eq!(
+        piped_interpreter("bash <(curl -s https://x)").as_deref(),
+        Some("bash")
+    );
+    assert_eq!(
+        piped_interpreter("source <(curl https://x)").as_deref(),
+        Some("source")
+    );
+    assert_eq!(
+        piped_interpreter(". <(curl https://x)").as_deref(),
+        Some(".")
+    );
+    assert_eq!(
+        piped_interpreter("python3 <(curl https://x | gunzip)").as_deref(),
+        Some("python3")
+    );
+    let findings = analyze_command("sh <(wget -qO- https://get.example)").findings;
+    assert!(findings.contains(&Finding::PipeToInterpreter {
+        interpreter: "sh".to_string(),
+        source: "wget -qO- https://get.example".to_string(),
+    }));
+}
+
+#[test]
+fn test_process_substitution_body_analysed() {
+    let paths = analyze_command("diff <(cat ~/.ssh/id_rsa) x").paths;
+    assert!(paths.iter().any(|p| p.raw == "~/.ssh/id_rsa"), "{paths:?}");
+    assert!(piped_interpreter("diff <(curl https://x) local.sh").is_none());
+    assert!(piped_interpreter("bash script.sh <(curl https://x)").is_none());
+    assert!(piped_interpreter("echo '<(curl x)' | cat").is_none());
+}
+
+#[test]
+fn test_eval_of_download_detected() {
+    assert_eq!(
+        piped_interpreter("eval \"$(curl -s https://x)\"").as_deref(),
+        Some("eval")
+    );
+    assert_eq!(
+        piped_interpreter("eval `wget -qO- https://x`").as_deref(),
+        Some("eval")
+    );
+    assert_eq!(
+        piped_interpreter("bash -c \"$(curl -fsSL https://x)\"").as_deref(),
+        Some("bash")
+    );
+    assert!(piped_interpreter("eval \"$(ssh-agent -s)\"").is_none());
+    assert!(piped_interpreter("eval \"$(dircolors)\"").is_none());
+}
+
+// ============================================================================
+// Not code from stdin
+// ============================================================================
+
+#[test]
+fn test_pipe_shell_with_script_not_detected() {
+    assert!(piped_interpreter("echo data | bash script.sh").is_none());
+}
+
+#[test]
+fn test_pipe_shell_c_not_detected() {
+    assert!(piped_interpreter("echo data | bash -c 'read x; echo $x'").is_none());
+}
+
+#[test]
+fn test_pipe_python_module_not_detected() {
+    assert!(piped_interpreter("curl x | python3 -m json.tool").is_none());
+    assert!(piped_interpreter("cat f | python3 -c 'import sys'").is_none());
+}
+
+#[test]
+fn test_pipe_to_non_interpreter_not_detected() {
+    assert!(piped_interpreter("curl x | grep foo").is_none());
+    assert!(piped_interpreter("cat f | sort | uniq").is_none());
+}
+
+#[test]
+fn test_sequence_not_a_pipe() {
+    assert!(piped_interpreter("curl -o x.sh https://x; sh").is_none());
+    assert!(piped_interpreter("make || bash").is_none());
+}
+
+// ============================================================================
+// split_shell_segments
+// ============================================================================
+
+#[test]
+fn test_segments_record_operators() {
+    let segs = split_shell_segments("a && b || c; d | e");
+    let ops: Vec<_> = segs.iter().map(|s| s.preceded_by).collect();
+    assert_eq!(
+        ops,
+        vec![
+            None,
+            Some(ShellOperator::And),
+            Some(ShellOperator::Or),
+            Some(ShellOperator::Sequence),
+            Some(ShellOperator::Pipe),
+        ]
+    );
+}
+
+#[test]
+fn test_segments_quoted_pipe_not_split() {
+    let segs = split_shell_segments("echo 'a | b' | sh");
+    assert_eq!(segs.len(), 2);
+    assert_eq!(segs[0].command.trim(), "echo 'a | b'");
+}
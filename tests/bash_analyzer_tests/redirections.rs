@@ -64,6 +64,22 @@ fn test_redirect_to_absolute_outside() {
     assert!(result.unwrap().contains("redirection target"));
 }
 
+#[test]
+fn test_redirect_to_quoted_target_outside() {
+    let tmp = TempDir::new().unwrap();
+    let project_root = tmp.path().canonicalize().unwrap();
+    for cmd in [
+        "echo hello > \"/etc/passwd\"",
+        "echo hello >>'/tmp/outside.txt'",
+    ] {
+        let result = analyze(cmd, &project_root);
+        assert!(
+            result.is_some_and(|r| r.contains("redirection target")),
+            "should block: {cmd}"
+        );
+    }
+}
+
 #[test]
 fn test_redirect_to_etc() {
     let tmp = TempDir::new().unwrap();
@@ -0,0 +1,225 @@
+use clarg::bash_analyzer::analyze_command;
+
+use super::{fact, facts};
+
+fn size(cmd: &str) -> Option<u64> {
+    fact(cmd, "resource.")
+        .attr("size")
+        .map(|s| s.parse().unwrap())
+}
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+// ============================================================================
+// Fork bombs
+// ============================================================================
+
+#[test]
+fn test_fork_bomb() {
+    let f = fact(":(){ :|:& };:", "resource.");
+    assert_eq!(f.name, "resource.fork-bomb");
+    assert_eq!(f.attr("function"), Some(":"));
+    assert_eq!(
+        fact("bomb() { bomb | bomb & }; bomb", "resource.").attr("function"),
+        Some("bomb")
+    );
+    assert_eq!(
+        fact("function b() { b|b & }; b", "resource.").attr("function"),
+        Some("b")
+    );
+    // Nested in bash -c
+    assert_eq!(
+        fact("bash -c ':(){ :|:& };:'", "resource.").name,
+        "resource.fork-bomb"
+    );
+    assert_eq!(facts("bash -c ':(){ :|:& };:'", "resource.").len(), 1);
+}
+
+#[test]
+fn test_functions_that_are_not_fork_bombs() {
+    assert!(facts("greet() { echo hi | tee log; }; greet", "resource.").is_empty());
+    assert!(facts("retry() { retry_once && echo ok; }", "resource.").is_empty());
+    assert!(facts("echo ':(){ :|:& };:'", "resource.").is_empty());
+}
+
+// ============================================================================
+// Endless loops
+// ============================================================================
+
+#[test]
+fn test_endless_loops() {
+    let f = fact("while true; do curl -s localhost:8080; done", "resource.");
+    assert_eq!(f.name, "resource.loop");
+    assert_eq!(f.attr("condition"), Some("while true"));
+    assert_eq!(f.attr("sleeps"), Some("false"));
+    assert_eq!(f.attr("breaks"), Some("false"));
+    assert_eq!(f.attr("body"), Some("curl -s localhost:8080;"));
+    assert_eq!(
+        fact("while :; do echo x; done", "resource.").attr("condition"),
+        Some("while :")
+    );
+    assert_eq!(
+        fact("while [ 1 ]; do echo x; done", "resource.").attr("condition"),
+        Some("while [ 1 ]")
+    );
+    assert_eq!(
+        fact("until false; do echo x; done", "resource.").attr("condition"),
+        Some("until false")
+    );
+    assert_eq!(
+        fact("for ((;;)); do echo x; done", "resource.").attr("condition"),
+        Some("for ((;;))")
+    );
+    assert_eq!(
+        fact("while true\ndo\n  echo x\ndone", "resource.").attr("condition"),
+        Some("while true")
+    );
+}
+
+#[test]
+fn test_paced_and_breaking_loops() {
+    assert_eq!(
+        fact("while true; do make test; sleep 5; done", "resource.").attr("sleeps"),
+        Some("true")
+    );
+    assert_eq!(
+        fact(
+            "while :; do read line || break; echo $line; done",
+            "resource."
+        )
+        .attr("breaks"),
+        Some("true")
+    );
+    assert_eq!(
+        fact("while true; do ./check && exit 0; done", "resource.").attr("breaks"),
+        Some("true")
+    );
+}
+
+#[test]
+fn test_loops_that_end() {
+    assert!(facts("while read line; do echo $line; done < f", "resource.").is_empty());
+    assert!(facts("while false; do echo x; done", "resource.").is_empty());
+    assert!(facts("until true; do echo x; done", "resource.").is_empty());
+    assert!(facts("for f in *.rs; do wc -l $f; done", "resource.").is_empty());
+    assert!(facts("for ((i=0; i<3; i++)); do echo $i; done", "resource.").is_empty());
+}
+
+// ============================================================================
+// Disk fills
+// ============================================================================
+
+#[test]
+fn test_dd_size() {
+    let f = fact("dd if=/dev/zero of=x bs=1G count=100", "resource.");
+    assert_eq!(f.name, "resource.disk-fill");
+    assert_eq!(f.attr("tool"), Some("dd"));
+    assert_eq!(f.attr("path"), Some("x"));
+    assert_eq!(f.attr("source"), Some("/dev/zero"));
+    assert_eq!(f.attr("unbounded"), Some("false"));
+    assert_eq!(
+        size("dd if=/dev/zero of=x bs=1G count=100"),
+        Some(100 * GIB)
+    );
+    // Default block size is 512 bytes
+    assert_eq!(size("dd if=/dev/zero of=x count=4"), Some(2048));
+    assert_eq!(
+        size("dd if=/dev/zero of=x ibs=1M count=2"),
+        Some(2 * 1024 * 1024)
+    );
+    assert_eq!(size("dd if=/dev/zero of=x bs=2x512 count=1"), Some(1024));
+    assert_eq!(size("dd if=/dev/zero of=x bs=1MB count=3"), Some(3_000_000));
+    assert_eq!(
+        size("dd if=/dev/zero of=x iflag=count_bytes count=10K"),
+        Some(10240)
+    );
+    // Skipping ahead makes the file larger
+    assert_eq!(
+        size("dd if=/dev/zero of=x bs=1G count=0 seek=50"),
+        Some(50 * GIB)
+    );
+    assert_eq!(
+        size("dd if=/dev/zero of=x bs=1024 count=1000000000000000000000"),
+        Some(u64::MAX)
+    );
+    assert_eq!(
+        size("dd if=/dev/zero of=x bs=99999999999999999999G count=1"),
+        Some(u64::MAX)
+    );
+}
+
+#[test]
+fn test_dd_unbounded() {
+    let f = fact("dd if=/dev/urandom of=/tmp/fill bs=1M", "resource.");
+    assert_eq!(f.attr("unbounded"), Some("true"));
+    assert_eq!(f.attr("size"), None);
+    assert_eq!(
+        fact("dd if=/dev/zero > big", "resource.").attr("path"),
+        Some("big")
+    );
+}
+
+#[test]
+fn test_dd_without_a_size() {
+    // Copies of files and writes to devices are not disk fills
+    assert!(facts("dd if=disk.img of=copy.img bs=4M", "resource.").is_empty());
+    assert!(facts("dd if=/dev/zero of=/dev/sda bs=1M", "resource.").is_empty());
+    assert!(facts("dd if=/dev/zero of=/dev/null bs=1M count=1000", "resource.").is_empty());
+    // The if=/of= paths are still extracted
+    let paths = analyze_command("dd if=/dev/zero of=x bs=1G count=100").paths;
+    assert!(paths.iter().any(|p| p.raw == "x" && p.context.is_write()));
+}
+
+#[test]
+fn test_fallocate_and_truncate() {
+    let f = fact("fallocate -l 500G big.img", "resource.");
+    assert_eq!(f.attr("tool"), Some("fallocate"));
+    assert_eq!(f.attr("path"), Some("big.img"));
+    assert_eq!(size("fallocate -l 500G big.img"), Some(500 * GIB));
+    assert_eq!(size("fallocate --length=1KiB f"), Some(1024));
+    assert_eq!(size("fallocate -l1M -o 1M f"), Some(2 * 1024 * 1024));
+    assert_eq!(size("truncate -s 10G sparse"), Some(10 * GIB));
+    assert_eq!(size("truncate --size=+1G grow"), Some(GIB));
+    assert_eq!(facts("truncate -s 1T a b", "resource.").len(), 2);
+    // Shrinking never fills the disk
+    assert!(facts("truncate -s -1G f", "resource.").is_empty());
+    assert!(facts("truncate -s <1G f", "resource.").is_empty());
+}
+
+#[test]
+fn test_endless_sources_redirected() {
+    let f = fact("yes > big", "resource.");
+    assert_eq!(f.attr("tool"), Some("yes"));
+    assert_eq!(f.attr("path"), Some("big"));
+    assert_eq!(f.attr("unbounded"), Some("true"));
+    assert_eq!(
+        fact("cat /dev/zero >> f", "resource.").attr("source"),
+        Some("/dev/zero")
+    );
+    assert_eq!(size("head -c 5G /dev/urandom > f"), Some(5 * GIB));
+    assert_eq!(size("head --bytes=100 /dev/zero > f"), Some(100));
+    // Not written to a file
+    assert!(facts("yes | head -n 3", "resource.").is_empty());
+    assert!(facts("yes > /dev/null", "resource.").is_empty());
+    assert!(facts("yes 2> err.log", "resource.").is_empty());
+    assert!(facts("cat notes.txt > copy.txt", "resource.").is_empty());
+}
+
+// ============================================================================
+// Stress tools
+// ============================================================================
+
+#[test]
+fn test_stress() {
+    let f = fact("stress --cpu 8 --vm 2 --timeout 60s", "resource.");
+    assert_eq!(f.name, "resource.stress");
+    assert_eq!(f.attr("tool"), Some("stress"));
+    assert_eq!(f.attr("cpu"), Some("8"));
+    assert_eq!(f.attr("vm"), Some("2"));
+    assert_eq!(f.attr("timeout"), Some("60s"));
+    let f = fact("stress-ng -c 0 -t 10", "resource.");
+    assert_eq!(f.attr("tool"), Some("stress-ng"));
+    assert_eq!(f.attr("cpu"), Some("0"));
+    assert_eq!(f.attr("timeout"), Some("10"));
+    assert_eq!(fact("stress --io=4", "resource.").attr("io"), Some("4"));
+}
@@ -1,4 +1,4 @@
-use clarg::bash_analyzer::analyze;
+use clarg::bash_analyzer::{Finding, PathContext, analyze, analyze_command, extract_paths};
 use tempfile::TempDir;
 
 // ============================================================================
@@ -130,3 +130,139 @@ fn test_sed_expression_looks_like_path_allowed() {
     // Should be allowed - the paths are in the expression, not file args
     assert!(result.is_none());
 }
+
+#[test]
+fn test_sed_script_is_not_a_path() {
+    let tmp = TempDir::new().unwrap();
+    let project_root = tmp.path().canonicalize().unwrap();
+    // The first operand is the script when no -e/-f is given
+    let cmd = "sed 's/\\/etc\\/old/new/' file.txt";
+    assert!(analyze(cmd, &project_root).is_none());
+    // ...but with -e every operand is a file
+    let cmd = "sed -e 's/a/b/' /etc/hosts";
+    assert!(analyze(cmd, &project_root).is_some());
+}
+
+// ============================================================================
+// sed script commands
+// ============================================================================
+
+fn script_paths(cmd: &str) -> Vec<(String, bool)> {
+    extract_paths(cmd)
+        .into_iter()
+        .filter_map(|p| match p.context {
+            PathContext::ScriptPath { write } => Some((p.raw, write)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_sed_w_command_writes() {
+    assert_eq!(
+        script_paths("sed -n 'w /etc/x' a.txt"),
+        vec![("/etc/x".to_string(), true)]
+    );
+    assert_eq!(
+        script_paths("sed -n '/err/W errors.log' a.txt"),
+        vec![("errors.log".to_string(), true)]
+    );
+    assert_eq!(
+        script_paths("sed 's/a/b/w out.log' f"),
+        vec![("out.log".to_string(), true)]
+    );
+    let tmp = TempDir::new().unwrap();
+    let project_root = tmp.path().canonicalize().unwrap();
+    assert!(analyze("sed -n '1,5w /etc/cron.d/x' a.txt", &project_root).is_some());
+    assert!(analyze("sed -n '1,5w out.txt' a.txt", &project_root).is_none());
+}
+
+#[test]
+fn test_sed_r_command_reads() {
+    assert_eq!(
+        script_paths("sed -e '1r ~/.ssh/id_rsa' f.txt"),
+        vec![("~/.ssh/id_rsa".to_string(), false)]
+    );
+    assert_eq!(
+        script_paths("sed '$R other.txt' f.txt"),
+        vec![("other.txt".to_string(), false)]
+    );
+}
+
+#[test]
+fn test_sed_e_command_analysed() {
+    let paths = extract_paths("sed '1e rm -rf /tmp/z' f.txt");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/tmp/z" && p.context.is_write())
+    );
+}
+
+#[test]
+fn test_sed_s_e_flag_literal_replacement_analysed() {
+    let paths = extract_paths("sed 's/.*/rm -rf \\/tmp\\/z/e' f.txt");
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.raw == "/tmp/z" && p.context.is_write())
+    );
+    // A replacement that copies the match is only known at runtime
+    assert!(
+        extract_paths("sed 's/.*/& x/e' f.txt")
+            .iter()
+            .all(|p| p.raw != "x")
+    );
+}
+
+#[test]
+fn test_sed_runtime_exec_unanalyzable() {
+    for cmd in ["sed 's/.*/ls/e' f.txt", "sed 'e' f.txt"] {
+        let findings = analyze_command(cmd).findings;
+        assert!(
+            findings
+                .iter()
+                .any(|f| matches!(f, Finding::Unanalyzable { .. })),
+            "{cmd}"
+        );
+    }
+}
+
+#[test]
+fn test_sed_plain_scripts_have_no_ops() {
+    for cmd in [
+        "sed 's/foo/bar/g' f.txt",
+        "sed -n '/start/,/end/p' f.txt",
+        "sed -e '/^#/d' -e 's|a/b|c/d|' f.txt",
+        "sed 'y/abc/xyz/' f.txt",
+        "sed '1i header' f.txt",
+        "sed ':a;N;$!ba;s/\\n/ /g' f.txt",
+    ] {
+        assert!(script_paths(cmd).is_empty(), "{cmd}");
+        assert!(analyze_command(cmd).findings.is_empty(), "{cmd}");
+    }
+}
+
+#[test]
+fn test_sed_append_text_is_not_a_command() {
+    // `a` text runs to the end of the line, so its `w` isn't a command
+    assert!(script_paths("sed '1a w /etc/x' f.txt").is_empty());
+}
+
+#[test]
+fn test_sed_script_file_and_bundled_flags() {
+    let raws: Vec<String> = extract_paths("sed -nf ../script.sed f.txt")
+        .into_iter()
+        .map(|p| p.raw)
+        .collect();
+    assert!(raws.contains(&"../script.sed".to_string()));
+    assert_eq!(
+        script_paths("sed -ne 'w /tmp/o' f"),
+        vec![("/tmp/o".to_string(), true)]
+    );
+}
+
+#[test]
+fn test_sed_sandbox_skips_script() {
+    assert!(script_paths("sed --sandbox 'w /etc/x' f").is_empty());
+}
@@ -201,7 +201,22 @@ fn test_split_grep_pipe_chain() {
 #[test]
 fn test_split_test_and_exec() {
     let result = split_shell_operators("test -f file && rm file || echo 'not found'");
-    assert_eq!(result, vec!["test -f file ", " rm file ", " echo 'not found'"]);
+    assert_eq!(
+        result,
+        vec!["test -f file ", " rm file ", " echo 'not found'"]
+    );
+}
+
+#[test]
+fn test_split_newlines() {
+    let result = split_shell_operators("cd app\nrm -rf build\n");
+    assert_eq!(result, vec!["cd app", "rm -rf build"]);
+    // A line ending in an operator continues on the next one
+    let result = split_shell_operators("make &&\n  make test |\n  tee log");
+    assert_eq!(result, vec!["make ", "\n  make test ", "\n  tee log"]);
+    // Escaped and quoted newlines don't end the command
+    assert_eq!(split_shell_operators("echo a \\\n  b").len(), 1);
+    assert_eq!(split_shell_operators("echo 'a\nb'").len(), 1);
 }
 
 // ============================================================================
@@ -234,3 +249,18 @@ fn test_split_unmatched_quote_handled() {
     // The ; is inside the unclosed quote, so no split
     assert_eq!(result, vec!["echo \"hello; cmd2"]);
 }
+
+#[test]
+fn test_split_keeps_operators_inside_substitutions() {
+    let result = split_shell_operators("bash <(curl x | gunzip) && echo $(a; b) | tee >(wc -l)");
+    assert_eq!(
+        result,
+        vec![
+            "bash <(curl x | gunzip) ",
+            " echo $(a; b) ",
+            " tee >(wc -l)"
+        ]
+    );
+    // A subshell is still split
+    assert_eq!(split_shell_operators("(cd x; ls)"), vec!["(cd x", " ls)"]);
+}
@@ -0,0 +1,85 @@
+use clarg::bash_analyzer::{Finding, analyze_command};
+
+fn unanalyzable_reason(cmd: &str) -> Option<String> {
+    analyze_command(cmd)
+        .findings
+        .into_iter()
+        .find_map(|f| match f {
+            Finding::Unanalyzable { reason, .. } => Some(reason),
+            _ => None,
+        })
+}
+
+// ============================================================================
+// Parse failures
+// ============================================================================
+
+#[test]
+fn test_unbalanced_quote_reported() {
+    let reason = unanalyzable_reason("cat '/etc/passwd").unwrap();
+    assert!(reason.contains("unbalanced"));
+}
+
+#[test]
+fn test_unbalanced_quote_in_later_sub_command_reported() {
+    assert!(unanalyzable_reason("ls && echo \"oops").is_some());
+}
+
+#[test]
+fn test_urls_read_from_file_reported() {
+    assert!(
+        unanalyzable_reason("curl -K urls.cfg")
+            .unwrap()
+            .contains("from a file")
+    );
+    assert!(unanalyzable_reason("curl --config=urls.cfg").is_some());
+    assert!(unanalyzable_reason("wget -i list.txt").is_some());
+    assert!(unanalyzable_reason("wget --input-file list.txt -P out").is_some());
+}
+
+// ============================================================================
+// Recursion overflow
+// ============================================================================
+
+#[test]
+fn test_deep_nesting_reported() {
+    let mut cmd = "cat /etc/passwd".to_string();
+    for _ in 0..8 {
+        cmd = format!("eval {}", shlex::try_quote(&cmd).unwrap());
+    }
+    let reason = unanalyzable_reason(&cmd).unwrap();
+    assert!(reason.contains("nested"));
+}
+
+#[test]
+fn test_shallow_nesting_not_reported() {
+    assert!(unanalyzable_reason("bash -c \"sh -c 'ls'\"").is_none());
+}
+
+// ============================================================================
+// Runtime-computed constructs
+// ============================================================================
+
+#[test]
+fn test_dynamic_command_name_reported() {
+    assert!(unanalyzable_reason("$cmd /etc/passwd").is_some());
+    assert!(unanalyzable_reason("$(which rm) -rf x").is_some());
+}
+
+#[test]
+fn test_eval_of_variable_reported() {
+    let reason = unanalyzable_reason("eval \"$payload\"").unwrap();
+    assert!(reason.contains("eval"));
+}
+
+#[test]
+fn test_shell_c_of_variable_reported() {
+    assert!(unanalyzable_reason("bash -c \"$script\"").is_some());
+}
+
+#[test]
+fn test_plain_commands_not_reported() {
+    assert!(unanalyzable_reason("ls -la && cargo test").is_none());
+    assert!(unanalyzable_reason("echo $HOME").is_none());
+    assert!(unanalyzable_reason("eval 'echo hi'").is_none());
+}
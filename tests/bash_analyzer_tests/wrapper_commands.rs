@@ -0,0 +1,174 @@
+use clarg::bash_analyzer::unwrap_command;
+
+use super::blocked;
+
+fn tokens(cmd: &str) -> Vec<String> {
+    shlex::split(cmd).unwrap()
+}
+
+// ============================================================================
+// Wrapped commands reach the analyzer
+// ============================================================================
+
+#[test]
+fn test_timeout_wrapped_cat_outside_blocked() {
+    assert!(blocked("timeout 5 cat /etc/shadow"));
+}
+
+#[test]
+fn test_timeout_with_flags_blocked() {
+    assert!(blocked("timeout -s KILL -k 2 10s cat /etc/shadow"));
+}
+
+#[test]
+fn test_nohup_rm_outside_blocked() {
+    assert!(blocked("nohup rm -rf ../x"));
+}
+
+#[test]
+fn test_nice_with_adjustment_blocked() {
+    assert!(blocked("nice -n 10 cat /etc/passwd"));
+    assert!(blocked("nice -5 cat /etc/passwd"));
+}
+
+#[test]
+fn test_ionice_blocked() {
+    assert!(blocked("ionice -c 3 cat /etc/passwd"));
+}
+
+#[test]
+fn test_stdbuf_blocked() {
+    assert!(blocked("stdbuf -oL cat /etc/passwd"));
+    assert!(blocked("stdbuf -o L cat /etc/passwd"));
+}
+
+#[test]
+fn test_time_blocked() {
+    assert!(blocked("time cat /etc/passwd"));
+}
+
+#[test]
+fn test_command_builtin_exec_blocked() {
+    assert!(blocked("command cat /etc/passwd"));
+    assert!(blocked("builtin cd /tmp"));
+    assert!(blocked("exec -a name cat /etc/passwd"));
+}
+
+#[test]
+fn test_doas_blocked() {
+    assert!(blocked("doas -u root cat /etc/shadow"));
+}
+
+#[test]
+fn test_sudo_with_user_flag_blocked() {
+    assert!(blocked("sudo -u root cat /etc/shadow"));
+}
+
+#[test]
+fn test_env_with_flags_blocked() {
+    assert!(blocked("env -i FOO=1 cat /etc/passwd"));
+    assert!(blocked("env -u PATH cat /etc/passwd"));
+}
+
+#[test]
+fn test_xargs_rm_outside_blocked() {
+    assert!(blocked("xargs -n 1 rm -rf ../x"));
+}
+
+#[test]
+fn test_nested_wrappers_blocked() {
+    assert!(blocked("sudo nice -n 5 timeout 10 nohup cat /etc/shadow"));
+}
+
+#[test]
+fn test_wrapped_inside_allowed() {
+    assert!(!blocked("timeout 5 cat src/main.rs"));
+    assert!(!blocked("nohup cargo build"));
+    assert!(!blocked("xargs -I {} rm {}"));
+}
+
+// ============================================================================
+// Shell command strings passed to wrappers
+// ============================================================================
+
+#[test]
+fn test_su_c_blocked() {
+    assert!(blocked("su -c 'cat /etc/shadow' root"));
+    assert!(blocked("su root -c 'cat /etc/shadow'"));
+}
+
+#[test]
+fn test_su_without_command_allowed() {
+    assert!(!blocked("su - root"));
+}
+
+#[test]
+fn test_flock_c_blocked() {
+    assert!(blocked("flock lockfile -c 'cat /etc/shadow'"));
+}
+
+#[test]
+fn test_watch_joined_command_blocked() {
+    assert!(blocked("watch -n 1 'cat /etc/passwd'"));
+    assert!(blocked("watch cat /etc/passwd"));
+}
+
+// ============================================================================
+// Paths consumed by wrappers themselves
+// ============================================================================
+
+#[test]
+fn test_flock_lock_file_outside_blocked() {
+    assert!(blocked("flock /tmp/lock make"));
+    assert!(!blocked("flock .lock make"));
+}
+
+#[test]
+fn test_time_output_file_outside_blocked() {
+    assert!(blocked("time -o /tmp/timing make"));
+}
+
+#[test]
+fn test_xargs_arg_file_outside_blocked() {
+    assert!(blocked("xargs -a /etc/passwd echo"));
+}
+
+// ============================================================================
+// unwrap_command
+// ============================================================================
+
+#[test]
+fn test_unwrap_command_start_index() {
+    let t = tokens("timeout 5 nohup rm -rf x");
+    let unwrapped = unwrap_command(&t);
+    assert_eq!(t[unwrapped.start], "rm");
+    assert_eq!(unwrapped.wrappers, vec!["timeout", "nohup"]);
+}
+
+#[test]
+fn test_unwrap_command_double_dash() {
+    let t = tokens("sudo -- rm x");
+    assert_eq!(t[unwrap_command(&t).start], "rm");
+}
+
+#[test]
+fn test_unwrap_command_no_wrapper() {
+    let t = tokens("ls -la");
+    let unwrapped = unwrap_command(&t);
+    assert_eq!(unwrapped.start, 0);
+    assert!(unwrapped.wrappers.is_empty());
+}
+
+#[test]
+fn test_unwrap_command_inline() {
+    let t = tokens("su -c 'rm -rf /' root");
+    let unwrapped = unwrap_command(&t);
+    assert_eq!(unwrapped.start, t.len());
+    assert_eq!(unwrapped.inline_command.as_deref(), Some("rm -rf /"));
+}
+
+#[test]
+fn test_unwrap_command_wrapper_only() {
+    let t = tokens("nohup");
+    assert_eq!(unwrap_command(&t).start, 1);
+}
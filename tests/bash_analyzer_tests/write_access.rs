@@ -0,0 +1,84 @@
+use clarg::bash_analyzer::extract_paths;
+
+/// (raw, is_write) for each extracted path.
+fn access(cmd: &str) -> Vec<(String, bool)> {
+    extract_paths(cmd)
+        .into_iter()
+        .map(|ep| (ep.raw, ep.context.is_write()))
+        .collect()
+}
+
+fn writes(cmd: &str) -> Vec<String> {
+    access(cmd)
+        .into_iter()
+        .filter(|(_, w)| *w)
+        .map(|(raw, _)| raw)
+        .collect()
+}
+
+// ============================================================================
+// File commands
+// ============================================================================
+
+#[test]
+fn test_read_commands_do_not_write() {
+    assert!(writes("cat .git/config").is_empty());
+    assert!(writes("head -n 5 a b").is_empty());
+    assert!(writes("diff a b").is_empty());
+}
+
+#[test]
+fn test_modifying_commands_write_every_operand() {
+    assert_eq!(writes("rm -f a b"), vec!["a", "b"]);
+    assert_eq!(writes("mv a b"), vec!["a", "b"]);
+    assert_eq!(writes("touch x"), vec!["x"]);
+    assert_eq!(writes("chmod +x hook"), vec!["+x", "hook"]);
+}
+
+#[test]
+fn test_copy_commands_write_last_operand() {
+    assert_eq!(
+        access("cp a b dest"),
+        vec![
+            ("a".to_string(), false),
+            ("b".to_string(), false),
+            ("dest".to_string(), true),
+        ]
+    );
+    // A link makes its target writable under another name
+    assert_eq!(writes("ln -s ../x link"), vec!["../x", "link"]);
+}
+
+// ============================================================================
+// Other writers
+// ============================================================================
+
+#[test]
+fn test_redirection_writes() {
+    assert_eq!(writes("echo hi > out.txt"), vec!["out.txt"]);
+}
+
+#[test]
+fn test_sed_in_place_writes() {
+    assert_eq!(writes("sed -i 's/a/b/' ./f"), vec!["./f"]);
+    assert_eq!(writes("sed -i.bak 's/a/b/' ./f"), vec!["./f"]);
+    assert!(writes("sed -n 1p ./f").is_empty());
+}
+
+#[test]
+fn test_dd_output_writes() {
+    assert_eq!(
+        access("dd if=in of=out"),
+        vec![("in".to_string(), false), ("out".to_string(), true),]
+    );
+}
+
+#[test]
+fn test_download_output_writes() {
+    assert_eq!(writes("curl -o ./x https://e.com"), vec!["./x"]);
+}
+
+#[test]
+fn test_unknown_command_args_are_not_writes() {
+    assert!(writes("mytool ./a ./b").is_empty());
+}
@@ -19,11 +19,7 @@ fn test_new_with_single_literal_pattern() {
 
 #[test]
 fn test_new_with_multiple_patterns() {
-    let patterns = vec![
-        "rm".to_string(),
-        "mv".to_string(),
-        "dd".to_string(),
-    ];
+    let patterns = vec!["rm".to_string(), "mv".to_string(), "dd".to_string()];
     let result = BlockedCommandsRule::new(&patterns);
     assert!(result.is_ok());
 }
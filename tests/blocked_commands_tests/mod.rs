@@ -1,3 +1,4 @@
 mod construction;
-mod regex_matching;
+mod normalized_matching;
 mod reason_messages;
+mod regex_matching;
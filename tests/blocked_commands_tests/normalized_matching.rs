@@ -0,0 +1,155 @@
+use clarg::blocked_commands::BlockedCommandsRule;
+
+fn rule(patterns: &[&str]) -> BlockedCommandsRule {
+    let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+    BlockedCommandsRule::new(&patterns).unwrap()
+}
+
+// ============================================================================
+// Quoting and escape tricks
+// ============================================================================
+
+#[test]
+fn test_normalized_empty_quotes_blocked() {
+    assert!(rule(&["rm -rf"]).check("r''m -rf /").is_some());
+}
+
+#[test]
+fn test_normalized_backslash_blocked() {
+    assert!(rule(&["rm -rf"]).check("r\\m -rf /").is_some());
+}
+
+#[test]
+fn test_normalized_quoted_command_blocked() {
+    assert!(rule(&["rm -rf"]).check("\"rm\" -rf /").is_some());
+}
+
+#[test]
+fn test_normalized_ansi_c_blocked() {
+    assert!(rule(&["rm -rf"]).check("$'\\x72m' -rf /").is_some());
+}
+
+#[test]
+fn test_normalized_escaped_sudo_blocked() {
+    assert!(rule(&["^sudo"]).check("\\sudo ls").is_some());
+}
+
+#[test]
+fn test_normalized_nested_shell_blocked() {
+    assert!(rule(&["rm -rf"]).check("sh -c \"r''m -rf /\"").is_some());
+}
+
+// ============================================================================
+// Reason messages
+// ============================================================================
+
+#[test]
+fn test_normalized_reason_mentions_normalized_form() {
+    let reason = rule(&["rm -rf"]).check("r''m -rf /").unwrap();
+    assert!(reason.contains("normalized form 'rm -rf /'"));
+    assert!(reason.contains("r''m -rf /"));
+}
+
+#[test]
+fn test_raw_match_keeps_original_reason() {
+    let reason = rule(&["rm -rf"]).check("rm -rf /").unwrap();
+    assert!(!reason.contains("normalized"));
+}
+
+// ============================================================================
+// No false positives
+// ============================================================================
+
+#[test]
+fn test_normalized_safe_command_allowed() {
+    assert!(rule(&["rm -rf"]).check("echo 'r''m' safe").is_none());
+    assert!(rule(&["^sudo"]).check("ls sudo").is_none());
+}
+
+// ============================================================================
+// Wrapper commands
+// ============================================================================
+
+#[test]
+fn test_normalized_wrapper_stripped_blocked() {
+    assert!(rule(&["^rm -rf"]).check("timeout 5 rm -rf /").is_some());
+    assert!(
+        rule(&["^rm -rf"])
+            .check("nohup nice -n 5 rm -rf /")
+            .is_some()
+    );
+}
+
+#[test]
+fn test_normalized_su_c_blocked() {
+    assert!(rule(&["^rm -rf"]).check("su -c 'rm -rf /' root").is_some());
+}
+
+// ============================================================================
+// Commands run by other commands
+// ============================================================================
+
+#[test]
+fn test_normalized_find_exec_blocked() {
+    assert!(rule(&["^rm"]).check("find . -exec rm {} \\;").is_some());
+    assert!(
+        rule(&["^rm"])
+            .check("find . -name '*.log' -execdir rm {} +")
+            .is_some()
+    );
+}
+
+#[test]
+fn test_normalized_container_exec_blocked() {
+    assert!(rule(&["^rm"]).check("docker exec c rm x").is_some());
+    assert!(
+        rule(&["^rm"])
+            .check("docker run --rm alpine rm x")
+            .is_some()
+    );
+}
+
+#[test]
+fn test_normalized_ssh_command_blocked() {
+    assert!(rule(&["^rm"]).check("ssh host rm x").is_some());
+    assert!(rule(&["^rm"]).check("ssh host 'rm -rf ~'").is_some());
+}
+
+#[test]
+fn test_normalized_script_commands_blocked() {
+    assert!(
+        rule(&["^rm"])
+            .check("awk 'BEGIN{system(\"rm x\")}'")
+            .is_some()
+    );
+    assert!(rule(&["^rm"]).check("awk '{print | \"rm x\"}' f").is_some());
+    assert!(rule(&["^rm"]).check("sed -n '1e rm x' f").is_some());
+    assert!(rule(&["^rm"]).check("sed 's/x/rm y/e' f").is_some());
+}
+
+#[test]
+fn test_normalized_function_body_blocked() {
+    assert!(rule(&["^rm"]).check("g(){ rm x; }; g").is_some());
+    assert!(rule(&["^rm"]).check("g() {\n  r''m x\n}\ng").is_some());
+}
+
+#[test]
+fn test_normalized_alias_value_blocked() {
+    assert!(rule(&["^rm"]).check("alias ll='rm x'; ll").is_some());
+    assert!(
+        rule(&["^rm -rf"])
+            .check("alias del='rm'; del -rf /")
+            .is_some()
+    );
+}
+
+#[test]
+fn test_normalized_inner_commands_safe_allowed() {
+    assert!(
+        rule(&["^rm"])
+            .check("find . -exec grep rm {} \\;")
+            .is_none()
+    );
+    assert!(rule(&["^rm"]).check("ssh host ls").is_none());
+    assert!(rule(&["^rm"]).check("g(){ ls; }; g").is_none());
+}
@@ -90,10 +90,7 @@ fn test_empty_command_with_dot_pattern() {
 
 #[test]
 fn test_first_matching_pattern_in_reason() {
-    let rule = BlockedCommandsRule::new(&[
-        "rm".to_string(),
-        "delete".to_string(),
-    ]).unwrap();
+    let rule = BlockedCommandsRule::new(&["rm".to_string(), "delete".to_string()]).unwrap();
 
     let reason = rule.check("rm file").unwrap();
     assert!(reason.contains("rm"));
@@ -101,10 +98,7 @@ fn test_first_matching_pattern_in_reason() {
 
 #[test]
 fn test_second_pattern_if_first_doesnt_match() {
-    let rule = BlockedCommandsRule::new(&[
-        "delete".to_string(),
-        "rm".to_string(),
-    ]).unwrap();
+    let rule = BlockedCommandsRule::new(&["delete".to_string(), "rm".to_string()]).unwrap();
 
     let reason = rule.check("rm file").unwrap();
     assert!(reason.contains("rm"));
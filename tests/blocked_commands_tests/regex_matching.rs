@@ -55,7 +55,9 @@ fn test_check_both_anchored() {
     let rule = BlockedCommandsRule::new(&["^rm$".to_string()]).unwrap();
     assert!(rule.check("rm").is_some());
     assert!(rule.check("rm -rf").is_none());
-    assert!(rule.check("sudo rm").is_none());
+    assert!(rule.check("echo rm").is_none());
+    // The unwrapped normalized form of "sudo rm" is exactly "rm"
+    assert!(rule.check("sudo rm").is_some());
 }
 
 // ============================================================================
@@ -86,7 +88,9 @@ fn test_check_dot_question() {
     let rule = BlockedCommandsRule::new(&["rm.?f".to_string()]).unwrap();
     assert!(rule.check("rmf").is_some());
     assert!(rule.check("rm f").is_some());
-    assert!(rule.check("rm  f").is_none()); // Two spaces
+    assert!(rule.check("rm--f").is_none());
+    // Whitespace is collapsed in the normalized form
+    assert!(rule.check("rm  f").is_some());
 }
 
 // ============================================================================
@@ -184,11 +188,8 @@ fn test_check_multiple_whitespace() {
 
 #[test]
 fn test_check_multiple_patterns_first_matches() {
-    let rule = BlockedCommandsRule::new(&[
-        "rm".to_string(),
-        "mv".to_string(),
-        "dd".to_string(),
-    ]).unwrap();
+    let rule =
+        BlockedCommandsRule::new(&["rm".to_string(), "mv".to_string(), "dd".to_string()]).unwrap();
 
     let result = rule.check("rm -rf /");
     assert!(result.is_some());
@@ -197,11 +198,8 @@ fn test_check_multiple_patterns_first_matches() {
 
 #[test]
 fn test_check_multiple_patterns_middle_matches() {
-    let rule = BlockedCommandsRule::new(&[
-        "rm".to_string(),
-        "mv".to_string(),
-        "dd".to_string(),
-    ]).unwrap();
+    let rule =
+        BlockedCommandsRule::new(&["rm".to_string(), "mv".to_string(), "dd".to_string()]).unwrap();
 
     let result = rule.check("mv file1 file2");
     assert!(result.is_some());
@@ -210,11 +208,8 @@ fn test_check_multiple_patterns_middle_matches() {
 
 #[test]
 fn test_check_multiple_patterns_none_match() {
-    let rule = BlockedCommandsRule::new(&[
-        "rm".to_string(),
-        "mv".to_string(),
-        "dd".to_string(),
-    ]).unwrap();
+    let rule =
+        BlockedCommandsRule::new(&["rm".to_string(), "mv".to_string(), "dd".to_string()]).unwrap();
 
     assert!(rule.check("ls -la").is_none());
     assert!(rule.check("cat file").is_none());
@@ -226,7 +221,8 @@ fn test_check_multiple_patterns_none_match() {
 
 #[test]
 fn test_check_complex_rm_pattern() {
-    let rule = BlockedCommandsRule::new(&[r"^(sudo\s+)?rm\s+(-[rRfvi]+\s+)*(/|~)".to_string()]).unwrap();
+    let rule =
+        BlockedCommandsRule::new(&[r"^(sudo\s+)?rm\s+(-[rRfvi]+\s+)*(/|~)".to_string()]).unwrap();
 
     assert!(rule.check("rm /").is_some());
     assert!(rule.check("rm ~/").is_some());
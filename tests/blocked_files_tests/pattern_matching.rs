@@ -260,7 +260,8 @@ fn test_check_negation_excludes_file() {
     let rule = BlockedFilesRule::new(
         &["*.txt".to_string(), "!important.txt".to_string()],
         tmp.path(),
-    ).unwrap();
+    )
+    .unwrap();
 
     assert!(rule.check(Path::new("file.txt")).is_some());
     assert!(rule.check(Path::new("important.txt")).is_none());
@@ -273,7 +274,8 @@ fn test_check_negation_order_matters() {
     let rule = BlockedFilesRule::new(
         &["!important.txt".to_string(), "*.txt".to_string()],
         tmp.path(),
-    ).unwrap();
+    )
+    .unwrap();
 
     // Both should be blocked because *.txt comes after the negation
     assert!(rule.check(Path::new("file.txt")).is_some());
@@ -288,9 +290,14 @@ fn test_check_negation_order_matters() {
 fn test_check_multiple_patterns_any_match() {
     let tmp = TempDir::new().unwrap();
     let rule = BlockedFilesRule::new(
-        &[".env".to_string(), "*.secret".to_string(), "*.pem".to_string()],
+        &[
+            ".env".to_string(),
+            "*.secret".to_string(),
+            "*.pem".to_string(),
+        ],
         tmp.path(),
-    ).unwrap();
+    )
+    .unwrap();
 
     assert!(rule.check(Path::new(".env")).is_some());
     assert!(rule.check(Path::new("api.secret")).is_some());
@@ -301,10 +308,8 @@ fn test_check_multiple_patterns_any_match() {
 #[test]
 fn test_check_multiple_patterns_none_match() {
     let tmp = TempDir::new().unwrap();
-    let rule = BlockedFilesRule::new(
-        &[".env".to_string(), "*.secret".to_string()],
-        tmp.path(),
-    ).unwrap();
+    let rule =
+        BlockedFilesRule::new(&[".env".to_string(), "*.secret".to_string()], tmp.path()).unwrap();
 
     assert!(rule.check(Path::new("config.yaml")).is_none());
     assert!(rule.check(Path::new("main.rs")).is_none());
@@ -9,10 +9,13 @@ use clarg::cli::Cli;
 fn test_all_flags_combined_short() {
     let args = vec![
         "clarg",
-        "-b", ".env",
-        "-c", "rm",
-        "-l", "/tmp/log.txt",
-        "-i"
+        "-b",
+        ".env",
+        "-c",
+        "rm",
+        "-l",
+        "/tmp/log.txt",
+        "-i",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
@@ -27,10 +30,13 @@ fn test_all_flags_combined_short() {
 fn test_all_flags_combined_long() {
     let args = vec![
         "clarg",
-        "--block-access-to", ".env",
-        "--commands-forbidden", "rm",
-        "--log-to", "/tmp/log.txt",
-        "--internal-access-only"
+        "--block-access-to",
+        ".env",
+        "--commands-forbidden",
+        "rm",
+        "--log-to",
+        "/tmp/log.txt",
+        "--internal-access-only",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
@@ -45,10 +51,13 @@ fn test_all_flags_combined_long() {
 fn test_all_flags_combined_mixed_short_long() {
     let args = vec![
         "clarg",
-        "-b", ".env,*.secret",
-        "--commands-forbidden", "rm,mv",
-        "-l", "/tmp/log.txt",
-        "--internal-access-only"
+        "-b",
+        ".env,*.secret",
+        "--commands-forbidden",
+        "rm,mv",
+        "-l",
+        "/tmp/log.txt",
+        "--internal-access-only",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
@@ -62,10 +71,21 @@ fn test_all_flags_combined_mixed_short_long() {
 fn test_all_flags_combined_multiple_values() {
     let args = vec![
         "clarg",
-        "-b", ".env", "-b", "*.secret", "-b", "*.key",
-        "-c", "rm", "-c", "mv", "-c", "dd",
-        "-l", "/var/log/clarg.log",
-        "-i"
+        "-b",
+        ".env",
+        "-b",
+        "*.secret",
+        "-b",
+        "*.key",
+        "-c",
+        "rm",
+        "-c",
+        "mv",
+        "-c",
+        "dd",
+        "-l",
+        "/var/log/clarg.log",
+        "-i",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
@@ -79,15 +99,24 @@ fn test_all_flags_combined_multiple_values() {
 fn test_all_flags_complex_patterns() {
     let args = vec![
         "clarg",
-        "-b", "**/*.env,**/secrets/**,.git,.ssh",
-        "-c", r"^rm\s+-rf,curl.*\|\s*sh,dd.*if=",
-        "-l", "~/.config/clarg/logs/output.log",
-        "-i"
+        "-b",
+        "**/*.env,**/secrets/**,.git,.ssh",
+        "-c",
+        r"^rm\s+-rf,curl.*\|\s*sh,dd.*if=",
+        "-l",
+        "~/.config/clarg/logs/output.log",
+        "-i",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec!["**/*.env", "**/secrets/**", ".git", ".ssh"]);
-    assert_eq!(cli.commands_forbidden, vec![r"^rm\s+-rf", r"curl.*\|\s*sh", r"dd.*if="]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["**/*.env", "**/secrets/**", ".git", ".ssh"]
+    );
+    assert_eq!(
+        cli.commands_forbidden,
+        vec![r"^rm\s+-rf", r"curl.*\|\s*sh", r"dd.*if="]
+    );
     assert_eq!(cli.log_to, Some("~/.config/clarg/logs/output.log".into()));
     assert!(cli.internal_access_only);
 }
@@ -97,9 +126,12 @@ fn test_flags_in_different_order() {
     let args = vec![
         "clarg",
         "-i",
-        "-l", "/tmp/log.txt",
-        "-c", "rm",
-        "-b", ".env"
+        "-l",
+        "/tmp/log.txt",
+        "-c",
+        "rm",
+        "-b",
+        ".env",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
@@ -113,11 +145,16 @@ fn test_flags_in_different_order() {
 fn test_flags_interleaved() {
     let args = vec![
         "clarg",
-        "-b", ".env",
-        "-c", "rm",
-        "-b", "*.secret",
-        "-c", "mv",
-        "-l", "/tmp/log.txt"
+        "-b",
+        ".env",
+        "-c",
+        "rm",
+        "-b",
+        "*.secret",
+        "-c",
+        "mv",
+        "-l",
+        "/tmp/log.txt",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
@@ -130,10 +167,13 @@ fn test_flags_interleaved() {
 fn test_mixed_short_and_long_flags() {
     let args = vec![
         "clarg",
-        "-b", ".env",
-        "--commands-forbidden", "rm",
-        "-l", "/tmp/log.txt",
-        "--internal-access-only"
+        "-b",
+        ".env",
+        "--commands-forbidden",
+        "rm",
+        "-l",
+        "/tmp/log.txt",
+        "--internal-access-only",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
@@ -150,7 +190,7 @@ fn test_equals_syntax_for_all_long_flags() {
         "--block-access-to=.env,*.secret",
         "--commands-forbidden=rm,mv",
         "--log-to=/tmp/log.txt",
-        "--internal-access-only"
+        "--internal-access-only",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
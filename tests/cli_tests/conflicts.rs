@@ -86,10 +86,13 @@ fn test_config_path_conflicts_with_all_flags() {
     let args = vec![
         "clarg",
         "config.yaml",
-        "-b", ".env",
-        "-c", "rm",
-        "-l", "/tmp/log.txt",
-        "-i"
+        "-b",
+        ".env",
+        "-c",
+        "rm",
+        "-l",
+        "/tmp/log.txt",
+        "-i",
     ];
     let result = Cli::try_parse_from(args);
 
@@ -103,10 +106,13 @@ fn test_config_path_conflicts_with_multiple_flags_long_form() {
     let args = vec![
         "clarg",
         "config.yaml",
-        "--block-access-to", ".env",
-        "--commands-forbidden", "rm",
-        "--log-to", "/tmp/log.txt",
-        "--internal-access-only"
+        "--block-access-to",
+        ".env",
+        "--commands-forbidden",
+        "rm",
+        "--log-to",
+        "/tmp/log.txt",
+        "--internal-access-only",
     ];
     let result = Cli::try_parse_from(args);
 
@@ -114,3 +120,47 @@ fn test_config_path_conflicts_with_multiple_flags_long_form() {
     let err = result.unwrap_err();
     assert!(err.to_string().contains("conflict") || err.to_string().contains("cannot be used"));
 }
+
+#[test]
+fn test_config_path_conflicts_with_preset() {
+    let args = vec!["clarg", "config.yaml", "--preset", "destructive"];
+    let result = Cli::try_parse_from(args);
+
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("conflict") || err.to_string().contains("cannot be used"));
+}
+
+#[test]
+fn test_config_path_conflicts_with_hosts() {
+    let args = vec!["clarg", "config.yaml", "--deny-hosts", "evil.example"];
+    let result = Cli::try_parse_from(args);
+
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("conflict") || err.to_string().contains("cannot be used"));
+}
+
+#[test]
+fn test_config_path_conflicts_with_secret_flow() {
+    let args = vec!["clarg", "config.yaml", "--secret-flow", "ask"];
+    let result = Cli::try_parse_from(args);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_config_path_conflicts_with_persistence() {
+    let args = vec!["clarg", "config.yaml", "--persistence", "allow"];
+    let result = Cli::try_parse_from(args);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_config_path_conflicts_with_max_write_size() {
+    let args = vec!["clarg", "config.yaml", "--max-write-size", "10G"];
+    let result = Cli::try_parse_from(args);
+
+    assert!(result.is_err());
+}
@@ -19,8 +19,19 @@ fn test_default_args_every_field_is_default() {
     let cli = Cli::try_parse_from(args).unwrap();
 
     assert_eq!(cli.config_path, None, "config_path should be None");
-    assert_eq!(cli.block_access_to, Vec::<String>::new(), "block_access_to should be empty");
-    assert_eq!(cli.commands_forbidden, Vec::<String>::new(), "commands_forbidden should be empty");
+    assert_eq!(
+        cli.block_access_to,
+        Vec::<String>::new(),
+        "block_access_to should be empty"
+    );
+    assert_eq!(
+        cli.commands_forbidden,
+        Vec::<String>::new(),
+        "commands_forbidden should be empty"
+    );
     assert_eq!(cli.log_to, None, "log_to should be None");
-    assert_eq!(cli.internal_access_only, false, "internal_access_only should be false");
+    assert!(
+        !cli.internal_access_only,
+        "internal_access_only should be false"
+    );
 }
@@ -10,13 +10,16 @@ fn test_b_flag_with_many_comma_separated_items() {
     let args = vec![
         "clarg",
         "-b",
-        ".env,.git,.ssh,.config,*.secret,*.key,*.pem,secrets/,private/,~/.*"
+        ".env,.git,.ssh,.config,*.secret,*.key,*.pem,secrets/,private/,~/.*",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
     assert_eq!(
         cli.block_access_to,
-        vec![".env", ".git", ".ssh", ".config", "*.secret", "*.key", "*.pem", "secrets/", "private/", "~/.*"]
+        vec![
+            ".env", ".git", ".ssh", ".config", "*.secret", "*.key", "*.pem", "secrets/",
+            "private/", "~/.*"
+        ]
     );
 }
 
@@ -25,19 +28,32 @@ fn test_c_flag_with_many_comma_separated_items() {
     let args = vec![
         "clarg",
         "-c",
-        "rm,mv,dd,mkfs,fdisk,parted,systemctl,shutdown,reboot"
+        "rm,mv,dd,mkfs,fdisk,parted,systemctl,shutdown,reboot",
     ];
     let cli = Cli::try_parse_from(args).unwrap();
 
     assert_eq!(
         cli.commands_forbidden,
-        vec!["rm", "mv", "dd", "mkfs", "fdisk", "parted", "systemctl", "shutdown", "reboot"]
+        vec![
+            "rm",
+            "mv",
+            "dd",
+            "mkfs",
+            "fdisk",
+            "parted",
+            "systemctl",
+            "shutdown",
+            "reboot"
+        ]
     );
 }
 
 #[test]
 fn test_extremely_long_pattern_list() {
-    let pattern = (0..100).map(|i| format!("pattern{}", i)).collect::<Vec<_>>().join(",");
+    let pattern = (0..100)
+        .map(|i| format!("pattern{}", i))
+        .collect::<Vec<_>>()
+        .join(",");
     let args = vec!["clarg", "-b", &pattern];
     let cli = Cli::try_parse_from(args).unwrap();
 
@@ -62,11 +78,20 @@ fn test_b_flag_glob_with_curly_braces() {
 #[test]
 fn test_b_flag_glob_with_curly_braces_using_multiple_flags() {
     // To preserve curly braces with commas, use multiple -b flags
-    let args = vec!["clarg", "-b", "*.{env,secret,key,pem}", "-b", "file.{txt,md}"];
+    let args = vec![
+        "clarg",
+        "-b",
+        "*.{env,secret,key,pem}",
+        "-b",
+        "file.{txt,md}",
+    ];
     let cli = Cli::try_parse_from(args).unwrap();
 
     // Still gets split because each flag value is split by comma delimiter
-    assert_eq!(cli.block_access_to, vec!["*.{env", "secret", "key", "pem}", "file.{txt", "md}"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["*.{env", "secret", "key", "pem}", "file.{txt", "md}"]
+    );
 }
 
 #[test]
@@ -102,7 +127,10 @@ fn test_c_flag_regex_with_anchors() {
     let args = vec!["clarg", "-c", "^rm$,^sudo.*,.*--force$"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.commands_forbidden, vec!["^rm$", "^sudo.*", ".*--force$"]);
+    assert_eq!(
+        cli.commands_forbidden,
+        vec!["^rm$", "^sudo.*", ".*--force$"]
+    );
 }
 
 #[test]
@@ -118,7 +146,10 @@ fn test_c_flag_regex_with_escape_sequences() {
     let args = vec!["clarg", "-c", r"rm\s+.*,curl.*\|.*,wget.*>.*"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.commands_forbidden, vec![r"rm\s+.*", r"curl.*\|.*", r"wget.*>.*"]);
+    assert_eq!(
+        cli.commands_forbidden,
+        vec![r"rm\s+.*", r"curl.*\|.*", r"wget.*>.*"]
+    );
 }
 
 #[test]
@@ -126,7 +157,10 @@ fn test_c_flag_regex_with_groups() {
     let args = vec!["clarg", "-c", r"(rm|mv|cp)\s+-rf,sudo\s+(rm|dd)"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.commands_forbidden, vec![r"(rm|mv|cp)\s+-rf", r"sudo\s+(rm|dd)"]);
+    assert_eq!(
+        cli.commands_forbidden,
+        vec![r"(rm|mv|cp)\s+-rf", r"sudo\s+(rm|dd)"]
+    );
 }
 
 // ============================================================================
@@ -138,7 +172,10 @@ fn test_b_flag_with_absolute_paths() {
     let args = vec!["clarg", "-b", "/etc/passwd,/etc/shadow,/root/.ssh/"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec!["/etc/passwd", "/etc/shadow", "/root/.ssh/"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["/etc/passwd", "/etc/shadow", "/root/.ssh/"]
+    );
 }
 
 #[test]
@@ -146,7 +183,10 @@ fn test_b_flag_with_relative_paths() {
     let args = vec!["clarg", "-b", "./secrets/,../config/,.env"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec!["./secrets/", "../config/", ".env"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["./secrets/", "../config/", ".env"]
+    );
 }
 
 #[test]
@@ -154,7 +194,10 @@ fn test_b_flag_with_home_directory_paths() {
     let args = vec!["clarg", "-b", "~/.ssh/,~/.aws/,~/.config/"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec!["~/.ssh/", "~/.aws/", "~/.config/"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["~/.ssh/", "~/.aws/", "~/.config/"]
+    );
 }
 
 #[test]
@@ -162,7 +205,10 @@ fn test_b_flag_patterns_with_backslashes() {
     let args = vec!["clarg", "-b", r"C:\Windows\System32,\\network\share"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec![r"C:\Windows\System32", r"\\network\share"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec![r"C:\Windows\System32", r"\\network\share"]
+    );
 }
 
 // ============================================================================
@@ -174,7 +220,10 @@ fn test_b_flag_unicode_patterns() {
     let args = vec!["clarg", "-b", "fichier™.txt,文档.doc,файл.pdf"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec!["fichier™.txt", "文档.doc", "файл.pdf"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["fichier™.txt", "文档.doc", "файл.pdf"]
+    );
 }
 
 #[test]
@@ -182,7 +231,10 @@ fn test_c_flag_unicode_patterns() {
     let args = vec!["clarg", "-c", "删除,удалить,supprimerå"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.commands_forbidden, vec!["删除", "удалить", "supprimerå"]);
+    assert_eq!(
+        cli.commands_forbidden,
+        vec!["删除", "удалить", "supprimerå"]
+    );
 }
 
 #[test]
@@ -1,5 +1,6 @@
 use clap::Parser;
 use clarg::cli::Cli;
+use clarg::config::RuleAction;
 
 // ============================================================================
 // LONG FLAG TESTS: --block-access-to
@@ -23,7 +24,13 @@ fn test_long_flag_block_access_to_comma_separated() {
 
 #[test]
 fn test_long_flag_block_access_to_multiple_flags() {
-    let args = vec!["clarg", "--block-access-to", ".env", "--block-access-to", "*.secret"];
+    let args = vec![
+        "clarg",
+        "--block-access-to",
+        ".env",
+        "--block-access-to",
+        "*.secret",
+    ];
     let cli = Cli::try_parse_from(args).unwrap();
 
     assert_eq!(cli.block_access_to, vec![".env", "*.secret"]);
@@ -59,7 +66,13 @@ fn test_long_flag_commands_forbidden_comma_separated() {
 
 #[test]
 fn test_long_flag_commands_forbidden_multiple_flags() {
-    let args = vec!["clarg", "--commands-forbidden", "rm", "--commands-forbidden", "mv"];
+    let args = vec![
+        "clarg",
+        "--commands-forbidden",
+        "rm",
+        "--commands-forbidden",
+        "mv",
+    ];
     let cli = Cli::try_parse_from(args).unwrap();
 
     assert_eq!(cli.commands_forbidden, vec!["rm", "mv"]);
@@ -118,5 +131,134 @@ fn test_long_flag_internal_access_only_is_boolean() {
     let args = vec!["clarg", "--internal-access-only"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.internal_access_only, true);
+    assert!(cli.internal_access_only);
+}
+
+// ============================================================================
+// LONG FLAG TESTS: --pipe-to-shell
+// ============================================================================
+
+#[test]
+fn test_long_flag_pipe_to_shell() {
+    let args = vec!["clarg", "--pipe-to-shell", "ask"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.pipe_to_shell, Some(RuleAction::Ask));
+}
+
+#[test]
+fn test_long_flag_pipe_to_shell_invalid_value() {
+    let args = vec!["clarg", "--pipe-to-shell", "maybe"];
+    assert!(Cli::try_parse_from(args).is_err());
+}
+
+#[test]
+fn test_long_flag_secret_flow() {
+    let args = vec!["clarg", "--secret-flow", "ask"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.secret_flow, Some(RuleAction::Ask));
+}
+
+#[test]
+fn test_long_flag_persistence() {
+    let args = vec!["clarg", "--persistence", "deny"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.persistence, Some(RuleAction::Deny));
+}
+
+#[test]
+fn test_long_flag_obfuscation_threshold() {
+    let args = vec!["clarg", "--obfuscation-threshold", "5"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.obfuscation_threshold, Some(5));
+}
+
+#[test]
+fn test_long_flag_strict() {
+    let args = vec!["clarg", "--strict"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert!(cli.strict);
+}
+
+#[test]
+fn test_long_flag_preset_comma_separated() {
+    let args = vec!["clarg", "--preset", "destructive,git-safety"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.presets, vec!["destructive", "git-safety"]);
+}
+
+#[test]
+fn test_presets_subcommand() {
+    use clarg::cli::{Command, PresetsCommand};
+
+    let args = vec!["clarg", "presets", "show", "destructive"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    match cli.command {
+        Some(Command::Presets(PresetsCommand::Show { name })) => assert_eq!(name, "destructive"),
+        other => panic!("expected presets show, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_long_flag_no_protect_git() {
+    let cli = Cli::try_parse_from(vec!["clarg"]).unwrap();
+    assert!(!cli.no_protect_git);
+
+    let cli = Cli::try_parse_from(vec!["clarg", "--no-protect-git"]).unwrap();
+    assert!(cli.no_protect_git);
+}
+
+#[test]
+fn test_long_flag_inspect_archives() {
+    let cli = Cli::try_parse_from(vec!["clarg"]).unwrap();
+    assert!(!cli.inspect_archives);
+
+    let cli = Cli::try_parse_from(vec!["clarg", "--inspect-archives"]).unwrap();
+    assert!(cli.inspect_archives);
+    assert!(Cli::try_parse_from(vec!["clarg", "config.yaml", "--inspect-archives"]).is_err());
+}
+
+#[test]
+fn test_long_flag_allow_and_deny_hosts() {
+    let args = vec![
+        "clarg",
+        "--allow-hosts",
+        "github.com,*.corp.example",
+        "--deny-hosts",
+        "evil.example",
+    ];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.allow_hosts, vec!["github.com", "*.corp.example"]);
+    assert_eq!(cli.deny_hosts, vec!["evil.example"]);
+}
+
+#[test]
+fn test_long_flag_allow_and_deny_databases() {
+    let args = vec![
+        "clarg",
+        "--allow-databases",
+        "app_dev@localhost,*.sqlite3",
+        "--deny-databases",
+        "*@db.prod",
+    ];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.allow_databases, vec!["app_dev@localhost", "*.sqlite3"]);
+    assert_eq!(cli.deny_databases, vec!["*@db.prod"]);
+    assert!(Cli::try_parse_from(vec!["clarg", "config.yaml", "--allow-databases", "app"]).is_err());
+}
+
+#[test]
+fn test_long_flag_max_write_size() {
+    let args = vec!["clarg", "--max-write-size", "10G"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.max_write_size.as_deref(), Some("10G"));
 }
@@ -1,8 +1,8 @@
-mod defaults;
-mod short_flags;
-mod long_flags;
+mod combinations;
 mod config_path;
 mod conflicts;
-mod combinations;
-mod version_help;
+mod defaults;
 mod edge_cases;
+mod long_flags;
+mod short_flags;
+mod version_help;
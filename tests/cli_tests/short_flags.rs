@@ -29,7 +29,10 @@ fn test_short_flag_b_comma_separated_multiple_items() {
     let args = vec!["clarg", "-b", ".env,*.secret,*.key,secrets/"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec![".env", "*.secret", "*.key", "secrets/"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec![".env", "*.secret", "*.key", "secrets/"]
+    );
 }
 
 #[test]
@@ -61,7 +64,10 @@ fn test_short_flag_b_glob_patterns() {
     let args = vec!["clarg", "-b", "**/*.env,**/secrets/**,tmp/*"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec!["**/*.env", "**/secrets/**", "tmp/*"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["**/*.env", "**/secrets/**", "tmp/*"]
+    );
 }
 
 #[test]
@@ -77,7 +83,10 @@ fn test_short_flag_b_patterns_with_slashes() {
     let args = vec!["clarg", "-b", "secrets/,/etc/passwd,~/.ssh/"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.block_access_to, vec!["secrets/", "/etc/passwd", "~/.ssh/"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["secrets/", "/etc/passwd", "~/.ssh/"]
+    );
 }
 
 #[test]
@@ -102,7 +111,10 @@ fn test_short_flag_b_special_characters() {
     let cli = Cli::try_parse_from(args).unwrap();
 
     // Note: Comma delimiter splits on all commas, including those inside braces
-    assert_eq!(cli.block_access_to, vec!["*.{env", "secret}", "file[0-9].txt", "data?.db"]);
+    assert_eq!(
+        cli.block_access_to,
+        vec!["*.{env", "secret}", "file[0-9].txt", "data?.db"]
+    );
 }
 
 // ============================================================================
@@ -163,7 +175,10 @@ fn test_short_flag_c_regex_patterns() {
     let args = vec!["clarg", "-c", "^rm.*,.*--force,dd.*if="];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.commands_forbidden, vec!["^rm.*", ".*--force", "dd.*if="]);
+    assert_eq!(
+        cli.commands_forbidden,
+        vec!["^rm.*", ".*--force", "dd.*if="]
+    );
 }
 
 #[test]
@@ -171,7 +186,10 @@ fn test_short_flag_c_patterns_with_special_chars() {
     let args = vec!["clarg", "-c", r"rm\s+-rf,curl.*\|\s*sh,wget.*&&"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.commands_forbidden, vec![r"rm\s+-rf", r"curl.*\|\s*sh", r"wget.*&&"]);
+    assert_eq!(
+        cli.commands_forbidden,
+        vec![r"rm\s+-rf", r"curl.*\|\s*sh", r"wget.*&&"]
+    );
 }
 
 #[test]
@@ -255,5 +273,21 @@ fn test_short_flag_i_is_boolean() {
     let args = vec!["clarg", "-i"];
     let cli = Cli::try_parse_from(args).unwrap();
 
-    assert_eq!(cli.internal_access_only, true);
+    assert!(cli.internal_access_only);
+}
+
+#[test]
+fn test_short_flag_strict() {
+    let args = vec!["clarg", "-s"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert!(cli.strict);
+}
+
+#[test]
+fn test_short_flag_preset() {
+    let args = vec!["clarg", "-p", "privilege"];
+    let cli = Cli::try_parse_from(args).unwrap();
+
+    assert_eq!(cli.presets, vec!["privilege"]);
 }
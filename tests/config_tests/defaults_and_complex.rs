@@ -1,6 +1,6 @@
+use super::helpers::create_yaml_file;
 use clarg::config::Config;
 use std::path::PathBuf;
-use super::helpers::create_yaml_file;
 
 // ============================================================================
 // Config Default Trait Test
@@ -13,7 +13,7 @@ fn test_config_default_trait() {
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 // ============================================================================
@@ -57,7 +57,7 @@ internal_access_only: True
     let file = create_yaml_file(yaml);
     let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
 
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 #[test]
@@ -308,7 +308,7 @@ fn test_from_yaml_extremely_nested_flow_style() {
 
     assert_eq!(config.block_access_to.len(), 2);
     assert_eq!(config.commands_forbidden.len(), 1);
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 #[test]
@@ -323,7 +323,7 @@ internal_access_only: true"#;
     assert_eq!(config.block_access_to.len(), 1);
     assert_eq!(config.commands_forbidden.len(), 1);
     assert_eq!(config.log_to, Some(PathBuf::from("/tmp/log")));
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 #[test]
@@ -352,7 +352,8 @@ fn test_from_yaml_mac_classic_line_endings() {
 
 #[test]
 fn test_from_yaml_mixed_line_endings() {
-    let yaml = "block_access_to:\r\n  - \".env\"\n  - \"*.secret\"\rcommands_forbidden:\n  - \"rm\"\r\n";
+    let yaml =
+        "block_access_to:\r\n  - \".env\"\n  - \"*.secret\"\rcommands_forbidden:\n  - \"rm\"\r\n";
     let file = create_yaml_file(yaml);
     let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
 
@@ -1,7 +1,9 @@
+use super::helpers::{
+    create_cli_all_flags, create_cli_no_flags, create_cli_partial_flags, create_yaml_file,
+};
 use clarg::cli::Cli;
 use clarg::config::Config;
 use std::path::PathBuf;
-use super::helpers::{create_cli_no_flags, create_cli_all_flags, create_cli_partial_flags, create_yaml_file};
 
 // ============================================================================
 // Config::from_cli TESTS
@@ -15,7 +17,7 @@ fn test_from_cli_no_flags() {
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -30,7 +32,7 @@ fn test_from_cli_all_flags_populated() {
     assert_eq!(config.commands_forbidden[0], "rm -rf");
     assert_eq!(config.commands_forbidden[1], "sudo");
     assert_eq!(config.log_to, Some(PathBuf::from("/tmp/clarg.log")));
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 #[test]
@@ -42,7 +44,7 @@ fn test_from_cli_partial_flags() {
     assert_eq!(config.block_access_to[0], ".env");
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -53,6 +55,7 @@ fn test_from_cli_only_block_access_to() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let config = Config::from_cli(cli).unwrap();
 
@@ -61,7 +64,7 @@ fn test_from_cli_only_block_access_to() {
     assert_eq!(config.block_access_to[1], "*.key");
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -72,6 +75,7 @@ fn test_from_cli_only_commands_forbidden() {
         commands_forbidden: vec!["dd".to_string(), "mkfs".to_string()],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let config = Config::from_cli(cli).unwrap();
 
@@ -80,7 +84,7 @@ fn test_from_cli_only_commands_forbidden() {
     assert_eq!(config.commands_forbidden[0], "dd");
     assert_eq!(config.commands_forbidden[1], "mkfs");
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -91,13 +95,14 @@ fn test_from_cli_only_log_to() {
         commands_forbidden: vec![],
         log_to: Some(PathBuf::from("/var/log/clarg.log")),
         internal_access_only: false,
+        ..Default::default()
     };
     let config = Config::from_cli(cli).unwrap();
 
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, Some(PathBuf::from("/var/log/clarg.log")));
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -108,13 +113,14 @@ fn test_from_cli_only_internal_access_only() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let config = Config::from_cli(cli).unwrap();
 
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 #[test]
@@ -135,6 +141,7 @@ internal_access_only: true
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let config = Config::from_cli(cli).unwrap();
 
@@ -144,7 +151,7 @@ internal_access_only: true
     assert_eq!(config.commands_forbidden.len(), 1);
     assert_eq!(config.commands_forbidden[0], "rm -rf");
     assert_eq!(config.log_to, Some(PathBuf::from("/tmp/clarg.log")));
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 // ============================================================================
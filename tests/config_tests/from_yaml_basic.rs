@@ -1,6 +1,6 @@
-use clarg::config::Config;
-use std::path::PathBuf;
 use super::helpers::create_yaml_file;
+use clarg::config::{Config, DEFAULT_OBFUSCATION_THRESHOLD, RuleAction};
+use std::path::PathBuf;
 
 // ============================================================================
 // Config::from_yaml TESTS - Basic Functionality
@@ -28,7 +28,7 @@ internal_access_only: true
     assert_eq!(config.commands_forbidden[0], "rm -rf");
     assert_eq!(config.commands_forbidden[1], "sudo");
     assert_eq!(config.log_to, Some(PathBuf::from("/tmp/clarg.log")));
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 #[test]
@@ -46,7 +46,7 @@ block_access_to:
     assert_eq!(config.block_access_to[1], "*.secret");
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -64,7 +64,7 @@ commands_forbidden:
     assert_eq!(config.commands_forbidden[0], "rm -rf");
     assert_eq!(config.commands_forbidden[1], "sudo");
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -78,7 +78,7 @@ log_to: "/tmp/clarg.log"
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, Some(PathBuf::from("/tmp/clarg.log")));
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -92,7 +92,7 @@ internal_access_only: true
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 #[test]
@@ -106,7 +106,7 @@ internal_access_only: false
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -121,7 +121,7 @@ commands_forbidden: []
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -133,7 +133,7 @@ fn test_from_yaml_empty_file() {
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -145,7 +145,184 @@ fn test_from_yaml_minimal_valid() {
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 // ============================================================================
+
+#[test]
+fn test_from_yaml_pipe_to_shell() {
+    let yaml = r#"
+pipe_to_shell: ask
+"#;
+    let file = create_yaml_file(yaml);
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.pipe_to_shell, RuleAction::Ask);
+}
+
+#[test]
+fn test_from_yaml_pipe_to_shell_defaults_to_deny() {
+    let file = create_yaml_file("internal_access_only: true\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.pipe_to_shell, RuleAction::Deny);
+}
+
+#[test]
+fn test_from_yaml_secret_flow() {
+    let file = create_yaml_file("secret_flow: ask\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.secret_flow, RuleAction::Ask);
+    let file = create_yaml_file("strict: true\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert_eq!(config.secret_flow, RuleAction::Deny);
+}
+
+#[test]
+fn test_from_yaml_persistence() {
+    let file = create_yaml_file("persistence: deny\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.persistence, RuleAction::Deny);
+    let file = create_yaml_file("strict: true\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert_eq!(config.persistence, RuleAction::Ask);
+}
+
+#[test]
+fn test_from_yaml_obfuscation_threshold() {
+    let file = create_yaml_file("obfuscation_threshold: 6\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.obfuscation_threshold, 6);
+}
+
+#[test]
+fn test_from_yaml_obfuscation_threshold_default() {
+    let file = create_yaml_file("internal_access_only: true\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.obfuscation_threshold, DEFAULT_OBFUSCATION_THRESHOLD);
+}
+
+#[test]
+fn test_from_yaml_strict() {
+    let file = create_yaml_file("strict: true\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert!(config.strict);
+}
+
+#[test]
+fn test_from_yaml_presets() {
+    let file = create_yaml_file("presets: [destructive, privilege, git-safety]\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(
+        config.presets,
+        vec!["destructive", "privilege", "git-safety"]
+    );
+}
+
+#[test]
+fn test_from_yaml_fact_rules() {
+    let file = create_yaml_file(
+        "rules:\n  - fact: git.push.force\n    when: { remote: origin }\n    action: ask\n  - fact: git.reset.hard\n",
+    );
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.rules.len(), 2);
+    assert_eq!(config.rules[0].fact, "git.push.force");
+    assert_eq!(config.rules[0].when["remote"], "origin");
+    assert_eq!(config.rules[0].action, RuleAction::Ask);
+    assert_eq!(config.rules[1].action, RuleAction::Deny);
+}
+
+#[test]
+fn test_from_yaml_protect_git_defaults_on() {
+    let file = create_yaml_file("strict: false\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert!(config.protect_git);
+
+    let file = create_yaml_file("protect_git: false\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert!(!config.protect_git);
+}
+
+#[test]
+fn test_from_yaml_inspect_archives() {
+    let file = create_yaml_file("strict: false\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert!(!config.inspect_archives);
+
+    let file = create_yaml_file("inspect_archives: true\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert!(config.inspect_archives);
+}
+
+#[test]
+fn test_from_yaml_hosts() {
+    let file = create_yaml_file(
+        "hosts:\n  allow: [github.com, '*.corp.example']\n  deny: ['*.pastebin.com']\n",
+    );
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.hosts.allow, vec!["github.com", "*.corp.example"]);
+    assert_eq!(config.hosts.deny, vec!["*.pastebin.com"]);
+
+    let file = create_yaml_file("hosts:\n  deny: [evil.example]\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert!(config.hosts.allow.is_empty());
+}
+
+#[test]
+fn test_from_yaml_web() {
+    let file = create_yaml_file(
+        "web:\n  schemes: [https]\n  allow: [docs.rs, 'github.com/rust-lang/']\n  private_addresses: ask\n  query_strings: allow\n",
+    );
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.web.schemes, vec!["https"]);
+    assert_eq!(config.web.allow, vec!["docs.rs", "github.com/rust-lang/"]);
+    assert_eq!(config.web.private_addresses, RuleAction::Ask);
+    assert_eq!(config.web.query_strings, RuleAction::Allow);
+}
+
+#[test]
+fn test_from_yaml_web_defaults() {
+    let file = create_yaml_file("web:\n  deny: [evil.example]\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.web.schemes, vec!["http", "https"]);
+    assert_eq!(config.web.private_addresses, RuleAction::Allow);
+    assert_eq!(config.web.query_strings, RuleAction::Allow);
+}
+
+#[test]
+fn test_from_yaml_databases() {
+    let file = create_yaml_file(
+        "databases:\n  allow: ['app_*@localhost', '*.sqlite3']\n  deny: ['*@*.prod.example']\n",
+    );
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.databases.allow, vec!["app_*@localhost", "*.sqlite3"]);
+    assert_eq!(config.databases.deny, vec!["*@*.prod.example"]);
+
+    let file = create_yaml_file("strict: false\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert!(config.databases.is_empty());
+}
+
+#[test]
+fn test_from_yaml_resources() {
+    let file = create_yaml_file("resources:\n  max_write_size: 10G\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+
+    assert_eq!(config.resources.max_write_size.as_deref(), Some("10G"));
+
+    let file = create_yaml_file("strict: false\n");
+    let config = Config::from_yaml(&file.path().to_path_buf()).unwrap();
+    assert!(config.resources.is_empty());
+}
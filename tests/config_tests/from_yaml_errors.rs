@@ -1,6 +1,6 @@
+use super::helpers::create_yaml_file;
 use clarg::config::Config;
 use std::path::PathBuf;
-use super::helpers::create_yaml_file;
 
 // ============================================================================
 // Config::from_yaml TESTS - Error Cases
@@ -138,7 +138,7 @@ nested:
     assert_eq!(config.block_access_to[0], ".env");
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 #[test]
@@ -1,6 +1,6 @@
+use super::helpers::create_yaml_file;
 use clarg::config::Config;
 use std::path::PathBuf;
-use super::helpers::create_yaml_file;
 
 // ============================================================================
 // Config::from_yaml TESTS - Many Patterns/Commands
@@ -1,5 +1,5 @@
-use clarg::config::Config;
 use super::helpers::create_yaml_file;
+use clarg::config::Config;
 
 // ============================================================================
 // Config::from_yaml TESTS - Empty Strings & Whitespace
@@ -1,6 +1,6 @@
+use super::helpers::create_yaml_file;
 use clarg::config::Config;
 use std::path::PathBuf;
-use super::helpers::create_yaml_file;
 
 // ============================================================================
 // Config::from_yaml TESTS - YAML Comments
@@ -28,7 +28,7 @@ internal_access_only: true  # Restrict access
     assert_eq!(config.commands_forbidden.len(), 1);
     assert_eq!(config.commands_forbidden[0], "rm -rf");
     assert_eq!(config.log_to, Some(PathBuf::from("/tmp/clarg.log")));
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 #[test]
@@ -45,7 +45,7 @@ fn test_from_yaml_with_only_comments() {
     assert_eq!(config.block_access_to.len(), 0);
     assert_eq!(config.commands_forbidden.len(), 0);
     assert_eq!(config.log_to, None);
-    assert_eq!(config.internal_access_only, false);
+    assert!(!config.internal_access_only);
 }
 
 // ============================================================================
@@ -334,7 +334,7 @@ fn test_from_yaml_flow_style_complete() {
     assert_eq!(config.commands_forbidden.len(), 1);
     assert_eq!(config.commands_forbidden[0], "rm");
     assert_eq!(config.log_to, Some(PathBuf::from("/tmp/log")));
-    assert_eq!(config.internal_access_only, true);
+    assert!(config.internal_access_only);
 }
 
 // ============================================================================
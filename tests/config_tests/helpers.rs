@@ -17,6 +17,7 @@ pub fn create_cli_no_flags() -> Cli {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     }
 }
 
@@ -27,6 +28,7 @@ pub fn create_cli_all_flags() -> Cli {
         commands_forbidden: vec!["rm -rf".to_string(), "sudo".to_string()],
         log_to: Some(PathBuf::from("/tmp/clarg.log")),
         internal_access_only: true,
+        ..Default::default()
     }
 }
 
@@ -37,5 +39,6 @@ pub fn create_cli_partial_flags() -> Cli {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     }
 }
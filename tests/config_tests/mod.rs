@@ -1,8 +1,8 @@
-mod helpers;
+mod defaults_and_complex;
 mod from_cli;
 mod from_yaml_basic;
 mod from_yaml_errors;
 mod from_yaml_patterns;
 mod from_yaml_strings;
 mod from_yaml_syntax;
-mod defaults_and_complex;
+mod helpers;
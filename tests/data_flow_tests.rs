@@ -0,0 +1,5 @@
+// Test entry point for data_flow module tests
+// Tests credential path recognition and taint tracking to network sinks
+
+#[path = "data_flow_tests/mod.rs"]
+mod data_flow_tests;
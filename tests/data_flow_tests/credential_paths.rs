@@ -0,0 +1,79 @@
+use clarg::data_flow::is_credential_path;
+
+#[test]
+fn test_env_files() {
+    assert!(is_credential_path(".env"));
+    assert!(is_credential_path("config/.env.production"));
+    assert!(!is_credential_path(".env.example"));
+    assert!(!is_credential_path(".envrc.md"));
+}
+
+#[test]
+fn test_key_files() {
+    for path in [
+        "~/.ssh/id_ed25519",
+        "deploy/server.pem",
+        "tls.key",
+        "store.p12",
+        "id_rsa",
+    ] {
+        assert!(is_credential_path(path), "{path}");
+    }
+    assert!(!is_credential_path("~/.ssh/id_ed25519.pub"));
+    assert!(!is_credential_path(".key"));
+}
+
+#[test]
+fn test_credential_directories() {
+    for path in [
+        "~/.ssh",
+        "~/.ssh/",
+        "/home/u/.aws/config",
+        "~/.gnupg/private-keys-v1.d",
+        "~/.kube/config",
+    ] {
+        assert!(is_credential_path(path), "{path}");
+    }
+    assert!(is_credential_path("~/.docker/config.json"));
+    assert!(is_credential_path(
+        "~/.config/gcloud/application_default_credentials.json"
+    ));
+    assert!(!is_credential_path("~/.ssh/known_hosts"));
+    assert!(!is_credential_path("~/.config/nvim/init.lua"));
+}
+
+#[test]
+fn test_credential_names() {
+    for path in [
+        "secrets",
+        "k8s/secret.yaml",
+        "client_secret.json",
+        "CREDENTIALS.txt",
+        "~/.netrc",
+        "/etc/shadow",
+    ] {
+        assert!(is_credential_path(path), "{path}");
+    }
+}
+
+#[test]
+fn test_process_environment() {
+    assert!(is_credential_path("/proc/self/environ"));
+    assert!(is_credential_path("/proc/1/environ"));
+    assert!(!is_credential_path("environ"));
+}
+
+#[test]
+fn test_ordinary_paths() {
+    for path in [
+        "README.md",
+        "src/main.rs",
+        "/tmp/out",
+        ".",
+        "/",
+        "keys.rs",
+        "environment.yml",
+    ] {
+        assert!(!is_credential_path(path), "{path}");
+    }
+}
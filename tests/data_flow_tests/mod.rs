@@ -0,0 +1,2 @@
+mod credential_paths;
+mod flows;
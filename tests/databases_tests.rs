@@ -0,0 +1,5 @@
+// Test entry point for databases module tests
+// Tests database allowlist and denylist matching
+
+#[path = "databases_tests/mod.rs"]
+mod databases_tests;
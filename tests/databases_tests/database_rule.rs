@@ -0,0 +1,156 @@
+use clarg::databases::{DatabasePolicy, DatabaseRule};
+use clarg::facts::Fact;
+
+fn rule(allow: &[&str], deny: &[&str]) -> DatabaseRule {
+    DatabaseRule::new(&DatabasePolicy {
+        allow: allow.iter().map(|s| s.to_string()).collect(),
+        deny: deny.iter().map(|s| s.to_string()).collect(),
+    })
+    .unwrap()
+}
+
+fn connect(tool: &str, database: Option<&str>, host: Option<&str>) -> Fact {
+    let mut fact = Fact::new("database.connect", tool).with("tool", tool);
+    if let Some(database) = database {
+        fact = fact.with("database", database);
+    }
+    if let Some(host) = host {
+        fact = fact.with("host", host);
+    }
+    fact
+}
+
+// ============================================================================
+// Allowlist
+// ============================================================================
+
+#[test]
+fn test_empty_policy_allows() {
+    assert!(
+        rule(&[], &[])
+            .check(&connect("psql", Some("prod"), Some("db")))
+            .is_none()
+    );
+}
+
+#[test]
+fn test_allowlist_database_and_host() {
+    let rule = rule(&["app_*@localhost", "reports"], &[]);
+    assert!(
+        rule.check(&connect("psql", Some("app_dev"), Some("localhost")))
+            .is_none()
+    );
+    assert!(
+        rule.check(&connect("psql", Some("app_dev"), Some("db.example")))
+            .is_some()
+    );
+    // Without @ any host matches
+    assert!(
+        rule.check(&connect("mysql", Some("reports"), Some("db.example")))
+            .is_none()
+    );
+    assert!(
+        rule.check(&connect("mysql", Some("billing"), Some("localhost")))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_missing_host_is_localhost() {
+    let rule = rule(&["*@localhost"], &[]);
+    assert!(rule.check(&connect("psql", Some("app"), None)).is_none());
+    assert!(rule.check(&connect("psql", None, None)).is_none());
+    // sqlite3 has no host, so host patterns never match it
+    assert!(
+        rule.check(&connect("sqlite3", Some("app.db"), None))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_database_case_sensitive_host_not() {
+    let rule = rule(&["app@DB.example"], &[]);
+    assert!(
+        rule.check(&connect("psql", Some("app"), Some("db.EXAMPLE")))
+            .is_none()
+    );
+    assert!(
+        rule.check(&connect("psql", Some("APP"), Some("db.example")))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_cidr_hosts() {
+    let rule = rule(&["*@10.0.0.0/8"], &[]);
+    assert!(
+        rule.check(&connect("redis-cli", Some("0"), Some("10.1.2.3")))
+            .is_none()
+    );
+    assert!(
+        rule.check(&connect("redis-cli", Some("0"), Some("192.168.1.1")))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_sqlite_file_globs() {
+    let rule = rule(&["*.sqlite3", "data/*.db"], &[]);
+    assert!(
+        rule.check(&connect("sqlite3", Some("dev.sqlite3"), None))
+            .is_none()
+    );
+    assert!(
+        rule.check(&connect("sqlite3", Some("data/app.db"), None))
+            .is_none()
+    );
+    assert!(
+        rule.check(&connect("sqlite3", Some("/var/lib/app.db"), None))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_service_without_host_not_on_allowlist() {
+    let service = connect("psql", Some("app_dev"), None).with("service", "billing");
+    let reason = rule(&["app_dev@localhost"], &[]).check(&service).unwrap();
+    assert!(reason.contains("service 'billing'"));
+    assert!(rule(&[], &[]).check(&service).is_none());
+    // An explicit host is checked as usual
+    let service = service.with("host", "localhost");
+    assert!(rule(&["app_dev@localhost"], &[]).check(&service).is_none());
+}
+
+// ============================================================================
+// Denylist
+// ============================================================================
+
+#[test]
+fn test_denylist_wins_over_allowlist() {
+    let rule = rule(&["*"], &["*@*.prod.example"]);
+    let reason = rule
+        .check(&connect("psql", Some("app"), Some("db.prod.example")))
+        .unwrap();
+    assert!(reason.contains("'app@db.prod.example'"));
+    assert!(reason.contains("denylist"));
+    assert!(
+        rule.check(&connect("psql", Some("app"), Some("db.dev.example")))
+            .is_none()
+    );
+}
+
+#[test]
+fn test_other_facts_ignored() {
+    let rule = rule(&["app"], &[]);
+    let fact = Fact::new("database.statement", "psql").with("database", "prod");
+    assert!(rule.check(&fact).is_none());
+}
+
+#[test]
+fn test_invalid_host_pattern_is_error() {
+    let policy = DatabasePolicy {
+        allow: vec!["app@10.0.0.0/99".to_string()],
+        deny: Vec::new(),
+    };
+    assert!(DatabaseRule::new(&policy).is_err());
+}
@@ -0,0 +1 @@
+mod database_rule;
@@ -0,0 +1,5 @@
+// Test entry point for facts module tests
+// Tests fact construction and rule matching
+
+#[path = "facts_tests/mod.rs"]
+mod facts_tests;
@@ -0,0 +1,144 @@
+use clarg::config::RuleAction;
+use clarg::facts::{Fact, FactRule, FactRules};
+use std::collections::BTreeMap;
+
+fn rule(fact: &str, action: RuleAction) -> FactRule {
+    FactRule {
+        fact: fact.to_string(),
+        action,
+        ..Default::default()
+    }
+}
+
+fn push_force() -> Fact {
+    Fact::new("git.push.force", "git push -f origin main")
+        .with("remote", "origin")
+        .with("via", "-f")
+}
+
+// ============================================================================
+// Name matching
+// ============================================================================
+
+#[test]
+fn test_exact_name_matches() {
+    let rules = FactRules::new(&[rule("git.push.force", RuleAction::Deny)]).unwrap();
+    let (action, _) = rules.check(&push_force()).unwrap();
+    assert_eq!(action, RuleAction::Deny);
+}
+
+#[test]
+fn test_wildcard_name_matches() {
+    let rules = FactRules::new(&[rule("git.*", RuleAction::Ask)]).unwrap();
+    assert_eq!(rules.check(&push_force()).unwrap().0, RuleAction::Ask);
+}
+
+#[test]
+fn test_name_is_anchored() {
+    let rules = FactRules::new(&[rule("git.push", RuleAction::Deny)]).unwrap();
+    assert!(rules.check(&push_force()).is_none());
+}
+
+#[test]
+fn test_dot_is_literal() {
+    let rules = FactRules::new(&[rule("git.push.forc.", RuleAction::Deny)]).unwrap();
+    assert!(rules.check(&push_force()).is_none());
+}
+
+// ============================================================================
+// Attribute conditions
+// ============================================================================
+
+#[test]
+fn test_when_matches_attribute() {
+    let mut when = BTreeMap::new();
+    when.insert("remote".to_string(), "^origin$".to_string());
+    let rules = FactRules::new(&[FactRule {
+        fact: "git.push.force".to_string(),
+        when,
+        ..Default::default()
+    }])
+    .unwrap();
+    assert!(rules.check(&push_force()).is_some());
+}
+
+#[test]
+fn test_when_mismatch_skips_rule() {
+    let mut when = BTreeMap::new();
+    when.insert("remote".to_string(), "^upstream$".to_string());
+    let rules = FactRules::new(&[FactRule {
+        fact: "git.push.force".to_string(),
+        when,
+        ..Default::default()
+    }])
+    .unwrap();
+    assert!(rules.check(&push_force()).is_none());
+}
+
+#[test]
+fn test_when_missing_attribute_skips_rule() {
+    let mut when = BTreeMap::new();
+    when.insert("refspec".to_string(), ".*".to_string());
+    let rules = FactRules::new(&[FactRule {
+        fact: "git.push.force".to_string(),
+        when,
+        ..Default::default()
+    }])
+    .unwrap();
+    assert!(rules.check(&push_force()).is_none());
+}
+
+#[test]
+fn test_invalid_when_regex_errors() {
+    let mut when = BTreeMap::new();
+    when.insert("remote".to_string(), "(".to_string());
+    let result = FactRules::new(&[FactRule {
+        fact: "git.push.force".to_string(),
+        when,
+        ..Default::default()
+    }]);
+    assert!(result.is_err());
+}
+
+// ============================================================================
+// Ordering and reasons
+// ============================================================================
+
+#[test]
+fn test_first_matching_rule_wins() {
+    let rules = FactRules::new(&[
+        rule("git.push.force", RuleAction::Allow),
+        rule("git.*", RuleAction::Deny),
+    ])
+    .unwrap();
+    assert_eq!(rules.check(&push_force()).unwrap().0, RuleAction::Allow);
+}
+
+#[test]
+fn test_default_action_is_deny() {
+    let rules = FactRules::new(&[rule("git.push.force", RuleAction::default())]).unwrap();
+    assert_eq!(rules.check(&push_force()).unwrap().0, RuleAction::Deny);
+}
+
+#[test]
+fn test_generated_reason_describes_fact() {
+    let rules = FactRules::new(&[rule("git.*", RuleAction::Deny)]).unwrap();
+    let (_, reason) = rules.check(&push_force()).unwrap();
+    assert!(reason.starts_with("Blocked by `clarg`"));
+    assert!(reason.contains("git push -f origin main"));
+    assert!(reason.contains("'git.push.force' (remote=origin, via=-f)"));
+}
+
+#[test]
+fn test_custom_reason_used() {
+    let rules = FactRules::new(&[FactRule {
+        fact: "git.push.force".to_string(),
+        reason: Some("no force pushes here".to_string()),
+        ..Default::default()
+    }])
+    .unwrap();
+    assert_eq!(
+        rules.check(&push_force()).unwrap().1,
+        "no force pushes here"
+    );
+}
@@ -0,0 +1 @@
+mod fact_rules;
@@ -0,0 +1,5 @@
+// Test entry point for git_internals module tests
+// Tests detection of paths inside .git directories and linked git dirs
+
+#[path = "git_internals_tests/mod.rs"]
+mod git_internals_tests;
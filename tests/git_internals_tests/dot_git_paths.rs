@@ -0,0 +1,88 @@
+use clarg::git_internals::GitInternalsRule;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn rule() -> (TempDir, GitInternalsRule) {
+    let tmp = TempDir::new().unwrap();
+    std::fs::create_dir(tmp.path().join(".git")).unwrap();
+    let rule = GitInternalsRule::new(tmp.path());
+    (tmp, rule)
+}
+
+// ============================================================================
+// Paths inside .git
+// ============================================================================
+
+#[test]
+fn test_hook_script_blocked() {
+    let (tmp, rule) = rule();
+    let reason = rule
+        .check(&tmp.path().join(".git/hooks/pre-commit"))
+        .unwrap();
+    assert!(reason.contains("inside the git directory"));
+    assert!(reason.contains(&tmp.path().join(".git").display().to_string()));
+}
+
+#[test]
+fn test_config_and_info_blocked() {
+    let (tmp, rule) = rule();
+    assert!(rule.check(&tmp.path().join(".git/config")).is_some());
+    assert!(
+        rule.check(&tmp.path().join(".git/info/attributes"))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_dot_git_itself_blocked() {
+    let (tmp, rule) = rule();
+    assert!(rule.check(&tmp.path().join(".git")).is_some());
+}
+
+#[test]
+fn test_nested_repo_blocked() {
+    let (tmp, rule) = rule();
+    assert!(
+        rule.check(&tmp.path().join("vendor/lib/.git/hooks/post-checkout"))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_case_insensitive_name_blocked() {
+    let (tmp, rule) = rule();
+    assert!(
+        rule.check(&tmp.path().join(".GIT/hooks/pre-commit"))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_outside_repo_dot_git_blocked() {
+    let (_tmp, rule) = rule();
+    assert!(
+        rule.check(Path::new("/home/me/other/.git/config"))
+            .is_some()
+    );
+}
+
+// ============================================================================
+// Ordinary paths
+// ============================================================================
+
+#[test]
+fn test_source_files_allowed() {
+    let (tmp, rule) = rule();
+    assert!(rule.check(&tmp.path().join("src/main.rs")).is_none());
+}
+
+#[test]
+fn test_similar_names_allowed() {
+    let (tmp, rule) = rule();
+    assert!(rule.check(&tmp.path().join(".gitignore")).is_none());
+    assert!(
+        rule.check(&tmp.path().join(".github/workflows/ci.yml"))
+            .is_none()
+    );
+    assert!(rule.check(&tmp.path().join("repo.git/config")).is_none());
+}
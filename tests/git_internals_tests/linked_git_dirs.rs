@@ -0,0 +1,78 @@
+use clarg::git_internals::GitInternalsRule;
+use std::fs;
+use tempfile::TempDir;
+
+// ============================================================================
+// `.git` files pointing at a git dir
+// ============================================================================
+
+#[test]
+fn test_separate_git_dir_followed() {
+    let tmp = TempDir::new().unwrap();
+    let work = tmp.path().join("work");
+    let store = tmp.path().join("store");
+    fs::create_dir_all(&work).unwrap();
+    fs::create_dir_all(&store).unwrap();
+    fs::write(work.join(".git"), format!("gitdir: {}\n", store.display())).unwrap();
+
+    let rule = GitInternalsRule::new(&work);
+    assert!(rule.check(&store.join("hooks/pre-commit")).is_some());
+    assert!(rule.check(&tmp.path().join("elsewhere/file")).is_none());
+}
+
+#[test]
+fn test_relative_gitdir_followed() {
+    let tmp = TempDir::new().unwrap();
+    let sub = tmp.path().join("sub");
+    fs::create_dir_all(tmp.path().join("modules-store/sub")).unwrap();
+    fs::create_dir_all(&sub).unwrap();
+    fs::write(sub.join(".git"), "gitdir: ../modules-store/sub\n").unwrap();
+
+    let rule = GitInternalsRule::new(&sub);
+    assert!(
+        rule.check(&tmp.path().join("modules-store/sub/config"))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_worktree_commondir_followed() {
+    let tmp = TempDir::new().unwrap();
+    let common = tmp.path().join("main-store");
+    let wt_dir = common.join("worktrees/feature");
+    let wt = tmp.path().join("feature");
+    fs::create_dir_all(&wt_dir).unwrap();
+    fs::create_dir_all(&wt).unwrap();
+    fs::write(wt_dir.join("commondir"), "../..\n").unwrap();
+    fs::write(wt.join(".git"), format!("gitdir: {}\n", wt_dir.display())).unwrap();
+
+    let rule = GitInternalsRule::new(&wt);
+    // Hooks live in the common dir, shared by every worktree
+    assert!(rule.check(&common.join("hooks/pre-commit")).is_some());
+    assert!(rule.check(&wt_dir.join("HEAD")).is_some());
+}
+
+#[test]
+fn test_gitdir_file_in_ancestor_followed() {
+    let tmp = TempDir::new().unwrap();
+    let store = tmp.path().join("store");
+    let nested = tmp.path().join("work/crates/a");
+    fs::create_dir_all(&store).unwrap();
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(
+        tmp.path().join("work/.git"),
+        format!("gitdir: {}\n", store.display()),
+    )
+    .unwrap();
+
+    let rule = GitInternalsRule::new(&nested);
+    assert!(rule.check(&store.join("config")).is_some());
+}
+
+#[test]
+fn test_no_repo_only_dot_git_components() {
+    let tmp = TempDir::new().unwrap();
+    let rule = GitInternalsRule::new(tmp.path());
+    assert!(rule.check(&tmp.path().join("src/lib.rs")).is_none());
+    assert!(rule.check(&tmp.path().join(".git/config")).is_some());
+}
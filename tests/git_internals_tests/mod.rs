@@ -0,0 +1,2 @@
+mod dot_git_paths;
+mod linked_git_dirs;
@@ -24,12 +24,18 @@ fn test_full_hook_input_all_fields() {
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
     assert_eq!(hook.session_id, "sess-abc-123");
-    assert_eq!(hook.transcript_path, Some("/var/log/claude/transcript.json".to_string()));
+    assert_eq!(
+        hook.transcript_path,
+        Some("/var/log/claude/transcript.json".to_string())
+    );
     assert_eq!(hook.cwd.to_str().unwrap(), "/home/user/projects/my-app");
     assert_eq!(hook.permission_mode, Some("approved".to_string()));
     assert_eq!(hook.hook_event_name, "PreToolUse");
     assert_eq!(hook.tool_name, "Read");
-    assert_eq!(hook.file_path(), Some("/home/user/projects/my-app/src/main.rs"));
+    assert_eq!(
+        hook.file_path(),
+        Some("/home/user/projects/my-app/src/main.rs")
+    );
     assert_eq!(hook.tool_use_id, Some("toolu_abc123xyz789".to_string()));
 }
 
@@ -90,7 +96,10 @@ fn test_tool_input_deeply_nested() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.tool_input["level1"]["level2"]["level3"]["level4"]["level5"], "deep value");
+    assert_eq!(
+        hook.tool_input["level1"]["level2"]["level3"]["level4"]["level5"],
+        "deep value"
+    );
 }
 
 #[test]
@@ -200,7 +209,10 @@ fn test_command_with_pipes_and_redirects() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.command(), Some("cat file.txt | grep pattern > output.txt 2>&1"));
+    assert_eq!(
+        hook.command(),
+        Some("cat file.txt | grep pattern > output.txt 2>&1")
+    );
 }
 
 #[test]
@@ -216,7 +228,10 @@ fn test_file_path_with_backslashes() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.file_path(), Some("C:\\Users\\user\\Documents\\file.txt"));
+    assert_eq!(
+        hook.file_path(),
+        Some("C:\\Users\\user\\Documents\\file.txt")
+    );
 }
 
 // ============================================================================
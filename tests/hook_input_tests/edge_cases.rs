@@ -18,7 +18,10 @@ fn test_tool_input_with_url() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.tool_input["url"], "https://api.example.com/v1/data?key=value&filter=active");
+    assert_eq!(
+        hook.tool_input["url"],
+        "https://api.example.com/v1/data?key=value&filter=active"
+    );
 }
 
 #[test]
@@ -51,7 +54,10 @@ fn test_tool_input_with_prompt() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.tool_input["prompt"], "Extract all headings and summarize the main points");
+    assert_eq!(
+        hook.tool_input["prompt"],
+        "Extract all headings and summarize the main points"
+    );
 }
 
 #[test]
@@ -71,7 +77,10 @@ fn test_notebook_edit_tool() {
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
     assert_eq!(hook.tool_name, "NotebookEdit");
-    assert_eq!(hook.tool_input["notebook_path"], "/home/user/notebook.ipynb");
+    assert_eq!(
+        hook.tool_input["notebook_path"],
+        "/home/user/notebook.ipynb"
+    );
 }
 
 #[test]
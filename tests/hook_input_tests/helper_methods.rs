@@ -151,6 +151,33 @@ fn test_command_returns_some_when_present() {
     assert_eq!(hook.command(), Some("echo hello"));
 }
 
+#[test]
+fn test_run_in_background() {
+    let input = json!({
+        "session_id": "test-session-123",
+        "cwd": "/home/user",
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": {
+            "command": "npm run dev",
+            "run_in_background": true
+        }
+    });
+
+    let hook: HookInput = serde_json::from_value(input).unwrap();
+    assert!(hook.run_in_background());
+
+    let input = json!({
+        "session_id": "test-session-123",
+        "cwd": "/home/user",
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": { "command": "npm run dev", "run_in_background": "yes" }
+    });
+    let hook: HookInput = serde_json::from_value(input).unwrap();
+    assert!(!hook.run_in_background());
+}
+
 #[test]
 fn test_command_returns_none_when_absent() {
     let input = json!({
@@ -362,3 +389,44 @@ fn test_pattern_returns_none_when_tool_input_empty() {
 }
 
 // ============================================================================
+// HELPER METHOD TESTS: url(), query(), allowed_domains(), blocked_domains()
+// ============================================================================
+
+#[test]
+fn test_webfetch_url() {
+    let input = json!({
+        "session_id": "test-session-123",
+        "cwd": "/home/user",
+        "hook_event_name": "PreToolUse",
+        "tool_name": "WebFetch",
+        "tool_input": {
+            "url": "https://docs.rs/regex",
+            "prompt": "summarize"
+        }
+    });
+
+    let hook: HookInput = serde_json::from_value(input).unwrap();
+    assert_eq!(hook.url(), Some("https://docs.rs/regex"));
+}
+
+#[test]
+fn test_websearch_fields() {
+    let input = json!({
+        "session_id": "test-session-123",
+        "cwd": "/home/user",
+        "hook_event_name": "PreToolUse",
+        "tool_name": "WebSearch",
+        "tool_input": {
+            "query": "rust let chains",
+            "allowed_domains": ["doc.rust-lang.org", 7, "github.com"]
+        }
+    });
+
+    let hook: HookInput = serde_json::from_value(input).unwrap();
+    assert_eq!(hook.query(), Some("rust let chains"));
+    assert_eq!(
+        hook.allowed_domains(),
+        vec!["doc.rust-lang.org", "github.com"]
+    );
+    assert!(hook.blocked_domains().is_empty());
+}
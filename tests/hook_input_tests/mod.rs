@@ -1,11 +1,11 @@
-mod required_fields;
-mod optional_fields;
-mod tool_input;
-mod helper_methods;
 mod claude_tools;
-mod unicode_and_strings;
-mod paths;
-mod types_and_fields;
-mod permission_and_events;
 mod complex_scenarios;
 mod edge_cases;
+mod helper_methods;
+mod optional_fields;
+mod paths;
+mod permission_and_events;
+mod required_fields;
+mod tool_input;
+mod types_and_fields;
+mod unicode_and_strings;
@@ -19,7 +19,10 @@ fn test_all_optional_fields_present() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.transcript_path, Some("/tmp/transcript.txt".to_string()));
+    assert_eq!(
+        hook.transcript_path,
+        Some("/tmp/transcript.txt".to_string())
+    );
     assert_eq!(hook.permission_mode, Some("approved".to_string()));
     assert_eq!(hook.tool_use_id, Some("toolu_abc123".to_string()));
 }
@@ -35,7 +38,10 @@ fn test_transcript_path_present() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.transcript_path, Some("/var/log/transcript.json".to_string()));
+    assert_eq!(
+        hook.transcript_path,
+        Some("/var/log/transcript.json".to_string())
+    );
 }
 
 #[test]
@@ -145,7 +151,10 @@ fn test_only_transcript_path_present() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.transcript_path, Some("/tmp/transcript.txt".to_string()));
+    assert_eq!(
+        hook.transcript_path,
+        Some("/tmp/transcript.txt".to_string())
+    );
     assert_eq!(hook.permission_mode, None);
     assert_eq!(hook.tool_use_id, None);
 }
@@ -194,7 +203,10 @@ fn test_transcript_path_and_permission_mode_present() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.transcript_path, Some("/tmp/transcript.txt".to_string()));
+    assert_eq!(
+        hook.transcript_path,
+        Some("/tmp/transcript.txt".to_string())
+    );
     assert_eq!(hook.permission_mode, Some("default".to_string()));
     assert_eq!(hook.tool_use_id, None);
 }
@@ -228,7 +240,10 @@ fn test_transcript_path_and_tool_use_id_present() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.transcript_path, Some("/tmp/transcript.txt".to_string()));
+    assert_eq!(
+        hook.transcript_path,
+        Some("/tmp/transcript.txt".to_string())
+    );
     assert_eq!(hook.permission_mode, None);
     assert_eq!(hook.tool_use_id, Some("toolu_789".to_string()));
 }
@@ -42,7 +42,10 @@ fn test_cwd_with_spaces() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.cwd.to_str().unwrap(), "/home/user/My Documents/Project Files");
+    assert_eq!(
+        hook.cwd.to_str().unwrap(),
+        "/home/user/My Documents/Project Files"
+    );
 }
 
 #[test]
@@ -55,7 +58,10 @@ fn test_cwd_with_special_characters() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.cwd.to_str().unwrap(), "/home/user/project [v2.0]/files & docs");
+    assert_eq!(
+        hook.cwd.to_str().unwrap(),
+        "/home/user/project [v2.0]/files & docs"
+    );
 }
 
 #[test]
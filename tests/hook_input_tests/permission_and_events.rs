@@ -114,7 +114,7 @@ fn test_tool_input_with_mixed_types() {
         "tool_input": {
             "string": "value",
             "number": 42,
-            "float": 3.14,
+            "float": 2.5,
             "boolean": true,
             "null_value": null,
             "array": [1, 2, 3],
@@ -68,4 +68,3 @@ fn test_missing_tool_name_fails() {
     let result: Result<HookInput, _> = serde_json::from_value(input);
     assert!(result.is_err());
 }
-
@@ -29,7 +29,10 @@ fn test_unicode_in_transcript_path() {
     });
 
     let hook: HookInput = serde_json::from_value(input).unwrap();
-    assert_eq!(hook.transcript_path, Some("/tmp/日志/transcript™.txt".to_string()));
+    assert_eq!(
+        hook.transcript_path,
+        Some("/tmp/日志/transcript™.txt".to_string())
+    );
 }
 
 #[test]
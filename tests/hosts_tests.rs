@@ -0,0 +1,5 @@
+// Test entry point for hosts module tests
+// Tests host allowlist and denylist matching
+
+#[path = "hosts_tests/mod.rs"]
+mod hosts_tests;
@@ -0,0 +1,159 @@
+use clarg::facts::Fact;
+use clarg::hosts::{HostPolicy, HostRule};
+
+fn rule(allow: &[&str], deny: &[&str]) -> HostRule {
+    HostRule::new(&HostPolicy {
+        allow: allow.iter().map(|s| s.to_string()).collect(),
+        deny: deny.iter().map(|s| s.to_string()).collect(),
+    })
+    .unwrap()
+}
+
+fn to(host: &str) -> Fact {
+    Fact::new("remote.upload", &format!("scp f {host}:")).with("host", host)
+}
+
+// ============================================================================
+// Allowlist
+// ============================================================================
+
+#[test]
+fn test_empty_policy_allows() {
+    assert!(rule(&[], &[]).check(&to("anywhere.example")).is_none());
+}
+
+#[test]
+fn test_allowlist_exact_and_glob() {
+    let rule = rule(&["github.com", "*.corp.example"], &[]);
+    assert!(rule.check(&to("github.com")).is_none());
+    assert!(rule.check(&to("build.corp.example")).is_none());
+    assert!(rule.check(&to("evil.example")).is_some());
+    // A glob doesn't match the bare parent domain
+    assert!(rule.check(&to("corp.example")).is_some());
+}
+
+#[test]
+fn test_allowlist_case_insensitive() {
+    assert!(
+        rule(&["GitHub.com"], &[])
+            .check(&to("github.COM"))
+            .is_none()
+    );
+}
+
+#[test]
+fn test_dots_are_literal() {
+    assert!(rule(&["a.example"], &[]).check(&to("aXexample")).is_some());
+}
+
+// ============================================================================
+// Denylist
+// ============================================================================
+
+#[test]
+fn test_denylist_wins_over_allowlist() {
+    let rule = rule(&["*"], &["*.pastebin.com"]);
+    assert!(rule.check(&to("x.pastebin.com")).is_some());
+    assert!(rule.check(&to("github.com")).is_none());
+}
+
+#[test]
+fn test_jump_hosts_checked() {
+    let rule = rule(&["db", "jump1"], &[]);
+    let fact = Fact::new("remote.exec", "ssh -J jump1,jump2 db ls")
+        .with("host", "db")
+        .with("jump", "jump1,jump2");
+    let reason = rule.check(&fact).unwrap();
+    assert!(reason.contains("'jump2'"));
+}
+
+#[test]
+fn test_fact_without_host_ignored() {
+    let fact = Fact::new("git.push.force", "git push -f").with("remote", "origin");
+    assert!(rule(&["github.com"], &[]).check(&fact).is_none());
+}
+
+// ============================================================================
+// Reasons
+// ============================================================================
+
+#[test]
+fn test_reason_messages() {
+    let reason = rule(&["github.com"], &[])
+        .check(&to("evil.example"))
+        .unwrap();
+    assert!(reason.starts_with("Blocked by `clarg`"));
+    assert!(reason.contains("'evil.example'"));
+    assert!(reason.contains("not on the host allowlist"));
+    let reason = rule(&[], &["evil.*"]).check(&to("evil.example")).unwrap();
+    assert!(reason.contains("host denylist"));
+}
+
+// ============================================================================
+// CIDR ranges
+// ============================================================================
+
+#[test]
+fn test_cidr_allowlist() {
+    let rule = rule(&["10.0.0.0/8", "fd00::/8"], &[]);
+    assert!(rule.check(&to("10.1.2.3")).is_none());
+    assert!(rule.check(&to("11.0.0.1")).is_some());
+    assert!(rule.check(&to("fd12::1")).is_none());
+    assert!(rule.check(&to("[fd12::1]")).is_none());
+    // CIDR ranges don't match names
+    assert!(rule.check(&to("ten.example")).is_some());
+}
+
+#[test]
+fn test_cidr_denylist_catches_numeric_forms() {
+    let rule = rule(&[], &["127.0.0.0/8", "169.254.0.0/16"]);
+    assert!(rule.check(&to("127.0.0.1")).is_some());
+    assert!(rule.check(&to("2130706433")).is_some());
+    assert!(rule.check(&to("0x7f000001")).is_some());
+    assert!(rule.check(&to("127.1")).is_some());
+    assert!(rule.check(&to("0251.0376.0251.0376")).is_some());
+    assert!(rule.check(&to("0177.0.0x1")).is_some());
+    assert!(rule.check(&to("::ffff:169.254.169.254")).is_some());
+    assert!(rule.check(&to("8.8.8.8")).is_none());
+}
+
+#[test]
+fn test_cidr_prefix_edges() {
+    assert!(
+        rule(&["0.0.0.0/0"], &[])
+            .check(&to("203.0.113.9"))
+            .is_none()
+    );
+    assert!(
+        rule(&["192.0.2.1/32"], &[])
+            .check(&to("192.0.2.1"))
+            .is_none()
+    );
+    assert!(
+        rule(&["192.0.2.1/32"], &[])
+            .check(&to("192.0.2.2"))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_invalid_cidr_fails() {
+    for pattern in ["10.0.0.0/33", "nothost/8", "::/129"] {
+        let policy = HostPolicy {
+            allow: vec![pattern.to_string()],
+            deny: vec![],
+        };
+        assert!(HostRule::new(&policy).is_err(), "{pattern}");
+    }
+}
+
+#[test]
+fn test_proxy_host_checked() {
+    let fact = to("a.example").with("proxy", "proxy.evil");
+    assert!(
+        rule(&["*.example"], &[])
+            .check(&fact)
+            .unwrap()
+            .contains("'proxy.evil'")
+    );
+}
@@ -0,0 +1 @@
+mod host_rule;
@@ -1,5 +1,5 @@
-mod normalize_path;
-mod expand_home;
-mod resolve_target;
 mod check_path_containment;
+mod expand_home;
+mod normalize_path;
 mod resolve_project_root;
+mod resolve_target;
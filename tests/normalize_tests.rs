@@ -0,0 +1,5 @@
+// Test entry point for normalize module tests
+// Tests shell word splitting and command normalization
+
+#[path = "normalize_tests/mod.rs"]
+mod normalize_tests;
@@ -0,0 +1,2 @@
+mod normalize_command;
+mod shell_words;
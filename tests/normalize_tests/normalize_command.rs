@@ -0,0 +1,157 @@
+use clarg::normalize::normalize_command;
+
+fn has_form(command: &str, form: &str) -> bool {
+    normalize_command(command).iter().any(|f| f == form)
+}
+
+// ============================================================================
+// Quote and escape removal
+// ============================================================================
+
+#[test]
+fn test_normalize_plain_command() {
+    assert_eq!(normalize_command("ls -la"), vec!["ls -la"]);
+}
+
+#[test]
+fn test_normalize_removes_quotes() {
+    assert!(has_form("r''m -rf /", "rm -rf /"));
+    assert!(has_form("\"rm\" -rf /", "rm -rf /"));
+}
+
+#[test]
+fn test_normalize_removes_escapes() {
+    assert!(has_form("r\\m -rf /", "rm -rf /"));
+}
+
+#[test]
+fn test_normalize_decodes_ansi_c() {
+    assert!(has_form("$'\\x72m' -rf /", "rm -rf /"));
+}
+
+#[test]
+fn test_normalize_collapses_whitespace() {
+    assert!(has_form("rm    -rf\t/", "rm -rf /"));
+}
+
+// ============================================================================
+// Wrapper stripping
+// ============================================================================
+
+#[test]
+fn test_normalize_keeps_wrapped_and_unwrapped_forms() {
+    let forms = normalize_command("\\sudo rm -rf /");
+    assert!(forms.contains(&"sudo rm -rf /".to_string()));
+    assert!(forms.contains(&"rm -rf /".to_string()));
+}
+
+#[test]
+fn test_normalize_strips_env_assignments() {
+    assert!(has_form("FOO=1 BAR=2 rm -rf /", "rm -rf /"));
+}
+
+#[test]
+fn test_normalize_strips_command_builtin() {
+    assert!(has_form("command rm -rf /", "rm -rf /"));
+    assert!(has_form("builtin cd /", "cd /"));
+}
+
+// ============================================================================
+// Compound and nested commands
+// ============================================================================
+
+#[test]
+fn test_normalize_each_sub_command() {
+    let forms = normalize_command("ls && r''m -rf / | cat");
+    assert!(forms.contains(&"ls".to_string()));
+    assert!(forms.contains(&"rm -rf /".to_string()));
+    assert!(forms.contains(&"cat".to_string()));
+}
+
+#[test]
+fn test_normalize_recurses_into_bash_c() {
+    assert!(has_form("bash -c \"r''m -rf /\"", "rm -rf /"));
+}
+
+#[test]
+fn test_normalize_recurses_into_eval() {
+    assert!(has_form("eval 'r\\m -rf /'", "rm -rf /"));
+}
+
+#[test]
+fn test_normalize_recurses_into_find_exec() {
+    assert!(has_form("find . -exec rm {} \\;", "rm ."));
+    assert!(has_form(
+        "find src tests -name '*.o' -execdir rm -f {} +",
+        "rm -f src"
+    ));
+    assert!(has_form("find . -ok sh -c 'rm x' \\;", "rm x"));
+}
+
+#[test]
+fn test_normalize_recurses_into_container_commands() {
+    assert!(has_form("docker exec -it web rm x", "rm x"));
+    assert!(has_form(
+        "podman run --rm alpine sh -c 'rm -rf /data'",
+        "rm -rf /data"
+    ));
+    assert!(has_form(
+        "docker run --entrypoint rm alpine -rf /",
+        "rm -rf /"
+    ));
+}
+
+#[test]
+fn test_normalize_recurses_into_ssh_commands() {
+    assert!(has_form("ssh -p 22 host rm x", "rm x"));
+    assert!(has_form("ssh host 'r\\m -rf /'", "rm -rf /"));
+    assert!(has_form(
+        "ssh -o 'ProxyCommand=nc evil 22' host",
+        "nc evil 22"
+    ));
+    assert!(!has_form("ssh host", "host"));
+}
+
+#[test]
+fn test_normalize_recurses_into_awk_and_sed_commands() {
+    assert!(has_form("awk 'BEGIN{system(\"rm x\")}'", "rm x"));
+    assert!(has_form("awk '{print | \"rm x\"}' f", "rm x"));
+    assert!(has_form("gawk '{\"rm x\" | getline v}' f", "rm x"));
+    assert!(has_form("sed -n '1e rm x' f", "rm x"));
+    assert!(has_form("sed 's/.*/rm x/e' f", "rm x"));
+    assert!(!has_form("sed --sandbox '1e rm x' f", "rm x"));
+}
+
+#[test]
+fn test_normalize_expands_functions() {
+    assert!(has_form("g(){ rm x; }; g", "rm x"));
+    assert!(has_form(
+        "function del { rm \"$1\"; }; del a.txt",
+        "rm a.txt"
+    ));
+    // The definition's marker is not a form of its own
+    assert!(
+        !normalize_command("g(){ ls; }; g")
+            .iter()
+            .any(|f| f.contains("__clarg"))
+    );
+}
+
+#[test]
+fn test_normalize_expands_aliases() {
+    assert!(has_form("alias ll='rm x'; ll", "rm x"));
+    assert!(has_form("alias del='rm -f'; del a.txt", "rm -f a.txt"));
+    // A recursive alias is expanded once
+    assert!(has_form("alias ls='ls -la'; ls", "ls -la"));
+}
+
+#[test]
+fn test_normalize_no_duplicates() {
+    let forms = normalize_command("ls; ls");
+    assert_eq!(forms, vec!["ls"]);
+}
+
+#[test]
+fn test_normalize_unparseable_sub_command_skipped() {
+    assert!(normalize_command("echo 'unterminated").is_empty());
+}
@@ -0,0 +1,115 @@
+use clarg::normalize::shell_words;
+
+fn words(input: &str) -> Vec<String> {
+    shell_words(input).expect("should parse")
+}
+
+// ============================================================================
+// Plain words and whitespace
+// ============================================================================
+
+#[test]
+fn test_shell_words_simple() {
+    assert_eq!(words("rm -rf /tmp"), vec!["rm", "-rf", "/tmp"]);
+}
+
+#[test]
+fn test_shell_words_collapses_whitespace() {
+    assert_eq!(words("  rm \t  -rf   x "), vec!["rm", "-rf", "x"]);
+}
+
+#[test]
+fn test_shell_words_empty_input() {
+    assert!(words("").is_empty());
+    assert!(words("   ").is_empty());
+}
+
+// ============================================================================
+// Quotes and escapes
+// ============================================================================
+
+#[test]
+fn test_shell_words_empty_quotes_joined() {
+    assert_eq!(words("r''m -rf"), vec!["rm", "-rf"]);
+    assert_eq!(words("r\"\"m -rf"), vec!["rm", "-rf"]);
+}
+
+#[test]
+fn test_shell_words_quoted_command() {
+    assert_eq!(words("\"rm\" '-rf' x"), vec!["rm", "-rf", "x"]);
+}
+
+#[test]
+fn test_shell_words_backslash_escape() {
+    assert_eq!(words("r\\m -rf"), vec!["rm", "-rf"]);
+    assert_eq!(words("\\sudo ls"), vec!["sudo", "ls"]);
+}
+
+#[test]
+fn test_shell_words_escaped_space() {
+    assert_eq!(words("cat my\\ file"), vec!["cat", "my file"]);
+}
+
+#[test]
+fn test_shell_words_line_continuation() {
+    assert_eq!(words("rm \\\n-rf"), vec!["rm", "-rf"]);
+}
+
+#[test]
+fn test_shell_words_empty_quoted_word_kept() {
+    assert_eq!(words("echo ''"), vec!["echo", ""]);
+}
+
+#[test]
+fn test_shell_words_double_quote_keeps_unknown_escape() {
+    assert_eq!(words(r#""a\nb""#), vec![r"a\nb"]);
+    assert_eq!(words(r#""a\"b""#), vec![r#"a"b"#]);
+}
+
+#[test]
+fn test_shell_words_single_quote_is_literal() {
+    assert_eq!(words(r"'a\x72b'"), vec![r"a\x72b"]);
+}
+
+#[test]
+fn test_shell_words_unbalanced_quotes() {
+    assert!(shell_words("echo 'oops").is_none());
+    assert!(shell_words("echo \"oops").is_none());
+    assert!(shell_words("echo $'oops").is_none());
+}
+
+// ============================================================================
+// ANSI-C quoting
+// ============================================================================
+
+#[test]
+fn test_shell_words_ansi_c_hex() {
+    assert_eq!(words(r"$'\x72m' -rf"), vec!["rm", "-rf"]);
+}
+
+#[test]
+fn test_shell_words_ansi_c_octal() {
+    assert_eq!(words(r"$'\162\155'"), vec!["rm"]);
+}
+
+#[test]
+fn test_shell_words_ansi_c_unicode() {
+    assert_eq!(words(r"$'rm'"), vec!["rm"]);
+    assert_eq!(words(r"$'\U00000072m'"), vec!["rm"]);
+}
+
+#[test]
+fn test_shell_words_ansi_c_named_escapes() {
+    assert_eq!(words(r"$'a\tb\nc'"), vec!["a\tb\nc"]);
+    assert_eq!(words(r"$'it\'s'"), vec!["it's"]);
+}
+
+#[test]
+fn test_shell_words_locale_string() {
+    assert_eq!(words("$\"sudo\" ls"), vec!["sudo", "ls"]);
+}
+
+#[test]
+fn test_shell_words_dollar_variable_untouched() {
+    assert_eq!(words("echo $HOME"), vec!["echo", "$HOME"]);
+}
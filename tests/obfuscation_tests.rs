@@ -0,0 +1,5 @@
+// Test entry point for obfuscation module tests
+// Tests technique scoring and encoded payload decoding
+
+#[path = "obfuscation_tests/mod.rs"]
+mod obfuscation_tests;
@@ -0,0 +1,68 @@
+use clarg::obfuscation::decode_payloads;
+
+// ============================================================================
+// base64
+// ============================================================================
+
+#[test]
+fn test_decode_base64_payload() {
+    let payloads = decode_payloads("eval \"$(echo cm0gLXJmIC8= | base64 -d)\"");
+    assert!(payloads.contains(&"rm -rf /".to_string()));
+}
+
+#[test]
+fn test_decode_base64_requires_decoder() {
+    assert!(decode_payloads("echo cm0gLXJmIC8=").is_empty());
+}
+
+#[test]
+fn test_decode_base64_only_when_run() {
+    // Printed or saved, the decoded text is data
+    assert!(decode_payloads("base64 -d <<< cm0gLXJmIC8=").is_empty());
+    assert!(decode_payloads("echo cm0gLXJmIC8= | base64 -d > notes.txt").is_empty());
+    assert!(!decode_payloads("echo cm0gLXJmIC8= | base64 -d | bash").is_empty());
+    assert!(!decode_payloads("bash <(echo cm0gLXJmIC8= | base64 -d)").is_empty());
+    assert!(!decode_payloads("source <(echo cm0gLXJmIC8= | base64 -d)").is_empty());
+}
+
+#[test]
+fn test_decode_base64_binary_rejected() {
+    // Decodes to non-text bytes
+    assert!(decode_payloads("echo ////////AAAA | base64 -d | sh").is_empty());
+}
+
+// ============================================================================
+// hex
+// ============================================================================
+
+#[test]
+fn test_decode_hex_payload() {
+    let payloads = decode_payloads("echo 636174202f6574632f706173737764 | xxd -r -p | sh");
+    assert!(payloads.contains(&"cat /etc/passwd".to_string()));
+}
+
+#[test]
+fn test_decode_hex_requires_decoder() {
+    assert!(decode_payloads("echo 636174202f6574632f706173737764").is_empty());
+}
+
+// ============================================================================
+// printf escapes
+// ============================================================================
+
+#[test]
+fn test_decode_printf_hex_escapes() {
+    let payloads = decode_payloads("$(printf '\\x72\\x6d') -rf /");
+    assert!(payloads.contains(&"rm".to_string()));
+}
+
+#[test]
+fn test_decode_printf_octal_escapes() {
+    let payloads = decode_payloads("printf \"\\143\\141\\164 /etc/shadow\" | sh");
+    assert!(payloads.contains(&"cat /etc/shadow".to_string()));
+}
+
+#[test]
+fn test_decode_plain_command_has_no_payloads() {
+    assert!(decode_payloads("ls -la && cargo test").is_empty());
+}
@@ -0,0 +1,2 @@
+mod decode_payloads;
+mod score;
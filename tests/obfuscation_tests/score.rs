@@ -0,0 +1,118 @@
+use clarg::obfuscation::score;
+
+fn has_technique(cmd: &str, technique: &str) -> bool {
+    score(cmd).techniques.iter().any(|t| t == technique)
+}
+
+// ============================================================================
+// Plain commands score zero
+// ============================================================================
+
+#[test]
+fn test_score_plain_commands_zero() {
+    for cmd in [
+        "ls -la",
+        "cargo build --release",
+        "git log --format='%H %s'",
+        "for f in *.rs; do echo $f; done",
+        "echo \"$(date)\"",
+        "printf '%s\\n' hello",
+    ] {
+        assert_eq!(score(cmd).score, 0, "{cmd} should score 0");
+    }
+}
+
+// ============================================================================
+// Individual techniques
+// ============================================================================
+
+#[test]
+fn test_score_dynamic_eval() {
+    assert!(has_technique(
+        "eval \"$(cat x)\"",
+        "eval of dynamic content"
+    ));
+    assert!(has_technique("eval $cmd", "eval of dynamic content"));
+    assert!(!has_technique("eval 'echo hi'", "eval of dynamic content"));
+}
+
+#[test]
+fn test_score_base64_decoding() {
+    assert!(has_technique("echo aGk= | base64 -d", "base64 decoding"));
+    assert!(has_technique("base64 --decode f", "base64 decoding"));
+    assert!(has_technique("base64 -D f", "base64 decoding"));
+    assert!(!has_technique("base64 f", "base64 decoding"));
+}
+
+#[test]
+fn test_score_hex_decoding() {
+    assert!(has_technique("echo 726d | xxd -r -p", "hex decoding"));
+    assert!(has_technique("xxd -pr dump", "hex decoding"));
+    assert!(!has_technique("xxd file", "hex decoding"));
+}
+
+#[test]
+fn test_score_escaped_printf() {
+    assert!(has_technique(
+        "$(printf '\\x72\\x6d') -rf /",
+        "escaped printf"
+    ));
+    assert!(has_technique("printf '\\162\\155'", "escaped printf"));
+}
+
+#[test]
+fn test_score_variable_indirection() {
+    assert!(has_technique("echo ${!name}", "variable indirection"));
+}
+
+#[test]
+fn test_score_ifs_manipulation() {
+    assert!(has_technique("IFS=, read a b", "IFS manipulation"));
+    assert!(has_technique("cat${IFS}/etc/passwd", "IFS manipulation"));
+    assert!(has_technique("IFS=':'; set -- $PATH", "IFS manipulation"));
+}
+
+#[test]
+fn test_score_ifs_line_reading_not_counted() {
+    assert!(!has_technique(
+        "while IFS= read -r line; do echo \"$line\"; done < f",
+        "IFS manipulation"
+    ));
+    assert!(!has_technique("IFS='' read -r x", "IFS manipulation"));
+    assert!(!has_technique(
+        "IFS=$'\\n'; for f in $(ls); do echo $f; done",
+        "IFS manipulation"
+    ));
+}
+
+#[test]
+fn test_score_dynamic_command_word() {
+    let technique = "command name from a variable or substitution";
+    assert!(has_technique("$cmd -rf /", technique));
+    assert!(has_technique("${x} /etc/passwd", technique));
+    assert!(has_technique("$(echo rm) -rf /", technique));
+    assert!(has_technique("sudo $cmd", technique));
+    assert!(!has_technique("echo $cmd", technique));
+}
+
+// ============================================================================
+// Combined scores
+// ============================================================================
+
+#[test]
+fn test_score_eval_base64_combined() {
+    let result = score("eval \"$(echo cm0gLXJmIC8= | base64 -d)\"");
+    assert_eq!(result.score, 5);
+    assert_eq!(result.techniques.len(), 2);
+}
+
+#[test]
+fn test_score_printf_substitution_combined() {
+    assert_eq!(score("$(printf '\\x72\\x6d') -rf /").score, 4);
+}
+
+#[test]
+fn test_score_technique_counted_once() {
+    let result = score("echo a | base64 -d; echo b | base64 -d");
+    assert_eq!(result.score, 2);
+}
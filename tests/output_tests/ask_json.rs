@@ -0,0 +1,26 @@
+use clarg::output::ask_json;
+
+// ============================================================================
+// ask_json tests
+// ============================================================================
+
+#[test]
+fn test_ask_json_structure_is_correct() {
+    let result = ask_json("needs a human");
+
+    let hook_output = &result["hookSpecificOutput"];
+    assert_eq!(hook_output["hookEventName"], "PreToolUse");
+    assert_eq!(hook_output["permissionDecision"], "ask");
+    assert_eq!(hook_output["permissionDecisionReason"], "needs a human");
+}
+
+#[test]
+fn test_ask_json_escapes_special_characters() {
+    let result = ask_json("quote \" and newline \n");
+    let serialized = result.to_string();
+    let parsed: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(
+        parsed["hookSpecificOutput"]["permissionDecisionReason"],
+        "quote \" and newline \n"
+    );
+}
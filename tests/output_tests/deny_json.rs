@@ -40,10 +40,7 @@ fn test_deny_json_with_simple_reason() {
 #[test]
 fn test_deny_json_with_empty_reason() {
     let result = deny_json("");
-    assert_eq!(
-        result["hookSpecificOutput"]["permissionDecisionReason"],
-        ""
-    );
+    assert_eq!(result["hookSpecificOutput"]["permissionDecisionReason"], "");
 }
 
 #[test]
@@ -180,18 +177,11 @@ fn test_deny_json_with_control_characters() {
 #[test]
 fn test_deny_json_hook_event_name_is_correct() {
     let result = deny_json("any reason");
-    assert_eq!(
-        result["hookSpecificOutput"]["hookEventName"],
-        "PreToolUse"
-    );
+    assert_eq!(result["hookSpecificOutput"]["hookEventName"], "PreToolUse");
 }
 
 #[test]
 fn test_deny_json_permission_decision_is_deny() {
     let result = deny_json("any reason");
-    assert_eq!(
-        result["hookSpecificOutput"]["permissionDecision"],
-        "deny"
-    );
+    assert_eq!(result["hookSpecificOutput"]["permissionDecision"], "deny");
 }
-
@@ -96,7 +96,7 @@ fn test_log_message_with_binary_data() {
 
     // Read as bytes to handle binary data
     let content = fs::read(&log_path).unwrap();
-    assert!(content.len() > 0);
+    assert!(!content.is_empty());
 }
 
 #[test]
@@ -55,11 +55,7 @@ fn test_format_log_entry_timestamp_is_current() {
         .as_secs();
 
     // Timestamp should be within 5 seconds of now
-    let diff = if now > log_timestamp {
-        now - log_timestamp
-    } else {
-        log_timestamp - now
-    };
+    let diff = now.abs_diff(log_timestamp);
     assert!(diff < 5);
 }
 
@@ -201,4 +197,3 @@ fn test_format_log_entry_with_very_long_reason() {
     let result = format_log_entry("Tool", "allow", &reason);
     assert!(result.contains(&reason));
 }
-
@@ -112,18 +112,12 @@ fn test_deny_json_with_json_injection_attempt() {
     let json = deny_json(malicious_reason);
 
     // Should still be deny, not allow
-    assert_eq!(
-        json["hookSpecificOutput"]["permissionDecision"],
-        "deny"
-    );
+    assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
 
     // Reason should be escaped properly
     let json_string = serde_json::to_string(&json).unwrap();
     let parsed: Value = serde_json::from_str(&json_string).unwrap();
-    assert_eq!(
-        parsed["hookSpecificOutput"]["permissionDecision"],
-        "deny"
-    );
+    assert_eq!(parsed["hookSpecificOutput"]["permissionDecision"], "deny");
 }
 
 #[test]
@@ -137,4 +131,3 @@ fn test_format_log_entry_with_log_injection_attempt() {
     assert!(result.contains("tool=RealTool"));
     assert!(result.contains("verdict=deny"));
 }
-
@@ -77,8 +77,8 @@ fn test_log_message_preserves_order() {
     let content = fs::read_to_string(&log_path).unwrap();
     let lines: Vec<&str> = content.lines().collect();
 
-    for i in 0..10 {
-        assert!(lines[i].contains(&format!("message {}", i)));
+    for (i, line) in lines.iter().enumerate().take(10) {
+        assert!(line.contains(&format!("message {}", i)));
     }
 }
 
@@ -208,4 +208,3 @@ fn test_log_message_multiple_calls_separate_lines() {
 
     assert_eq!(lines.len(), 2);
 }
-
@@ -1,5 +1,6 @@
+mod ask_json;
 mod deny_json;
+mod edge_cases;
 mod format_log_entry;
-mod log_message;
 mod integration;
-mod edge_cases;
+mod log_message;
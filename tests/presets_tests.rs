@@ -0,0 +1,5 @@
+// Test entry point for presets module tests
+// Tests preset lookup, parsing, and what each built-in preset blocks
+
+#[path = "presets_tests/mod.rs"]
+mod presets_tests;
@@ -0,0 +1,70 @@
+use clarg::blocked_commands::BlockedCommandsRule;
+use clarg::facts::FactRules;
+use clarg::presets::{self, PRESETS};
+
+// ============================================================================
+// Lookup
+// ============================================================================
+
+#[test]
+fn test_find_known_preset() {
+    let preset = presets::find("destructive").unwrap();
+    assert_eq!(preset.name, "destructive");
+}
+
+#[test]
+fn test_find_unknown_preset_errors() {
+    let err = presets::find("nope").err().unwrap().to_string();
+    assert!(err.contains("unknown preset 'nope'"));
+    assert!(err.contains("git-safety"));
+}
+
+#[test]
+fn test_find_is_case_sensitive() {
+    assert!(presets::find("Destructive").is_err());
+}
+
+// ============================================================================
+// Every preset is well-formed
+// ============================================================================
+
+#[test]
+fn test_all_presets_parse_and_compile() {
+    for preset in PRESETS {
+        let rules = preset.rules().unwrap();
+        assert!(
+            !rules.description.is_empty(),
+            "{} has no description",
+            preset.name
+        );
+        assert!(
+            !rules.commands_forbidden.is_empty() || !rules.rules.is_empty(),
+            "{} has no rules",
+            preset.name
+        );
+        BlockedCommandsRule::new(&rules.commands_forbidden)
+            .unwrap_or_else(|e| panic!("{} has an invalid pattern: {e}", preset.name));
+        FactRules::new(&rules.rules)
+            .unwrap_or_else(|e| panic!("{} has an invalid fact rule: {e}", preset.name));
+    }
+}
+
+#[test]
+fn test_preset_names_are_unique() {
+    for (i, preset) in PRESETS.iter().enumerate() {
+        assert!(
+            PRESETS[i + 1..].iter().all(|p| p.name != preset.name),
+            "duplicate preset {}",
+            preset.name
+        );
+    }
+}
+
+#[test]
+fn test_source_contains_every_pattern() {
+    for preset in PRESETS {
+        for pattern in preset.rules().unwrap().commands_forbidden {
+            assert!(preset.source.contains(&pattern));
+        }
+    }
+}
@@ -0,0 +1,2 @@
+mod lookup;
+mod preset_rules;
@@ -0,0 +1,503 @@
+use clarg::config::{Config, RuleAction};
+use clarg::hook_input::HookInput;
+use clarg::router::{RuleSet, Verdict};
+use serde_json::json;
+use tempfile::TempDir;
+
+/// Evaluate a bash command with only the named preset enabled.
+fn evaluate(name: &str, command: &str) -> Verdict {
+    let tmp = TempDir::new().unwrap();
+    let config = Config {
+        presets: vec![name.to_string()],
+        persistence: RuleAction::Allow,
+        ..Default::default()
+    };
+    let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
+    let input: HookInput = serde_json::from_value(json!({
+        "session_id": "test-session",
+        "cwd": tmp.path(),
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": { "command": command }
+    }))
+    .unwrap();
+    ruleset.evaluate(&input)
+}
+
+fn assert_blocks(name: &str, commands: &[&str]) {
+    for cmd in commands {
+        assert!(
+            matches!(evaluate(name, cmd), Verdict::Deny(_)),
+            "{name} should block: {cmd}"
+        );
+    }
+}
+
+fn assert_allows(name: &str, commands: &[&str]) {
+    for cmd in commands {
+        assert!(
+            matches!(evaluate(name, cmd), Verdict::Allow),
+            "{name} should allow: {cmd}"
+        );
+    }
+}
+
+// ============================================================================
+// destructive
+// ============================================================================
+
+#[test]
+fn test_destructive_blocks() {
+    assert_blocks(
+        "destructive",
+        &[
+            "rm -rf /",
+            "rm -fr build",
+            "rm -r -f build",
+            "rm -f -r build",
+            "rm --recursive build",
+            "sudo rm -Rf /var",
+            "shred secret.txt",
+            "find . -name '*.o' -delete",
+            "chmod -R 777 .",
+            "chown -R me /srv",
+        ],
+    );
+}
+
+#[test]
+fn test_destructive_allows() {
+    assert_allows(
+        "destructive",
+        &[
+            "rm file.txt",
+            "rm -f file.txt",
+            "rmdir empty",
+            "chmod +x run.sh",
+            "find . -name '*.rs'",
+        ],
+    );
+}
+
+// ============================================================================
+// privilege
+// ============================================================================
+
+#[test]
+fn test_privilege_blocks() {
+    assert_blocks(
+        "privilege",
+        &[
+            "sudo ls",
+            "doas rm x",
+            "su -",
+            "su root -c id",
+            "pkexec bash",
+            "chmod u+s /bin/sh",
+            "chmod 4755 ./tool",
+            "echo 'me ALL=(ALL) NOPASSWD: ALL' >> /etc/sudoers",
+            "usermod -aG wheel me",
+        ],
+    );
+}
+
+#[test]
+fn test_privilege_allows() {
+    assert_allows(
+        "privilege",
+        &["ls", "echo sudoku", "chmod 755 run.sh", "summary"],
+    );
+}
+
+// ============================================================================
+// git-safety
+// ============================================================================
+
+#[test]
+fn test_git_safety_blocks() {
+    assert_blocks(
+        "git-safety",
+        &[
+            "git push --force",
+            "git push -f origin main",
+            "git push origin +main",
+            "git push --force-with-lease",
+            "git reset --hard HEAD~1",
+            "git clean -fdx",
+            "git checkout -- .",
+            "git restore .",
+            "git branch -D feature",
+            "git filter-branch --tree-filter 'rm x' HEAD",
+            "git commit --no-verify -m wip",
+            "git -C repo reset --hard",
+            "git stash clear",
+            "git -c core.hooksPath=/tmp/h commit -m x",
+            "sh -c 'git push -f'",
+        ],
+    );
+}
+
+#[test]
+fn test_git_safety_allows() {
+    assert_allows(
+        "git-safety",
+        &[
+            "git push",
+            "git push origin main",
+            "git reset HEAD file",
+            "git checkout -b feature",
+            "git branch -d merged",
+            "git commit -m 'fix'",
+            "git stash pop",
+            "git checkout -- src/main.rs",
+            "git restore --staged .",
+        ],
+    );
+}
+
+// ============================================================================
+// disk-wipe
+// ============================================================================
+
+#[test]
+fn test_disk_wipe_blocks() {
+    assert_blocks(
+        "disk-wipe",
+        &[
+            "mkfs.ext4 /dev/sda1",
+            "dd if=/dev/zero of=/dev/sda bs=1M",
+            "wipefs -a /dev/nvme0n1",
+            "fdisk /dev/sda",
+            "cat image > /dev/sdb",
+            "diskutil eraseDisk APFS X disk2",
+        ],
+    );
+}
+
+#[test]
+fn test_disk_wipe_allows() {
+    assert_allows(
+        "disk-wipe",
+        &["dd if=/dev/zero of=out.img bs=1M count=1", "df -h", "lsblk"],
+    );
+}
+
+// ============================================================================
+// fork-bomb
+// ============================================================================
+
+#[test]
+fn test_fork_bomb_blocks() {
+    assert_blocks(
+        "fork-bomb",
+        &[":(){ :|:& };:", "bomb() { bomb | bomb & }; bomb"],
+    );
+}
+
+#[test]
+fn test_fork_bomb_blocks_nested_definitions() {
+    assert_blocks(
+        "fork-bomb",
+        &[
+            "bash -c 'boom(){ boom|boom& }; boom'",
+            "function f() { f | f & }; f",
+        ],
+    );
+}
+
+#[test]
+fn test_fork_bomb_allows() {
+    assert_allows("fork-bomb", &["f() { echo hi; }; f", "make -j8 &"]);
+}
+
+// ============================================================================
+// shutdown
+// ============================================================================
+
+#[test]
+fn test_shutdown_blocks() {
+    assert_blocks(
+        "shutdown",
+        &[
+            "shutdown -h now",
+            "reboot",
+            "sudo poweroff",
+            "systemctl reboot",
+            "init 0",
+            "kill -9 -1",
+            "killall node",
+        ],
+    );
+}
+
+#[test]
+fn test_shutdown_allows() {
+    assert_allows(
+        "shutdown",
+        &[
+            "kill 1234",
+            "kill -9 1234",
+            "systemctl status nginx",
+            "echo shutdown-notes.md",
+        ],
+    );
+}
+
+// ============================================================================
+// listeners
+// ============================================================================
+
+#[test]
+fn test_listeners_blocks() {
+    assert_blocks(
+        "listeners",
+        &[
+            "python3 -m http.server",
+            "nc -lvnp 4444",
+            "nc -l 127.0.0.1 4444 -e /bin/sh",
+            "socat TCP-LISTEN:4444,fork EXEC:/bin/bash",
+            "php -S 0.0.0.0:80",
+            "npx serve /",
+            "flask run --host 0.0.0.0",
+        ],
+    );
+}
+
+#[test]
+fn test_listeners_asks_for_loopback() {
+    for cmd in [
+        "python3 -m http.server -b 127.0.0.1",
+        "php -S localhost:8000",
+        "uvicorn app:app",
+    ] {
+        assert!(
+            matches!(evaluate("listeners", cmd), Verdict::Ask(_)),
+            "listeners should ask: {cmd}"
+        );
+    }
+}
+
+#[test]
+fn test_listeners_allows() {
+    assert_allows(
+        "listeners",
+        &[
+            "python3 app.py",
+            "nc -z host 22",
+            "curl http://localhost:8000",
+            "npx eslint .",
+        ],
+    );
+}
+
+// ============================================================================
+// database
+// ============================================================================
+
+#[test]
+fn test_database_blocks() {
+    assert_blocks(
+        "database",
+        &[
+            "psql shop -c 'DROP TABLE users'",
+            "mysql -e 'TRUNCATE orders' shop",
+            "mysql shop -e 'DELETE FROM orders'",
+            "sqlite3 app.db 'UPDATE users SET admin = 1'",
+            "redis-cli FLUSHALL",
+            "redis-cli -h cache shutdown nosave",
+            "psql shop <<'SQL'\nBEGIN;\nDROP SCHEMA public CASCADE;\nSQL",
+            "echo 'DROP TABLE t' | psql",
+            "psql shop -c 'WITH x AS (DELETE FROM t RETURNING *) SELECT 1'",
+        ],
+    );
+}
+
+#[test]
+fn test_database_asks_for_schema_and_admin() {
+    for cmd in [
+        "psql shop -c 'ALTER TABLE t ADD c int'",
+        "mysql -e \"GRANT ALL ON *.* TO 'bob'\"",
+        "redis-cli CONFIG SET dir /tmp",
+        "sqlite3 app.db '.shell ls'",
+    ] {
+        assert!(
+            matches!(evaluate("database", cmd), Verdict::Ask(_)),
+            "database should ask: {cmd}"
+        );
+    }
+}
+
+#[test]
+fn test_database_allows() {
+    assert_allows(
+        "database",
+        &[
+            "psql shop -c 'SELECT count(*) FROM users'",
+            "mysql shop -e 'DELETE FROM carts WHERE id = 3'",
+            "redis-cli GET k",
+            "sqlite3 app.db 'INSERT INTO t VALUES (1)'",
+        ],
+    );
+}
+
+// ============================================================================
+// infra
+// ============================================================================
+
+#[test]
+fn test_infra_blocks_destructive_on_named_targets() {
+    assert_blocks(
+        "infra",
+        &[
+            "kubectl --context prod delete ns payments",
+            "kubectl --context prod-test delete ns x",
+            "kubectl --context mydevbox-prod delete ns x",
+            "helm uninstall api --kube-context prod-eu",
+            "aws s3 rm s3://bucket --recursive --profile prod",
+            "AWS_PROFILE=live aws ec2 terminate-instances --instance-ids i-1",
+            "gcloud projects delete shop --project shop-prod",
+            "TF_WORKSPACE=prod terraform destroy -auto-approve",
+            "terraform -chdir=envs/production apply -auto-approve",
+            "kubectl -n prod delete pod x",
+        ],
+    );
+}
+
+#[test]
+fn test_infra_asks() {
+    for cmd in [
+        // Destructive against the default target
+        "terraform destroy",
+        "terraform apply -auto-approve",
+        "kubectl delete ns payments",
+        "aws s3 rb s3://bucket",
+        "terraform -chdir=infra apply -auto-approve",
+        "terraform -chdir=../x destroy",
+        // Changes to a named non-dev target
+        "kubectl apply -f k8s/ --context prod",
+        "helm upgrade api ./chart --kube-context staging",
+    ] {
+        assert!(
+            matches!(evaluate("infra", cmd), Verdict::Ask(_)),
+            "infra should ask: {cmd}"
+        );
+    }
+}
+
+#[test]
+fn test_infra_allows() {
+    assert_allows(
+        "infra",
+        &[
+            "kubectl --context kind-local delete ns payments",
+            "kubectl --context minikube apply -f k8s/",
+            "AWS_PROFILE=dev aws s3 rm s3://scratch --recursive",
+            "TF_WORKSPACE=sandbox terraform destroy -auto-approve",
+            "kubectl --context k3d-demo delete ns x",
+            "helm uninstall api --kube-context shop-dev",
+            "terraform -chdir=envs/dev destroy -auto-approve",
+            "kubectl --context prod get pods",
+            "terraform plan",
+            "terraform apply",
+            "helm list -A --kube-context prod",
+            "gcloud compute instances list --project shop-prod",
+        ],
+    );
+}
+
+// ============================================================================
+// packages
+// ============================================================================
+
+#[test]
+fn test_packages_blocks_url_sources_and_global_installs() {
+    assert_blocks(
+        "packages",
+        &[
+            "pip install git+https://github.com/user/tool",
+            "npm i https://example.com/pkg.tgz",
+            "npx github:user/tool",
+            "cargo install --git https://github.com/user/tool",
+            "npm i -g typescript",
+            "pip install --user black",
+            "pipx install poetry",
+            "cargo install ripgrep@14.1.0",
+        ],
+    );
+}
+
+#[test]
+fn test_packages_asks_for_unpinned_runs() {
+    for cmd in [
+        "npx cowsay",
+        "uvx ruff check .",
+        "pnpm dlx create-vite@latest",
+        "pipx run black",
+    ] {
+        assert!(
+            matches!(evaluate("packages", cmd), Verdict::Ask(_)),
+            "packages should ask: {cmd}"
+        );
+    }
+}
+
+#[test]
+fn test_packages_allows() {
+    assert_allows(
+        "packages",
+        &[
+            "npx cowsay@1.6.0 hi",
+            "uvx ruff@0.4.2 check .",
+            "npm install lodash",
+            "pip install requests==2.31.0",
+            "npm install",
+            "cargo build",
+        ],
+    );
+}
+
+// ============================================================================
+// resources
+// ============================================================================
+
+#[test]
+fn test_resources_blocks() {
+    assert_blocks(
+        "resources",
+        &[
+            ":(){ :|:& };:",
+            "yes > big",
+            "cat /dev/urandom > fill",
+            "dd if=/dev/zero of=x bs=1M",
+        ],
+    );
+}
+
+#[test]
+fn test_resources_asks() {
+    for cmd in [
+        "dd if=/dev/zero of=x bs=1G count=100",
+        "fallocate -l 500G big.img",
+        "while true; do curl -s localhost:8080; done",
+        "stress --cpu 8",
+    ] {
+        assert!(
+            matches!(evaluate("resources", cmd), Verdict::Ask(_)),
+            "resources should ask: {cmd}"
+        );
+    }
+}
+
+#[test]
+fn test_resources_allows() {
+    assert_allows(
+        "resources",
+        &[
+            "dd if=/dev/zero of=x bs=1M count=10",
+            "truncate -s 0 app.log",
+            "while true; do make test; sleep 5; done",
+            "while read line; do echo $line; done < f",
+            "yes | head -n 3",
+        ],
+    );
+}
@@ -0,0 +1,5 @@
+// Test entry point for resources module tests
+// Tests size parsing and the write size limit
+
+#[path = "resources_tests/mod.rs"]
+mod resources_tests;
@@ -0,0 +1,2 @@
+mod parse_size;
+mod resource_rule;
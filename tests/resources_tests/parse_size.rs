@@ -0,0 +1,47 @@
+use clarg::resources::{format_size, parse_size};
+
+#[test]
+fn test_plain_and_dd_units() {
+    assert_eq!(parse_size("0"), Some(0));
+    assert_eq!(parse_size("4096"), Some(4096));
+    assert_eq!(parse_size("3c"), Some(3));
+    assert_eq!(parse_size("3w"), Some(6));
+    assert_eq!(parse_size("2b"), Some(1024));
+    assert_eq!(parse_size("2x512K"), Some(1024 * 1024));
+}
+
+#[test]
+fn test_binary_and_decimal_suffixes() {
+    assert_eq!(parse_size("1K"), Some(1024));
+    assert_eq!(parse_size("1k"), Some(1024));
+    assert_eq!(parse_size("1KiB"), Some(1024));
+    assert_eq!(parse_size("1KB"), Some(1000));
+    assert_eq!(parse_size("10G"), Some(10 * 1024 * 1024 * 1024));
+    assert_eq!(parse_size("1TB"), Some(1_000_000_000_000));
+    assert_eq!(parse_size("1E"), Some(1 << 60));
+}
+
+#[test]
+fn test_invalid_sizes() {
+    assert_eq!(parse_size(""), None);
+    assert_eq!(parse_size("G"), None);
+    assert_eq!(parse_size("1.5G"), None);
+    assert_eq!(parse_size("10Q"), None);
+    assert_eq!(parse_size("10Gb"), None);
+}
+
+#[test]
+fn test_huge_sizes_saturate() {
+    assert_eq!(parse_size("100000E"), Some(u64::MAX));
+    // Numbers that don't fit in u64 saturate too
+    assert_eq!(parse_size("1000000000000000000000"), Some(u64::MAX));
+    assert_eq!(parse_size("99999999999999999999G"), Some(u64::MAX));
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(300), "300 bytes");
+    assert_eq!(format_size(1024), "1 KiB");
+    assert_eq!(format_size(1536 * 1024 * 1024), "1.5 GiB");
+    assert_eq!(format_size(100 * 1024 * 1024 * 1024), "100 GiB");
+}
@@ -0,0 +1,57 @@
+use clarg::facts::Fact;
+use clarg::resources::{ResourcePolicy, ResourceRule};
+
+fn rule(max_write_size: &str) -> ResourceRule {
+    ResourceRule::new(&ResourcePolicy {
+        max_write_size: Some(max_write_size.to_string()),
+    })
+    .unwrap()
+}
+
+fn fill(size: Option<u64>) -> Fact {
+    let fact = Fact::new("resource.disk-fill", "dd if=/dev/zero of=big")
+        .with("tool", "dd")
+        .with("path", "big")
+        .with("unbounded", size.is_none().to_string());
+    match size {
+        Some(size) => fact.with("size", size.to_string()),
+        None => fact,
+    }
+}
+
+#[test]
+fn test_writes_within_the_limit() {
+    let rule = rule("1G");
+    assert!(rule.check(&fill(Some(1024))).is_none());
+    assert!(rule.check(&fill(Some(1024 * 1024 * 1024))).is_none());
+}
+
+#[test]
+fn test_writes_over_the_limit() {
+    let reason = rule("1G")
+        .check(&fill(Some(100 * 1024 * 1024 * 1024)))
+        .unwrap();
+    assert!(reason.contains("writes 100 GiB to 'big'"), "{reason}");
+    assert!(reason.contains("over the 1 GiB write limit"), "{reason}");
+}
+
+#[test]
+fn test_unbounded_writes_are_over_any_limit() {
+    let reason = rule("1T").check(&fill(None)).unwrap();
+    assert!(reason.contains("until the disk is full"), "{reason}");
+}
+
+#[test]
+fn test_other_facts_are_ignored() {
+    let stress = Fact::new("resource.stress", "stress --cpu 8").with("size", "999999999999");
+    assert!(rule("1K").check(&stress).is_none());
+}
+
+#[test]
+fn test_invalid_limit() {
+    let policy = ResourcePolicy {
+        max_write_size: Some("lots".to_string()),
+    };
+    let err = ResourceRule::new(&policy).err().unwrap();
+    assert!(err.to_string().contains("invalid max_write_size: lots"));
+}
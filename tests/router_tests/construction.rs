@@ -14,6 +14,7 @@ fn test_build_with_empty_config() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_ok());
@@ -27,6 +28,7 @@ fn test_build_with_internal_only() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_ok());
@@ -40,6 +42,7 @@ fn test_build_with_blocked_files() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_ok());
@@ -53,6 +56,7 @@ fn test_build_with_blocked_commands() {
         commands_forbidden: vec!["rm -rf".to_string(), "drop table".to_string()],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_ok());
@@ -66,6 +70,7 @@ fn test_build_with_all_rules() {
         commands_forbidden: vec!["rm -rf".to_string()],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_ok());
@@ -79,6 +84,7 @@ fn test_build_with_invalid_regex_fails() {
         commands_forbidden: vec!["[invalid".to_string()],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_err());
@@ -91,6 +97,7 @@ fn test_build_with_nonexistent_project_root_for_internal_only_fails() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, std::path::Path::new("/nonexistent/path/xyz123"));
     assert!(result.is_err());
@@ -104,6 +111,7 @@ fn test_build_blocked_files_without_internal_only() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_ok());
@@ -122,6 +130,7 @@ fn test_build_with_multiple_file_patterns() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_ok());
@@ -140,6 +149,7 @@ fn test_build_with_multiple_command_patterns() {
         ],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let result = RuleSet::build(&config, tmp.path());
     assert!(result.is_ok());
@@ -30,6 +30,7 @@ fn test_read_no_rules_allows_anything() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_file_tool_input("Read", "/etc/passwd", tmp.path().to_path_buf());
@@ -37,6 +38,7 @@ fn test_read_no_rules_allows_anything() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -48,6 +50,7 @@ fn test_read_internal_only_blocks_external() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_file_tool_input("Read", "/etc/passwd", tmp.path().to_path_buf());
@@ -57,6 +60,7 @@ fn test_read_internal_only_blocks_external() {
         Verdict::Deny(reason) => {
             assert!(reason.contains("outside") || reason.contains("/etc/passwd"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -69,14 +73,19 @@ fn test_read_internal_only_allows_internal() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
-    let file_path = project_root.join("src/main.rs").to_string_lossy().to_string();
+    let file_path = project_root
+        .join("src/main.rs")
+        .to_string_lossy()
+        .to_string();
     let input = make_file_tool_input("Read", &file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -89,6 +98,7 @@ fn test_read_blocked_files_denies_match() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
     let file_path = project_root.join(".env").to_string_lossy().to_string();
@@ -99,6 +109,7 @@ fn test_read_blocked_files_denies_match() {
         Verdict::Deny(reason) => {
             assert!(reason.contains(".env"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -111,14 +122,19 @@ fn test_read_blocked_files_allows_non_match() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
-    let file_path = project_root.join("config.json").to_string_lossy().to_string();
+    let file_path = project_root
+        .join("config.json")
+        .to_string_lossy()
+        .to_string();
     let input = make_file_tool_input("Read", &file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -134,6 +150,7 @@ fn test_write_internal_only_blocks_external() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_file_tool_input("Write", "/tmp/malicious.sh", tmp.path().to_path_buf());
@@ -141,9 +158,31 @@ fn test_write_internal_only_blocks_external() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
+#[test]
+fn test_write_startup_file_asks() {
+    let tmp = TempDir::new().unwrap();
+    let ruleset = RuleSet::build(&Config::default(), tmp.path()).unwrap();
+
+    for path in [
+        "~/.bashrc",
+        "/root/.config/autostart/x.desktop",
+        "/etc/cron.d/job",
+    ] {
+        let input = make_file_tool_input("Edit", path, tmp.path().to_path_buf());
+        match ruleset.evaluate(&input) {
+            Verdict::Ask(reason) => assert!(reason.contains("writes the startup file"), "{reason}"),
+            other => panic!("expected ask for {path}, got {other:?}"),
+        }
+    }
+    // Reading one is fine
+    let input = make_file_tool_input("Read", "~/.bashrc", tmp.path().to_path_buf());
+    assert!(matches!(ruleset.evaluate(&input), Verdict::Allow));
+}
+
 #[test]
 fn test_write_internal_only_allows_internal() {
     let tmp = TempDir::new().unwrap();
@@ -153,14 +192,19 @@ fn test_write_internal_only_allows_internal() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
-    let file_path = project_root.join("output.txt").to_string_lossy().to_string();
+    let file_path = project_root
+        .join("output.txt")
+        .to_string_lossy()
+        .to_string();
     let input = make_file_tool_input("Write", &file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -173,9 +217,13 @@ fn test_write_blocked_files_denies_match() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
-    let file_path = project_root.join("api.secret").to_string_lossy().to_string();
+    let file_path = project_root
+        .join("api.secret")
+        .to_string_lossy()
+        .to_string();
     let input = make_file_tool_input("Write", &file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
@@ -183,6 +231,7 @@ fn test_write_blocked_files_denies_match() {
         Verdict::Deny(reason) => {
             assert!(reason.contains(".secret") || reason.contains("api.secret"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -198,6 +247,7 @@ fn test_edit_internal_only_blocks_external() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_file_tool_input("Edit", "/etc/hosts", tmp.path().to_path_buf());
@@ -205,6 +255,7 @@ fn test_edit_internal_only_blocks_external() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -217,14 +268,19 @@ fn test_edit_internal_only_allows_internal() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
-    let file_path = project_root.join("src/lib.rs").to_string_lossy().to_string();
+    let file_path = project_root
+        .join("src/lib.rs")
+        .to_string_lossy()
+        .to_string();
     let input = make_file_tool_input("Edit", &file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -237,14 +293,19 @@ fn test_edit_blocked_files_denies_match() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
-    let file_path = project_root.join(".env.local").to_string_lossy().to_string();
+    let file_path = project_root
+        .join(".env.local")
+        .to_string_lossy()
+        .to_string();
     let input = make_file_tool_input("Edit", &file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -260,6 +321,7 @@ fn test_file_tool_missing_file_path_allowed() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -275,6 +337,7 @@ fn test_file_tool_missing_file_path_allowed() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow when file_path is missing"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -290,6 +353,7 @@ fn test_file_tool_internal_only_checked_before_blocked_files() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     // External path that also matches blocked_files pattern
@@ -301,6 +365,7 @@ fn test_file_tool_internal_only_checked_before_blocked_files() {
             // Should be blocked by internal_only, mentioning "outside"
             assert!(reason.contains("outside"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -317,14 +382,19 @@ fn test_read_parent_traversal_blocked() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
-    let file_path = project_root.join("../../../etc/passwd").to_string_lossy().to_string();
+    let file_path = project_root
+        .join("../../../etc/passwd")
+        .to_string_lossy()
+        .to_string();
     let input = make_file_tool_input("Read", &file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -336,6 +406,7 @@ fn test_write_tilde_expansion_blocked() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_file_tool_input("Write", "~/.bashrc", tmp.path().to_path_buf());
@@ -343,6 +414,7 @@ fn test_write_tilde_expansion_blocked() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -373,6 +445,7 @@ fn test_notebookedit_internal_only_blocks_external() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_notebook_edit_input("/etc/notebooks/evil.ipynb", tmp.path().to_path_buf());
@@ -382,6 +455,7 @@ fn test_notebookedit_internal_only_blocks_external() {
         Verdict::Deny(reason) => {
             assert!(reason.contains("outside"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -394,14 +468,19 @@ fn test_notebookedit_internal_only_allows_internal() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
-    let nb_path = project_root.join("notebook.ipynb").to_string_lossy().to_string();
+    let nb_path = project_root
+        .join("notebook.ipynb")
+        .to_string_lossy()
+        .to_string();
     let input = make_notebook_edit_input(&nb_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -414,9 +493,13 @@ fn test_notebookedit_blocked_files_denies_match() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
-    let nb_path = project_root.join("secret.ipynb").to_string_lossy().to_string();
+    let nb_path = project_root
+        .join("secret.ipynb")
+        .to_string_lossy()
+        .to_string();
     let input = make_notebook_edit_input(&nb_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
@@ -424,6 +507,7 @@ fn test_notebookedit_blocked_files_denies_match() {
         Verdict::Deny(reason) => {
             assert!(reason.contains("ipynb"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -435,6 +519,7 @@ fn test_notebookedit_no_rules_allows() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_notebook_edit_input("/anywhere/notebook.ipynb", tmp.path().to_path_buf());
@@ -442,6 +527,7 @@ fn test_notebookedit_no_rules_allows() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow with no rules"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -453,6 +539,7 @@ fn test_notebookedit_case_insensitive_routing() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -472,5 +559,46 @@ fn test_notebookedit_case_insensitive_routing() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny for NOTEBOOKEDIT (uppercase)"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
+
+// ============================================================================
+// Git internals protection
+// ============================================================================
+
+#[test]
+fn test_write_git_hook_denied_by_default() {
+    let tmp = TempDir::new().unwrap();
+    let ruleset = RuleSet::build(&Config::default(), tmp.path()).unwrap();
+
+    for tool in ["Write", "Edit"] {
+        let input = make_file_tool_input(tool, ".git/hooks/pre-commit", tmp.path().to_path_buf());
+        match ruleset.evaluate(&input) {
+            Verdict::Deny(reason) => assert!(reason.contains("git directory")),
+            other => panic!("{tool}: expected deny, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_read_git_config_allowed() {
+    let tmp = TempDir::new().unwrap();
+    let ruleset = RuleSet::build(&Config::default(), tmp.path()).unwrap();
+    let input = make_file_tool_input("Read", ".git/config", tmp.path().to_path_buf());
+
+    assert!(matches!(ruleset.evaluate(&input), Verdict::Allow));
+}
+
+#[test]
+fn test_write_git_config_allowed_when_unprotected() {
+    let tmp = TempDir::new().unwrap();
+    let config = Config {
+        protect_git: false,
+        ..Default::default()
+    };
+    let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
+    let input = make_file_tool_input("Write", ".git/config", tmp.path().to_path_buf());
+
+    assert!(matches!(ruleset.evaluate(&input), Verdict::Allow));
+}
@@ -44,6 +44,7 @@ fn test_glob_no_rules_allows_anything() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_glob_input("/etc", "*.conf", tmp.path().to_path_buf());
@@ -51,6 +52,7 @@ fn test_glob_no_rules_allows_anything() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -66,6 +68,7 @@ fn test_glob_internal_only_blocks_external_path() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_glob_input("/etc", "*.conf", tmp.path().to_path_buf());
@@ -75,6 +78,7 @@ fn test_glob_internal_only_blocks_external_path() {
         Verdict::Deny(reason) => {
             assert!(reason.contains("outside") || reason.contains("/etc"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -87,6 +91,7 @@ fn test_glob_internal_only_allows_internal_path() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let path = project_root.join("src").to_string_lossy().to_string();
@@ -95,6 +100,7 @@ fn test_glob_internal_only_allows_internal_path() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -106,6 +112,7 @@ fn test_glob_internal_only_blocks_tilde_path() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_glob_input("~/Documents", "*.txt", tmp.path().to_path_buf());
@@ -113,6 +120,7 @@ fn test_glob_internal_only_blocks_tilde_path() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -125,6 +133,7 @@ fn test_glob_internal_only_blocks_parent_traversal() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let path = project_root.join("../..").to_string_lossy().to_string();
@@ -133,6 +142,7 @@ fn test_glob_internal_only_blocks_parent_traversal() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -149,6 +159,7 @@ fn test_glob_blocked_files_denies_blocked_path() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
     let path = project_root.join("secrets").to_string_lossy().to_string();
@@ -159,6 +170,7 @@ fn test_glob_blocked_files_denies_blocked_path() {
         Verdict::Deny(reason) => {
             assert!(reason.contains("secrets"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -171,6 +183,7 @@ fn test_glob_blocked_files_allows_non_blocked_path() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
     let path = project_root.join("src").to_string_lossy().to_string();
@@ -179,6 +192,7 @@ fn test_glob_blocked_files_allows_non_blocked_path() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -194,6 +208,7 @@ fn test_glob_internal_checked_before_blocked_files() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     // External path that also matches blocked_files
@@ -205,6 +220,7 @@ fn test_glob_internal_checked_before_blocked_files() {
             // Should be blocked by internal_only
             assert!(reason.contains("outside"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -220,6 +236,7 @@ fn test_glob_missing_path_allowed() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -237,6 +254,7 @@ fn test_glob_missing_path_allowed() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow when path is missing"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -252,6 +270,7 @@ fn test_glob_relative_path_inside_allowed() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_glob_input_path_only("./src", tmp.path().to_path_buf());
@@ -259,6 +278,7 @@ fn test_glob_relative_path_inside_allowed() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -270,6 +290,7 @@ fn test_glob_relative_parent_traversal_blocked() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_glob_input_path_only("../outside", tmp.path().to_path_buf());
@@ -277,5 +298,6 @@ fn test_glob_relative_parent_traversal_blocked() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
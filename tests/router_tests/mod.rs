@@ -5,3 +5,4 @@ mod glob_tool;
 mod grep_tool;
 mod passthrough_tools;
 mod rule_ordering;
+mod web_tools;
@@ -5,7 +5,7 @@ use serde_json::json;
 use tempfile::TempDir;
 
 // ============================================================================
-// WebFetch tool (always allowed - not filesystem)
+// WebFetch tool (filesystem rules don't apply)
 // ============================================================================
 
 #[test]
@@ -16,6 +16,7 @@ fn test_webfetch_always_allowed_no_rules() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -33,6 +34,7 @@ fn test_webfetch_always_allowed_no_rules() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -44,6 +46,7 @@ fn test_webfetch_allowed_with_internal_only() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -61,6 +64,7 @@ fn test_webfetch_allowed_with_internal_only() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow - WebFetch is not filesystem"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -72,6 +76,7 @@ fn test_webfetch_allowed_with_all_rules() {
         commands_forbidden: vec!["curl".to_string()],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -89,11 +94,12 @@ fn test_webfetch_allowed_with_all_rules() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
 // ============================================================================
-// WebSearch tool (always allowed - not filesystem)
+// WebSearch tool (filesystem rules don't apply)
 // ============================================================================
 
 #[test]
@@ -104,6 +110,7 @@ fn test_websearch_always_allowed_no_rules() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -121,6 +128,7 @@ fn test_websearch_always_allowed_no_rules() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -132,6 +140,7 @@ fn test_websearch_allowed_with_internal_only() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -149,6 +158,7 @@ fn test_websearch_allowed_with_internal_only() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow - WebSearch is not filesystem"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -164,6 +174,7 @@ fn test_task_always_allowed_no_rules() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -183,6 +194,7 @@ fn test_task_always_allowed_no_rules() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -194,6 +206,7 @@ fn test_task_allowed_with_internal_only() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -213,6 +226,7 @@ fn test_task_allowed_with_internal_only() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow - Task subagents get their own hooks"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -228,6 +242,7 @@ fn test_unknown_tool_allowed_no_rules() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -245,6 +260,7 @@ fn test_unknown_tool_allowed_no_rules() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow for unknown tool, got deny: {reason}"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -256,6 +272,7 @@ fn test_unknown_tool_allowed_with_all_rules() {
         commands_forbidden: vec!["rm".to_string()],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -274,6 +291,7 @@ fn test_unknown_tool_allowed_with_all_rules() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow for unknown tool, got deny: {reason}"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -285,6 +303,7 @@ fn test_mcp_tool_allowed() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -302,6 +321,7 @@ fn test_mcp_tool_allowed() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow for MCP tool, got deny: {reason}"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -317,6 +337,7 @@ fn test_webfetch_empty_input_allowed() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -332,6 +353,7 @@ fn test_webfetch_empty_input_allowed() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -343,6 +365,7 @@ fn test_task_empty_input_allowed() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -358,6 +381,7 @@ fn test_task_empty_input_allowed() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -373,6 +397,7 @@ fn test_bash_lowercase_routed_correctly() {
         commands_forbidden: vec!["rm -rf".to_string()],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -390,6 +415,7 @@ fn test_bash_lowercase_routed_correctly() {
         Verdict::Deny(reason) => {
             assert!(reason.contains("rm -rf"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -401,6 +427,7 @@ fn test_bash_uppercase_routed_correctly() {
         commands_forbidden: vec!["rm -rf".to_string()],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -418,6 +445,7 @@ fn test_bash_uppercase_routed_correctly() {
         Verdict::Deny(reason) => {
             assert!(reason.contains("rm -rf"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -429,6 +457,7 @@ fn test_read_mixed_case_routed_correctly() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -444,6 +473,7 @@ fn test_read_mixed_case_routed_correctly() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny for 'rEaD'"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -455,6 +485,7 @@ fn test_glob_case_insensitive_routing() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -470,6 +501,7 @@ fn test_glob_case_insensitive_routing() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny for 'GLOB'"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -481,6 +513,7 @@ fn test_grep_lowercase_routed_correctly() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
 
@@ -496,5 +529,6 @@ fn test_grep_lowercase_routed_correctly() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny for 'grep'"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
